@@ -42,8 +42,9 @@ impl Catalog {
         }
     }
 
+    // function names are case insensitive, they are registered in lowercase
     pub fn get_function_by_name(&self, name: &str) -> Option<&FunctionCatalog> {
-        self.functions.get(name)
+        self.functions.get(&name.to_lowercase())
     }
 }
 
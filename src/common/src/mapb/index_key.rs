@@ -1,28 +1,59 @@
 //! Index key encoding utilities.
 //!
 //! This module provides utilities for encoding scalar values into binary format
-//! suitable for use as index keys. The encoding reuses the `mapb` binary format
-//! for consistency with property storage.
+//! suitable for use as index keys. The encoding is memcomparable: comparing two
+//! encoded values byte by byte gives the same result as comparing the values
+//! with the openCypher orderability rules (see [`crate::order::cmp_datum`]).
 //!
 //! # Format
 //!
 //! Each value is encoded as: `[type_tag: 1B][data]`
 //!
-//! For variable-length types (string, list), a length prefix is included:
-//! `[type_tag: 1B][length: 4B LE][data]`
+//! The type tags follow the order of the types:
+//!   map < node < relationship < list < path < zoned datetime < local datetime
+//!   < date < local time < duration < string < boolean < number < null
 //!
-//! Composite keys are encoded by concatenating individual value encodings.
+//! - Integers and floats share the number tag, so that `1` and `1.0` have the same key. A number is encoded as the
+//!   nearest float, followed by the difference of the exact integer to that float, both as order-preserving big endian
+//!   values.
+//! - Strings are terminated by `0x00 0x01`, a `0x00` byte in the string is escaped as `0x00 0xFF`.
+//! - Lists are the encodings of their elements terminated by `0x00`, maps are their entries sorted by key, each as
+//!   `0x01`, the key as a string and the value, terminated by `0x00`.
+//! - Zoned datetimes are encoded by their instant, the offset does not take part in the order.
+//!
+//! Every encoding is prefix free, so composite keys are encoded by concatenating individual
+//! value encodings, and the keys sharing leading values can be found with a prefix seek.
 
 use bytes::{BufMut, BytesMut};
 
-use super::meta::*;
 use crate::scalar::ScalarRef;
 
+const MAP_TAG: u8 = 0x10;
+const NODE_TAG: u8 = 0x11;
+const REL_TAG: u8 = 0x12;
+const LIST_TAG: u8 = 0x13;
+const PATH_TAG: u8 = 0x14;
+const ZONED_DATE_TIME_TAG: u8 = 0x15;
+const LOCAL_DATE_TIME_TAG: u8 = 0x16;
+const DATE_TAG: u8 = 0x17;
+const LOCAL_TIME_TAG: u8 = 0x18;
+const DURATION_TAG: u8 = 0x19;
+const STRING_TAG: u8 = 0x1A;
+const BOOL_TAG: u8 = 0x1B;
+const NUMBER_TAG: u8 = 0x1C;
+const NULL_TAG: u8 = 0x1D;
+
+// terminates lists, maps and paths, lower than any type tag
+const END_MARKER: u8 = 0x00;
+// precedes the entries of maps and the steps of paths
+const ITEM_MARKER: u8 = 0x01;
+const STRING_TERMINATOR: [u8; 2] = [0x00, 0x01];
+const STRING_ESCAPED_ZERO: [u8; 2] = [0x00, 0xFF];
+
 /// Encoder for index keys.
 ///
-/// This codec produces a compact binary representation suitable for use as
-/// RocksDB keys. The format is compatible with the `mapb` property storage
-/// format.
+/// This codec produces an order-preserving binary representation suitable for
+/// use as RocksDB keys. Equal values, as compared by Cypher, have the same key.
 pub struct IndexKeyCodec;
 
 impl IndexKeyCodec {
@@ -35,8 +66,8 @@ impl IndexKeyCodec {
 
     /// Encode multiple values as a composite index key.
     ///
-    /// Values are encoded in order and concatenated. Each value is self-describing
-    /// via its type tag and (for variable-length types) length prefix.
+    /// Values are encoded in order and concatenated. Each value is self-delimiting,
+    /// so the composite keys are ordered by their first value, then the second and so on.
     pub fn encode_composite(values: &[ScalarRef]) -> Vec<u8> {
         let mut buf = BytesMut::with_capacity(64);
         for value in values {
@@ -45,146 +76,340 @@ impl IndexKeyCodec {
         buf.to_vec()
     }
 
+    /// Encode the prefix shared by the keys of all the strings starting with `prefix`.
+    pub fn encode_string_prefix(prefix: &str) -> Vec<u8> {
+        let mut buf = BytesMut::with_capacity(prefix.len() + 1);
+        buf.put_u8(STRING_TAG);
+        Self::put_escaped(&mut buf, prefix.as_bytes());
+        buf.to_vec()
+    }
+
+    /// The keys of the values comparable with `value` start with the returned byte, and
+    /// are lower than the keys starting with the next byte.
+    pub fn type_tag(value: &ScalarRef) -> u8 {
+        match value {
+            ScalarRef::Null => NULL_TAG,
+            ScalarRef::Bool(_) => BOOL_TAG,
+            ScalarRef::Integer(_) | ScalarRef::Float(_) => NUMBER_TAG,
+            ScalarRef::Date(_) => DATE_TAG,
+            ScalarRef::LocalTime(_) => LOCAL_TIME_TAG,
+            ScalarRef::LocalDateTime(_) => LOCAL_DATE_TIME_TAG,
+            ScalarRef::ZonedDateTime(_) => ZONED_DATE_TIME_TAG,
+            ScalarRef::Duration(_) => DURATION_TAG,
+            ScalarRef::String(_) => STRING_TAG,
+            ScalarRef::VirtualNode(_) | ScalarRef::Node(_) => NODE_TAG,
+            ScalarRef::VirtualRel(_) | ScalarRef::Rel(_) => REL_TAG,
+            ScalarRef::VirtualPath(_) | ScalarRef::Path(_) => PATH_TAG,
+            ScalarRef::List(_) => LIST_TAG,
+            ScalarRef::Struct(_) => MAP_TAG,
+        }
+    }
+
     /// Encode a single value into the buffer.
     fn encode_value(buf: &mut BytesMut, value: &ScalarRef) {
+        buf.put_u8(Self::type_tag(value));
         match value {
-            ScalarRef::Null => {
-                buf.put_u8(NULL_TAG);
-            }
-            ScalarRef::Bool(b) => {
-                buf.put_u8(BOOL_TAG);
-                buf.put_u8(if *b { 1 } else { 0 });
-            }
-            ScalarRef::Integer(i) => {
-                buf.put_u8(INTEGER_TAG);
-                buf.put_i64_le(*i);
-            }
+            ScalarRef::Null => {}
+            ScalarRef::Bool(b) => buf.put_u8(*b as u8),
+            ScalarRef::Integer(i) => Self::put_integer(buf, *i),
             ScalarRef::Float(f) => {
-                buf.put_u8(FLOAT_TAG);
-                buf.put_f64_le(**f);
-            }
-            ScalarRef::Date(d) => {
-                buf.put_u8(DATE_TAG);
-                // Date is stored as days since epoch (i32)
-                buf.put_slice(&d.to_le_bytes());
-            }
-            ScalarRef::LocalTime(t) => {
-                buf.put_u8(LOCAL_TIME_TAG);
-                buf.put_slice(&t.to_le_bytes());
+                Self::put_f64(buf, **f);
+                Self::put_i64(buf, 0);
             }
+            ScalarRef::Date(d) => Self::put_i64(buf, d.0),
+            ScalarRef::LocalTime(t) => buf.put_u64(t.0),
             ScalarRef::LocalDateTime(dt) => {
-                buf.put_u8(LOCAL_DATE_TIME_TAG);
-                buf.put_slice(&dt.to_le_bytes());
+                Self::put_i64(buf, dt.seconds);
+                buf.put_u32(dt.nanoseconds);
             }
             ScalarRef::ZonedDateTime(dt) => {
-                buf.put_u8(ZONED_DATE_TIME_TAG);
-                buf.put_slice(&dt.to_le_bytes());
+                Self::put_i64(buf, dt.seconds);
+                buf.put_u32(dt.nanoseconds);
+            }
+            ScalarRef::Duration(d) => {
+                Self::put_i64(buf, d.months);
+                Self::put_i64(buf, d.days);
+                Self::put_i64(buf, d.seconds);
+                Self::put_i64(buf, d.nanoseconds);
             }
             ScalarRef::String(s) => {
-                buf.put_u8(STRING_TAG);
-                buf.put_u32_le(s.len() as u32);
-                buf.put_slice(s.as_bytes());
+                Self::put_escaped(buf, s.as_bytes());
+                buf.put_slice(&STRING_TERMINATOR);
             }
-            // Node/Rel/Path/Duration/Struct types are not supported as index keys
-            ScalarRef::VirtualNode(_)
-            | ScalarRef::VirtualRel(_)
-            | ScalarRef::Node(_)
-            | ScalarRef::Rel(_)
-            | ScalarRef::Duration(_)
-            | ScalarRef::VirtualPath(_)
-            | ScalarRef::Path(_)
-            | ScalarRef::Struct(_) => {
-                panic!("This type cannot be used as index key");
+            ScalarRef::VirtualNode(id) => buf.put_u64(id.0),
+            ScalarRef::Node(node) => buf.put_u64(node.id.0),
+            ScalarRef::VirtualRel(rel) => buf.put_u64(rel.id.0),
+            ScalarRef::Rel(rel) => buf.put_u64(rel.id.0),
+            // paths are ordered by their nodes and relationships, in path order
+            ScalarRef::VirtualPath(path) => {
+                let rels = path.rel_iter().map(|rel| rel.map(|rel| rel.id.0));
+                Self::put_path(buf, path.node_iter().map(|node| node.map(|id| id.0)), rels);
+            }
+            ScalarRef::Path(path) => {
+                let nodes = path.node_iter().map(|node| node.map(|node| node.id.0));
+                let rels = path.rel_iter().map(|rel| rel.map(|rel| rel.id.0));
+                Self::put_path(buf, nodes, rels);
             }
             ScalarRef::List(list) => {
-                // For list types, we encode the entire list
-                // This is mainly for completeness; lists as index keys are unusual
-                if let Some(integers) = list.as_integer_list() {
-                    buf.put_u8(LIST_INTEGER_TAG);
-                    buf.put_u32_le(integers.len() as u32);
-                    for i in integers {
-                        buf.put_i64_le(i);
-                    }
-                } else if let Some(floats) = list.as_float_list() {
-                    buf.put_u8(LIST_FLOAT_TAG);
-                    buf.put_u32_le(floats.len() as u32);
-                    for f in floats {
-                        buf.put_f64_le(*f);
-                    }
-                } else if let Some(strings) = list.as_string_list() {
-                    buf.put_u8(LIST_STRING_TAG);
-                    buf.put_u32_le(strings.len() as u32);
-                    for s in strings {
-                        buf.put_u32_le(s.len() as u32);
-                        buf.put_slice(s.as_bytes());
-                    }
-                } else {
-                    panic!("Unsupported list type for index key");
+                for elem in list.iter() {
+                    Self::encode_value(buf, &elem);
+                }
+                buf.put_u8(END_MARKER);
+            }
+            ScalarRef::Struct(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                for (key, value) in entries {
+                    buf.put_u8(ITEM_MARKER);
+                    Self::put_escaped(buf, key.as_bytes());
+                    buf.put_slice(&STRING_TERMINATOR);
+                    Self::encode_value(buf, &value);
                 }
+                buf.put_u8(END_MARKER);
             }
         }
     }
+
+    // the nearest float, then the remainder of the integer that the float can not represent
+    fn put_integer(buf: &mut BytesMut, i: i64) {
+        let approx = i as f64;
+        // |i - approx| is at most 2^10 for any i64, the difference can not overflow
+        let remainder = (i as i128 - approx as i128) as i64;
+        Self::put_f64(buf, approx);
+        Self::put_i64(buf, remainder);
+    }
+
+    // flip the sign bit of positive floats and all the bits of negative ones,
+    // -0.0 is the same as 0.0 and NaN is larger than any other number
+    fn put_f64(buf: &mut BytesMut, f: f64) {
+        let f = if f == 0.0 {
+            0.0
+        } else if f.is_nan() {
+            f64::NAN
+        } else {
+            f
+        };
+        let bits = f.to_bits();
+        let bits = if bits & (1 << 63) != 0 { !bits } else { bits | (1 << 63) };
+        buf.put_u64(bits);
+    }
+
+    // big endian with the sign bit flipped, so that negative values come first
+    fn put_i64(buf: &mut BytesMut, i: i64) {
+        buf.put_u64((i as u64) ^ (1 << 63));
+    }
+
+    fn put_escaped(buf: &mut BytesMut, bytes: &[u8]) {
+        for chunk in bytes.split_inclusive(|b| *b == 0) {
+            match chunk.split_last() {
+                Some((0, rest)) => {
+                    buf.put_slice(rest);
+                    buf.put_slice(&STRING_ESCAPED_ZERO);
+                }
+                _ => buf.put_slice(chunk),
+            }
+        }
+    }
+
+    fn put_path(
+        buf: &mut BytesMut,
+        nodes: impl Iterator<Item = Option<u64>>,
+        mut rels: impl Iterator<Item = Option<u64>>,
+    ) {
+        for node in nodes {
+            buf.put_u8(ITEM_MARKER);
+            buf.put_u64(node.unwrap_or_default());
+            if let Some(rel) = rels.next() {
+                buf.put_u8(ITEM_MARKER);
+                buf.put_u64(rel.unwrap_or_default());
+            }
+        }
+        buf.put_u8(END_MARKER);
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use super::*;
     use crate::data_type::F64;
-    use crate::scalar::ScalarValue;
+    use crate::order::cmp_datum;
+    use crate::scalar::{Date, Duration, ListValue, LocalDateTime, LocalTime, ScalarValue, ZonedDateTime};
+
+    fn encode(value: &ScalarValue) -> Vec<u8> {
+        IndexKeyCodec::encode_single(&value.as_scalar_ref())
+    }
+
+    fn list(values: Vec<ScalarValue>) -> ScalarValue {
+        ScalarValue::List(Box::new(ListValue::new(values)))
+    }
+
+    // the keys are in the order of the values, and equal values have equal keys
+    fn assert_order(values: &[ScalarValue]) {
+        for (i, lhs) in values.iter().enumerate() {
+            for rhs in &values[i + 1..] {
+                let ord = cmp_datum(Some(lhs.as_scalar_ref()), Some(rhs.as_scalar_ref()));
+                assert_eq!(encode(lhs).cmp(&encode(rhs)), ord, "{:?} {:?}", lhs, rhs);
+                assert_ne!(ord, Ordering::Greater, "{:?} {:?}", lhs, rhs);
+            }
+        }
+    }
 
     #[test]
-    fn test_encode_integer() {
-        let value = ScalarValue::Integer(12345);
-        let encoded = IndexKeyCodec::encode_single(&value.as_scalar_ref());
-        assert_eq!(encoded[0], INTEGER_TAG);
-        assert_eq!(encoded.len(), 9); // 1 tag + 8 bytes
+    fn test_number_order() {
+        assert_order(&[
+            ScalarValue::Float(F64::from(f64::NEG_INFINITY)),
+            ScalarValue::Integer(i64::MIN),
+            ScalarValue::Integer(-1_000_000),
+            ScalarValue::Float(F64::from(-2.5)),
+            ScalarValue::Integer(-1),
+            ScalarValue::Float(F64::from(-0.5)),
+            ScalarValue::Integer(0),
+            ScalarValue::Float(F64::from(0.5)),
+            ScalarValue::Integer(1),
+            ScalarValue::Integer(2),
+            ScalarValue::Float(F64::from(1e10)),
+            ScalarValue::Integer(i64::MAX),
+            ScalarValue::Float(F64::from(f64::INFINITY)),
+            ScalarValue::Float(F64::from(f64::NAN)),
+        ]);
     }
 
     #[test]
-    fn test_encode_string() {
-        let value = ScalarValue::String("hello".to_string());
-        let encoded = IndexKeyCodec::encode_single(&value.as_scalar_ref());
-        assert_eq!(encoded[0], STRING_TAG);
-        assert_eq!(encoded.len(), 1 + 4 + 5); // tag + len + "hello"
+    fn test_number_equality() {
+        assert_eq!(
+            encode(&ScalarValue::Integer(1)),
+            encode(&ScalarValue::Float(F64::from(1.0)))
+        );
+        assert_eq!(
+            encode(&ScalarValue::Float(F64::from(-0.0))),
+            encode(&ScalarValue::Float(F64::from(0.0)))
+        );
+        // integers beyond the precision of floats stay distinct
+        let large = 1i64 << 60;
+        assert!(encode(&ScalarValue::Integer(large)) < encode(&ScalarValue::Integer(large + 1)));
+        assert!(encode(&ScalarValue::Integer(-large - 1)) < encode(&ScalarValue::Integer(-large)));
     }
 
     #[test]
-    fn test_encode_composite() {
-        let v1 = ScalarValue::String("alice@example.com".to_string());
-        let v2 = ScalarValue::Integer(30);
-        let values = vec![v1.as_scalar_ref(), v2.as_scalar_ref()];
-        let encoded = IndexKeyCodec::encode_composite(&values);
+    fn test_string_order() {
+        assert_order(&[
+            ScalarValue::String("".to_string()),
+            ScalarValue::String("a".to_string()),
+            ScalarValue::String("a\0".to_string()),
+            ScalarValue::String("a\0b".to_string()),
+            ScalarValue::String("ab".to_string()),
+            ScalarValue::String("b".to_string()),
+            ScalarValue::String("é".to_string()),
+        ]);
+    }
 
-        // String: 1 + 4 + 17 = 22 bytes
-        // Integer: 1 + 8 = 9 bytes
-        // Total: 31 bytes
-        assert_eq!(encoded.len(), 22 + 9);
+    #[test]
+    fn test_string_prefix() {
+        let prefix = IndexKeyCodec::encode_string_prefix("ab");
+        assert!(encode(&ScalarValue::String("ab".to_string())).starts_with(&prefix));
+        assert!(encode(&ScalarValue::String("abc".to_string())).starts_with(&prefix));
+        assert!(!encode(&ScalarValue::String("a".to_string())).starts_with(&prefix));
+        assert!(!encode(&ScalarValue::String("ac".to_string())).starts_with(&prefix));
     }
 
     #[test]
-    fn test_encode_null() {
-        let value = ScalarValue::Unknown;
-        let encoded = IndexKeyCodec::encode_single(&value.as_scalar_ref());
-        assert_eq!(encoded, vec![NULL_TAG]);
+    fn test_temporal_order() {
+        assert_order(&[
+            ScalarValue::LocalDateTime(LocalDateTime {
+                seconds: -10,
+                nanoseconds: 5,
+            }),
+            ScalarValue::LocalDateTime(LocalDateTime {
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+            ScalarValue::LocalDateTime(LocalDateTime {
+                seconds: 0,
+                nanoseconds: 1,
+            }),
+            ScalarValue::Date(Date(-1)),
+            ScalarValue::Date(Date(0)),
+            ScalarValue::Date(Date(19_000)),
+            ScalarValue::LocalTime(LocalTime(0)),
+            ScalarValue::LocalTime(LocalTime(1_000_000_000)),
+            ScalarValue::Duration(Duration {
+                months: 0,
+                days: -1,
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+            ScalarValue::Duration(Duration {
+                months: 0,
+                days: 1,
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+        ]);
     }
 
     #[test]
-    fn test_encode_bool() {
-        let true_val = ScalarValue::Bool(true);
-        let false_val = ScalarValue::Bool(false);
+    fn test_zoned_datetime_instant() {
+        let utc = ZonedDateTime {
+            seconds: 3600,
+            nanoseconds: 0,
+            tz_offset_seconds: 0,
+        };
+        let other_zone = ZonedDateTime {
+            tz_offset_seconds: 7200,
+            ..utc
+        };
+        let later = ZonedDateTime { seconds: 3601, ..utc };
+        assert_eq!(
+            encode(&ScalarValue::ZonedDateTime(utc)),
+            encode(&ScalarValue::ZonedDateTime(other_zone))
+        );
+        assert!(encode(&ScalarValue::ZonedDateTime(other_zone)) < encode(&ScalarValue::ZonedDateTime(later)));
+    }
 
-        let encoded_true = IndexKeyCodec::encode_single(&true_val.as_scalar_ref());
-        let encoded_false = IndexKeyCodec::encode_single(&false_val.as_scalar_ref());
+    #[test]
+    fn test_list_order() {
+        assert_order(&[
+            list(vec![]),
+            list(vec![ScalarValue::String("a".to_string())]),
+            list(vec![ScalarValue::Integer(1)]),
+            list(vec![ScalarValue::Integer(1), ScalarValue::Integer(-5)]),
+            list(vec![ScalarValue::Integer(1), ScalarValue::Integer(2)]),
+            list(vec![ScalarValue::Float(F64::from(1.5))]),
+            list(vec![ScalarValue::Integer(2)]),
+        ]);
+    }
 
-        assert_eq!(encoded_true, vec![BOOL_TAG, 1]);
-        assert_eq!(encoded_false, vec![BOOL_TAG, 0]);
+    #[test]
+    fn test_cross_type_order() {
+        assert_order(&[
+            list(vec![ScalarValue::Integer(1)]),
+            ScalarValue::LocalDateTime(LocalDateTime {
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+            ScalarValue::Date(Date(0)),
+            ScalarValue::LocalTime(LocalTime(0)),
+            ScalarValue::String("z".to_string()),
+            ScalarValue::Bool(false),
+            ScalarValue::Bool(true),
+            ScalarValue::Integer(-3),
+            ScalarValue::Unknown,
+        ]);
+        let tag = IndexKeyCodec::type_tag(&ScalarRef::Integer(0));
+        let key = encode(&ScalarValue::Float(F64::from(-1e300)));
+        assert!(key.as_slice() > [tag].as_slice() && key.as_slice() < [tag + 1].as_slice());
     }
 
     #[test]
-    fn test_encode_float() {
-        let value = ScalarValue::Float(F64::from(3.333));
-        let encoded = IndexKeyCodec::encode_single(&value.as_scalar_ref());
-        assert_eq!(encoded[0], FLOAT_TAG);
-        assert_eq!(encoded.len(), 9); // 1 tag + 8 bytes
+    fn test_composite_prefix() {
+        let key = |s: &str, i: i64| IndexKeyCodec::encode_composite(&[ScalarRef::String(s), ScalarRef::Integer(i)]);
+        let prefix = IndexKeyCodec::encode_single(&ScalarRef::String("c1"));
+        assert!(key("c1", -5) < key("c1", 3));
+        assert!(key("c1", 3) < key("c10", -5));
+        assert!(key("c1", 100) < key("c2", 0));
+        assert!(key("c1", 100).starts_with(&prefix));
+        assert!(!key("c10", 0).starts_with(&prefix));
     }
 }
@@ -1,5 +1,9 @@
+use std::cmp::Ordering;
+
 use derive_more;
 
+use crate::data_type::F64;
+use crate::scalar::{ListValue, ScalarPartialOrd, ScalarRef, ScalarValue, StructValue};
 use crate::variable::VariableName;
 
 #[derive(Clone, Debug, Copy, Default, PartialEq, Eq, derive_more::Display)]
@@ -15,3 +19,193 @@ pub struct ColumnOrder {
     pub column: VariableName,
     pub direction: SortDirection,
 }
+
+/// Compare two values with the given direction.
+///
+/// Null is the largest value, so it goes last for ASC and first for DESC.
+pub fn cmp_datum_with_direction(
+    lhs: Option<ScalarRef<'_>>,
+    rhs: Option<ScalarRef<'_>>,
+    direction: SortDirection,
+) -> Ordering {
+    let ord = cmp_datum(lhs, rhs);
+    match direction {
+        SortDirection::Asc => ord,
+        SortDirection::Desc => ord.reverse(),
+    }
+}
+
+/// Total order over all values, following the openCypher orderability rules.
+///
+/// Values of different types are ordered by type:
+///   map < node < relationship < list < path < zoned datetime < local datetime
+///   < date < local time < duration < string < boolean < number < null
+///
+/// Integers and floats are compared numerically, NaN is larger than any other number.
+pub fn cmp_datum(lhs: Option<ScalarRef<'_>>, rhs: Option<ScalarRef<'_>>) -> Ordering {
+    cmp_scalar(lhs.unwrap_or(ScalarRef::Null), rhs.unwrap_or(ScalarRef::Null))
+}
+
+fn cmp_scalar(lhs: ScalarRef<'_>, rhs: ScalarRef<'_>) -> Ordering {
+    match type_rank(&lhs).cmp(&type_rank(&rhs)) {
+        Ordering::Equal => {}
+        ord => return ord,
+    }
+
+    match (lhs, rhs) {
+        (ScalarRef::Null, ScalarRef::Null) => Ordering::Equal,
+        (ScalarRef::Bool(x), ScalarRef::Bool(y)) => x.cmp(&y),
+        (ScalarRef::Integer(x), ScalarRef::Integer(y)) => x.cmp(&y),
+        (ScalarRef::Float(x), ScalarRef::Float(y)) => x.cmp(&y),
+        (ScalarRef::Integer(x), ScalarRef::Float(y)) => F64::from(x as f64).cmp(&y),
+        (ScalarRef::Float(x), ScalarRef::Integer(y)) => x.cmp(&F64::from(y as f64)),
+        (ScalarRef::String(x), ScalarRef::String(y)) => x.cmp(y),
+        (ScalarRef::List(x), ScalarRef::List(y)) => {
+            for (v1, v2) in x.iter().zip(y.iter()) {
+                match cmp_scalar(v1, v2) {
+                    Ordering::Equal => continue,
+                    ord => return ord,
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        (ScalarRef::Struct(x), ScalarRef::Struct(y)) => {
+            for ((k1, v1), (k2, v2)) in x.iter().zip(y.iter()) {
+                match k1.cmp(k2).then_with(|| cmp_scalar(v1, v2)) {
+                    Ordering::Equal => continue,
+                    ord => return ord,
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        (lhs, rhs) if lhs.get_node_id().is_some() => lhs.get_node_id().cmp(&rhs.get_node_id()),
+        (lhs, rhs) if rel_id(&lhs).is_some() => rel_id(&lhs).cmp(&rel_id(&rhs)),
+        // temporal types and paths of the same kind, incomparable values are treated as equal
+        (lhs, rhs) => lhs.scalar_partial_cmp(&rhs).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// A key of the value for hashing, values that are equivalent by the openCypher rules have equal keys.
+///
+/// Floats with an integral value are replaced by integers, also inside lists and maps,
+/// so that `1` and `1.0` are the same grouping key or distinct value.
+pub fn equivalence_key(value: ScalarRef<'_>) -> ScalarValue {
+    match value {
+        // i64::MIN and i64::MAX + 1 are exactly representable
+        ScalarRef::Float(f) if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < -(i64::MIN as f64) => {
+            ScalarValue::Integer(*f as i64)
+        }
+        ScalarRef::List(list) => {
+            ScalarValue::List(Box::new(ListValue::new(list.iter().map(equivalence_key).collect())))
+        }
+        ScalarRef::Struct(map) => ScalarValue::Struct(Box::new(StructValue::new(
+            map.iter()
+                .map(|(key, value)| (key.clone(), equivalence_key(value)))
+                .collect(),
+        ))),
+        value => value.to_owned_scalar(),
+    }
+}
+
+fn rel_id(value: &ScalarRef<'_>) -> Option<crate::RelationshipId> {
+    match value {
+        ScalarRef::VirtualRel(rel) => Some(rel.id),
+        ScalarRef::Rel(rel) => Some(rel.id),
+        _ => None,
+    }
+}
+
+fn type_rank(value: &ScalarRef<'_>) -> u8 {
+    match value {
+        ScalarRef::Struct(_) => 0,
+        ScalarRef::VirtualNode(_) | ScalarRef::Node(_) => 1,
+        ScalarRef::VirtualRel(_) | ScalarRef::Rel(_) => 2,
+        ScalarRef::List(_) => 3,
+        ScalarRef::VirtualPath(_) | ScalarRef::Path(_) => 4,
+        ScalarRef::ZonedDateTime(_) => 5,
+        ScalarRef::LocalDateTime(_) => 6,
+        ScalarRef::Date(_) => 7,
+        ScalarRef::LocalTime(_) => 8,
+        ScalarRef::Duration(_) => 9,
+        ScalarRef::String(_) => 10,
+        ScalarRef::Bool(_) => 11,
+        ScalarRef::Integer(_) | ScalarRef::Float(_) => 12,
+        ScalarRef::Null => 13,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::ScalarValue;
+
+    fn sorted(mut values: Vec<Option<ScalarValue>>, direction: SortDirection) -> Vec<Option<ScalarValue>> {
+        values.sort_by(|a, b| {
+            cmp_datum_with_direction(
+                a.as_ref().map(|x| x.as_scalar_ref()),
+                b.as_ref().map(|x| x.as_scalar_ref()),
+                direction,
+            )
+        });
+        values
+    }
+
+    #[test]
+    fn test_cross_type_order() {
+        let values = vec![
+            None,
+            Some(ScalarValue::Integer(1)),
+            Some(ScalarValue::Bool(true)),
+            Some(ScalarValue::String("a".to_string())),
+            Some(ScalarValue::Float(F64::from(0.5))),
+            Some(ScalarValue::Bool(false)),
+        ];
+        let expected = vec![
+            Some(ScalarValue::String("a".to_string())),
+            Some(ScalarValue::Bool(false)),
+            Some(ScalarValue::Bool(true)),
+            Some(ScalarValue::Float(F64::from(0.5))),
+            Some(ScalarValue::Integer(1)),
+            None,
+        ];
+        assert_eq!(sorted(values.clone(), SortDirection::Asc), expected);
+        assert_eq!(
+            sorted(values, SortDirection::Desc),
+            expected.into_iter().rev().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_number_order() {
+        let nan = Some(ScalarValue::Float(F64::from(f64::NAN)));
+        let one = Some(ScalarValue::Integer(1));
+        let one_f = Some(ScalarValue::Float(F64::from(1.0)));
+        let two = Some(ScalarValue::Integer(2));
+        let cmp = |a: &Option<ScalarValue>, b: &Option<ScalarValue>| {
+            cmp_datum(
+                a.as_ref().map(|x| x.as_scalar_ref()),
+                b.as_ref().map(|x| x.as_scalar_ref()),
+            )
+        };
+        assert_eq!(cmp(&one, &one_f), Ordering::Equal);
+        assert_eq!(cmp(&one_f, &two), Ordering::Less);
+        assert_eq!(cmp(&two, &nan), Ordering::Less);
+        assert_eq!(cmp(&nan, &None), Ordering::Less);
+    }
+
+    #[test]
+    fn test_equivalence_key() {
+        let key = |value: ScalarValue| equivalence_key(value.as_scalar_ref());
+        let float = |f: f64| ScalarValue::Float(F64::from(f));
+        let list = |values: Vec<ScalarValue>| ScalarValue::List(Box::new(ListValue::new(values)));
+        assert_eq!(key(float(1.0)), key(ScalarValue::Integer(1)));
+        assert_eq!(key(float(-0.0)), key(ScalarValue::Integer(0)));
+        assert_ne!(key(float(1.5)), key(ScalarValue::Integer(1)));
+        assert_eq!(key(float(f64::NAN)), key(float(f64::NAN)));
+        assert_eq!(key(float(1e300)), float(1e300));
+        assert_eq!(
+            key(list(vec![float(2.0), ScalarValue::Unknown])),
+            key(list(vec![ScalarValue::Integer(2), ScalarValue::Unknown]))
+        );
+    }
+}
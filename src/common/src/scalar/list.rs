@@ -24,6 +24,10 @@ impl ListValue {
     pub fn new(values: Vec<ScalarValue>) -> Self {
         Self { values }
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ScalarValue> {
+        self.values.iter()
+    }
 }
 
 #[derive(Debug, Clone, Copy, derive_more::Display)]
@@ -2,14 +2,17 @@ use std::path::Path;
 use std::sync::Arc;
 
 use elio_catalog::Catalog;
-use elio_exec::task::ExecContext;
+use elio_exec::task::{ExecConfig, ExecContext};
 use elio_storage::graph::GraphStore;
+use elio_storage::transaction::IsolationLevel;
 
 use crate::error::Error;
 use crate::session::Session;
 
 pub struct DbConfig {
     store_path: String,
+    exec: ExecConfig,
+    isolation: IsolationLevel,
 }
 
 impl DbConfig {
@@ -20,8 +23,22 @@ impl DbConfig {
                 .to_str()
                 .expect("database path must be valid utf-8 string")
                 .to_string(),
+            exec: ExecConfig::default(),
+            isolation: IsolationLevel::default(),
         }
     }
+
+    /// Memory budget in bytes of a single sort before it spills to disk.
+    pub fn with_sort_memory_budget(mut self, bytes: usize) -> Self {
+        self.exec.sort_memory_budget = bytes;
+        self
+    }
+
+    /// Isolation level of the transactions, snapshot isolation by default.
+    pub fn with_isolation_level(mut self, isolation: IsolationLevel) -> Self {
+        self.isolation = isolation;
+        self
+    }
 }
 
 pub struct DbEnv {
@@ -32,9 +49,9 @@ pub struct DbEnv {
 
 impl DbEnv {
     pub fn open(config: &DbConfig) -> Result<Arc<DbEnv>, Error> {
-        let store = Arc::new(GraphStore::open(&config.store_path)?);
+        let store = Arc::new(GraphStore::open(&config.store_path)?.with_isolation_level(config.isolation));
         let catalog = Arc::new(Catalog::new(store.token_store().clone()));
-        let exec_ctx = Arc::new(ExecContext::new(catalog.clone(), store.clone()));
+        let exec_ctx = Arc::new(ExecContext::new(catalog.clone(), store.clone(), config.exec.clone()));
         let me = Self { catalog, exec_ctx };
         Ok(Arc::new(me))
     }
@@ -3,16 +3,18 @@
 //! Handles CREATE CONSTRAINT and DROP CONSTRAINT statements.
 
 use std::backtrace::Backtrace;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use elio_common::RelationshipId;
 use elio_common::array::Array;
 use elio_common::mapb::IndexKeyCodec;
+use elio_common::scalar::{ScalarRef, StructValueRef};
 use elio_exec::error::ExecError;
 use elio_parser::ast;
 use elio_storage::constraint::{ConstraintKind, ConstraintMeta, EntityType};
 use elio_storage::graph::GraphStore;
-use elio_storage::transaction::NodeScanOptions;
+use elio_storage::transaction::{NodeScanOptions, RelScanOptions, TransactionImpl};
 
 use crate::error::Error;
 
@@ -22,7 +24,7 @@ use crate::error::Error;
 /// 1. Validates the constraint doesn't already exist
 /// 2. Acquires an exclusive lock on the label
 /// 3. Scans existing data to check for constraint violations
-/// 4. Builds the unique index for existing data
+/// 4. Builds the unique index for existing data, or checks the existing data has the NOT NULL properties
 /// 5. Stores the constraint metadata
 pub fn create_constraint(store: &Arc<GraphStore>, constraint: &ast::CreateConstraint) -> Result<(), Error> {
     let tx = store.transaction();
@@ -43,29 +45,41 @@ pub fn create_constraint(store: &Arc<GraphStore>, constraint: &ast::CreateConstr
 
     let properties: Vec<String> = match &constraint.constraint_type {
         ast::ConstraintType::Unique { properties } => properties.iter().map(|p| p.property.clone()).collect(),
-        ast::ConstraintType::NodeKey { properties } => properties.iter().map(|p| p.property.clone()).collect(),
+        ast::ConstraintType::NodeKey { properties } | ast::ConstraintType::RelationshipKey { properties } => {
+            properties.iter().map(|p| p.property.clone()).collect()
+        }
         ast::ConstraintType::NotNull { property } => vec![property.property.clone()],
     };
 
-    let constraint_kind = match &constraint.constraint_type {
-        ast::ConstraintType::Unique { .. } => ConstraintKind::Unique,
-        ast::ConstraintType::NodeKey { .. } => ConstraintKind::NodeKey,
-        ast::ConstraintType::NotNull { .. } => ConstraintKind::NotNull,
+    let constraint_kind = match (&constraint.constraint_type, entity_type) {
+        (ast::ConstraintType::Unique { .. }, _) => ConstraintKind::Unique,
+        (ast::ConstraintType::NodeKey { .. }, EntityType::Node) => ConstraintKind::NodeKey,
+        (ast::ConstraintType::RelationshipKey { .. }, EntityType::Relationship) => ConstraintKind::RelationshipKey,
+        (ast::ConstraintType::NotNull { .. }, _) => ConstraintKind::NotNull,
+        (constraint_type, _) => {
+            return Err(Error::InvalidConstraint(format!(
+                "{} is not allowed for {}",
+                constraint_type, constraint.entity
+            )));
+        }
     };
 
-    // 3. Get or create token IDs
-    let label_id = store.token_store().get_or_create_label_id(&label_name)?;
+    // 3. Get or create token IDs, a relationship constraint is keyed by the relationship type
+    let label_id = match entity_type {
+        EntityType::Node => store.token_store().get_or_create_label_id(&label_name)?,
+        EntityType::Relationship => store.token_store().get_or_create_reltype_id(&label_name)?,
+    };
     let prop_key_ids: Vec<u16> = properties
         .iter()
         .map(|p| store.token_store().get_or_create_property_key_id(p))
         .collect::<Result<_, _>>()?;
 
     // 4. Acquire exclusive lock for the label
-    let _lock = store.acquire_label_write(label_id);
+    let mut lock = store.acquire_label_write(label_id);
 
-    // 5. For UNIQUE/NODE KEY constraints, scan existing data and check for duplicates
-    if matches!(constraint_kind, ConstraintKind::Unique | ConstraintKind::NodeKey) {
-        backfill_unique_index(
+    // 5. For UNIQUE/NODE KEY/RELATIONSHIP KEY constraints, scan existing data and check for duplicates
+    match (entity_type, constraint_kind) {
+        (EntityType::Node, ConstraintKind::Unique | ConstraintKind::NodeKey) => backfill_unique_index(
             store,
             &tx,
             &label_name,
@@ -73,7 +87,26 @@ pub fn create_constraint(store: &Arc<GraphStore>, constraint: &ast::CreateConstr
             &properties,
             &prop_key_ids,
             &constraint.name,
-        )?;
+        )?,
+        (EntityType::Relationship, ConstraintKind::Unique | ConstraintKind::RelationshipKey) => {
+            backfill_rel_unique_index(
+                &tx,
+                label_id,
+                &properties,
+                &prop_key_ids,
+                &constraint.name,
+                constraint_kind == ConstraintKind::RelationshipKey,
+            )?
+        }
+        _ => {}
+    }
+
+    // For NOT NULL constraints, all the existing nodes or relationships must have the property
+    if constraint_kind == ConstraintKind::NotNull {
+        match entity_type {
+            EntityType::Node => validate_nodes_not_null(&tx, &label_name, &properties, &constraint.name)?,
+            EntityType::Relationship => validate_rels_not_null(&tx, label_id, &properties, &constraint.name)?,
+        }
     }
 
     // 6. Store constraint metadata
@@ -86,8 +119,9 @@ pub fn create_constraint(store: &Arc<GraphStore>, constraint: &ast::CreateConstr
     };
     tx.put_constraint(&meta)?;
 
-    // 7. Commit the transaction
+    // 7. Commit the transaction, the transactions writing the label since before it fail to commit
     tx.commit()?;
+    lock.set_schema_seq(store.last_commit_seq());
 
     Ok(())
 }
@@ -159,6 +193,144 @@ fn backfill_unique_index(
     Ok(())
 }
 
+/// Backfill the unique index of a relationship type for existing data
+///
+/// Scans all relationships of the type, checks for duplicate values, and for a RELATIONSHIP KEY also for missing
+/// properties.
+fn backfill_rel_unique_index(
+    tx: &Arc<TransactionImpl>,
+    reltype_id: u16,
+    properties: &[String],
+    prop_key_ids: &[u16],
+    constraint_name: &str,
+    is_key: bool,
+) -> Result<(), Error> {
+    let mut seen_values: HashMap<Vec<u8>, RelationshipId> = HashMap::new();
+
+    let opts = RelScanOptions {
+        batch_size: 1024,
+        reltypes: vec![reltype_id],
+    };
+    let mut iter = tx.rel_scan(opts)?;
+
+    while let Some(chunk) = iter.next_batch()? {
+        let column = chunk.column(0);
+        let rels = column.as_rel().unwrap();
+        for rel in rels.iter().flatten() {
+            let Some(prop_values) = extract_property_values(&rel.props, properties) else {
+                if is_key && let Some(prop_name) = find_missing_property(&rel.props, properties) {
+                    return Err(ExecError::ConstraintViolation {
+                        constraint: constraint_name.to_string(),
+                        reason: format!(
+                            "Relationship({}) with type '{}' does not have property '{}'",
+                            rel.id, rel.reltype, prop_name
+                        ),
+                        trace: Backtrace::capture(),
+                    }
+                    .into());
+                }
+                continue;
+            };
+
+            if let Some(other) = seen_values.insert(prop_values.concat(), rel.id) {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint_name.to_string(),
+                    reason: format!(
+                        "Relationship({}) and Relationship({}) with type '{}' have the same value for property {:?}",
+                        other, rel.id, rel.reltype, properties
+                    ),
+                    trace: Backtrace::capture(),
+                }
+                .into());
+            }
+
+            let prop_value_refs: Vec<&[u8]> = prop_values.iter().map(|v| v.as_slice()).collect();
+            tx.put_rel_unique_index(reltype_id, prop_key_ids, &prop_value_refs, rel.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check all the nodes with the label have the properties
+fn validate_nodes_not_null(
+    tx: &Arc<TransactionImpl>,
+    label_name: &str,
+    properties: &[String],
+    constraint_name: &str,
+) -> Result<(), Error> {
+    let opts = NodeScanOptions { batch_size: 1024 };
+    let mut iter = tx.node_scan(opts)?;
+
+    while let Some(chunk) = iter.next_batch()? {
+        let vis = chunk.visibility().clone();
+        let column = chunk.column(0);
+        let node_ids = column.as_virtual_node().unwrap();
+        let nodes = tx.materialize_node(node_ids, &vis)?;
+
+        for node in nodes.iter().flatten() {
+            if !node.labels.iter().any(|l| l.as_ref() == label_name) {
+                continue;
+            }
+            if let Some(prop_name) = find_missing_property(&node.props, properties) {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint_name.to_string(),
+                    reason: format!(
+                        "Node({}) with label '{}' does not have property '{}'",
+                        node.id, label_name, prop_name
+                    ),
+                    trace: Backtrace::capture(),
+                }
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check all the relationships of the type have the properties
+fn validate_rels_not_null(
+    tx: &Arc<TransactionImpl>,
+    reltype_id: u16,
+    properties: &[String],
+    constraint_name: &str,
+) -> Result<(), Error> {
+    let opts = RelScanOptions {
+        batch_size: 1024,
+        reltypes: vec![reltype_id],
+    };
+    let mut iter = tx.rel_scan(opts)?;
+
+    while let Some(chunk) = iter.next_batch()? {
+        let column = chunk.column(0);
+        let rels = column.as_rel().unwrap();
+        for rel in rels.iter().flatten() {
+            if let Some(prop_name) = find_missing_property(&rel.props, properties) {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint_name.to_string(),
+                    reason: format!(
+                        "Relationship({}) with type '{}' does not have property '{}'",
+                        rel.id, rel.reltype, prop_name
+                    ),
+                    trace: Backtrace::capture(),
+                }
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The first of the properties which is missing or NULL
+fn find_missing_property<'a>(props: &StructValueRef<'_>, property_names: &'a [String]) -> Option<&'a str> {
+    property_names
+        .iter()
+        .find(|prop_name| matches!(props.field_at(prop_name), None | Some(ScalarRef::Null)))
+        .map(String::as_str)
+}
+
 /// Extract property values from a node's properties
 fn extract_property_values(
     props: &elio_common::scalar::StructValueRef<'_>,
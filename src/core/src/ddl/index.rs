@@ -0,0 +1,98 @@
+//! Index DDL operations
+//!
+//! Handles CREATE INDEX and DROP INDEX statements.
+
+use std::sync::Arc;
+
+use elio_parser::ast;
+use elio_storage::graph::GraphStore;
+use elio_storage::index::IndexMeta;
+
+use crate::error::Error;
+
+/// Execute CREATE INDEX statement
+///
+/// This function:
+/// 1. Acquires an exclusive lock on the label, then opens the transaction
+/// 2. Validates no index with the same name, or on the same label and properties, exists
+/// 3. Adds the entries of the existing nodes
+/// 4. Stores the index metadata
+pub fn create_index(store: &Arc<GraphStore>, index: &ast::CreateIndex) -> Result<(), Error> {
+    if let Some(prop) = index.properties.iter().find(|p| p.variable != index.variable) {
+        return Err(Error::InvalidIndex(format!(
+            "property {} does not belong to variable {}",
+            prop, index.variable
+        )));
+    }
+
+    let label_id = store.token_store().get_or_create_label_id(&index.label)?;
+    let property_key_ids: Vec<u16> = index
+        .properties
+        .iter()
+        .map(|p| store.token_store().get_or_create_property_key_id(&p.property))
+        .collect::<Result<_, _>>()?;
+
+    // the snapshot is taken after the lock, so that it has the nodes of all the writers before
+    let mut lock = store.acquire_label_write(label_id);
+    let tx = store.transaction();
+
+    if tx.get_index(&index.name)?.is_some() {
+        if index.if_not_exists {
+            return Ok(());
+        }
+        return Err(Error::IndexAlreadyExists(index.name.clone()));
+    }
+
+    if let Some(existing) = tx
+        .get_indexes_for_label(label_id)?
+        .into_iter()
+        .find(|meta| meta.property_key_ids == property_key_ids)
+    {
+        if index.if_not_exists {
+            return Ok(());
+        }
+        return Err(Error::InvalidIndex(format!(
+            "index '{}' already exists on the same label and properties",
+            existing.name
+        )));
+    }
+
+    let meta = IndexMeta {
+        name: index.name.clone(),
+        label_id,
+        property_key_ids,
+    };
+    tx.build_index(&meta)?;
+    tx.put_index(&meta)?;
+    tx.commit()?;
+    // the transactions writing the label since before the index fail to commit, their nodes may miss entries
+    lock.set_schema_seq(store.last_commit_seq());
+
+    Ok(())
+}
+
+/// Execute DROP INDEX statement, the index entries are deleted with the metadata
+pub fn drop_index(store: &Arc<GraphStore>, index: &ast::DropIndex) -> Result<(), Error> {
+    let Some(meta) = store.transaction().get_index(&index.name)? else {
+        if index.if_exists {
+            return Ok(());
+        }
+        return Err(Error::IndexNotFound(index.name.clone()));
+    };
+
+    // the transactions writing the label since before the drop fail to commit, they would add entries to the index.
+    // The snapshot is taken after the lock, so that it has the entries of all the writers before.
+    let mut lock = store.acquire_label_write(meta.label_id);
+    let tx = store.transaction();
+    if tx.get_index(&index.name)?.is_none() {
+        if index.if_exists {
+            return Ok(());
+        }
+        return Err(Error::IndexNotFound(index.name.clone()));
+    }
+    tx.delete_index(&index.name)?;
+    tx.commit()?;
+    lock.set_schema_seq(store.last_commit_seq());
+
+    Ok(())
+}
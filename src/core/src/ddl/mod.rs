@@ -3,8 +3,11 @@
 //! This module handles schema-related operations like:
 //! - CREATE CONSTRAINT
 //! - DROP CONSTRAINT
-//! - (future) CREATE INDEX, DROP INDEX, etc.
+//! - CREATE INDEX
+//! - DROP INDEX
 
 mod constraint;
+mod index;
 
 pub use constraint::{create_constraint, drop_constraint};
+pub use index::{create_index, drop_index};
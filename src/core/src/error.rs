@@ -2,20 +2,18 @@ use std::backtrace::Backtrace;
 
 use elio_cypher::error::PlanError;
 use elio_exec::error::ExecError;
+use elio_storage::error::GraphStoreError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("open db failed")]
-    OpenDbFailed {
-        #[from]
-        source: elio_storage::error::GraphStoreError,
-    },
+    OpenDbFailed { source: GraphStoreError },
 
     #[error("{0}")]
     PlanError(#[from] PlanError, #[backtrace] Backtrace),
     #[error("{0}")]
-    ExecError(#[from] ExecError, #[backtrace] Backtrace),
+    ExecError(ExecError, #[backtrace] Backtrace),
 
     // DDL errors
     #[error("constraint '{0}' already exists")]
@@ -23,4 +21,56 @@ pub enum Error {
 
     #[error("constraint '{0}' not found")]
     ConstraintNotFound(String),
+
+    #[error("invalid constraint: {0}")]
+    InvalidConstraint(String),
+
+    #[error("index '{0}' already exists")]
+    IndexAlreadyExists(String),
+
+    #[error("index '{0}' not found")]
+    IndexNotFound(String),
+
+    #[error("invalid index: {0}")]
+    InvalidIndex(String),
+
+    // transaction errors
+    #[error("a transaction is already open in this session")]
+    TransactionAlreadyOpen,
+
+    #[error("no transaction is open in this session")]
+    NoOpenTransaction,
+
+    #[error("schema changes are not allowed in an explicit transaction")]
+    DdlInTransaction,
+
+    /// The transaction conflicts with a concurrent one and has been rolled back, it can be retried
+    #[error("{0}")]
+    TransactionConflict(GraphStoreError),
+}
+
+impl Error {
+    /// The statement or transaction may succeed if it is run again
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::TransactionConflict(_))
+    }
+}
+
+impl From<GraphStoreError> for Error {
+    fn from(source: GraphStoreError) -> Self {
+        if source.is_retryable() {
+            Self::TransactionConflict(source)
+        } else {
+            Self::OpenDbFailed { source }
+        }
+    }
+}
+
+impl From<ExecError> for Error {
+    fn from(e: ExecError) -> Self {
+        match e {
+            ExecError::StoreError(source, _) if source.is_retryable() => Self::TransactionConflict(source),
+            e => Self::ExecError(e, Backtrace::capture()),
+        }
+    }
 }
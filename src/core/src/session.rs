@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_stream::stream;
 use elio_catalog::Catalog;
@@ -11,7 +11,7 @@ use elio_common::{LabelId, PropertyKeyId, TokenId, TokenKind};
 use elio_cypher::plan_context::PlanContext;
 use elio_cypher::session::{IndexHint, PlannerSession, parse_statement, plan_query};
 use elio_exec::error::ExecError;
-use elio_exec::task::{ExecContext, create_task};
+use elio_exec::task::{ExecContext, ExplicitTransaction, create_task};
 use elio_parser::ast;
 use futures::Stream;
 use futures::stream::BoxStream;
@@ -25,11 +25,47 @@ use crate::result::ResultHandle;
 pub struct Session {
     pub(crate) catalog: Arc<Catalog>,
     pub(crate) exec_ctx: Arc<ExecContext>,
+    // transaction opened by BEGIN, None if every statement commits on its own
+    transaction: Mutex<Option<Arc<ExplicitTransaction>>>,
 }
 
 impl Session {
     pub fn new(catalog: Arc<Catalog>, exec_ctx: Arc<ExecContext>) -> Self {
-        Self { catalog, exec_ctx }
+        Self {
+            catalog,
+            exec_ctx,
+            transaction: Mutex::new(None),
+        }
+    }
+
+    /// Open an explicit transaction, the following statements run in it until commit or rollback
+    pub fn begin(&self) -> Result<(), Error> {
+        let mut transaction = self.transaction.lock().unwrap();
+        if transaction.is_some() {
+            return Err(Error::TransactionAlreadyOpen);
+        }
+        *transaction = Some(Arc::new(ExplicitTransaction::begin(&self.exec_ctx)));
+        Ok(())
+    }
+
+    /// Commit the explicit transaction after its running statements finish
+    pub async fn commit(&self) -> Result<(), Error> {
+        let transaction = self.take_transaction()?;
+        Ok(transaction.commit().await?)
+    }
+
+    /// Discard the writes of the explicit transaction after its running statements finish
+    pub async fn rollback(&self) -> Result<(), Error> {
+        let transaction = self.take_transaction()?;
+        Ok(transaction.rollback().await?)
+    }
+
+    fn take_transaction(&self) -> Result<Arc<ExplicitTransaction>, Error> {
+        self.transaction.lock().unwrap().take().ok_or(Error::NoOpenTransaction)
+    }
+
+    fn current_transaction(&self) -> Option<Arc<ExplicitTransaction>> {
+        self.transaction.lock().unwrap().clone()
     }
 }
 
@@ -80,6 +116,19 @@ impl PlannerSession for Session {
         None
     }
 
+    fn find_indexes(&self, label_id: LabelId) -> Vec<IndexHint> {
+        let store = self.exec_ctx.store();
+        let indexes = store.index_store().get_indexes_for_label(label_id).unwrap_or_default();
+        indexes
+            .into_iter()
+            .map(|index| IndexHint {
+                constraint_name: index.name,
+                label_id: index.label_id,
+                property_key_ids: index.property_key_ids,
+            })
+            .collect()
+    }
+
     fn send_notification(&self, _notification: String) {
         todo!()
     }
@@ -89,21 +138,43 @@ impl Session {
     pub async fn execute(
         self: &Arc<Self>,
         query: String,
-        _params: HashMap<String, ScalarValue>,
+        params: HashMap<String, ScalarValue>,
     ) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
         let ast = parse_statement(&query)?;
         match ast {
-            ast::Statement::Query(regular_query) => self.handle_query(&regular_query).await,
+            ast::Statement::Query(regular_query) => self.handle_query(&regular_query, params).await,
             ast::Statement::CreateConstraint(constraint) => self.handle_create_constraint(&constraint).await,
             ast::Statement::DropConstraint(constraint) => self.handle_drop_constraint(&constraint).await,
+            ast::Statement::CreateIndex(index) => self.handle_create_index(&index).await,
+            ast::Statement::DropIndex(index) => self.handle_drop_index(&index).await,
+            ast::Statement::Begin => {
+                self.begin()?;
+                Ok(Box::pin(EmptyResultHandle::new(vec![])))
+            }
+            ast::Statement::Commit => {
+                self.commit().await?;
+                Ok(Box::pin(EmptyResultHandle::new(vec![])))
+            }
+            ast::Statement::Rollback => {
+                self.rollback().await?;
+                Ok(Box::pin(EmptyResultHandle::new(vec![])))
+            }
         }
     }
 
-    async fn handle_query(self: &Arc<Self>, query: &ast::RegularQuery) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
-        let plan = plan_query(self.clone(), query)?;
+    async fn handle_query(
+        self: &Arc<Self>,
+        query: &ast::RegularQuery,
+        params: HashMap<String, ScalarValue>,
+    ) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
+        let transaction = self.current_transaction();
+        if transaction.as_ref().is_some_and(|t| t.is_failed()) {
+            return Err(ExecError::transaction_failed().into());
+        }
+        let plan = plan_query(self.clone(), query, params)?;
         // execute query
         let query_id = uuid::Uuid::new_v4().to_string().into();
-        let handle = create_task(&self.exec_ctx, query_id, plan).await?;
+        let handle = create_task(&self.exec_ctx, query_id, plan, transaction.as_ref()).await?;
         let bridge = TaskHandleBridge::new(handle.columns.clone(), handle.recv);
         Ok(Box::pin(bridge))
     }
@@ -112,6 +183,7 @@ impl Session {
         self: &Arc<Self>,
         constraint: &ast::CreateConstraint,
     ) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
+        self.check_no_transaction()?;
         ddl::create_constraint(self.exec_ctx.store(), constraint)?;
         Ok(Box::pin(EmptyResultHandle::new(vec!["result".to_string()])))
     }
@@ -120,9 +192,33 @@ impl Session {
         self: &Arc<Self>,
         constraint: &ast::DropConstraint,
     ) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
+        self.check_no_transaction()?;
         ddl::drop_constraint(self.exec_ctx.store(), constraint)?;
         Ok(Box::pin(EmptyResultHandle::new(vec!["result".to_string()])))
     }
+
+    async fn handle_create_index(
+        self: &Arc<Self>,
+        index: &ast::CreateIndex,
+    ) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
+        self.check_no_transaction()?;
+        ddl::create_index(self.exec_ctx.store(), index)?;
+        Ok(Box::pin(EmptyResultHandle::new(vec!["result".to_string()])))
+    }
+
+    async fn handle_drop_index(self: &Arc<Self>, index: &ast::DropIndex) -> Result<Pin<Box<dyn ResultHandle>>, Error> {
+        self.check_no_transaction()?;
+        ddl::drop_index(self.exec_ctx.store(), index)?;
+        Ok(Box::pin(EmptyResultHandle::new(vec!["result".to_string()])))
+    }
+
+    // DDL commits on its own, it can not be part of an explicit transaction
+    fn check_no_transaction(&self) -> Result<(), Error> {
+        match self.current_transaction() {
+            Some(_) => Err(Error::DdlInTransaction),
+            None => Ok(()),
+        }
+    }
 }
 
 pub struct TaskHandleBridge {
@@ -170,7 +266,7 @@ impl ResultHandle for TaskHandleBridge {
     }
 }
 
-/// Empty result handle for DDL statements
+/// Empty result handle for DDL and transaction control statements, without columns it has no row
 pub struct EmptyResultHandle {
     columns: Vec<String>,
     done: bool,
@@ -189,7 +285,7 @@ impl Stream for EmptyResultHandle {
         mut self: std::pin::Pin<&mut Self>,
         _cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        if self.done {
+        if self.done || self.columns.is_empty() {
             std::task::Poll::Ready(None)
         } else {
             self.done = true;
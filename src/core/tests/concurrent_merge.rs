@@ -0,0 +1,123 @@
+//! MERGE of concurrent sessions must not create duplicates.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use elio_common::scalar::{Row, ScalarValue};
+use elio_core::db_env::{DbConfig, DbEnv};
+use elio_core::error::Error;
+use elio_core::session::Session;
+use futures::StreamExt;
+
+const SESSIONS: usize = 8;
+const ROUNDS: i64 = 20;
+
+async fn execute(session: &Arc<Session>, query: &str, round: i64) -> Result<Vec<Row>, Error> {
+    let params = HashMap::from([("round".to_string(), ScalarValue::Integer(round))]);
+    let mut result = session.execute(query.to_string(), params).await?;
+    let mut rows = vec![];
+    while let Some(row) = result.next().await {
+        rows.push(row?);
+    }
+    Ok(rows)
+}
+
+// runs the query in all the sessions at the same time, once per round
+async fn run_concurrently(db: &Arc<DbEnv>, query: &'static str) {
+    for round in 0..ROUNDS {
+        let tasks = (0..SESSIONS)
+            .map(|_| {
+                let session = db.new_session();
+                tokio::spawn(async move { execute(&session, query, round).await.unwrap() })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+}
+
+async fn count(db: &Arc<DbEnv>, query: &str) -> Vec<Row> {
+    execute(&db.new_session(), query, 0).await.unwrap()
+}
+
+fn open_db(dir: &tempfile::TempDir) -> Arc<DbEnv> {
+    DbEnv::open(&DbConfig::with_db_path(dir.path())).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_node_merge() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = open_db(&dir);
+    run_concurrently(&db, "MERGE (n:Person {round: $round})").await;
+
+    let rows = count(
+        &db,
+        "MATCH (n:Person) RETURN n.round AS round, count(*) AS c ORDER BY c DESC LIMIT 1",
+    )
+    .await;
+    assert_eq!(rows[0][1], Some(ScalarValue::Integer(1)));
+    let rows = count(&db, "MATCH (n:Person) RETURN count(*)").await;
+    assert_eq!(rows, vec![vec![Some(ScalarValue::Integer(ROUNDS))]]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_relationship_merge() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = open_db(&dir);
+    execute(
+        &db.new_session(),
+        "CREATE (:Person {name: 'a'})-[:FRIEND]->(:Person {name: 'b'})",
+        0,
+    )
+    .await
+    .unwrap();
+    // the nodes are bound before MERGE, only the relationship is merged
+    run_concurrently(
+        &db,
+        "MATCH (a:Person)-[:FRIEND]->(b:Person) MERGE (a)-[:KNOWS {round: $round}]->(b)",
+    )
+    .await;
+
+    let rows = count(&db, "MATCH (a)-[r:KNOWS]->(b) RETURN count(r)").await;
+    assert_eq!(rows, vec![vec![Some(ScalarValue::Integer(ROUNDS))]]);
+}
+
+// merges in an explicit transaction, which is retried on conflicts
+async fn merge_in_transaction(session: &Arc<Session>, round: i64) -> Result<(), Error> {
+    loop {
+        session.begin()?;
+        let result = execute(session, "MERGE (n:Person {round: $round})", round).await;
+        let result = match result {
+            Ok(_) => session.commit().await,
+            Err(e) => {
+                session.rollback().await?;
+                Err(e)
+            }
+        };
+        match result {
+            Err(e) if e.is_retryable() => continue,
+            result => return result,
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_merge_in_transactions() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = open_db(&dir);
+    for round in 0..ROUNDS {
+        let tasks = (0..SESSIONS)
+            .map(|_| {
+                let session = db.new_session();
+                tokio::spawn(async move { merge_in_transaction(&session, round).await.unwrap() })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    let rows = count(&db, "MATCH (n:Person) RETURN count(*)").await;
+    assert_eq!(rows, vec![vec![Some(ScalarValue::Integer(ROUNDS))]]);
+}
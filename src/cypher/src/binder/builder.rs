@@ -1,4 +1,5 @@
 use elio_common::schema::Variable;
+use elio_common::variable::VariableName;
 use indexmap::IndexSet;
 
 use crate::ir::query::{IrSingleQuery, IrSingleQueryPart};
@@ -34,7 +35,10 @@ impl IrSingleQueryBuilder {
         self.parts.push(tail);
     }
 
-    pub fn build(self) -> IrSingleQuery {
-        IrSingleQuery { parts: self.parts }
+    pub fn build(self, output: Vec<VariableName>) -> IrSingleQuery {
+        IrSingleQuery {
+            parts: self.parts,
+            output,
+        }
     }
 }
@@ -14,6 +14,7 @@ use crate::binder::scope::{Scope, ScopeItem};
 use crate::error::{PlanError, SemanticError};
 use crate::expr::CreateStruct;
 use crate::ir::mutating_pattern::{CreateNode, CreatePattern, CreateRel};
+use crate::ir::node_connection::PathMode;
 
 pub fn bind_create(
     bctx: &BindContext,
@@ -31,6 +32,7 @@ pub fn bind_create(
         reject_qpp: true,
         reject_named_path: false,
         reject_selective: true,
+        mode: PathMode::default(),
     };
 
     let mut scope = bind_create_pattern(&pctx, builder, in_scope, pattern)?;
@@ -89,7 +91,7 @@ fn bind_create_pattern(
     Ok(out_scope)
 }
 
-fn bind_create_part(
+pub(crate) fn bind_create_part(
     pctx: &PatternContext,
     in_scope: &Scope,
     create_scope: &mut Scope,
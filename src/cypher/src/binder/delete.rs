@@ -0,0 +1,46 @@
+use elio_common::data_type::DataType;
+use elio_parser::ast;
+
+use crate::binder::BindContext;
+use crate::binder::builder::IrSingleQueryBuilder;
+use crate::binder::expr::bind_expr;
+use crate::binder::scope::Scope;
+use crate::error::{PlanError, SemanticError};
+use crate::expr::ExprNode;
+use crate::ir::mutating_pattern::DeletePattern;
+
+/// Semantic
+/// 1. Expressions must be evaluated to nodes or relationships
+/// 2. Variables are still visible after DELETE
+pub fn bind_delete(
+    bctx: &BindContext,
+    builder: &mut IrSingleQueryBuilder,
+    in_scope: Scope,
+    delete @ ast::DeleteClause { detach, exprs }: &ast::DeleteClause,
+) -> Result<Scope, PlanError> {
+    let ctx_name = delete.to_string();
+    let mut ectx = bctx.derive_expr_context(&in_scope, &ctx_name);
+    ectx.sema_flags.set_reject_aggregate(true);
+
+    let mut nodes = vec![];
+    let mut rels = vec![];
+    for expr in exprs {
+        let expr = bind_expr(&ectx, &bctx.outer_scopes, expr)?;
+        match expr.typ() {
+            DataType::VirtualNode | DataType::Node => nodes.push(expr.boxed()),
+            DataType::VirtualRel | DataType::Rel => rels.push(expr.boxed()),
+            typ => return Err(SemanticError::invalid_delete_expr_type(&typ, &ctx_name).into()),
+        }
+    }
+
+    builder
+        .tail_mut()
+        .unwrap()
+        .query_graph
+        .add_delete_pattern(DeletePattern {
+            detach: *detach,
+            nodes,
+            rels,
+        });
+    Ok(in_scope)
+}
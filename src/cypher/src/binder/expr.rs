@@ -1,21 +1,30 @@
 use core::f64;
+use std::cell::RefCell;
 use std::sync::Arc;
 
 use elio_catalog::FunctionCatalog;
 use elio_common::data_type::{DataType, F64};
+use elio_common::schema::Variable;
 use elio_common::{IrToken, TokenKind};
 use elio_expr::func::sig::FuncImpl;
 use elio_parser::ast;
+use indexmap::IndexSet;
 use itertools::Itertools;
 use ordered_float::Float;
 use paste::paste;
 
 use crate::binder::BindContext;
-use crate::binder::scope::Scope;
+use crate::binder::pattern::{PatternContext, bind_pattern, rel_uniqueness_filter};
+use crate::binder::query::ClauseKind;
+use crate::binder::scope::{Scope, ScopeItem};
 use crate::error::{PlanError, SemanticError};
 use crate::expr::value::Constant;
-use crate::expr::{AggCall, CreateList, Expr, ExprNode, FilterExprs, FuncCall, PropertyAccess, VariableRef};
-use crate::not_supported;
+use crate::expr::{
+    AggCall, Case, CreateList, CreateStruct, Expr, ExprNode, FilterExprs, FuncCall, ListComprehension, ListPredicate,
+    ListPredicateKind, PropertyAccess, Reduce, VariableRef,
+};
+use crate::ir::node_connection::PathMode;
+use crate::ir::query_graph::{PatternComprehension, QueryGraph};
 
 #[derive(Clone)]
 pub struct ExprContext<'a> {
@@ -23,6 +32,9 @@ pub struct ExprContext<'a> {
     pub scope: &'a Scope,
     pub name: &'a str,
     pub sema_flags: ExprSemanticFlag,
+    // pattern comprehensions are collected here and planned before the expression is evaluated,
+    // None if not allowed in this context
+    pub pattern_comprehensions: Option<&'a RefCell<Vec<PatternComprehension>>>,
 }
 
 #[derive(Default, Copy, Clone)]
@@ -62,9 +74,10 @@ pub fn bind_expr(ectx: &ExprContext, outer_scope: &[Scope], expr: &ast::Expr) ->
     match expr {
         ast::Expr::Literal { lit } => bind_constant(ectx, lit).map(Into::into),
         ast::Expr::Variable { name } => bind_variable(ectx, name, outer_scope).map(Into::into),
-        ast::Expr::Parameter { .. } => not_supported!("parameter binding"),
-        ast::Expr::MapExpression { .. } => {
-            unreachable!("map expr should not be bind directly, must be in some context")
+        ast::Expr::Parameter { name } => bind_parameter(ectx, name).map(Into::into),
+        ast::Expr::MapExpression { keys, values } => {
+            let properties = bind_map_expr_to_property_map(ectx, outer_scope, keys, values)?;
+            Ok(CreateStruct::new(properties).into())
         }
         ast::Expr::PropertyAccess { map, key } => {
             let expr = bind_expr(ectx, outer_scope, map)?;
@@ -77,9 +90,37 @@ pub fn bind_expr(ectx: &ExprContext, outer_scope: &[Scope], expr: &ast::Expr) ->
         ast::Expr::Unary { op, oprand } => bind_unary(ectx, outer_scope, op, oprand),
         ast::Expr::Binary { left, op, right } => bind_binary(ectx, outer_scope, left, op, right),
         ast::Expr::FunctionCall { name, distinct, args } => bind_func_call(ectx, outer_scope, name, *distinct, args),
+        ast::Expr::CountStar => bind_func_call(ectx, outer_scope, "count", false, &[]),
         ast::Expr::ListExpression { items } => bind_list_expression(ectx, outer_scope, items),
         ast::Expr::ListSlice { list, start, end } => bind_list_slice(ectx, outer_scope, list, start, end),
         ast::Expr::ListIndex { list, index } => bind_list_index(ectx, outer_scope, list, index),
+        ast::Expr::Case {
+            operand,
+            alternatives,
+            else_,
+        } => bind_case(ectx, outer_scope, operand, alternatives, else_),
+        ast::Expr::ListComprehension {
+            variable,
+            list,
+            filter,
+            map,
+        } => bind_list_comprehension(ectx, outer_scope, variable, list, filter, map),
+        ast::Expr::ListPredicate {
+            kind,
+            variable,
+            list,
+            predicate,
+        } => bind_list_predicate(ectx, outer_scope, kind, variable, list, predicate),
+        ast::Expr::Reduce {
+            accumulator,
+            init,
+            variable,
+            list,
+            expr,
+        } => bind_reduce(ectx, outer_scope, accumulator, init, variable, list, expr),
+        ast::Expr::PatternComprehension { pattern, filter, map } => {
+            bind_pattern_comprehension(ectx, outer_scope, pattern, filter, map)
+        }
     }
 }
 
@@ -107,6 +148,16 @@ fn bind_constant(_ectx: &ExprContext, lit: &ast::Literal) -> Result<Constant, Pl
     }
 }
 
+// parameters are bound as constants of the given values
+fn bind_parameter(ectx: &ExprContext, name: &str) -> Result<Constant, PlanError> {
+    let value = ectx
+        .bctx
+        .params
+        .get(name)
+        .ok_or_else(|| SemanticError::parameter_not_provided(name, ectx.name))?;
+    Ok(Constant::from_value(value.clone()))
+}
+
 fn bind_variable(ectx: &ExprContext, name: &str, outer_scope: &[Scope]) -> Result<VariableRef, PlanError> {
     if let Some(item) = ectx.scope.resolve_symbol(name) {
         return Ok(VariableRef::from_variable(&item.as_variable()));
@@ -251,6 +302,259 @@ fn bind_list_expression(ectx: &ExprContext, outer_scope: &[Scope], items: &[ast:
     Ok(CreateList::new(elements, elem_type).into())
 }
 
+fn bind_case(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    operand: &Option<Box<ast::Expr>>,
+    alternatives: &[(ast::Expr, ast::Expr)],
+    else_: &Option<Box<ast::Expr>>,
+) -> Result<Expr, PlanError> {
+    let operand = operand.as_ref().map(|x| bind_expr(ectx, outer_scope, x)).transpose()?;
+
+    let mut conditions = vec![];
+    let mut results = vec![];
+    for (when, then) in alternatives {
+        let when = bind_expr(ectx, outer_scope, when)?;
+        let when = match &operand {
+            // CASE x WHEN v THEN r is bound as CASE WHEN x = v THEN r
+            Some(operand) => {
+                let func_name = ast::BinaryOperator::Eq.as_func_name();
+                let args = vec![operand.clone(), when];
+                let (func_impl, _is_agg, typ, coerced_types) = resolve_func(ectx, func_name, &args)?;
+                let args = coerce_null_args(args, &coerced_types);
+                FuncCall::new_unchecked(func_name.to_string(), func_impl.func_id, args, typ).into()
+            }
+            None => when,
+        };
+        conditions.push(when);
+        results.push(bind_expr(ectx, outer_scope, then)?);
+    }
+    let conditions = coerce_null_args(conditions, &vec![DataType::Bool; alternatives.len()]);
+    if let Some(cond) = conditions.iter().find(|cond| cond.typ() != DataType::Bool) {
+        return Err(SemanticError::invalid_case_condition_type(&cond.typ(), ectx.name).into());
+    }
+
+    if let Some(else_) = else_ {
+        results.push(bind_expr(ectx, outer_scope, else_)?);
+    }
+    // the branches of different types are unified to Any
+    let typ = results
+        .iter()
+        .filter(|x| !x.as_constant().is_some_and(|c| c.is_untyped_null()))
+        .map(|x| x.typ())
+        .all_equal_value()
+        .unwrap_or(DataType::Any);
+    let coerced_types = vec![typ.clone(); results.len()];
+    let mut results = coerce_null_args(results, &coerced_types);
+    let else_ = else_.as_ref().map(|_| results.pop().unwrap().boxed());
+
+    let when_then = conditions.into_iter().zip(results).collect();
+    Ok(Case::new_unchecked(when_then, else_, typ).into())
+}
+
+// bind the list of a lambda expression, returns the list and the type of its elements
+fn bind_lambda_list(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    list: &ast::Expr,
+) -> Result<(Expr, DataType), PlanError> {
+    let list = bind_expr(ectx, outer_scope, list)?;
+    let list = coerce_null_args(vec![list], &[DataType::new_list(DataType::Any)])
+        .pop()
+        .unwrap();
+    let elem_type = match list.typ() {
+        DataType::List(inner) => *inner,
+        DataType::Any => DataType::Any,
+        typ => return Err(SemanticError::invalid_list_type(&typ, ectx.name).into()),
+    };
+    Ok((list, elem_type))
+}
+
+// the lambda variables shadow the symbols of the same name, the expressions already bound in the scope are not
+// resolved inside the lambda since they may refer to a shadowed symbol
+fn lambda_scope(ectx: &ExprContext, variables: &[(&str, &Variable)]) -> Scope {
+    let mut scope = ectx.scope.clone();
+    scope.items.iter_mut().for_each(|item| item.expr.clear());
+    for (symbol, var) in variables.iter().rev() {
+        scope = scope.with_lambda(ScopeItem::new_variable(var.name.clone(), Some(symbol), var.typ.clone()));
+    }
+    scope
+}
+
+fn bind_lambda_predicate(ectx: &ExprContext, outer_scope: &[Scope], predicate: &ast::Expr) -> Result<Expr, PlanError> {
+    let predicate = bind_expr(ectx, outer_scope, predicate)?;
+    let predicate = coerce_null_args(vec![predicate], &[DataType::Bool]).pop().unwrap();
+    if predicate.typ() != DataType::Bool {
+        return Err(SemanticError::invalid_filter_expr_type(&predicate.typ(), ectx.name).into());
+    }
+    Ok(predicate)
+}
+
+fn bind_list_comprehension(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    variable: &str,
+    list: &ast::Expr,
+    filter: &Option<Box<ast::Expr>>,
+    map: &Option<Box<ast::Expr>>,
+) -> Result<Expr, PlanError> {
+    let (list, elem_type) = bind_lambda_list(ectx, outer_scope, list)?;
+    let var = Variable::new(&ectx.bctx.variable_generator.named(variable), &elem_type);
+    let scope = lambda_scope(ectx, &[(variable, &var)]);
+    let lambda_ectx = ExprContext {
+        scope: &scope,
+        pattern_comprehensions: None,
+        ..ectx.clone()
+    };
+    let filter = filter
+        .as_ref()
+        .map(|x| bind_lambda_predicate(&lambda_ectx, outer_scope, x))
+        .transpose()?;
+    let map = map
+        .as_ref()
+        .map(|x| bind_expr(&lambda_ectx, outer_scope, x))
+        .transpose()?;
+    Ok(ListComprehension::new(var, list.boxed(), filter.map(Expr::boxed), map.map(Expr::boxed)).into())
+}
+
+fn bind_list_predicate(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    kind: &ast::ListPredicateKind,
+    variable: &str,
+    list: &ast::Expr,
+    predicate: &ast::Expr,
+) -> Result<Expr, PlanError> {
+    let (list, elem_type) = bind_lambda_list(ectx, outer_scope, list)?;
+    let var = Variable::new(&ectx.bctx.variable_generator.named(variable), &elem_type);
+    let scope = lambda_scope(ectx, &[(variable, &var)]);
+    let lambda_ectx = ExprContext {
+        scope: &scope,
+        pattern_comprehensions: None,
+        ..ectx.clone()
+    };
+    let predicate = bind_lambda_predicate(&lambda_ectx, outer_scope, predicate)?;
+    let kind = match kind {
+        ast::ListPredicateKind::All => ListPredicateKind::All,
+        ast::ListPredicateKind::Any => ListPredicateKind::Any,
+        ast::ListPredicateKind::None => ListPredicateKind::None,
+        ast::ListPredicateKind::Single => ListPredicateKind::Single,
+    };
+    Ok(ListPredicate {
+        kind,
+        variable: var,
+        list: list.boxed(),
+        predicate: predicate.boxed(),
+    }
+    .into())
+}
+
+fn bind_reduce(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    accumulator: &str,
+    init: &ast::Expr,
+    variable: &str,
+    list: &ast::Expr,
+    expr: &ast::Expr,
+) -> Result<Expr, PlanError> {
+    let init = bind_expr(ectx, outer_scope, init)?;
+    let (list, elem_type) = bind_lambda_list(ectx, outer_scope, list)?;
+    let var = Variable::new(&ectx.bctx.variable_generator.named(variable), &elem_type);
+    let acc_name = ectx.bctx.variable_generator.named(accumulator);
+
+    let bind_body = |acc_type: &DataType| {
+        let acc = Variable::new(&acc_name, acc_type);
+        let scope = lambda_scope(ectx, &[(accumulator, &acc), (variable, &var)]);
+        let lambda_ectx = ExprContext {
+            scope: &scope,
+            pattern_comprehensions: None,
+            ..ectx.clone()
+        };
+        bind_expr(&lambda_ectx, outer_scope, expr).map(|expr| (acc, expr))
+    };
+    // the accumulator starts with the type of init, if the step changes the type the accumulator is of any type
+    let (accumulator, expr) = {
+        let (acc, expr) = bind_body(&init.typ())?;
+        if expr.typ() == acc.typ {
+            (acc, expr)
+        } else {
+            bind_body(&DataType::Any)?
+        }
+    };
+    Ok(Reduce {
+        accumulator,
+        init: init.boxed(),
+        variable: var,
+        list: list.boxed(),
+        expr: expr.boxed(),
+    }
+    .into())
+}
+
+// the pattern comprehension is planned as a subquery collecting the projections of the matches, the expression
+// refers to the list variable produced by the subquery
+fn bind_pattern_comprehension(
+    ectx: &ExprContext,
+    outer_scope: &[Scope],
+    pattern: &ast::PatternPart,
+    filter: &Option<Box<ast::Expr>>,
+    map: &ast::Expr,
+) -> Result<Expr, PlanError> {
+    let Some(collector) = ectx.pattern_comprehensions else {
+        return Err(SemanticError::pattern_comprehension_not_allowed(ectx.name).into());
+    };
+
+    let pctx = PatternContext {
+        bctx: ectx.bctx,
+        clause: ClauseKind::Match,
+        name: ectx.name,
+        allow_update: false,
+        reject_qpp: false,
+        reject_named_path: false,
+        reject_selective: true,
+        mode: PathMode::Trail,
+    };
+    let (paths, scope) = bind_pattern(&pctx, ectx.scope.clone(), std::slice::from_ref(pattern))?;
+
+    let mut qg = QueryGraph::empty();
+    paths.iter().for_each(|path| qg.add_path_pattern(path));
+    let mut predicate = rel_uniqueness_filter(&paths);
+    let pattern_ectx = ExprContext {
+        scope: &scope,
+        pattern_comprehensions: None,
+        ..ectx.clone()
+    };
+    if let Some(filter) = filter {
+        predicate.push(bind_lambda_predicate(&pattern_ectx, outer_scope, filter)?);
+    }
+    qg.add_filter(predicate);
+    let projection = bind_expr(&pattern_ectx, outer_scope, map)?;
+
+    // the variables of the input rows used by the pattern comprehension are imported
+    let mut used = qg.used_variables();
+    used.extend(projection.collect_variables());
+    let imported: IndexSet<Variable> = ectx
+        .scope
+        .items
+        .iter()
+        .map(|item| item.as_variable())
+        .filter(|var| used.contains(var))
+        .collect();
+    qg.add_imported_set(&imported);
+
+    let variable = Variable::new(
+        &ectx.bctx.variable_generator.unnamed(),
+        &DataType::new_list(projection.typ()),
+    );
+    collector.borrow_mut().push(PatternComprehension {
+        variable: variable.clone(),
+        qg,
+        projection,
+    });
+    Ok(VariableRef::from_variable(&variable).into())
+}
+
 fn bind_func_call(
     ectx: &ExprContext,
     outer_scope: &[Scope],
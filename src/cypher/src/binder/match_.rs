@@ -5,11 +5,12 @@ use indexmap::IndexSet;
 use crate::binder::BindContext;
 use crate::binder::builder::IrSingleQueryBuilder;
 use crate::binder::expr::bind_expr;
-use crate::binder::pattern::{PatternContext, bind_pattern};
+use crate::binder::pattern::{PatternContext, bind_pattern, rel_uniqueness_filter};
 use crate::binder::query::ClauseKind;
 use crate::binder::scope::Scope;
 use crate::error::PlanError;
 use crate::expr::FilterExprs;
+use crate::ir::node_connection::PathMode;
 use crate::ir::query_graph::QueryGraph;
 
 pub(crate) fn bind_match(
@@ -23,9 +24,11 @@ pub(crate) fn bind_match(
         where_,
     }: &ast::MatchClause,
 ) -> Result<Scope, PlanError> {
-    if matches!(mode, ast::MatchMode::WALK) {
-        return Err(PlanError::not_supported("TRAIL walk mode not supported"));
-    }
+    let mode = match mode {
+        ast::MatchMode::WALK => PathMode::Walk,
+        ast::MatchMode::TRAIL => PathMode::Trail,
+        ast::MatchMode::ACYCLIC => PathMode::Acyclic,
+    };
 
     // add the pattern graph to builder
     let pctx = PatternContext {
@@ -36,6 +39,7 @@ pub(crate) fn bind_match(
         reject_qpp: false,
         reject_named_path: false,
         reject_selective: false,
+        mode,
     };
     let (paths, mut scope) = bind_pattern(&pctx, in_scope.clone(), &pattern.patterns)?;
 
@@ -45,6 +49,21 @@ pub(crate) fn bind_match(
         qg
     };
 
+    let filter = {
+        let mut filter = match mode {
+            PathMode::Walk => FilterExprs::empty(),
+            PathMode::Trail | PathMode::Acyclic => rel_uniqueness_filter(&paths),
+        };
+        if let Some(expr) = where_ {
+            let ectx = bctx.derive_expr_context(&scope, "MATCH WHERE");
+            let expr = bind_expr(&ectx, &[], expr)?;
+            filter.push(expr);
+        }
+        filter
+    };
+    // WHERE belongs to the pattern, for OPTIONAL MATCH it only decides whether the pattern matches
+    qg.add_filter(filter);
+
     if *optional {
         // optional match qg imported variables := in_scope intersect qg's match used variables
         // SAFETY: tail must exists
@@ -57,19 +76,6 @@ pub(crate) fn bind_match(
         builder.tail_mut().unwrap().query_graph.merge(qg);
     }
 
-    let filter = {
-        let mut filter = FilterExprs::empty();
-        if let Some(expr) = where_ {
-            let ectx = bctx.derive_expr_context(&scope, "MATCH WHERE");
-            let expr = bind_expr(&ectx, &[], expr)?;
-            filter.push(expr);
-        }
-        filter
-    };
-
-    builder.tail_mut().unwrap().query_graph.add_filter(filter);
-
-    // TODO(pgao): semantic check match mode
     scope.remove_anonymous();
     Ok(scope)
 }
@@ -0,0 +1,154 @@
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
+use elio_common::store_types::RelDirection;
+use elio_common::variable::VariableName;
+use elio_common::{IrToken, SemanticDirection, TokenKind};
+use elio_parser::ast;
+use indexmap::IndexSet;
+
+use crate::binder::BindContext;
+use crate::binder::builder::IrSingleQueryBuilder;
+use crate::binder::create::bind_create_part;
+use crate::binder::pattern::PatternContext;
+use crate::binder::query::ClauseKind;
+use crate::binder::scope::Scope;
+use crate::binder::set::bind_set_items;
+use crate::error::{PlanError, SemanticError};
+use crate::expr::{CreateStruct, Expr, FilterExprs, HasLabel};
+use crate::ir::mutating_pattern::{CreatePattern, MergePattern};
+use crate::ir::node_connection::{PathMode, PatternLength, RelPattern};
+use crate::ir::query_graph::QueryGraph;
+
+/// Semantic
+/// 1. The merge pattern follows the rules of CREATE pattern
+/// 2. The whole pattern is matched, and created if there is no match
+/// 3. ON CREATE and ON MATCH items can reference variables of the merge pattern
+pub fn bind_merge(
+    bctx: &BindContext,
+    builder: &mut IrSingleQueryBuilder,
+    in_scope: Scope,
+    merge @ ast::MergeClause { pattern, actions }: &ast::MergeClause,
+) -> Result<Scope, PlanError> {
+    let name = merge.to_string();
+    let pctx = PatternContext {
+        bctx,
+        clause: ClauseKind::Merge,
+        name: &name,
+        allow_update: true,
+        reject_qpp: true,
+        reject_named_path: true,
+        reject_selective: true,
+        mode: PathMode::default(),
+    };
+
+    let simple = pattern
+        .as_simple_patterns()
+        .ok_or_else(|| SemanticError::qpp_not_allowed("MERGE", &pattern.to_string()))?;
+
+    let mut create_scope = Scope::empty();
+    let mut nodes = vec![];
+    let mut rels = vec![];
+    bind_create_part(&pctx, &in_scope, &mut create_scope, &mut nodes, &mut rels, simple)?;
+    // at least one entity must be created, e.g. MATCH (n) MERGE (n) is not allowed
+    if nodes.is_empty() && rels.is_empty() {
+        return Err(SemanticError::invalid_create_entity(&name).into());
+    }
+    // a null property never matches, so it would be created again each time
+    let properties = nodes
+        .iter()
+        .map(|node| &node.properties)
+        .chain(rels.iter().map(|rel| &rel.properties));
+    for (key, value) in properties.flat_map(|props| props.properties.iter()) {
+        if matches!(value, Expr::Constant(constant) if constant.is_null()) {
+            return Err(SemanticError::null_merge_property(key, &name).into());
+        }
+    }
+    let create = CreatePattern { nodes, rels };
+
+    let mut qg = bind_match_graph(bctx, &create);
+    // the variables bound before MERGE and referenced by the pattern are imported
+    let used: IndexSet<VariableName> = qg.used_variables().into_iter().map(|v| v.name).collect();
+    let imported: IndexSet<Variable> = in_scope
+        .items
+        .iter()
+        .filter(|item| used.contains(&item.variable))
+        .map(|item| item.as_variable())
+        .collect();
+    qg.add_imported_set(&imported);
+
+    let mut scope = in_scope.product(create_scope);
+    let mut on_match = vec![];
+    let mut on_create = vec![];
+    for ast::MergeAction {
+        on_create: is_create,
+        items,
+    } in actions
+    {
+        let items = bind_set_items(bctx, &scope, &name, items)?;
+        if *is_create {
+            on_create.extend(items);
+        } else {
+            on_match.extend(items);
+        }
+    }
+
+    builder.tail_mut().unwrap().query_graph.add_merge_pattern(MergePattern {
+        qg,
+        create,
+        on_match,
+        on_create,
+    });
+    scope.remove_anonymous();
+    Ok(scope)
+}
+
+// The pattern to match has the same labels, types and properties as the pattern to create
+fn bind_match_graph(bctx: &BindContext, create: &CreatePattern) -> QueryGraph {
+    let mut qg = QueryGraph::empty();
+    let mut filter = FilterExprs::empty();
+
+    for node in create.nodes.iter() {
+        qg.add_node(&node.variable);
+        let var = Expr::from_variable(&Variable::new(&node.variable, &DataType::VirtualNode));
+        // sort labels to make the plan stable
+        let mut labels = node.labels.iter().collect::<Vec<_>>();
+        labels.sort_by_key(|label| label.name().clone());
+        for label in labels {
+            filter.push(Expr::HasLabel(HasLabel {
+                entity: var.clone().boxed(),
+                label_or_rel: label.clone(),
+            }));
+        }
+        filter = filter.and(property_filter(bctx, var, &node.properties));
+    }
+
+    for rel in create.rels.iter() {
+        let dir = match rel.direction {
+            RelDirection::Out => SemanticDirection::Outgoing,
+            RelDirection::In => SemanticDirection::Incoming,
+        };
+        qg.add_rel(&RelPattern {
+            variable: rel.variable.clone(),
+            endpoints: (rel.left.clone(), rel.right.clone()),
+            dir,
+            types: vec![rel.reltype.clone()],
+            length: PatternLength::Simple,
+            mode: PathMode::default(),
+        });
+        let var = Expr::from_variable(&Variable::new(&rel.variable, &DataType::Rel));
+        filter = filter.and(property_filter(bctx, var, &rel.properties));
+    }
+
+    qg.add_filter(filter);
+    qg
+}
+
+fn property_filter(bctx: &BindContext, entity: Expr, properties: &CreateStruct) -> FilterExprs {
+    let mut filter = FilterExprs::empty();
+    for (key, value) in properties.properties.iter() {
+        let token: IrToken = bctx.resolve_token(key, TokenKind::PropertyKey);
+        let prop = entity.clone().property(&token, &DataType::Any);
+        filter.push(prop.equal(value.clone()));
+    }
+    filter
+}
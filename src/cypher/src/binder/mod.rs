@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use elio_common::scalar::ScalarValue;
 use elio_common::{IrToken, TokenKind};
 use elio_expr::func::sig::FuncDef;
 
@@ -9,14 +11,18 @@ use crate::session::PlannerSession;
 use crate::variable::VariableGenerator;
 mod builder;
 pub mod create;
+pub mod delete;
 pub mod expr;
 pub mod label_expr;
 pub mod load;
 pub mod match_;
+pub mod merge;
 pub mod pattern;
 pub mod project_body;
 pub mod query;
 pub mod scope;
+pub mod set;
+pub mod unwind;
 
 /// Context to bind a query
 #[derive(Debug, Clone)]
@@ -25,15 +31,18 @@ pub struct BindContext {
     // TODO(pgao): seems outer_scopes is not needed?
     pub outer_scopes: Vec<Scope>,
     pub variable_generator: Arc<VariableGenerator>,
+    // query parameters, referenced as $name
+    pub params: Arc<HashMap<String, ScalarValue>>,
     // TODO(pgao): semantic context like disable some semantics
 }
 
 impl BindContext {
-    pub fn new(sctx: Arc<dyn PlannerSession>) -> Self {
+    pub fn new(sctx: Arc<dyn PlannerSession>, params: HashMap<String, ScalarValue>) -> Self {
         Self {
             sctx,
             outer_scopes: Vec::new(),
             variable_generator: Arc::new(VariableGenerator::default()),
+            params: Arc::new(params),
         }
     }
 
@@ -43,6 +52,7 @@ impl BindContext {
             scope,
             name,
             sema_flags: Default::default(),
+            pattern_comprehensions: None,
         }
     }
 }
@@ -7,20 +7,21 @@ use elio_common::variable::VariableName;
 use elio_common::{IrToken, TokenKind};
 use elio_parser::ast::{self, NodePattern, RelationshipPattern};
 use indexmap::IndexSet;
+use itertools::Itertools;
 
 use crate::binder::BindContext;
 use crate::binder::expr::{ExprContext, bind_expr};
 use crate::binder::label_expr::bind_label_expr;
 use crate::binder::query::ClauseKind;
 use crate::binder::scope::{Scope, ScopeItem};
-use crate::error::PlanError;
+use crate::error::{PlanError, SemanticError};
 use crate::expr::property_access::PropertyAccess;
-use crate::expr::{Expr, ExprNode, FilterExprs, PathStep, ProjectPath};
+use crate::expr::{Expr, ExprNode, FilterExprs, FuncCall, PathStep, ProjectPath};
 use crate::ir::node_connection::{
-    ExhaustiveNodeConnection, NodeBinding, PatternLength, QuantifiedPathPattern, RelPattern, Repetition,
+    ExhaustiveNodeConnection, NodeBinding, PathMode, PatternLength, QuantifiedPathPattern, RelPattern, Repetition,
     VariableGrouping,
 };
-use crate::ir::path_pattern::{NodeConnections, PathPattern, SingleNode};
+use crate::ir::path_pattern::{NodeConnections, PathPattern, SelectivePathPattern, Selector, SingleNode};
 
 #[derive(Debug, Clone)]
 pub struct PatternContext<'a> {
@@ -37,6 +38,8 @@ pub struct PatternContext<'a> {
     pub reject_named_path: bool,
     // true on reject selective path pattenr
     pub reject_selective: bool,
+    // whether the nodes and relationships of a path can repeat
+    pub mode: PathMode,
 }
 
 impl<'a> PatternContext<'a> {
@@ -46,6 +49,7 @@ impl<'a> PatternContext<'a> {
             scope,
             name,
             sema_flags: Default::default(),
+            pattern_comprehensions: None,
         }
     }
 }
@@ -91,9 +95,22 @@ pub(crate) fn bind_pattern_part(
         factors,
     } = pattern;
 
-    // not supported selective path pattern
-    if selector.is_selective() {
-        return Err(PlanError::not_supported("Selective path pattern not supported"));
+    let selector = bind_selector(selector);
+    if selector.is_some() && pctx.reject_selective {
+        return Err(PlanError::not_supported(format!(
+            "Selective path pattern not supported in {}",
+            pctx.name
+        )));
+    }
+    // the selector is applied after the whole pattern matched, relationship predicates would be applied too late
+    if selector.is_some()
+        && factors.iter().any(|factor| {
+            matches!(factor, ast::PathFactor::Simple(simple) if simple.relationships.iter().any(|r| r.properties.is_some()))
+        })
+    {
+        return Err(PlanError::not_supported(
+            "Relationship properties in selective path pattern not supported",
+        ));
     }
     if variable.is_some() && pctx.reject_named_path {
         return Err(PlanError::not_supported("Path variable not supported"));
@@ -104,6 +121,18 @@ pub(crate) fn bind_pattern_part(
     // simple - (qpp - simple)*
 
     let (simple, quantified) = partition_factors(factors)?;
+    if !quantified.is_empty() && pctx.reject_qpp {
+        return Err(PlanError::not_supported(format!(
+            "Nested quantified path pattern not supported in {}",
+            pctx.name
+        )));
+    }
+    // TODO(pgao): construct path steps for quantified path patterns
+    if !quantified.is_empty() && variable.is_some() {
+        return Err(PlanError::not_supported(
+            "Path variable with quantified path pattern not supported",
+        ));
+    }
 
     // bind all simple
     let mut bound_simple = vec![];
@@ -134,11 +163,12 @@ pub(crate) fn bind_pattern_part(
             steps.push(PathStep::NodeStep(nodes.first().unwrap().clone()));
 
             for r in rels.iter() {
-                steps.push(PathStep::SingleRelStep {
-                    rel: r.variable.clone(),
-                    direction: r.dir,
-                    other: r.endpoints.1.clone(),
-                });
+                let (rel, direction, other) = (r.variable.clone(), r.dir, r.endpoints.1.clone());
+                if r.length.is_simple() {
+                    steps.push(PathStep::SingleRelStep { rel, direction, other });
+                } else {
+                    steps.push(PathStep::MutliRelStep { rel, direction, other });
+                }
             }
             steps
         };
@@ -181,13 +211,22 @@ pub(crate) fn bind_pattern_part(
         )
     };
 
+    if let Some(selector) = selector {
+        return bind_selective_path(pctx, scope, path, extra, variable.as_deref(), selector);
+    }
+
+    // the nodes can not repeat in the path, check it on the path projected from the steps
+    let mut extra = extra;
+    if matches!(pctx.mode, PathMode::Acyclic) && steps.len() > 1 {
+        let path = ProjectPath { steps: steps.clone() }.into();
+        extra.post_filter.push(FuncCall::is_acyclic_unchecked(path).into());
+    }
+
     // named path
     let path_var = if let Some(name) = variable {
         let (var, is_outer) = bind_variable(pctx, &mut scope, Some(name), &DataType::VirtualPath)?;
         if is_outer {
-            return Err(PlanError::semantic_err(
-                "Named path pattern cannot reference outer variable".to_string(),
-            ));
+            return Err(SemanticError::named_path_outer_reference(name, pctx.name).into());
         }
         // bind path expression, add project path to scope
         let project_path = ProjectPath { steps }.into();
@@ -213,6 +252,71 @@ pub(crate) fn bind_pattern_part(
     Ok((path, path_extra, scope))
 }
 
+fn bind_selector(selector: &ast::Selector) -> Option<Selector> {
+    match selector {
+        ast::Selector::AllPaths => None,
+        ast::Selector::AnyPath(k) => Some(Selector::AnyK(*k as i64)),
+        ast::Selector::AnyShortestPath => Some(Selector::ShortestK(1)),
+        ast::Selector::AllShortest => Some(Selector::ShortestKGroup(1)),
+        ast::Selector::CountedShortestPath(k) => Some(Selector::ShortestK(*k as i64)),
+        ast::Selector::CountedShortestGroup(k) => Some(Selector::ShortestKGroup(*k as i64)),
+    }
+}
+
+// The paths of a selective path pattern are computed by the selector, so the path variable is bound as a column
+// instead of a path projection. Only a single relationship between two nodes is supported, e.g.
+// ANY SHORTEST (a)-[:KNOWS*]->(b)
+fn bind_selective_path(
+    pctx: &PatternContext,
+    mut scope: Scope,
+    path: PathPattern,
+    extra: NodeConnectionExtra,
+    variable: Option<&str>,
+    selector: Selector,
+) -> Result<(PathPattern, PathPatternExtra, Scope), PlanError> {
+    let path_pattern = match path {
+        PathPattern::NodeConnections(conns) if matches!(conns.connections.as_slice(), [ExhaustiveNodeConnection::RelPattern(rel)] if rel.left() != rel.right()) => {
+            conns
+        }
+        _ => {
+            return Err(PlanError::not_supported(
+                "Selective path pattern must be a single relationship between two different nodes",
+            ));
+        }
+    };
+
+    let (var, is_outer) = bind_variable(pctx, &mut scope, variable, &DataType::VirtualPath)?;
+    if is_outer {
+        return Err(SemanticError::named_path_outer_reference(variable.unwrap_or_default(), pctx.name).into());
+    }
+
+    let path = PathPattern::SelectivePathPattern(SelectivePathPattern {
+        path_pattern,
+        path: var.name.clone(),
+        selector,
+    });
+    let path_extra = PathPatternExtra {
+        name: variable.map(|_| var),
+        outer: extra.outer,
+        post_filter: extra.post_filter,
+    };
+    Ok((path, path_extra, scope))
+}
+
+// the relationships can not repeat in the whole pattern, each pair of the relationships (or relationship lists) bound
+// by the pattern must be disjoint
+pub(crate) fn rel_uniqueness_filter(paths: &[PathPatternWithExtra]) -> FilterExprs {
+    let rels = paths.iter().flat_map(|path| path.pattern.rel_variables()).collect_vec();
+    let mut filter = FilterExprs::empty();
+    for (i, lhs) in rels.iter().enumerate() {
+        for rhs in rels[i + 1..].iter() {
+            let args = vec![Expr::from_variable(lhs), Expr::from_variable(rhs)];
+            filter.push(FuncCall::rels_disjoint_unchecked(args).into());
+        }
+    }
+    filter
+}
+
 fn partition_factors(
     factors: &[ast::PathFactor],
 ) -> Result<(Vec<&ast::SimplePathPattern>, Vec<&ast::QuantifiedPathPattern>), PlanError> {
@@ -367,6 +471,11 @@ fn bind_simple_pattern(
             },
         };
 
+        // repeated relationships would make an unbounded walk infinite
+        if matches!(pctx.mode, PathMode::Walk) && matches!(length, PatternLength::Var { max: None, .. }) {
+            return Err(SemanticError::unbounded_walk(&relationships[i].to_string(), pctx.name).into());
+        }
+
         filter = filter.and(rel_filter);
 
         let rel = RelPattern {
@@ -375,6 +484,7 @@ fn bind_simple_pattern(
             dir: *direction,
             types: reltypes,
             length,
+            mode: pctx.mode,
         };
         ir_rels.push(rel);
     }
@@ -405,7 +515,7 @@ fn bind_quantified_path_pattern(
     mut scope: Scope,
     left: &VariableName,
     right: &VariableName,
-    _qpp @ ast::QuantifiedPathPattern {
+    ast_qpp @ ast::QuantifiedPathPattern {
         non_selective_part,
         quantifier,
         filter,
@@ -433,10 +543,24 @@ fn bind_quantified_path_pattern(
         post_filter: mut inner_filter, // this works as pre-filter in qpp
     } = path_extra;
     assert!(name.is_none(), "Named path not allowed in quantified path pattern.");
-    assert!(
-        outer.is_empty(),
-        "Outer reference not allowed in quantified path pattern."
-    );
+    // singletons are bound for each iteration, they can not join with the variables outside
+    let joined = rels
+        .iter()
+        .flat_map(|r| [&r.endpoints.0, &r.variable, &r.endpoints.1])
+        .find(|var| outer.contains(*var) || scope.resolve_variable(var).is_some());
+    if let Some(var) = joined {
+        let symbol = inner_scope.resolve_variable(var).and_then(|item| item.symbol.clone());
+        return Err(SemanticError::variable_already_defined(
+            symbol.as_deref().unwrap_or_default(),
+            "QuantifiedPathPattern",
+        )
+        .into());
+    }
+    if rels.iter().any(|r| !r.length.is_simple()) {
+        return Err(PlanError::not_supported(
+            "Variable length relationship in quantified path pattern not supported",
+        ));
+    }
 
     let left_binding = NodeBinding {
         inner: rels.first().unwrap().endpoints.0.clone(),
@@ -458,28 +582,48 @@ fn bind_quantified_path_pattern(
             max: upper.map(|x| x as i64),
         },
     };
-    let node_grouping = {
-        let nodes: IndexSet<VariableName> = rels
-            .iter()
-            .flat_map(|r| vec![r.endpoints.0.clone(), r.endpoints.1.clone()])
-            .collect();
-        nodes
+    if repetition.max.is_some_and(|max| max < repetition.min) {
+        return Err(SemanticError::invalid_quantifier(&repetition.to_string(), "QuantifiedPathPattern").into());
+    }
+    if matches!(pctx.mode, PathMode::Walk) && repetition.max.is_none() {
+        return Err(SemanticError::unbounded_walk(&ast_qpp.to_string(), pctx.name).into());
+    }
+    // TODO(pgao): node uniqueness of the iterations
+    if matches!(pctx.mode, PathMode::Acyclic) {
+        return Err(PlanError::not_supported(
+            "Quantified path pattern in ACYCLIC mode not supported",
+        ));
+    }
+
+    // only named singletons have group variables, the anonymous ones can not be referenced.
+    // Except for the anonymous relationships which are still grouped when they can not repeat, the uniqueness
+    // of the relationships in the whole pattern is checked on the groups
+    let grouping = |singletons: Vec<VariableName>, anonymous: bool| -> IndexSet<VariableGrouping> {
+        singletons
             .into_iter()
-            .map(|singleton| VariableGrouping {
-                singleton: singleton.clone(),
-                group: pctx.bctx.variable_generator.named(&singleton),
-            })
-            .collect()
-    };
-    let rel_grouping = {
-        let rels: IndexSet<VariableName> = rels.iter().map(|r| r.variable.clone()).collect();
-        rels.into_iter()
-            .map(|singleton| VariableGrouping {
-                singleton: singleton.clone(),
-                group: pctx.bctx.variable_generator.named(&singleton),
+            .filter_map(|singleton| {
+                // safety: must be resolved, since do not allow implicit join in QPP
+                let group = match &inner_scope.resolve_variable(&singleton).unwrap().symbol {
+                    Some(symbol) => pctx.bctx.variable_generator.named(symbol),
+                    None if anonymous => pctx.bctx.variable_generator.unnamed(),
+                    None => return None,
+                };
+                Some(VariableGrouping { group, singleton })
             })
             .collect()
     };
+    let node_grouping = grouping(
+        rels.iter()
+            .flat_map(|r| [r.endpoints.0.clone(), r.endpoints.1.clone()])
+            .collect::<IndexSet<_>>()
+            .into_iter()
+            .collect(),
+        false,
+    );
+    let rel_grouping = grouping(
+        rels.iter().map(|r| r.variable.clone()).collect(),
+        !matches!(pctx.mode, PathMode::Walk),
+    );
 
     // bind filter
     // TODO(pgao): support variable grouping filters
@@ -502,6 +646,7 @@ fn bind_quantified_path_pattern(
         node_grouping,
         rel_grouping,
         filter: inner_filter,
+        mode: pctx.mode,
     };
 
     let extra = NodeConnectionExtra {
@@ -510,22 +655,10 @@ fn bind_quantified_path_pattern(
     };
 
     // add group variable in current scope
-    for vg in qpp.node_grouping.iter() {
-        let symbol = &inner_scope
-            .resolve_variable(&vg.singleton)
-            // safety: must be resolved, since do not allow implicit join in QPP
-            .unwrap()
-            .symbol;
-        let item = ScopeItem::new_variable(vg.group.clone(), symbol.as_deref(), DataType::VirtualNode);
-        scope.add_item(item);
-    }
-    for vg in qpp.rel_grouping.iter() {
-        let symbol = &inner_scope
-            .resolve_variable(&vg.singleton)
-            // safety: must be resolved, since do not allow implicit join in QPP
-            .unwrap()
-            .symbol;
-        let item = ScopeItem::new_variable(vg.group.clone(), symbol.as_deref(), DataType::Rel);
+    let groups = qpp.node_grouping.iter().chain(qpp.rel_grouping.iter());
+    for (vg, var) in groups.zip(qpp.group_variables()) {
+        let symbol = &inner_scope.resolve_variable(&vg.singleton).unwrap().symbol;
+        let item = ScopeItem::new_variable(vg.group.clone(), symbol.as_deref(), var.typ);
         scope.add_item(item);
     }
 
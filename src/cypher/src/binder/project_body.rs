@@ -1,6 +1,8 @@
+use std::cell::RefCell;
 use std::collections::HashSet;
 
 use elio_catalog::FunctionCatalog;
+use elio_common::data_type::DataType;
 use elio_common::scalar::ScalarValue;
 use elio_common::schema::Variable;
 use elio_parser::ast::{self, ReturnItem};
@@ -13,7 +15,7 @@ use crate::binder::expr::bind_expr;
 use crate::binder::query::ClauseKind;
 use crate::binder::scope::{Scope, ScopeItem};
 use crate::error::{PlanError, SemanticError};
-use crate::expr::{Constant, Expr, ExprNode, FilterExprs, VariableRef};
+use crate::expr::{Constant, Expr, ExprNode, FilterExprs};
 use crate::ir::order::SortItem;
 use crate::ir::query_project::{
     AggregateProjection, DistinctProjection, Pagination, Projection, QueryProjection, RegularProjection,
@@ -80,14 +82,16 @@ pub fn bind_return_items(
         }
     }
 
-    let (mut agg_in_scope, group_by_expr) = {
+    let (group_by_scope, group_by_expr) = {
         // regular projection or distinct or before aggregation
         // TODO(pgao): if there's no projection, the scope should be inscope
         // for the case WITH * [WHERE]
         let mut out_scope = Scope::empty();
         let mut projections = IndexMap::new();
         let clause = format!("{} Clause", for_clause);
-        let ectx = bctx.derive_expr_context(&scope, &clause);
+        let pattern_comprehensions = RefCell::new(vec![]);
+        let mut ectx = bctx.derive_expr_context(&scope, &clause);
+        ectx.pattern_comprehensions = Some(&pattern_comprehensions);
         for ReturnItem { expr, alias } in group_by {
             let bound_expr = bind_expr(&ectx, &bctx.outer_scopes, expr)?;
             let symbol = alias.clone().unwrap_or(expr.to_string());
@@ -116,10 +120,18 @@ pub fn bind_return_items(
             projections.insert(var.clone(), expr.clone());
         }
 
+        // pattern comprehensions are solved before the projection
+        builder
+            .tail_mut()
+            .unwrap()
+            .query_graph
+            .pattern_comprehensions
+            .extend(pattern_comprehensions.into_inner());
+
         (out_scope, projections)
     };
 
-    if group_by_expr.is_empty() {
+    if group_by_expr.is_empty() && post_proj.is_empty() {
         // TODO(pgao): we should have an bind context here
         return Err(SemanticError::at_least_one_return_item("").into());
     }
@@ -146,72 +158,55 @@ pub fn bind_return_items(
             }
         };
         builder.tail_mut().unwrap().with_projection(query_project);
-        return Ok(agg_in_scope);
+        return Ok(group_by_scope);
     }
 
     // handle aggreegation
     // MATCH (a)--(b) WITH *, a.name AS col1, SUM(a.age + b.age)/COUNT(b) AS b
     // in_scope = [*]
-    // agg_in_scope = [*, a.name AS col1, a.age + b.age AS col2, b]
-    // agg_out_scope = [*, col1, SUM(col2), COUNT(b)]
-    // project_out_scope = [*, col1, SUM(col2)/COUNT(b) AS b]
-
-    let post_proj_scope = agg_in_scope.clone();
-    let ectx = bctx.derive_expr_context(&scope, "Aggregation");
-    // bind agg args in agg_in_scope
-    let agg_args = agg
-        .iter()
-        .flat_map(|expr| {
-            let mut ret_args = vec![];
-            for e in expr.iter() {
-                if let ast::Expr::FunctionCall { args, .. } = e {
-                    ret_args.extend(args);
-                }
-            }
-            ret_args
-        })
-        .collect_vec();
+    // group_by_scope = [*, a.name AS col1]
+    // agg_out_scope = [*, col1, SUM(a.age + b.age) AS agg1, COUNT(b) AS agg2]
+    // project_out_scope = [*, col1, agg1/agg2 AS b]
 
-    for arg in agg_args {
-        let bound_expr = bind_expr(&ectx, &bctx.outer_scopes, arg)?;
-        let item = if let Expr::VariableRef(VariableRef { name, .. }) = &bound_expr {
-            ScopeItem {
-                symbol: None,
-                variable: name.clone(),
-                expr: HashSet::from_iter(vec![arg.clone()]),
-                typ: bound_expr.typ(),
-                bound_expr: None,
+    // bind aggregate calls in in_scope, their arguments are evaluated on the input rows
+    let mut agg_out_scope = group_by_scope.clone();
+    let mut agg_expr = IndexMap::default();
+    {
+        // do not resolve an aggregate to the output of a previous aggregation, e.g.
+        // WITH count(*) AS c RETURN count(*)
+        let mut agg_in_scope = scope.clone();
+        agg_in_scope.items.iter_mut().for_each(|item| item.expr.clear());
+        let pattern_comprehensions = RefCell::new(vec![]);
+        let mut ectx = bctx.derive_expr_context(&agg_in_scope, "Aggregation");
+        ectx.pattern_comprehensions = Some(&pattern_comprehensions);
+        for item in agg.iter().flatten() {
+            // same aggregate appears multiple times, only compute once
+            if agg_out_scope.resolve_expr(item).is_some() {
+                continue;
             }
-        } else {
-            // create another variable
+            let bound_expr = bind_expr(&ectx, &bctx.outer_scopes, item)?;
             let var_name = bctx.variable_generator.unnamed();
-            ScopeItem {
+            agg_out_scope.add_item(ScopeItem {
                 symbol: None,
-                variable: var_name,
-                expr: HashSet::from_iter(vec![arg.clone()]),
+                variable: var_name.clone(),
+                expr: HashSet::from_iter(vec![item.clone()]),
                 typ: bound_expr.typ(),
                 bound_expr: None,
-            }
-        };
-        if agg_in_scope.resolve_variable(&item.variable).is_none() {
-            agg_in_scope.add_item(item);
+            });
+            agg_expr.insert(var_name, bound_expr);
         }
-    }
-
-    // bind aggregate, works on agg_in_scope
-    let mut agg_expr = IndexMap::default();
-
-    let ectx = bctx.derive_expr_context(&agg_in_scope, "Aggregation");
-    for item in agg.iter().flatten() {
-        let bound_expr = bind_expr(&ectx, &bctx.outer_scopes, item)?;
-        let var_name = bctx.variable_generator.unnamed();
-        agg_expr.insert(var_name, bound_expr);
+        builder
+            .tail_mut()
+            .unwrap()
+            .query_graph
+            .pattern_comprehensions
+            .extend(pattern_comprehensions.into_inner());
     }
 
     // add projection to builder
     {
         let agg_proj = AggregateProjection {
-            group_by: group_by_expr.clone(),
+            group_by: group_by_expr,
             aggregate: agg_expr,
             order_by: Default::default(),
             pagination: Default::default(),
@@ -223,11 +218,19 @@ pub fn bind_return_items(
             .with_projection(QueryProjection::Project(Projection::Aggregate(agg_proj)));
     }
 
-    // post projection
-    let mut out_scope = post_proj_scope.clone();
+    // post projection, works on agg_out_scope
+    let mut out_scope = group_by_scope;
     {
-        let mut projs = group_by_expr.clone();
-        let ectx = bctx.derive_expr_context(&post_proj_scope, "Aggregation");
+        let mut projs: IndexMap<_, _> = out_scope
+            .items
+            .iter()
+            .map(|item| (item.variable.clone(), item.as_expr()))
+            .collect();
+        let pattern_comprehensions = RefCell::new(vec![]);
+        let mut ectx = bctx.derive_expr_context(&agg_out_scope, "Aggregation");
+        ectx.pattern_comprehensions = Some(&pattern_comprehensions);
+        // all aggregates are already computed, any other one is nested
+        ectx.sema_flags.set_reject_aggregate(true);
         for item in post_proj {
             let expr = bind_expr(&ectx, &bctx.outer_scopes, &item.expr)?;
             let symbol = item.alias.clone().unwrap_or(item.expr.to_string());
@@ -243,18 +246,33 @@ pub fn bind_return_items(
                 bound_expr: None,
             });
         }
-        // add new part
-        // TODO(pgao): we should add the imported variables to the tail
-        builder.new_tail(Default::default());
+        // add new part, which imports the output of aggregation
+        builder.new_tail(agg_out_scope.items.iter().map(|item| item.as_variable()).collect());
         builder
             .tail_mut()
             .unwrap()
-            .with_projection(QueryProjection::Project(Projection::Regular(RegularProjection {
+            .query_graph
+            .pattern_comprehensions
+            .extend(pattern_comprehensions.into_inner());
+        let query_project = if distinct {
+            Projection::Distinct(DistinctProjection {
+                group_by: projs,
+                order_by: Default::default(),
+                pagination: Default::default(),
+                filter: FilterExprs::empty(),
+            })
+        } else {
+            Projection::Regular(RegularProjection {
                 items: projs,
                 order_by: Default::default(),
                 pagination: Default::default(),
                 filter: FilterExprs::empty(),
-            })));
+            })
+        };
+        builder
+            .tail_mut()
+            .unwrap()
+            .with_projection(QueryProjection::Project(query_project));
     }
 
     Ok(out_scope)
@@ -283,6 +301,17 @@ fn extract_top_level_aggregate(bctx: &BindContext, expr: &ast::Expr) -> Result<V
                 aggs.extend(children.into_iter().flatten());
             }
         }
+        ast::Expr::CountStar => aggs.push(expr.clone()),
+        ast::Expr::PropertyAccess { map, .. } => aggs.extend(extract_top_level_aggregate(bctx, map)?),
+        ast::Expr::ListExpression { items } => {
+            for item in items {
+                aggs.extend(extract_top_level_aggregate(bctx, item)?);
+            }
+        }
+        ast::Expr::ListIndex { list, index } => {
+            aggs.extend(extract_top_level_aggregate(bctx, list)?);
+            aggs.extend(extract_top_level_aggregate(bctx, index)?);
+        }
         _ => (),
     };
     Ok(aggs)
@@ -337,15 +366,10 @@ pub fn bind_pagination(
         ectx.sema_flags.reject_aggregate();
         ectx.sema_flags.reject_outer_reference();
         let expr = bind_expr(&ectx, &bctx.outer_scopes, skip)?;
-        if let Expr::Constant(Constant {
-            data: Some(ScalarValue::Integer(i)),
-            ..
-        }) = expr
-        {
-            pagination.offset = Some(i);
-        } else {
+        if !is_valid_pagination_expr(&expr) {
             return Err(SemanticError::invalid_pagination_offset_type(&skip.to_string()).into());
         }
+        pagination.offset = Some(expr.boxed());
     }
 
     if let Some(limit) = limit {
@@ -353,15 +377,10 @@ pub fn bind_pagination(
         ectx.sema_flags.reject_aggregate();
         ectx.sema_flags.reject_outer_reference();
         let expr = bind_expr(&ectx, &bctx.outer_scopes, limit)?;
-        if let Expr::Constant(Constant {
-            data: Some(ScalarValue::Integer(i)),
-            ..
-        }) = expr
-        {
-            pagination.limit = Some(i);
-        } else {
+        if !is_valid_pagination_expr(&expr) {
             return Err(SemanticError::invalid_pagination_limit_type(&limit.to_string()).into());
         }
+        pagination.limit = Some(expr.boxed());
     }
 
     builder
@@ -370,3 +389,16 @@ pub fn bind_pagination(
         .update_projection(|p| p.set_pagination(pagination));
     Ok(())
 }
+
+// SKIP and LIMIT are evaluated once before the query runs, so they must not refer to any variable.
+// Constants are checked here, the others are checked when evaluated.
+fn is_valid_pagination_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Constant(Constant {
+            data: Some(ScalarValue::Integer(i)),
+            ..
+        }) => *i >= 0,
+        Expr::Constant(_) => false,
+        _ => expr.collect_variables().is_empty() && matches!(expr.typ(), DataType::Integer | DataType::Any),
+    }
+}
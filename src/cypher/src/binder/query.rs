@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use elio_common::scalar::ScalarValue;
 use elio_common::variable::VariableName;
 use elio_parser::ast;
 use indexmap::IndexMap;
@@ -7,11 +9,15 @@ use indexmap::IndexMap;
 use crate::binder::BindContext;
 use crate::binder::builder::IrSingleQueryBuilder;
 use crate::binder::create::bind_create;
+use crate::binder::delete::bind_delete;
 use crate::binder::expr::bind_where;
 use crate::binder::load::bind_load;
 use crate::binder::match_::bind_match;
+use crate::binder::merge::bind_merge;
 use crate::binder::project_body::{bind_order_by, bind_pagination, bind_return_items};
 use crate::binder::scope::Scope;
+use crate::binder::set::{bind_remove, bind_set};
+use crate::binder::unwind::bind_unwind;
 use crate::error::{PlanError, SemanticError};
 use crate::ir::query::{IrQuery, IrQueryRoot, IrSingleQuery};
 use crate::session::PlannerSession;
@@ -19,13 +25,18 @@ use crate::session::PlannerSession;
 #[derive(Debug, Clone, derive_more::Display)]
 pub enum ClauseKind {
     Create,
+    Merge,
     Match,
     With,
     Return,
 }
 
-pub fn bind_root_query(sctx: Arc<dyn PlannerSession>, query: &ast::RegularQuery) -> Result<IrQueryRoot, PlanError> {
-    let bctx = BindContext::new(sctx);
+pub fn bind_root_query(
+    sctx: Arc<dyn PlannerSession>,
+    query: &ast::RegularQuery,
+    params: HashMap<String, ScalarValue>,
+) -> Result<IrQueryRoot, PlanError> {
+    let bctx = BindContext::new(sctx, params);
 
     let (ir, scope) = bind_query(&bctx, query)?;
 
@@ -82,17 +93,22 @@ fn bind_single_query(bctx: &BindContext, query: &ast::SingleQuery) -> Result<(Ir
         in_scope = match clause {
             ast::Clause::Load(load_clause) => bind_load(bctx, &mut builder, in_scope, load_clause)?,
             ast::Clause::Create(create_clause) => bind_create(bctx, &mut builder, in_scope, create_clause)?,
+            ast::Clause::Delete(delete_clause) => bind_delete(bctx, &mut builder, in_scope, delete_clause)?,
+            ast::Clause::Set(items) => bind_set(bctx, &mut builder, in_scope, clause, items)?,
+            ast::Clause::Remove(items) => bind_remove(bctx, &mut builder, in_scope, clause, items)?,
+            ast::Clause::Merge(merge_clause) => bind_merge(bctx, &mut builder, in_scope, merge_clause)?,
             ast::Clause::Match(match_clause) => bind_match(bctx, &mut builder, in_scope, match_clause)?,
             ast::Clause::With(with_clause) => bind_with(bctx, &mut builder, in_scope, with_clause)?,
             ast::Clause::Return(return_clause) => bind_return(bctx, &mut builder, in_scope, return_clause)?,
-            ast::Clause::Unwind(_unwind_clause) => todo!(),
+            ast::Clause::Unwind(unwind_clause) => bind_unwind(bctx, &mut builder, in_scope, unwind_clause)?,
         };
     }
     // fix: if clause does not ends with return, then return an empty in scope
     // if !matches!(clauses.last().unwrap(), ast::Clause::Return(_)) {
     // in_scope = Scope::empty();
     // }
-    Ok((builder.build(), in_scope))
+    let output = in_scope.items.iter().map(|item| item.variable.clone()).collect();
+    Ok((builder.build(output), in_scope))
 }
 
 /// Execution order of with clause is
@@ -79,6 +79,13 @@ impl Scope {
         self.items.iter().find(|item| &item.variable == variable)
     }
 
+    // the scope inside a lambda, the lambda variable shadows the items of the same symbol
+    pub fn with_lambda(&self, item: ScopeItem) -> Self {
+        let mut items = vec![item];
+        items.extend(self.items.iter().cloned());
+        Self { items }
+    }
+
     pub fn add_item(&mut self, item: ScopeItem) {
         self.items.push(item);
     }
@@ -0,0 +1,132 @@
+use elio_common::TokenKind;
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
+use elio_parser::ast;
+
+use crate::binder::BindContext;
+use crate::binder::builder::IrSingleQueryBuilder;
+use crate::binder::expr::{ExprContext, bind_expr};
+use crate::binder::scope::Scope;
+use crate::error::{PlanError, SemanticError};
+use crate::expr::{Constant, Expr, ExprNode};
+use crate::ir::mutating_pattern::{SetItem, SetPattern};
+
+/// Semantic
+/// 1. Properties can be set on nodes and relationships, labels can only be set on nodes
+/// 2. `SET n = expr` and `SET n += expr` take a map, node or relationship
+/// 3. Variables are still visible after SET
+pub fn bind_set(
+    bctx: &BindContext,
+    builder: &mut IrSingleQueryBuilder,
+    in_scope: Scope,
+    clause: &ast::Clause,
+    items: &[ast::SetItem],
+) -> Result<Scope, PlanError> {
+    let set_items = bind_set_items(bctx, &in_scope, &clause.to_string(), items)?;
+    add_set_pattern(builder, set_items);
+    Ok(in_scope)
+}
+
+/// Bind the items of SET, also used by ON CREATE and ON MATCH of MERGE
+pub(crate) fn bind_set_items(
+    bctx: &BindContext,
+    scope: &Scope,
+    ctx_name: &str,
+    items: &[ast::SetItem],
+) -> Result<Vec<SetItem>, PlanError> {
+    let mut ectx = bctx.derive_expr_context(scope, ctx_name);
+    ectx.sema_flags.set_reject_aggregate(true);
+
+    let mut set_items = vec![];
+    for item in items {
+        let set_item = match item {
+            ast::SetItem::Property { variable, key, value } => SetItem::Property {
+                variable: bind_target(&ectx, variable, false)?,
+                key: bctx.resolve_token(key, TokenKind::PropertyKey),
+                value: bind_expr(&ectx, &bctx.outer_scopes, value)?.boxed(),
+            },
+            ast::SetItem::Properties { variable, value, merge } => {
+                let value = bind_expr(&ectx, &bctx.outer_scopes, value)?;
+                if !matches!(
+                    value.typ(),
+                    DataType::Struct(_)
+                        | DataType::Node
+                        | DataType::VirtualNode
+                        | DataType::Rel
+                        | DataType::VirtualRel
+                        | DataType::Any
+                ) {
+                    return Err(SemanticError::invalid_set_properties_type(&value.typ(), ctx_name).into());
+                }
+                SetItem::Properties {
+                    variable: bind_target(&ectx, variable, false)?,
+                    value: value.boxed(),
+                    merge: *merge,
+                }
+            }
+            ast::SetItem::Labels { variable, labels } => SetItem::Labels {
+                variable: bind_target(&ectx, variable, true)?,
+                labels: labels.iter().map(|l| bctx.resolve_token(l, TokenKind::Label)).collect(),
+                remove: false,
+            },
+        };
+        set_items.push(set_item);
+    }
+    Ok(set_items)
+}
+
+/// REMOVE n.p is SET n.p = null
+pub fn bind_remove(
+    bctx: &BindContext,
+    builder: &mut IrSingleQueryBuilder,
+    in_scope: Scope,
+    clause: &ast::Clause,
+    items: &[ast::RemoveItem],
+) -> Result<Scope, PlanError> {
+    let ctx_name = clause.to_string();
+    let ectx = bctx.derive_expr_context(&in_scope, &ctx_name);
+
+    let mut set_items = vec![];
+    for item in items {
+        let set_item = match item {
+            ast::RemoveItem::Property { variable, key } => SetItem::Property {
+                variable: bind_target(&ectx, variable, false)?,
+                key: bctx.resolve_token(key, TokenKind::PropertyKey),
+                value: Expr::from(Constant::untyped_null()).boxed(),
+            },
+            ast::RemoveItem::Labels { variable, labels } => SetItem::Labels {
+                variable: bind_target(&ectx, variable, true)?,
+                labels: labels.iter().map(|l| bctx.resolve_token(l, TokenKind::Label)).collect(),
+                remove: true,
+            },
+        };
+        set_items.push(set_item);
+    }
+
+    add_set_pattern(builder, set_items);
+    Ok(in_scope)
+}
+
+// the updated entity must be a node, or a relationship if labels are not updated
+fn bind_target(ectx: &ExprContext, name: &str, node_only: bool) -> Result<Variable, PlanError> {
+    let item = ectx
+        .scope
+        .resolve_symbol(name)
+        .ok_or_else(|| SemanticError::variable_not_defined(name, ectx.name))?;
+    match &item.typ {
+        DataType::Node | DataType::VirtualNode => Ok(item.as_variable()),
+        DataType::Rel | DataType::VirtualRel if !node_only => Ok(item.as_variable()),
+        typ => {
+            let expected = if node_only { "node" } else { "node or relationship" };
+            Err(SemanticError::invalid_update_target(name, expected, typ, ectx.name).into())
+        }
+    }
+}
+
+fn add_set_pattern(builder: &mut IrSingleQueryBuilder, items: Vec<SetItem>) {
+    builder
+        .tail_mut()
+        .unwrap()
+        .query_graph
+        .add_set_pattern(SetPattern { items });
+}
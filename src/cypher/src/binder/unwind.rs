@@ -0,0 +1,56 @@
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
+use elio_parser::ast;
+
+use crate::binder::BindContext;
+use crate::binder::builder::IrSingleQueryBuilder;
+use crate::binder::expr::bind_expr;
+use crate::binder::scope::{Scope, ScopeItem};
+use crate::error::{PlanError, SemanticError};
+use crate::expr::ExprNode;
+use crate::ir::query_project::{QueryProjection, Unwind};
+
+pub(crate) fn bind_unwind(
+    bctx: &BindContext,
+    builder: &mut IrSingleQueryBuilder,
+    in_scope: Scope,
+    unwind: &ast::UnwindClause,
+) -> Result<Scope, PlanError> {
+    let ctx_name = unwind.to_string();
+
+    // Semantic check: Variable name must not conflict with existing variables
+    if in_scope.resolve_symbol(&unwind.variable).is_some() {
+        return Err(SemanticError::variable_already_defined(&unwind.variable, &ctx_name).into());
+    }
+
+    let mut ectx = bctx.derive_expr_context(&in_scope, &ctx_name);
+    ectx.sema_flags.set_reject_aggregate(true);
+    let expr = bind_expr(&ectx, &bctx.outer_scopes, &unwind.expr)?;
+
+    // a non-list value is unwound as a single row
+    let typ = match expr.typ() {
+        DataType::List(elem) => *elem,
+        other => other,
+    };
+    let var_name = bctx.variable_generator.named(&unwind.variable);
+    let ir_unwind = Unwind {
+        variable: Variable::new(&var_name, &typ),
+        expr,
+    };
+
+    // Add Unwind projection to builder
+    builder
+        .tail_mut()
+        .unwrap()
+        .with_projection(QueryProjection::Unwind(ir_unwind));
+
+    // Update scope with the new variable
+    let mut out_scope = in_scope;
+    let item = ScopeItem::new_variable(var_name, Some(&unwind.variable), typ);
+    out_scope.add_item(item);
+
+    // create a new part
+    builder.new_tail(out_scope.items.iter().map(|item| item.as_variable()).collect());
+
+    Ok(out_scope)
+}
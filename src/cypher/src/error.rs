@@ -64,6 +64,11 @@ impl SemanticError {
         Self::new(msg)
     }
 
+    pub fn parameter_not_provided(name: &str, ctx: &str) -> Self {
+        let msg = format!("Parameter ${} is not provided in {}", name, ctx);
+        Self::new(msg)
+    }
+
     pub fn invalid_literal(typ: &DataType, lit: &str) -> Self {
         let msg = format!("Invalid literal {} for type {}", lit, typ);
         Self::new(msg)
@@ -84,6 +89,21 @@ impl SemanticError {
         Self::new(msg)
     }
 
+    pub fn invalid_list_type(typ: &DataType, ctx: &str) -> Self {
+        let msg = format!("Expected a list, got {} in {}", typ, ctx);
+        Self::new(msg)
+    }
+
+    pub fn pattern_comprehension_not_allowed(ctx: &str) -> Self {
+        let msg = format!("Pattern comprehension is not allowed in {}", ctx);
+        Self::new(msg)
+    }
+
+    pub fn invalid_case_condition_type(typ: &DataType, ctx: &str) -> Self {
+        let msg = format!("CASE condition must be a boolean, got {} in {}", typ, ctx);
+        Self::new(msg)
+    }
+
     pub fn invalid_filter_expr_type(typ: &DataType, ctx: &str) -> Self {
         let msg = format!("Filter expression must be a boolean, got {} in {}", typ, ctx);
         Self::new(msg)
@@ -108,12 +128,12 @@ impl SemanticError {
     }
 
     pub fn invalid_pagination_offset_type(ctx: &str) -> Self {
-        let msg = format!("Pagination offset must be an integer in {}", ctx);
+        let msg = format!("Pagination offset must be a non-negative integer in {}", ctx);
         Self::new(msg)
     }
 
     pub fn invalid_pagination_limit_type(ctx: &str) -> Self {
-        let msg = format!("Pagination limit must be an integer in {}", ctx);
+        let msg = format!("Pagination limit must be a non-negative integer in {}", ctx);
         Self::new(msg)
     }
 
@@ -130,10 +150,54 @@ impl SemanticError {
         Self::new(msg)
     }
 
+    pub fn invalid_quantifier(quantifier: &str, ctx: &str) -> Self {
+        let msg = format!(
+            "Invalid quantifier {}, the upper bound is less than the lower bound in {}",
+            quantifier, ctx
+        );
+        Self::new(msg)
+    }
+
+    pub fn unbounded_walk(pattern: &str, ctx: &str) -> Self {
+        let msg = format!("Pattern {} must have an upper bound in WALK mode in {}", pattern, ctx);
+        Self::new(msg)
+    }
+
+    pub fn named_path_outer_reference(name: &str, ctx: &str) -> Self {
+        let msg = format!("Named path {} cannot reference outer variable in {}", name, ctx);
+        Self::new(msg)
+    }
+
     pub fn invalid_create_entity(ctx: &str) -> Self {
         let msg = format!("Invalid CREATE entity in {}", ctx);
         Self::new(msg)
     }
+
+    pub fn null_merge_property(key: &str, ctx: &str) -> Self {
+        let msg = format!("Cannot merge using null property value for {} in {}", key, ctx);
+        Self::new(msg)
+    }
+
+    pub fn invalid_delete_expr_type(typ: &DataType, ctx: &str) -> Self {
+        let msg = format!(
+            "DELETE expression must be a node or relationship, got {} in {}",
+            typ, ctx
+        );
+        Self::new(msg)
+    }
+
+    pub fn invalid_update_target(name: &str, expected: &str, typ: &DataType, ctx: &str) -> Self {
+        let msg = format!("Variable {} must be a {}, got {} in {}", name, expected, typ, ctx);
+        Self::new(msg)
+    }
+
+    pub fn invalid_set_properties_type(typ: &DataType, ctx: &str) -> Self {
+        let msg = format!(
+            "Properties must be set from a map, node or relationship, got {} in {}",
+            typ, ctx
+        );
+        Self::new(msg)
+    }
 }
 
 // LOAD clause semantics
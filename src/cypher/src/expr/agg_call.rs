@@ -20,6 +20,21 @@ impl AggCall {
             typ,
         }
     }
+
+    pub fn pretty(&self) -> String {
+        let args = if self.args.is_empty() {
+            // count(*) is the only aggregate without argument
+            "*".to_string()
+        } else {
+            self.args.iter().map(|a| a.pretty()).collect::<Vec<_>>().join(", ")
+        };
+        format!(
+            "{}({}{})",
+            self.func,
+            if self.distinct { "DISTINCT " } else { "" },
+            args
+        )
+    }
 }
 
 impl ExprNode for AggCall {
@@ -0,0 +1,43 @@
+use elio_common::data_type::DataType;
+
+use crate::expr::{BoxedExpr, Expr, ExprNode};
+
+// CASE WHEN c1 THEN r1 WHEN c2 THEN r2 ELSE d END
+// the simple form `CASE x WHEN v THEN r` is bound to `CASE WHEN x = v THEN r`
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct Case {
+    pub when_then: Vec<(Expr, Expr)>,
+    // null if no condition is true and there is no else branch
+    pub else_: Option<BoxedExpr>,
+    typ: DataType,
+}
+
+impl Case {
+    pub fn new_unchecked(when_then: Vec<(Expr, Expr)>, else_: Option<BoxedExpr>, typ: DataType) -> Self {
+        Self { when_then, else_, typ }
+    }
+
+    pub fn pretty(&self) -> String {
+        let mut s = "CASE".to_string();
+        for (when, then) in &self.when_then {
+            s.push_str(&format!(" WHEN {} THEN {}", when.pretty(), then.pretty()));
+        }
+        if let Some(else_) = &self.else_ {
+            s.push_str(&format!(" ELSE {}", else_.pretty()));
+        }
+        s.push_str(" END");
+        s
+    }
+}
+
+impl ExprNode for Case {
+    fn typ(&self) -> DataType {
+        self.typ.clone()
+    }
+}
+
+impl From<Case> for Expr {
+    fn from(val: Case) -> Self {
+        Expr::Case(val)
+    }
+}
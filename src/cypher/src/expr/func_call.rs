@@ -38,6 +38,29 @@ impl FuncCall {
         let equal_impl = FUNCTION_REGISTRY.get_equal_func_impl();
         Self::new_unchecked("eq".to_string(), equal_impl.func_id.clone(), args, DataType::Bool)
     }
+
+    // no node repeats in the path
+    pub fn is_acyclic_unchecked(path: Expr) -> Self {
+        let is_acyclic_impl = FUNCTION_REGISTRY.get_is_acyclic_func_impl();
+        Self::new_unchecked(
+            "is_acyclic".to_string(),
+            is_acyclic_impl.func_id.clone(),
+            vec![path],
+            DataType::Bool,
+        )
+    }
+
+    // no relationship is shared by the two relationships or relationship lists
+    pub fn rels_disjoint_unchecked(args: Vec<Expr>) -> Self {
+        assert_eq!(args.len(), 2);
+        let rels_disjoint_impl = FUNCTION_REGISTRY.get_rels_disjoint_func_impl();
+        Self::new_unchecked(
+            "rels_disjoint".to_string(),
+            rels_disjoint_impl.func_id.clone(),
+            args,
+            DataType::Bool,
+        )
+    }
 }
 
 impl ExprNode for FuncCall {
@@ -0,0 +1,162 @@
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
+use indexmap::IndexSet;
+
+use crate::expr::{BoxedExpr, Expr, ExprNode};
+
+// Expressions evaluated for each element of a list, the element is bound to a lambda variable which is only
+// visible inside the expression.
+
+// [x IN list WHERE filter | map]
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct ListComprehension {
+    pub variable: Variable,
+    pub list: BoxedExpr,
+    pub filter: Option<BoxedExpr>,
+    pub map: Option<BoxedExpr>,
+    typ: DataType,
+}
+
+impl ListComprehension {
+    pub fn new(variable: Variable, list: BoxedExpr, filter: Option<BoxedExpr>, map: Option<BoxedExpr>) -> Self {
+        let elem_type = map.as_ref().map(|map| map.typ()).unwrap_or(variable.typ.clone());
+        Self {
+            variable,
+            list,
+            filter,
+            map,
+            typ: DataType::new_list(elem_type),
+        }
+    }
+
+    pub fn used_variables(&self) -> IndexSet<Variable> {
+        let mut vars = lambda_variables(self.filter.iter().chain(self.map.iter()), &[&self.variable]);
+        vars.extend(self.list.collect_variables());
+        vars
+    }
+
+    pub fn pretty(&self) -> String {
+        let mut s = format!("[{} IN {}", self.variable.name, self.list.pretty());
+        if let Some(filter) = &self.filter {
+            s.push_str(&format!(" WHERE {}", filter.pretty()));
+        }
+        if let Some(map) = &self.map {
+            s.push_str(&format!(" | {}", map.pretty()));
+        }
+        s.push(']');
+        s
+    }
+}
+
+impl ExprNode for ListComprehension {
+    fn typ(&self) -> DataType {
+        self.typ.clone()
+    }
+}
+
+impl From<ListComprehension> for Expr {
+    fn from(val: ListComprehension) -> Self {
+        Expr::ListComprehension(val)
+    }
+}
+
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum ListPredicateKind {
+    #[display("all")]
+    All,
+    #[display("any")]
+    Any,
+    #[display("none")]
+    None,
+    #[display("single")]
+    Single,
+}
+
+// all/any/none/single(x IN list WHERE predicate)
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct ListPredicate {
+    pub kind: ListPredicateKind,
+    pub variable: Variable,
+    pub list: BoxedExpr,
+    pub predicate: BoxedExpr,
+}
+
+impl ListPredicate {
+    pub fn used_variables(&self) -> IndexSet<Variable> {
+        let mut vars = lambda_variables(std::iter::once(&self.predicate), &[&self.variable]);
+        vars.extend(self.list.collect_variables());
+        vars
+    }
+
+    pub fn pretty(&self) -> String {
+        format!(
+            "{}({} IN {} WHERE {})",
+            self.kind,
+            self.variable.name,
+            self.list.pretty(),
+            self.predicate.pretty()
+        )
+    }
+}
+
+impl ExprNode for ListPredicate {
+    fn typ(&self) -> DataType {
+        DataType::Bool
+    }
+}
+
+impl From<ListPredicate> for Expr {
+    fn from(val: ListPredicate) -> Self {
+        Expr::ListPredicate(val)
+    }
+}
+
+// reduce(acc = init, x IN list | expr)
+// the type of the accumulator is the type of the result
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct Reduce {
+    pub accumulator: Variable,
+    pub init: BoxedExpr,
+    pub variable: Variable,
+    pub list: BoxedExpr,
+    pub expr: BoxedExpr,
+}
+
+impl Reduce {
+    pub fn used_variables(&self) -> IndexSet<Variable> {
+        let mut vars = lambda_variables(std::iter::once(&self.expr), &[&self.accumulator, &self.variable]);
+        vars.extend(self.init.collect_variables());
+        vars.extend(self.list.collect_variables());
+        vars
+    }
+
+    pub fn pretty(&self) -> String {
+        format!(
+            "reduce({} = {}, {} IN {} | {})",
+            self.accumulator.name,
+            self.init.pretty(),
+            self.variable.name,
+            self.list.pretty(),
+            self.expr.pretty()
+        )
+    }
+}
+
+impl ExprNode for Reduce {
+    fn typ(&self) -> DataType {
+        self.accumulator.typ.clone()
+    }
+}
+
+impl From<Reduce> for Expr {
+    fn from(val: Reduce) -> Self {
+        Expr::Reduce(val)
+    }
+}
+
+// the variables used by the lambda body, except the lambda variables
+fn lambda_variables<'a>(body: impl Iterator<Item = &'a BoxedExpr>, lambda: &[&Variable]) -> IndexSet<Variable> {
+    body.flat_map(|expr| expr.collect_variables())
+        .filter(|var| lambda.iter().all(|lambda| lambda.name != var.name))
+        .collect()
+}
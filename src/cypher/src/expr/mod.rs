@@ -4,12 +4,14 @@ use elio_common::schema::Variable;
 use enum_as_inner::EnumAsInner;
 
 pub mod agg_call;
+pub mod case;
 pub mod create_list;
 pub mod create_map;
 pub mod filters;
 /// Logical expr
 pub mod func_call;
 pub mod label;
+pub mod lambda;
 pub mod project_path;
 pub mod property_access;
 pub mod subquery;
@@ -17,11 +19,13 @@ pub mod utils;
 pub mod value;
 pub mod variable_ref;
 pub use agg_call::*;
+pub use case::*;
 pub use create_list::*;
 pub use create_map::*;
 pub use filters::*;
 pub use func_call::*;
 pub use label::*;
+pub use lambda::*;
 pub use project_path::*;
 pub use property_access::*;
 pub use subquery::*;
@@ -40,6 +44,10 @@ pub enum Expr {
     HasLabel(HasLabel),
     CreateStruct(CreateStruct),
     CreateList(CreateList),
+    Case(Case),
+    ListComprehension(ListComprehension),
+    ListPredicate(ListPredicate),
+    Reduce(Reduce),
     // graph
     ProjectPath(ProjectPath),
 }
@@ -84,6 +92,10 @@ impl_expr_node_for_enum!(
     HasLabel,
     CreateStruct,
     CreateList,
+    Case,
+    ListComprehension,
+    ListPredicate,
+    Reduce,
     ProjectPath
 );
 
@@ -146,7 +158,7 @@ impl Expr {
                     func_call.args.iter().map(|a| a.pretty()).collect::<Vec<_>>().join(", ")
                 )
             }
-            Expr::AggCall(_agg_call) => todo!(),
+            Expr::AggCall(agg_call) => agg_call.pretty(),
             Expr::Subquery(_subquery) => todo!(),
             Expr::HasLabel(has_label) => {
                 format!("{}:{}", has_label.entity.pretty(), has_label.label_or_rel)
@@ -163,6 +175,10 @@ impl Expr {
                 )
             }
             Expr::CreateList(create_list) => create_list.pretty(),
+            Expr::Case(case) => case.pretty(),
+            Expr::ListComprehension(list_comprehension) => list_comprehension.pretty(),
+            Expr::ListPredicate(list_predicate) => list_predicate.pretty(),
+            Expr::Reduce(reduce) => reduce.pretty(),
             Expr::ProjectPath(project_path) => project_path.pretty(),
         }
     }
@@ -28,6 +28,18 @@ impl Expr {
             Expr::CreateList(CreateList { elements, .. }) => {
                 vars.extend(elements.iter().flat_map(|expr| expr.collect_variables()));
             }
+            Expr::Case(Case { when_then, else_, .. }) => {
+                vars.extend(
+                    when_then
+                        .iter()
+                        .flat_map(|(when, then)| [when, then])
+                        .chain(else_.as_deref())
+                        .flat_map(|expr| expr.collect_variables()),
+                );
+            }
+            Expr::ListComprehension(list_comprehension) => vars.extend(list_comprehension.used_variables()),
+            Expr::ListPredicate(list_predicate) => vars.extend(list_predicate.used_variables()),
+            Expr::Reduce(reduce) => vars.extend(reduce.used_variables()),
             Expr::ProjectPath(project_path) => vars.extend(project_path.used_variable()),
         }
         vars
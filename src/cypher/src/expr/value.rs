@@ -1,5 +1,6 @@
 use elio_common::data_type::{DataType, F64};
 use elio_common::scalar::ScalarValue;
+use itertools::Itertools;
 
 use crate::expr::{Expr, ExprNode};
 
@@ -38,6 +39,17 @@ impl Constant {
         }
     }
 
+    /// Constant of a runtime value, e.g. a query parameter, the type is inferred from the value.
+    pub fn from_value(value: ScalarValue) -> Self {
+        match value {
+            ScalarValue::Unknown => Self::untyped_null(),
+            value => Self {
+                typ: Some(value_type(&value)),
+                data: Some(value),
+            },
+        }
+    }
+
     pub fn untyped_null() -> Self {
         Self { data: None, typ: None }
     }
@@ -64,6 +76,42 @@ impl Constant {
     }
 }
 
+fn value_type(value: &ScalarValue) -> DataType {
+    match value {
+        ScalarValue::Unknown => DataType::Any,
+        ScalarValue::Bool(_) => DataType::Bool,
+        ScalarValue::Integer(_) => DataType::Integer,
+        ScalarValue::Float(_) => DataType::Float,
+        ScalarValue::Date(_) => DataType::Date,
+        ScalarValue::LocalTime(_) => DataType::LocalTime,
+        ScalarValue::LocalDateTime(_) => DataType::LocalDateTime,
+        ScalarValue::ZonedDateTime(_) => DataType::ZonedDateTime,
+        ScalarValue::Duration(_) => DataType::Duration,
+        ScalarValue::String(_) => DataType::String,
+        ScalarValue::VirtualNode(_) => DataType::VirtualNode,
+        ScalarValue::VirtualRel(_) => DataType::VirtualRel,
+        ScalarValue::VirtualPath(_) => DataType::VirtualPath,
+        ScalarValue::Node(_) => DataType::Node,
+        ScalarValue::Rel(_) => DataType::Rel,
+        ScalarValue::Path(_) => DataType::Path,
+        // elements of different types, or nulls only, are Any
+        ScalarValue::List(list) => {
+            let elem_types = list
+                .iter()
+                .filter(|v| !matches!(v, ScalarValue::Unknown))
+                .map(value_type)
+                .unique()
+                .collect_vec();
+            match elem_types.as_slice() {
+                [typ] => DataType::new_list(typ.clone()),
+                _ => DataType::new_list(DataType::Any),
+            }
+        }
+        // maps are accessed by key at runtime
+        ScalarValue::Struct(_) => DataType::Any,
+    }
+}
+
 impl ExprNode for Constant {
     fn typ(&self) -> DataType {
         self.typ.clone().unwrap_or(DataType::Any)
@@ -6,26 +6,39 @@ use elio_common::schema::Variable;
 use elio_common::store_types::RelDirection;
 use elio_common::variable::VariableName;
 use indexmap::IndexSet;
-use pretty_xmlish::XmlNode;
+use itertools::Itertools;
+use pretty_xmlish::{Pretty, XmlNode};
 
 use crate::expr::{BoxedExpr, CreateStruct};
-use crate::pretty_utils::pretty_display_iter;
+use crate::ir::query_graph::QueryGraph;
+use crate::pretty_utils::{pretty_display_iter, pretty_exprs};
 
 pub enum MutatingPattern {
     Create(CreatePattern),
+    Delete(DeletePattern),
+    Set(SetPattern),
+    Merge(Box<MergePattern>),
 }
 
 impl MutatingPattern {
     pub fn used_variables(&self) -> IndexSet<Variable> {
         let mut vars = IndexSet::new();
         match self {
-            MutatingPattern::Create(create_pattern) => {
-                create_pattern.nodes.iter().for_each(|n| {
-                    vars.insert(Variable::new(&n.variable, &DataType::Node));
-                });
-                create_pattern.rels.iter().for_each(|r| {
-                    vars.insert(Variable::new(&r.variable, &DataType::Rel));
-                });
+            MutatingPattern::Create(create_pattern) => vars.extend(create_pattern.used_variables()),
+            MutatingPattern::Delete(delete_pattern) => {
+                delete_pattern
+                    .nodes
+                    .iter()
+                    .chain(delete_pattern.rels.iter())
+                    .for_each(|e| vars.extend(e.collect_variables()));
+            }
+            MutatingPattern::Set(set_pattern) => vars.extend(set_items_used_variables(&set_pattern.items)),
+            MutatingPattern::Merge(merge_pattern) => {
+                vars.extend(merge_pattern.qg.imported().iter().cloned());
+                vars.extend(merge_pattern.qg.used_variables());
+                vars.extend(merge_pattern.create.used_variables());
+                vars.extend(set_items_used_variables(&merge_pattern.on_match));
+                vars.extend(set_items_used_variables(&merge_pattern.on_create));
             }
         }
         vars
@@ -34,6 +47,9 @@ impl MutatingPattern {
     pub fn xmlnode(&self) -> XmlNode<'_> {
         match self {
             MutatingPattern::Create(create_pattern) => create_pattern.xmlnode(),
+            MutatingPattern::Delete(delete_pattern) => delete_pattern.xmlnode(),
+            MutatingPattern::Set(set_pattern) => set_pattern.xmlnode(),
+            MutatingPattern::Merge(merge_pattern) => merge_pattern.xmlnode(),
         }
     }
 }
@@ -47,6 +63,17 @@ pub struct CreatePattern {
 }
 
 impl CreatePattern {
+    pub fn used_variables(&self) -> IndexSet<Variable> {
+        let mut vars = IndexSet::new();
+        self.nodes.iter().for_each(|n| {
+            vars.insert(Variable::new(&n.variable, &DataType::Node));
+        });
+        self.rels.iter().for_each(|r| {
+            vars.insert(Variable::new(&r.variable, &DataType::Rel));
+        });
+        vars
+    }
+
     pub fn xmlnode(&self) -> XmlNode<'_> {
         let fields = vec![
             ("nodes", pretty_display_iter(self.nodes.iter())),
@@ -56,6 +83,137 @@ impl CreatePattern {
     }
 }
 
+/// Expressions of DELETE clause, evaluated to nodes or relationships to be deleted.
+pub struct DeletePattern {
+    pub detach: bool,
+    pub nodes: Vec<BoxedExpr>,
+    pub rels: Vec<BoxedExpr>,
+}
+
+impl DeletePattern {
+    pub fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![("detach", Pretty::display(&self.detach))];
+        if !self.nodes.is_empty() {
+            fields.push(("nodes", pretty_exprs(self.nodes.iter())));
+        }
+        if !self.rels.is_empty() {
+            fields.push(("rels", pretty_exprs(self.rels.iter())));
+        }
+        XmlNode::simple_record("DeletePattern", fields, vec![])
+    }
+}
+
+/// Items of SET and REMOVE clause, applied in order.
+pub struct SetPattern {
+    pub items: Vec<SetItem>,
+}
+
+impl SetPattern {
+    pub fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![("items", pretty_display_iter(self.items.iter()))];
+        XmlNode::simple_record("SetPattern", fields, vec![])
+    }
+}
+
+/// MERGE matches the whole pattern, and creates it if there is no match.
+pub struct MergePattern {
+    // pattern to match, variables bound before MERGE are imported
+    pub qg: QueryGraph,
+    // pattern to create, reference nodes are bound by the imported variables
+    pub create: CreatePattern,
+    pub on_match: Vec<SetItem>,
+    pub on_create: Vec<SetItem>,
+}
+
+impl MergePattern {
+    pub fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![];
+        if !self.on_match.is_empty() {
+            fields.push(("on_match", pretty_display_iter(self.on_match.iter())));
+        }
+        if !self.on_create.is_empty() {
+            fields.push(("on_create", pretty_display_iter(self.on_create.iter())));
+        }
+        let children = vec![Pretty::Record(self.qg.xmlnode()), Pretty::Record(self.create.xmlnode())];
+        XmlNode::simple_record("MergePattern", fields, children)
+    }
+}
+
+fn set_items_used_variables(items: &[SetItem]) -> IndexSet<Variable> {
+    let mut vars = IndexSet::new();
+    for item in items {
+        vars.insert(item.variable().clone());
+        if let Some(value) = item.value() {
+            vars.extend(value.collect_variables());
+        }
+    }
+    vars
+}
+
+#[derive(Debug, Clone)]
+pub enum SetItem {
+    // SET n.p = expr, REMOVE n.p sets the property to null
+    Property {
+        variable: Variable,
+        key: IrToken,
+        value: BoxedExpr,
+    },
+    // SET n = expr replaces all the properties, SET n += expr merges them
+    Properties {
+        variable: Variable,
+        value: BoxedExpr,
+        merge: bool,
+    },
+    // SET n:A, REMOVE n:A
+    Labels {
+        variable: Variable,
+        labels: Vec<IrToken>,
+        remove: bool,
+    },
+}
+
+impl SetItem {
+    pub fn variable(&self) -> &Variable {
+        match self {
+            SetItem::Property { variable, .. } => variable,
+            SetItem::Properties { variable, .. } => variable,
+            SetItem::Labels { variable, .. } => variable,
+        }
+    }
+
+    pub fn value(&self) -> Option<&BoxedExpr> {
+        match self {
+            SetItem::Property { value, .. } => Some(value),
+            SetItem::Properties { value, .. } => Some(value),
+            SetItem::Labels { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SetItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetItem::Property { variable, key, value } => {
+                write!(f, "{}.{} = {}", variable.name, key, value.pretty())
+            }
+            SetItem::Properties { variable, value, merge } => {
+                let op = if *merge { "+=" } else { "=" };
+                write!(f, "{} {} {}", variable.name, op, value.pretty())
+            }
+            SetItem::Labels {
+                variable,
+                labels,
+                remove,
+            } => {
+                if *remove {
+                    write!(f, "REMOVE ")?;
+                }
+                write!(f, "{}:{}", variable.name, labels.iter().join(":"))
+            }
+        }
+    }
+}
+
 pub struct CreateNode {
     pub variable: VariableName,
     // labels are conjuncted with AND
@@ -1,10 +1,20 @@
 use educe::{self, Educe};
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
 use elio_common::variable::{PathElement, VariableName};
 use elio_common::{IrToken, SemanticDirection};
 use indexmap::IndexSet;
 
 use crate::expr::FilterExprs;
 
+#[derive(Default, Debug, Clone, Copy, Hash, Eq, PartialEq, derive_more::Display)]
+pub enum PathMode {
+    Walk, // repeated node, repeated rel
+    #[default]
+    Trail, // repeated node, non-repeated rel
+    Acyclic, // non-repeated node, non-repeated rel
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct RelPattern {
     pub variable: VariableName,
@@ -12,6 +22,8 @@ pub struct RelPattern {
     pub dir: SemanticDirection,
     pub types: Vec<IrToken>,
     pub length: PatternLength,
+    // how the relationships of a variable length pattern may repeat
+    pub mode: PathMode,
 }
 
 impl RelPattern {
@@ -65,14 +77,14 @@ impl std::fmt::Display for RelPattern {
 // (x) ( (a)--(b)--(c) ){1,3} (y)
 // left node biding is inner(a) --> outer(x)
 // right node binding is inner(c) --> outer(y)
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct NodeBinding {
     pub inner: VariableName,
     pub outer: VariableName,
 }
 
 #[derive(Educe)]
-#[educe(Clone, Hash, Eq, PartialEq)]
+#[educe(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct QuantifiedPathPattern {
     pub left_binding: NodeBinding,
     pub right_binding: NodeBinding,
@@ -85,12 +97,85 @@ pub struct QuantifiedPathPattern {
     pub node_grouping: IndexSet<VariableGrouping>,
     #[educe(Hash(ignore))]
     pub rel_grouping: IndexSet<VariableGrouping>,
+    // whether the relationships can repeat across the iterations
+    pub mode: PathMode,
 }
 
 impl QuantifiedPathPattern {
     pub fn endpoint_nodes(&self) -> Vec<&VariableName> {
         vec![&self.left_binding.outer, &self.right_binding.outer]
     }
+
+    pub fn other_node(&self, node: &VariableName) -> &VariableName {
+        if *node == self.left_binding.outer {
+            &self.right_binding.outer
+        } else {
+            &self.left_binding.outer
+        }
+    }
+
+    // the node variables inside the pattern, each of them is bound once per iteration
+    pub fn inner_nodes(&self) -> IndexSet<VariableName> {
+        self.rels
+            .iter()
+            .flat_map(|r| [r.endpoints.0.clone(), r.endpoints.1.clone()])
+            .collect()
+    }
+
+    // group variables are lists of the singleton variables bound by each iteration
+    pub fn group_variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        let nodes = self
+            .node_grouping
+            .iter()
+            .map(|vg| Variable::new(&vg.group, &DataType::new_list(DataType::VirtualNode)));
+        nodes.chain(self.rel_group_variables())
+    }
+
+    pub fn rel_group_variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        self.rel_grouping
+            .iter()
+            .map(|vg| Variable::new(&vg.group, &DataType::new_list(DataType::Rel)))
+    }
+
+    // the pattern can be solved by variable length expand if it is a single relationship without predicates,
+    // and the nodes of each iteration are not used
+    pub fn as_var_length_rel(&self) -> Option<RelPattern> {
+        let [rel] = self.rels.as_slice() else {
+            return None;
+        };
+        if !self.filter.is_true() || !self.node_grouping.is_empty() || !rel.length.is_simple() {
+            return None;
+        }
+        let variable = self
+            .rel_grouping
+            .first()
+            .map(|vg| vg.group.clone())
+            .unwrap_or_else(|| rel.variable.clone());
+        Some(RelPattern {
+            variable,
+            endpoints: (self.left_binding.outer.clone(), self.right_binding.outer.clone()),
+            dir: rel.dir,
+            types: rel.types.clone(),
+            length: PatternLength::Var {
+                min: self.repetition.min as usize,
+                max: self.repetition.max.map(|max| max as usize),
+            },
+            mode: self.mode,
+        })
+    }
+}
+
+impl std::fmt::Display for QuantifiedPathPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}) (", self.left_binding.outer)?;
+        for rel in self.rels.iter() {
+            write!(f, "{rel}")?;
+        }
+        if !self.filter.is_true() {
+            write!(f, " WHERE {}", self.filter.pretty())?;
+        }
+        write!(f, "){} ({})", self.repetition, self.right_binding.outer)
+    }
 }
 
 #[derive(Clone, Hash, PartialEq, Eq)]
@@ -164,6 +249,15 @@ pub struct Repetition {
     // inclusive
     pub max: Option<i64>,
 }
+
+impl std::fmt::Display for Repetition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) => write!(f, "{{{},{}}}", self.min, max),
+            None => write!(f, "{{{},}}", self.min),
+        }
+    }
+}
 // For Quantified path pattern
 // (x) ( (a)--(b)--(c) ){1,3} (y)
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
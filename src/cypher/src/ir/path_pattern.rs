@@ -1,6 +1,7 @@
+use elio_common::data_type::DataType;
+use elio_common::schema::Variable;
 use elio_common::variable::VariableName;
 
-use crate::expr::FilterExprs;
 use crate::ir::node_connection::{ExhaustiveNodeConnection, RelPattern};
 
 pub enum PathPattern {
@@ -16,6 +17,14 @@ impl PathPattern {
             _ => None,
         }
     }
+
+    pub fn rel_variables(&self) -> Vec<Variable> {
+        match self {
+            PathPattern::SingleNode(_) => vec![],
+            PathPattern::NodeConnections(conns) => conns.rel_variables(),
+            PathPattern::SelectivePathPattern(spp) => spp.path_pattern.rel_variables(),
+        }
+    }
 }
 
 // length 0 path pattern of single node
@@ -40,6 +49,23 @@ impl NodeConnections {
 }
 
 impl NodeConnections {
+    // the variables bound to the relationships, variable length relationships and quantified path patterns bind
+    // relationship lists
+    pub fn rel_variables(&self) -> Vec<Variable> {
+        self.connections
+            .iter()
+            .flat_map(|conn| match conn {
+                ExhaustiveNodeConnection::RelPattern(rel) if rel.length.is_simple() => {
+                    vec![Variable::new(&rel.variable, &DataType::Rel)]
+                }
+                ExhaustiveNodeConnection::RelPattern(rel) => {
+                    vec![Variable::new(&rel.variable, &DataType::new_list(DataType::Rel))]
+                }
+                ExhaustiveNodeConnection::QuantifiedPathPattern(qpp) => qpp.rel_group_variables().collect(),
+            })
+            .collect()
+    }
+
     pub fn as_rels(self) -> Option<Vec<RelPattern>> {
         let mut rels = Vec::new();
         for conn in self.connections {
@@ -52,25 +78,52 @@ impl NodeConnections {
     }
 }
 
+// path pattern with a selector, e.g. ANY SHORTEST (a)-[r*]->(b)
+// the selector picks paths for each pair of endpoints
 #[derive(Clone)]
 pub struct SelectivePathPattern {
-    path_pattern: NodeConnections,
-    _filter: FilterExprs,
-    _selector: Selector,
+    pub path_pattern: NodeConnections,
+    // path variable, anonymous if the path is not named
+    pub path: VariableName,
+    pub selector: Selector,
 }
 
 impl SelectivePathPattern {
     pub fn endpoint_nodes(&self) -> Vec<&VariableName> {
         self.path_pattern.endpoint_nodes()
     }
+
+    // selective path pattern with a single relationship, this is the only form the planner supports
+    pub fn as_single_rel(&self) -> Option<&RelPattern> {
+        match self.path_pattern.connections.as_slice() {
+            [ExhaustiveNodeConnection::RelPattern(rel)] => Some(rel),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SelectivePathPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {} ", self.path, self.selector)?;
+        for conn in self.path_pattern.connections.iter() {
+            match conn {
+                ExhaustiveNodeConnection::RelPattern(rel) => write!(f, "{rel}")?,
+                ExhaustiveNodeConnection::QuantifiedPathPattern(qpp) => write!(f, "{qpp}")?,
+            }
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, derive_more::Display)]
 pub enum Selector {
     /// Any k paths
+    #[display("ANY {_0}")]
     AnyK(i64),
     // shortest k paths
+    #[display("SHORTEST {_0}")]
     ShortestK(i64),
-    // TODO(pgao): Shortest K GROUPS
+    // all paths of the shortest k lengths
+    #[display("SHORTEST {_0} GROUPS")]
     ShortestKGroup(i64),
 }
@@ -61,6 +61,8 @@ impl IrQuery {
 #[derive(Default)]
 pub struct IrSingleQuery {
     pub parts: Vec<IrSingleQueryPart>,
+    // variables returned by the query, in the order of return items
+    pub output: Vec<VariableName>,
     // pub query_graph: QueryGraph,
     // pub query_project: QueryProjection,
     // pub tail: Option<Box<IrSingleQuery>>,
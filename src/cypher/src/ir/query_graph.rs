@@ -8,9 +8,9 @@ use itertools::Itertools;
 use pretty_xmlish::{Pretty, XmlNode};
 
 use crate::binder::pattern::PathPatternWithExtra;
-use crate::expr::FilterExprs;
-use crate::ir::mutating_pattern::{CreatePattern, MutatingPattern};
-use crate::ir::node_connection::{ExhaustiveNodeConnection, RelPattern};
+use crate::expr::{Expr, FilterExprs};
+use crate::ir::mutating_pattern::{CreatePattern, DeletePattern, MergePattern, MutatingPattern, SetPattern};
+use crate::ir::node_connection::{ExhaustiveNodeConnection, QuantifiedPathPattern, RelPattern};
 use crate::ir::path_pattern::{PathPattern, SelectivePathPattern, SingleNode};
 use crate::pretty_utils::pretty_display_iter;
 
@@ -20,6 +20,8 @@ pub struct QueryGraph {
     pub nodes: IndexSet<VariableName>,
     // node connections
     pub rels: IndexSet<RelPattern>,
+    // quantified path patterns
+    quantified_paths: IndexSet<QuantifiedPathPattern>,
     // selective path patterns
     selective_paths: Vec<SelectivePathPattern>,
     // predicate, i.e. post filter
@@ -28,6 +30,8 @@ pub struct QueryGraph {
     pub optional_matches: Vec<QueryGraph>,
     // mutating patterns
     pub mutating_patterns: Vec<MutatingPattern>,
+    // pattern comprehensions used by the projection
+    pub pattern_comprehensions: Vec<PatternComprehension>,
     // imported variables as query graph inputs
     // imported may contain node/rels that does not exists in current qg's nodes and resl
     // TODO(pgao): just use variable name?
@@ -69,17 +73,23 @@ impl QueryGraph {
         self.rels.insert(rel.clone());
     }
 
+    // add quantified path pattern endpoint nodes and the pattern itself
+    pub fn add_quantified_path(&mut self, qpp: &QuantifiedPathPattern) {
+        qpp.endpoint_nodes().iter().for_each(|x| self.add_node(x));
+        self.quantified_paths.insert(qpp.clone());
+    }
+
     pub fn add_selective_path(&mut self, spp: &SelectivePathPattern) {
         spp.endpoint_nodes().iter().for_each(|x| self.add_node(x));
-        self.selective_paths.push(spp.clone());
+        if !self.selective_paths.iter().any(|x| x.path == spp.path) {
+            self.selective_paths.push(spp.clone());
+        }
     }
 
     pub fn add_node_connection(&mut self, conn: &ExhaustiveNodeConnection) {
         match conn {
             ExhaustiveNodeConnection::RelPattern(rel_pattern) => self.add_rel(rel_pattern),
-            ExhaustiveNodeConnection::QuantifiedPathPattern(_quantified_path_pattern) => {
-                unreachable!("not supported")
-            }
+            ExhaustiveNodeConnection::QuantifiedPathPattern(qpp) => self.add_quantified_path(qpp),
         }
     }
 
@@ -98,6 +108,10 @@ impl QueryGraph {
     pub fn merge(&mut self, other: QueryGraph) {
         other.nodes.iter().for_each(|n| self.add_node(n));
         other.rels.iter().for_each(|r| self.add_rel(r));
+        other
+            .quantified_paths
+            .iter()
+            .for_each(|qpp| self.add_quantified_path(qpp));
         other
             .selective_paths
             .iter()
@@ -105,6 +119,7 @@ impl QueryGraph {
         self.filter = self.filter.clone().and(other.filter.clone());
         self.optional_matches.extend(other.optional_matches);
         self.mutating_patterns.extend(other.mutating_patterns);
+        self.pattern_comprehensions.extend(other.pattern_comprehensions);
         other.imported.iter().for_each(|v| {
             self.imported.insert(v.clone());
         });
@@ -120,6 +135,18 @@ impl QueryGraph {
     pub fn add_create_pattern(&mut self, c: CreatePattern) {
         self.mutating_patterns.push(MutatingPattern::Create(c));
     }
+
+    pub fn add_delete_pattern(&mut self, d: DeletePattern) {
+        self.mutating_patterns.push(MutatingPattern::Delete(d));
+    }
+
+    pub fn add_set_pattern(&mut self, s: SetPattern) {
+        self.mutating_patterns.push(MutatingPattern::Set(s));
+    }
+
+    pub fn add_merge_pattern(&mut self, m: MergePattern) {
+        self.mutating_patterns.push(MutatingPattern::Merge(Box::new(m)));
+    }
 }
 
 impl QueryGraph {
@@ -131,6 +158,32 @@ impl QueryGraph {
         &self.imported
     }
 
+    pub fn quantified_paths(&self) -> &IndexSet<QuantifiedPathPattern> {
+        &self.quantified_paths
+    }
+
+    pub fn selective_paths(&self) -> &[SelectivePathPattern] {
+        &self.selective_paths
+    }
+
+    // variables introduced by selective path patterns, the path and its relationships
+    fn selective_path_variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        self.selective_paths.iter().flat_map(|spp| {
+            let rels = spp
+                .path_pattern
+                .connections
+                .iter()
+                .filter_map(|conn| match conn {
+                    ExhaustiveNodeConnection::RelPattern(rel) => {
+                        Some(Variable::new(&rel.variable, &DataType::new_list(DataType::Rel)))
+                    }
+                    ExhaustiveNodeConnection::QuantifiedPathPattern(_) => None,
+                })
+                .collect_vec();
+            std::iter::once(Variable::new(&spp.path, &DataType::VirtualPath)).chain(rels)
+        })
+    }
+
     pub fn outputs(&self) -> IndexSet<Variable> {
         let mut vars = IndexSet::new();
         self.imported.iter().for_each(|v| {
@@ -142,6 +195,8 @@ impl QueryGraph {
         self.rels.iter().for_each(|v| {
             vars.insert(Variable::new(&v.variable, &DataType::Rel));
         });
+        vars.extend(self.quantified_paths.iter().flat_map(|qpp| qpp.group_variables()));
+        vars.extend(self.selective_path_variables());
         vars
     }
 
@@ -156,6 +211,12 @@ impl QueryGraph {
                 vars.insert(e.variable().clone());
             })
         });
+        vars.extend(
+            self.quantified_paths
+                .iter()
+                .flat_map(|qpp| qpp.group_variables().map(|v| v.name)),
+        );
+        vars.extend(self.selective_path_variables().map(|v| v.name));
         // including imported variables
         vars.extend(self.imported.iter().map(|v| v.name.clone()));
         vars
@@ -170,6 +231,14 @@ impl QueryGraph {
         for rel in self.rels.iter() {
             vars.insert(Variable::new(&rel.variable, &DataType::Rel));
         }
+        for qpp in self.quantified_paths.iter() {
+            vars.extend(qpp.group_variables());
+            // the variables referenced by the predicates of each iteration
+            for e in qpp.filter.iter() {
+                vars.extend(e.collect_variables());
+            }
+        }
+        vars.extend(self.selective_path_variables());
         // optional match pattern
         for qg in self.optional_matches.iter() {
             vars.extend(qg.used_variables());
@@ -178,6 +247,10 @@ impl QueryGraph {
         for mp in self.mutating_patterns.iter() {
             vars.extend(mp.used_variables());
         }
+        // pattern comprehension
+        for pc in self.pattern_comprehensions.iter() {
+            vars.extend(pc.qg.imported().iter().cloned());
+        }
         // filter
         for e in self.filter.iter() {
             vars.extend(e.collect_variables());
@@ -188,9 +261,7 @@ impl QueryGraph {
     pub fn contains_node_connection(&self, nc: &ExhaustiveNodeConnection) -> bool {
         match nc {
             ExhaustiveNodeConnection::RelPattern(rel_pattern) => self.rels.contains(rel_pattern),
-            ExhaustiveNodeConnection::QuantifiedPathPattern(_quantified_path_pattern) => {
-                unreachable!("not supported")
-            }
+            ExhaustiveNodeConnection::QuantifiedPathPattern(qpp) => self.quantified_paths.contains(qpp),
         }
     }
 
@@ -217,7 +288,7 @@ impl QueryGraph {
             // argument only filter and other filters may be solved by qg
             let arg = self.imported.first().unwrap();
             let mut qg = self.component_for_node(&arg.name, &mut visited);
-            if !qg.rels.is_empty() {
+            if !qg.rels.is_empty() || !qg.quantified_paths.is_empty() || !qg.selective_paths.is_empty() {
                 // if there's no relaltionships, which means this is an empty qg, we do nothing
                 // since the planner will handle the case.
                 qg.add_imported_set(&self.imported);
@@ -268,6 +339,13 @@ impl QueryGraph {
             for nc in ncs {
                 qg.add_node_connection(&nc);
             }
+            for spp in self
+                .selective_paths
+                .iter()
+                .filter(|spp| spp.endpoint_nodes().contains(&&node))
+            {
+                qg.add_selective_path(spp);
+            }
             for nb in nbrs {
                 qg.add_node(&nb);
                 to_visit.push_back(nb.clone());
@@ -305,6 +383,25 @@ impl QueryGraph {
                 nodes.insert(rel.other_node(node).clone());
             }
         }
+        // connected by quantified path
+        for qpp in self.quantified_paths.iter() {
+            if qpp.endpoint_nodes().contains(&node) {
+                ncs.insert(ExhaustiveNodeConnection::QuantifiedPathPattern(qpp.clone()));
+                nodes.insert(qpp.other_node(node).clone());
+            }
+        }
+        // connected by selective path
+        for spp in self.selective_paths.iter() {
+            if let [left, right] = spp.endpoint_nodes().as_slice()
+                && (*left == node || *right == node)
+            {
+                nodes.insert(if *left == node {
+                    (*right).clone()
+                } else {
+                    (*left).clone()
+                });
+            }
+        }
         // TODO(pgao): maybe we should move connected by filter condition of arguments here?
         (ncs, nodes)
     }
@@ -316,6 +413,12 @@ impl QueryGraph {
             .iter()
             .filter(move |rel| rel.endpoints.0 == *node || rel.endpoints.1 == *node)
     }
+
+    pub fn quantified_connections(&self, node: &VariableName) -> impl Iterator<Item = &QuantifiedPathPattern> {
+        self.quantified_paths
+            .iter()
+            .filter(move |qpp| qpp.endpoint_nodes().contains(&node))
+    }
 }
 
 impl QueryGraph {
@@ -333,6 +436,12 @@ impl QueryGraph {
         if !self.rels.is_empty() {
             fields.push(("rels", pretty_display_iter(self.rels.iter())));
         };
+        if !self.quantified_paths.is_empty() {
+            fields.push(("quantified_paths", pretty_display_iter(self.quantified_paths.iter())));
+        };
+        if !self.selective_paths.is_empty() {
+            fields.push(("selective_paths", pretty_display_iter(self.selective_paths.iter())));
+        };
         if !self.filter.is_true() {
             fields.push(("filter", Pretty::display(&self.filter.pretty())));
         };
@@ -353,7 +462,38 @@ impl QueryGraph {
                 .collect_vec();
             children.push(Pretty::simple_record("mutating_pattern", vec![], mutating_patterns));
         };
+        if !self.pattern_comprehensions.is_empty() {
+            let pattern_comprehensions = self
+                .pattern_comprehensions
+                .iter()
+                .map(|x| Pretty::Record(x.xmlnode()))
+                .collect_vec();
+            children.push(Pretty::simple_record(
+                "pattern_comprehensions",
+                vec![],
+                pattern_comprehensions,
+            ));
+        };
 
         XmlNode::simple_record("QueryGraph", fields, children)
     }
 }
+
+// [(a)-->(b) WHERE b.age > 20 | b.name]
+// the pattern is matched for each input row, with the variables of the row imported, and the projections of the
+// matches are collected into the list variable
+pub struct PatternComprehension {
+    pub variable: Variable,
+    pub qg: QueryGraph,
+    pub projection: Expr,
+}
+
+impl PatternComprehension {
+    pub fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![
+            ("variable", Pretty::display(&self.variable.name)),
+            ("projection", Pretty::display(&self.projection.pretty())),
+        ];
+        XmlNode::simple_record("PatternComprehension", fields, vec![Pretty::Record(self.qg.xmlnode())])
+    }
+}
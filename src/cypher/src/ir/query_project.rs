@@ -111,21 +111,24 @@ impl Projection {
     }
 }
 
+// offset and limit are expressions without any variable reference,
+// they are evaluated once per query
 #[derive(Default)]
 pub struct Pagination {
-    pub offset: Option<i64>,
-    pub limit: Option<i64>,
+    pub offset: Option<Box<Expr>>,
+    pub limit: Option<Box<Expr>>,
 }
 
 impl std::fmt::Display for Pagination {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(off) = self.offset {
-            write!(f, "offset: {}", off)?;
+        let mut parts = vec![];
+        if let Some(off) = &self.offset {
+            parts.push(format!("offset: {}", off.pretty()));
         }
-        if let Some(lim) = self.limit {
-            write!(f, " limit: {}", lim)?;
+        if let Some(lim) = &self.limit {
+            parts.push(format!("limit: {}", lim.pretty()));
         }
-        Ok(())
+        write!(f, "{}", parts.join(" "))
     }
 }
 
@@ -0,0 +1,84 @@
+use super::*;
+use crate::expr::AggCall;
+use crate::pretty_utils::pretty_project_items;
+
+#[derive(Debug, Clone)]
+pub struct Aggregate {
+    pub base: PlanBase,
+    pub(crate) inner: AggregateInner,
+}
+
+impl Aggregate {
+    pub fn new(inner: AggregateInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for Aggregate {
+    type Inner = AggregateInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![];
+        if !self.inner.group_by.is_empty() {
+            fields.push((
+                "group_by",
+                pretty_project_items(self.inner.group_by.iter().map(|(k, v)| (k, v))),
+            ));
+        }
+        if !self.inner.aggregates.is_empty() {
+            fields.push((
+                "aggregates",
+                Pretty::Array(
+                    self.inner
+                        .aggregates
+                        .iter()
+                        .map(|(k, v)| Pretty::display(&format!("{} AS {}", k, v.pretty())))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("Aggregate", fields, children)
+    }
+}
+
+/// Group the input rows by `group_by`, and compute `aggregates` for each group.
+/// Without any aggregate, this is the DISTINCT of `group_by`.
+#[derive(Debug, Clone)]
+pub struct AggregateInner {
+    pub input: Box<PlanExpr>,
+    // group keys, evaluated on input rows
+    pub group_by: Vec<(VariableName, Expr)>,
+    // arguments of the aggregates are evaluated on input rows
+    pub aggregates: Vec<(VariableName, AggCall)>,
+}
+
+impl AggregateInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = Schema::empty();
+        for (var, expr) in &self.group_by {
+            schema.fields.push(Variable::new(var, &expr.typ()));
+        }
+        for (var, agg) in &self.aggregates {
+            schema.fields.push(Variable::new(var, &agg.typ()));
+        }
+        schema.into()
+    }
+}
+
+impl InnerNode for AggregateInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.build_schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+}
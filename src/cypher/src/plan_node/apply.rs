@@ -33,14 +33,30 @@ impl PlanNode for Apply {
             .map(|x| x.xmlnode())
             .map(Pretty::Record)
             .collect_vec();
-        XmlNode::simple_record("Apply", vec![], children)
+        let name = match self.inner.kind {
+            ApplyKind::Inner => "Apply",
+            ApplyKind::LeftOuter => "OptionalApply",
+        };
+        XmlNode::simple_record(name, vec![], children)
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum ApplyKind {
+    #[default]
+    #[display("Inner")]
+    // left rows without right rows are dropped
+    Inner,
+    #[display("LeftOuter")]
+    // left rows without right rows are kept, padded with nulls, e.g. OPTIONAL MATCH
+    LeftOuter,
+}
+
 #[derive(Debug, Clone)]
 pub struct ApplyInner {
     pub left: Box<PlanExpr>,
     pub right: Box<PlanExpr>,
+    pub kind: ApplyKind,
     // TODO(pgao): should we put argument here?
 }
 
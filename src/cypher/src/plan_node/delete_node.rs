@@ -0,0 +1,56 @@
+use super::*;
+use crate::expr::BoxedExpr;
+
+#[derive(Debug, Clone)]
+pub struct DeleteNode {
+    pub base: PlanBase,
+    pub(crate) inner: DeleteNodeInner,
+}
+
+impl DeleteNode {
+    pub fn new(inner: DeleteNodeInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for DeleteNode {
+    type Inner = DeleteNodeInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![
+            ("detach", Pretty::display(&self.inner.detach)),
+            (
+                "nodes",
+                Pretty::Array(self.inner.nodes.iter().map(|x| Pretty::from(x.pretty())).collect_vec()),
+            ),
+        ];
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("DeleteNode", fields, children)
+    }
+}
+
+/// Delete the nodes evaluated from `nodes` for each input row, nulls are ignored.
+/// With `detach`, relationships of the nodes are deleted as well.
+#[derive(Debug, Clone)]
+pub struct DeleteNodeInner {
+    pub input: Box<PlanExpr>,
+    pub nodes: Vec<BoxedExpr>,
+    pub detach: bool,
+}
+
+impl InnerNode for DeleteNodeInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.input.schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+}
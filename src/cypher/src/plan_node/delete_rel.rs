@@ -0,0 +1,51 @@
+use super::*;
+use crate::expr::BoxedExpr;
+
+#[derive(Debug, Clone)]
+pub struct DeleteRel {
+    pub base: PlanBase,
+    pub(crate) inner: DeleteRelInner,
+}
+
+impl DeleteRel {
+    pub fn new(inner: DeleteRelInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for DeleteRel {
+    type Inner = DeleteRelInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![(
+            "rels",
+            Pretty::Array(self.inner.rels.iter().map(|x| Pretty::from(x.pretty())).collect_vec()),
+        )];
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("DeleteRel", fields, children)
+    }
+}
+
+/// Delete the relationships evaluated from `rels` for each input row, nulls are ignored.
+#[derive(Debug, Clone)]
+pub struct DeleteRelInner {
+    pub input: Box<PlanExpr>,
+    pub rels: Vec<BoxedExpr>,
+}
+
+impl InnerNode for DeleteRelInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.input.schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+}
@@ -0,0 +1,123 @@
+use elio_common::IrToken;
+
+use super::*;
+use crate::ir::SetItem;
+
+#[derive(Debug, Clone)]
+pub struct Merge {
+    pub base: PlanBase,
+    pub(crate) inner: MergeInner,
+}
+
+impl Merge {
+    pub fn new(inner: MergeInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for Merge {
+    type Inner = MergeInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![];
+        if !self.inner.arguments.is_empty() {
+            fields.push((
+                "arguments",
+                Pretty::Array(
+                    self.inner
+                        .arguments
+                        .iter()
+                        .map(|x| Pretty::from(x.name.as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        if !self.inner.lock_labels.is_empty() {
+            fields.push((
+                "lock_labels",
+                Pretty::Array(
+                    self.inner
+                        .lock_labels
+                        .iter()
+                        .map(|x| Pretty::from(x.name().as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        if !self.inner.lock_reltypes.is_empty() {
+            fields.push((
+                "lock_reltypes",
+                Pretty::Array(
+                    self.inner
+                        .lock_reltypes
+                        .iter()
+                        .map(|x| Pretty::from(x.name().as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        if !self.inner.on_match.is_empty() {
+            fields.push((
+                "on_match",
+                Pretty::Array(self.inner.on_match.iter().map(Pretty::display).collect_vec()),
+            ));
+        }
+        if !self.inner.on_create.is_empty() {
+            fields.push((
+                "on_create",
+                Pretty::Array(self.inner.on_create.iter().map(Pretty::display).collect_vec()),
+            ));
+        }
+        let children = vec![
+            Pretty::Record(self.inner.input.xmlnode()),
+            Pretty::Record(self.inner.match_plan.xmlnode()),
+            Pretty::Record(self.inner.create_plan.xmlnode()),
+        ];
+        XmlNode::simple_record("Merge", fields, children)
+    }
+}
+
+/// For each input row, the arguments are passed to `match_plan`, and `create_plan` is executed if there is no match.
+/// The matched or created rows are appended to the input row, then `on_match` or `on_create` items are applied.
+/// `lock_labels` and `lock_reltypes` are locked exclusively until the transaction ends, so that concurrent merges
+/// are serialized.
+#[derive(Debug, Clone)]
+pub struct MergeInner {
+    pub input: Box<PlanExpr>,
+    pub match_plan: Box<PlanExpr>,
+    pub create_plan: Box<PlanExpr>,
+    pub arguments: Vec<Variable>,
+    pub on_match: Vec<SetItem>,
+    pub on_create: Vec<SetItem>,
+    pub lock_labels: Vec<IrToken>,
+    pub lock_reltypes: Vec<IrToken>,
+}
+
+impl MergeInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = (*self.input.schema()).clone();
+        for field in self.create_plan.schema().columns() {
+            if !schema.fields.iter().any(|x| x.name == field.name) {
+                schema.fields.push(field.clone());
+            }
+        }
+        schema.into()
+    }
+}
+
+impl InnerNode for MergeInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.build_schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input, &self.match_plan, &self.create_plan]
+    }
+}
@@ -10,57 +10,81 @@ use crate::expr::{Expr, ExprNode};
 use crate::plan_context::PlanContext;
 use crate::plan_node::plan_base::{PlanBase, PlanNodeId};
 
+pub mod aggregate;
 pub mod all_node_scan;
 pub mod apply;
 pub mod argument;
 pub mod create_node;
 pub mod create_rel;
+pub mod delete_node;
+pub mod delete_rel;
 pub mod empty;
 pub mod expand;
 pub mod filter;
 pub mod get_prop;
 pub mod load;
+pub mod merge;
+pub mod node_by_label_scan;
+pub mod node_index_range_seek;
 pub mod node_index_seek;
 pub mod pagination;
 pub mod plan_base;
 pub mod produce_result;
 pub mod project;
+pub mod quantified_expand;
+pub mod rel_scan;
+pub mod set;
+pub mod shortest_path;
 pub mod sort;
+pub mod union;
 pub mod unit;
+pub mod unwind;
 pub mod var_expand;
+pub use aggregate::*;
 pub use all_node_scan::*;
 pub use apply::*;
 pub use argument::*;
 pub use create_node::*;
 pub use create_rel::*;
+pub use delete_node::*;
+pub use delete_rel::*;
 pub use empty::*;
 pub use expand::*;
 pub use filter::*;
 pub use get_prop::*;
 pub use load::*;
+pub use merge::*;
+pub use node_by_label_scan::*;
+pub use node_index_range_seek::*;
 pub use node_index_seek::*;
 pub use pagination::*;
 pub use produce_result::*;
 pub use project::*;
+pub use quantified_expand::*;
+pub use rel_scan::*;
+pub use set::*;
+pub use shortest_path::*;
 pub use sort::*;
+pub use union::*;
 pub use unit::*;
+pub use unwind::*;
 pub use var_expand::*;
 
-#[derive(Default, Debug, Clone, Copy, derive_more::Display)]
-pub enum PathMode {
-    Walk, // repeated node, repeated rel
-    #[default]
-    Trail, // repeated node, non-repeated rel
-}
+pub use crate::ir::node_connection::PathMode;
 
 #[derive(Clone, Debug)]
 pub enum PlanExpr {
     // graph
     AllNodeScan(AllNodeScan),
+    NodeByLabelScan(NodeByLabelScan),
     NodeIndexSeek(NodeIndexSeek),
+    NodeIndexRangeSeek(NodeIndexRangeSeek),
+    RelScan(RelScan),
     GetProperty(GetProperty),
     Expand(Expand),
     VarExpand(VarExpand),
+    QuantifiedExpand(QuantifiedExpand),
+    ShortestPath(ShortestPath),
     Apply(Apply),
     Argument(Argument),
     Unit(Unit),
@@ -68,12 +92,19 @@ pub enum PlanExpr {
     // graph-modify
     CreateNode(CreateNode),
     CreateRel(CreateRel),
+    DeleteNode(DeleteNode),
+    DeleteRel(DeleteRel),
+    Set(Set),
+    Merge(Merge),
     // relational
     Load(Load),
+    Unwind(Unwind),
     Project(Project),
     Sort(Sort),
     Filter(Filter),
+    Aggregate(Aggregate),
     Pagination(Pagination),
+    Union(Union),
     Empty(Empty),
 }
 
@@ -140,20 +171,32 @@ macro_rules! impl_plan_node_common {
 }
 
 impl_plan_node_common!(AllNodeScan, AllNodeScanInner);
+impl_plan_node_common!(NodeByLabelScan, NodeByLabelScanInner);
 impl_plan_node_common!(NodeIndexSeek, NodeIndexSeekInner);
+impl_plan_node_common!(NodeIndexRangeSeek, NodeIndexRangeSeekInner);
+impl_plan_node_common!(RelScan, RelScanInner);
 impl_plan_node_common!(GetProperty, GetPropertyInner);
 impl_plan_node_common!(Expand, ExpandInner);
 impl_plan_node_common!(VarExpand, VarExpandInner);
+impl_plan_node_common!(QuantifiedExpand, QuantifiedExpandInner);
+impl_plan_node_common!(ShortestPath, ShortestPathInner);
 impl_plan_node_common!(Apply, ApplyInner);
 impl_plan_node_common!(Argument, ArgumentInner);
 impl_plan_node_common!(Unit, UnitInner);
 impl_plan_node_common!(CreateNode, CreateNodeInner);
 impl_plan_node_common!(CreateRel, CreateRelInner);
+impl_plan_node_common!(DeleteNode, DeleteNodeInner);
+impl_plan_node_common!(DeleteRel, DeleteRelInner);
+impl_plan_node_common!(Set, SetInner);
+impl_plan_node_common!(Merge, MergeInner);
 impl_plan_node_common!(Load, LoadInner);
+impl_plan_node_common!(Unwind, UnwindInner);
 impl_plan_node_common!(Project, ProjectInner);
 impl_plan_node_common!(Sort, SortInner);
 impl_plan_node_common!(Filter, FilterInner);
+impl_plan_node_common!(Aggregate, AggregateInner);
 impl_plan_node_common!(Pagination, PaginationInner);
+impl_plan_node_common!(Union, UnionInner);
 impl_plan_node_common!(Empty, EmptyInner);
 impl_plan_node_common!(ProduceResult, ProduceResultInner);
 
@@ -195,20 +238,32 @@ macro_rules! impl_plan_expr_dispatch {
 
 impl_plan_expr_dispatch!(
     AllNodeScan,
+    NodeByLabelScan,
     NodeIndexSeek,
+    NodeIndexRangeSeek,
+    RelScan,
     GetProperty,
     Expand,
     VarExpand,
+    QuantifiedExpand,
+    ShortestPath,
     Apply,
     Argument,
     Unit,
     CreateNode,
     CreateRel,
+    DeleteNode,
+    DeleteRel,
+    Set,
+    Merge,
     Load,
+    Unwind,
     Project,
     Sort,
     Filter,
+    Aggregate,
     Pagination,
+    Union,
     Empty,
     ProduceResult
 );
@@ -0,0 +1,83 @@
+use educe::{self, Educe};
+use elio_common::LabelId;
+use itertools::Itertools;
+
+use super::*;
+
+// Return VirtualNode of the nodes with the label
+#[derive(Debug, Clone)]
+pub struct NodeByLabelScan {
+    pub base: PlanBase,
+    pub(crate) inner: NodeByLabelScanInner,
+}
+
+impl NodeByLabelScan {
+    pub fn new(inner: NodeByLabelScanInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for NodeByLabelScan {
+    type Inner = NodeByLabelScanInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![
+            ("variable", Pretty::from(self.inner.variable.as_ref())),
+            ("label", Pretty::from(self.inner.label_name.as_str())),
+        ];
+        if !self.inner.arguments.is_empty() {
+            fields.push((
+                "arguments",
+                Pretty::Array(
+                    self.inner
+                        .arguments
+                        .iter()
+                        .map(|x| Pretty::from(x.name.as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        XmlNode::simple_record("NodeByLabelScan", fields, Default::default())
+    }
+}
+
+#[derive(Educe, Clone)]
+#[educe(Debug)]
+pub struct NodeByLabelScanInner {
+    pub variable: VariableName,
+    pub label_name: String,
+    pub label_id: LabelId,
+    pub arguments: Vec<Variable>,
+    #[educe(Debug(ignore))]
+    pub ctx: Arc<PlanContext>,
+}
+
+impl NodeByLabelScanInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = Schema::empty();
+        schema.fields.push(Variable {
+            name: self.variable.clone(),
+            typ: DataType::VirtualNode,
+        });
+        schema.fields.extend(self.arguments.clone());
+        schema.into()
+    }
+}
+
+impl InnerNode for NodeByLabelScanInner {
+    fn build_base(&self) -> PlanBase {
+        let schema = self.build_schema();
+        PlanBase::new(schema, self.ctx.clone())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![]
+    }
+}
@@ -0,0 +1,183 @@
+//! NodeIndexRangeSeek - Secondary index based node lookup
+//!
+//! This plan node scans the entries of a secondary index whose leading properties
+//! equal the given values, and whose next property satisfies the range predicates.
+
+use std::sync::Arc;
+
+use elio_common::data_type::DataType;
+use elio_common::schema::{Schema, Variable};
+use elio_common::variable::VariableName;
+use elio_common::{LabelId, PropertyKeyId};
+use itertools::Itertools;
+use pretty_xmlish::{Pretty, XmlNode};
+
+use super::*;
+use crate::expr::Expr;
+use crate::plan_context::PlanContext;
+use crate::plan_node::plan_base::PlanBase;
+
+/// NodeIndexRangeSeek uses a secondary index to lookup nodes by property values and ranges
+#[derive(Clone)]
+pub struct NodeIndexRangeSeek {
+    pub base: PlanBase,
+    pub(crate) inner: NodeIndexRangeSeekInner,
+}
+
+impl std::fmt::Debug for NodeIndexRangeSeek {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeIndexRangeSeek")
+            .field("base", &self.base)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl NodeIndexRangeSeek {
+    pub fn new(inner: NodeIndexRangeSeekInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for NodeIndexRangeSeek {
+    type Inner = NodeIndexRangeSeekInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![
+            ("variable", Pretty::from(self.inner.variable.as_ref())),
+            ("label", Pretty::from(self.inner.label_name.as_str())),
+            ("index", Pretty::from(self.inner.index_name.as_str())),
+        ];
+
+        let mut props = self
+            .inner
+            .property_names
+            .iter()
+            .zip(self.inner.prefix_values.iter())
+            .map(|(name, val)| format!("{} = {}", name, val.pretty()))
+            .collect_vec();
+        if let Some(name) = self.inner.property_names.get(self.inner.prefix_values.len()) {
+            props.extend(self.inner.range.iter().map(|pred| pred.pretty(name)));
+        }
+        fields.push((
+            "properties",
+            Pretty::Array(props.into_iter().map(Pretty::from).collect_vec()),
+        ));
+        if !self.inner.arguments.is_empty() {
+            fields.push((
+                "arguments",
+                Pretty::Array(
+                    self.inner
+                        .arguments
+                        .iter()
+                        .map(|x| Pretty::from(x.name.as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+
+        XmlNode::simple_record("NodeIndexRangeSeek", fields, Default::default())
+    }
+}
+
+/// Predicate on the property following the leading properties of the index
+#[derive(Clone, Debug, PartialEq)]
+pub enum RangePredicate {
+    Lt(Expr),
+    LtEq(Expr),
+    Gt(Expr),
+    GtEq(Expr),
+    StartsWith(Expr),
+    /// The property is one of the elements of the list
+    In(Expr),
+}
+
+impl RangePredicate {
+    pub fn expr(&self) -> &Expr {
+        match self {
+            RangePredicate::Lt(expr)
+            | RangePredicate::LtEq(expr)
+            | RangePredicate::Gt(expr)
+            | RangePredicate::GtEq(expr)
+            | RangePredicate::StartsWith(expr)
+            | RangePredicate::In(expr) => expr,
+        }
+    }
+
+    pub fn pretty(&self, property: &str) -> String {
+        let op = match self {
+            RangePredicate::Lt(_) => "<",
+            RangePredicate::LtEq(_) => "<=",
+            RangePredicate::Gt(_) => ">",
+            RangePredicate::GtEq(_) => ">=",
+            RangePredicate::StartsWith(_) => "STARTS WITH",
+            RangePredicate::In(_) => "IN",
+        };
+        format!("{} {} {}", property, op, self.expr().pretty())
+    }
+}
+
+#[derive(Clone)]
+pub struct NodeIndexRangeSeekInner {
+    /// Output variable name for the node
+    pub variable: VariableName,
+    pub label_name: String,
+    pub label_id: LabelId,
+    pub index_name: String,
+    /// Names of the properties used by the lookup, in index order
+    pub property_names: Vec<String>,
+    /// All the properties of the index, in index order
+    pub property_key_ids: Vec<PropertyKeyId>,
+    /// Values of the leading properties, evaluated on the arguments
+    pub prefix_values: Vec<Expr>,
+    /// Predicates on the property following the leading properties, evaluated on the arguments
+    pub range: Vec<RangePredicate>,
+    pub arguments: Vec<Variable>,
+    pub ctx: Arc<PlanContext>,
+}
+
+impl std::fmt::Debug for NodeIndexRangeSeekInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeIndexRangeSeekInner")
+            .field("variable", &self.variable)
+            .field("label_name", &self.label_name)
+            .field("label_id", &self.label_id)
+            .field("index_name", &self.index_name)
+            .field("property_names", &self.property_names)
+            .field("property_key_ids", &self.property_key_ids)
+            .field("prefix_values", &self.prefix_values)
+            .field("range", &self.range)
+            .field("arguments", &self.arguments)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NodeIndexRangeSeekInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = Schema::empty();
+        schema.fields.push(Variable {
+            name: self.variable.clone(),
+            typ: DataType::VirtualNode,
+        });
+        schema.fields.extend(self.arguments.clone());
+        schema.into()
+    }
+}
+
+impl InnerNode for NodeIndexRangeSeekInner {
+    fn build_base(&self) -> PlanBase {
+        let schema = self.build_schema();
+        PlanBase::new(schema, self.ctx.clone())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![]
+    }
+}
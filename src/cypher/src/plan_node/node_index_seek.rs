@@ -67,6 +67,18 @@ impl PlanNode for NodeIndexSeek {
             "properties",
             Pretty::Array(props.into_iter().map(Pretty::from).collect_vec()),
         ));
+        if !self.inner.arguments.is_empty() {
+            fields.push((
+                "arguments",
+                Pretty::Array(
+                    self.inner
+                        .arguments
+                        .iter()
+                        .map(|x| Pretty::from(x.name.as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
 
         XmlNode::simple_record("NodeIndexSeek", fields, Default::default())
     }
@@ -81,7 +93,9 @@ pub struct NodeIndexSeekInner {
     pub constraint_name: String,
     pub property_names: Vec<String>,
     pub property_key_ids: Vec<PropertyKeyId>,
+    /// Values to look up, evaluated on the arguments
     pub property_values: Vec<Expr>,
+    pub arguments: Vec<Variable>,
     pub ctx: Arc<PlanContext>,
 }
 
@@ -95,6 +109,7 @@ impl std::fmt::Debug for NodeIndexSeekInner {
             .field("property_names", &self.property_names)
             .field("property_key_ids", &self.property_key_ids)
             .field("property_values", &self.property_values)
+            .field("arguments", &self.arguments)
             .finish_non_exhaustive()
     }
 }
@@ -106,6 +121,7 @@ impl NodeIndexSeekInner {
             name: self.variable.clone(),
             typ: DataType::VirtualNode,
         });
+        schema.fields.extend(self.arguments.clone());
         schema.into()
     }
 }
@@ -23,10 +23,13 @@ impl PlanNode for Pagination {
     }
 
     fn xmlnode(&self) -> XmlNode<'_> {
-        let fields = vec![
-            ("offset", Pretty::from(self.inner.offset.to_string())),
-            ("limit", Pretty::from(self.inner.limit.to_string())),
-        ];
+        let mut fields = vec![];
+        if let Some(offset) = &self.inner.offset {
+            fields.push(("offset", Pretty::from(offset.pretty())));
+        }
+        if let Some(limit) = &self.inner.limit {
+            fields.push(("limit", Pretty::from(limit.pretty())));
+        }
         let children = vec![Pretty::Record(self.inner.input.xmlnode())];
         XmlNode::simple_record("Pagination", fields, children)
     }
@@ -35,9 +38,10 @@ impl PlanNode for Pagination {
 #[derive(Debug, Clone)]
 pub struct PaginationInner {
     pub input: Box<PlanExpr>,
-    pub offset: i64,
-    // -1 means all records should be returned
-    pub limit: i64,
+    // skip no records if None
+    pub offset: Option<Box<Expr>>,
+    // return all records if None
+    pub limit: Option<Box<Expr>>,
 }
 
 impl InnerNode for PaginationInner {
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use super::*;
+use crate::ir::node_connection::QuantifiedPathPattern;
+use crate::pretty_utils::pretty_display_iter;
+
+#[derive(Debug, Clone)]
+pub struct QuantifiedExpand {
+    pub base: PlanBase,
+    pub(crate) inner: QuantifiedExpandInner,
+}
+
+impl QuantifiedExpand {
+    pub fn new(inner: QuantifiedExpandInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for QuantifiedExpand {
+    type Inner = QuantifiedExpandInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![
+            ("from", Pretty::from(self.inner.from.as_ref())),
+            ("to", Pretty::from(self.inner.to.as_ref())),
+            ("qpp", Pretty::display(&self.inner.qpp)),
+            ("path_mode", Pretty::display(&self.inner.qpp.mode)),
+        ];
+        let groups = self.inner.qpp.group_variables().map(|v| v.name).collect_vec();
+        if !groups.is_empty() {
+            fields.push(("groups", pretty_display_iter(groups.iter())));
+        }
+        let name = match self.inner.kind {
+            ExpandKind::All => "QuantifiedExpandAll",
+            ExpandKind::Into => "QuantifiedExpandInto",
+        };
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record(name, fields, children)
+    }
+}
+
+// (x) ((a)-[r]->(b) WHERE a.v < b.v){1,3} (y)
+// Expand all: given x/y, repeat the inner pattern from it, produce the group variables a, r, b and y/x,
+// where each group variable is a list of the values bound by every iteration
+// Expand into: given x and y, produce the group variables
+
+#[derive(Debug, Clone)]
+pub struct QuantifiedExpandInner {
+    pub input: Box<PlanExpr>,
+    pub from: VariableName,
+    pub to: VariableName,
+    pub qpp: QuantifiedPathPattern,
+    pub kind: ExpandKind,
+}
+
+impl InnerNode for QuantifiedExpandInner {
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+
+    // output schema = [input, node groups, rel groups, to]
+    fn build_base(&self) -> PlanBase {
+        let mut schema = Schema::from_arc(self.input.schema());
+        for var in self.qpp.group_variables() {
+            schema.add_column(var);
+        }
+        if matches!(self.kind, ExpandKind::All) {
+            schema.add_column(Variable::new(&self.to, &DataType::VirtualNode));
+        }
+        PlanBase::new(Arc::new(schema), self.input.ctx())
+    }
+}
@@ -0,0 +1,98 @@
+use educe::{self, Educe};
+use elio_common::{IrToken, SemanticDirection};
+use itertools::Itertools;
+
+use super::*;
+
+// Return VirtualNode, Rel and VirtualNode
+#[derive(Debug, Clone)]
+pub struct RelScan {
+    pub base: PlanBase,
+    pub(crate) inner: RelScanInner,
+}
+
+impl RelScan {
+    pub fn new(inner: RelScanInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for RelScan {
+    type Inner = RelScanInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let mut fields = vec![
+            ("left", Pretty::from(self.inner.left.as_ref())),
+            ("rel", Pretty::from(self.inner.rel.as_ref())),
+            ("right", Pretty::from(self.inner.right.as_ref())),
+            ("direction", Pretty::from(self.inner.direction.to_string())),
+            (
+                "types",
+                Pretty::Array(
+                    self.inner
+                        .types
+                        .iter()
+                        .map(|x| Pretty::from(x.to_string()))
+                        .collect_vec(),
+                ),
+            ),
+        ];
+        if !self.inner.arguments.is_empty() {
+            fields.push((
+                "arguments",
+                Pretty::Array(
+                    self.inner
+                        .arguments
+                        .iter()
+                        .map(|x| Pretty::from(x.name.as_ref()))
+                        .collect_vec(),
+                ),
+            ));
+        }
+        XmlNode::simple_record("RelScan", fields, Default::default())
+    }
+}
+
+/// Scan the relationships of the given types, `(left)-[rel]->(right)` for outgoing direction.
+/// With `Both` direction, each relationship is produced once from each side.
+#[derive(Educe, Clone)]
+#[educe(Debug)]
+pub struct RelScanInner {
+    pub left: VariableName,
+    pub rel: VariableName,
+    pub right: VariableName,
+    pub direction: SemanticDirection,
+    pub types: Vec<IrToken>,
+    pub arguments: Vec<Variable>,
+    #[educe(Debug(ignore))]
+    pub ctx: Arc<PlanContext>,
+}
+
+impl RelScanInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = Schema::empty();
+        schema.fields.push(Variable::new(&self.left, &DataType::VirtualNode));
+        schema.fields.push(Variable::new(&self.rel, &DataType::Rel));
+        schema.fields.push(Variable::new(&self.right, &DataType::VirtualNode));
+        schema.fields.extend(self.arguments.clone());
+        schema.into()
+    }
+}
+
+impl InnerNode for RelScanInner {
+    fn build_base(&self) -> PlanBase {
+        let schema = self.build_schema();
+        PlanBase::new(schema, self.ctx.clone())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![]
+    }
+}
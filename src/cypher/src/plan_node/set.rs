@@ -0,0 +1,52 @@
+use super::*;
+use crate::ir::SetItem;
+
+#[derive(Debug, Clone)]
+pub struct Set {
+    pub base: PlanBase,
+    pub(crate) inner: SetInner,
+}
+
+impl Set {
+    pub fn new(inner: SetInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for Set {
+    type Inner = SetInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![(
+            "items",
+            Pretty::Array(self.inner.items.iter().map(Pretty::display).collect_vec()),
+        )];
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("Set", fields, children)
+    }
+}
+
+/// Update properties and labels of the entities for each input row, null entities are ignored.
+/// Items are applied in order.
+#[derive(Debug, Clone)]
+pub struct SetInner {
+    pub input: Box<PlanExpr>,
+    pub items: Vec<SetItem>,
+}
+
+impl InnerNode for SetInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.input.schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+}
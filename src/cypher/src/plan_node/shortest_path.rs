@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use super::*;
+use crate::ir::node_connection::RelPattern;
+use crate::ir::path_pattern::Selector;
+
+#[derive(Debug, Clone)]
+pub struct ShortestPath {
+    pub base: PlanBase,
+    pub(crate) inner: ShortestPathInner,
+}
+
+impl ShortestPath {
+    pub fn new(inner: ShortestPathInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for ShortestPath {
+    type Inner = ShortestPathInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![
+            ("from", Pretty::from(self.inner.rel_pattern.left().as_ref())),
+            ("to", Pretty::from(self.inner.rel_pattern.right().as_ref())),
+            ("rel_pattern", Pretty::display(&self.inner.rel_pattern)),
+            ("path", Pretty::from(self.inner.path.as_ref())),
+            ("selector", Pretty::display(&self.inner.selector)),
+        ];
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("ShortestPath", fields, children)
+    }
+}
+
+// p = ANY SHORTEST (a)-[r*1..3]->(b)
+// given a and b, produce r and p for each path picked by the selector, where r is an list of rels
+
+#[derive(Debug, Clone)]
+pub struct ShortestPathInner {
+    pub input: Box<PlanExpr>,
+    pub rel_pattern: RelPattern,
+    pub path: VariableName,
+    pub selector: Selector,
+}
+
+impl InnerNode for ShortestPathInner {
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+
+    // output schema = [input, rel, path]
+    fn build_base(&self) -> PlanBase {
+        let mut schema = Schema::from_arc(self.input.schema());
+        schema.add_column(Variable::new(
+            &self.rel_pattern.variable,
+            &DataType::new_list(DataType::Rel),
+        ));
+        schema.add_column(Variable::new(&self.path, &DataType::VirtualPath));
+        PlanBase::new(Arc::new(schema), self.input.ctx())
+    }
+}
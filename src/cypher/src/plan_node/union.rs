@@ -0,0 +1,91 @@
+use super::*;
+use crate::error::{PlanError, SemanticError};
+
+#[derive(Debug, Clone)]
+pub struct Union {
+    pub base: PlanBase,
+    pub(crate) inner: UnionInner,
+}
+
+impl Union {
+    pub fn new(inner: UnionInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for Union {
+    type Inner = UnionInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![("distinct", Pretty::debug(&self.inner.distinct))];
+        let children = self
+            .inner
+            .inputs
+            .iter()
+            .map(|x| Pretty::Record(x.xmlnode()))
+            .collect_vec();
+        XmlNode::simple_record("Union", fields, children)
+    }
+}
+
+/// Concatenate the rows of all inputs, duplicated rows are removed if `distinct`.
+/// Inputs must have the same column names in the same order.
+#[derive(Debug, Clone)]
+pub struct UnionInner {
+    pub inputs: Vec<Box<PlanExpr>>,
+    pub distinct: bool,
+}
+
+impl UnionInner {
+    pub fn new(inputs: Vec<Box<PlanExpr>>, distinct: bool) -> Result<Self, PlanError> {
+        assert!(!inputs.is_empty());
+        let head = inputs[0].schema();
+        for input in inputs.iter().skip(1) {
+            let schema = input.schema();
+            if !head
+                .fields
+                .iter()
+                .map(|f| &f.name)
+                .eq(schema.fields.iter().map(|f| &f.name))
+            {
+                return Err(SemanticError::invalid_union(&format!(
+                    "[{}] and [{}]",
+                    head.fields.iter().map(|f| &f.name).join(", "),
+                    schema.fields.iter().map(|f| &f.name).join(", ")
+                ))
+                .into());
+            }
+        }
+        Ok(Self { inputs, distinct })
+    }
+
+    // columns of different types across inputs are Any
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = Schema::from_arc(self.inputs[0].schema());
+        for input in self.inputs.iter().skip(1) {
+            for (field, other) in schema.fields.iter_mut().zip_eq(input.schema().fields.iter()) {
+                if field.typ != other.typ {
+                    field.typ = DataType::Any;
+                }
+            }
+        }
+        schema.into()
+    }
+}
+
+impl InnerNode for UnionInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.build_schema(), self.inputs[0].ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        self.inputs.iter().map(|x| x.as_ref()).collect()
+    }
+}
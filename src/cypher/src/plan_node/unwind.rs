@@ -0,0 +1,61 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct Unwind {
+    pub base: PlanBase,
+    pub(crate) inner: UnwindInner,
+}
+
+impl Unwind {
+    pub fn new(inner: UnwindInner) -> Self {
+        Self {
+            base: inner.build_base(),
+            inner,
+        }
+    }
+}
+
+impl PlanNode for Unwind {
+    type Inner = UnwindInner;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn xmlnode(&self) -> XmlNode<'_> {
+        let fields = vec![
+            ("variable", Pretty::from(self.inner.variable.name.as_ref())),
+            ("expr", self.inner.expr.pretty().into()),
+        ];
+        let children = vec![Pretty::Record(self.inner.input.xmlnode())];
+        XmlNode::simple_record("Unwind", fields, children)
+    }
+}
+
+/// Expand the list `expr` into rows, each input row is repeated once for every list element,
+/// which is bound to `variable`. A null or empty list produces no row.
+#[derive(Debug, Clone)]
+pub struct UnwindInner {
+    pub input: Box<PlanExpr>,
+    pub variable: Variable,
+    // evaluated on input rows
+    pub expr: Expr,
+}
+
+impl UnwindInner {
+    fn build_schema(&self) -> Arc<Schema> {
+        let mut schema = self.input.schema().as_ref().clone();
+        schema.add_column(self.variable.clone());
+        schema.into()
+    }
+}
+
+impl InnerNode for UnwindInner {
+    fn build_base(&self) -> PlanBase {
+        PlanBase::new(self.build_schema(), self.input.ctx())
+    }
+
+    fn inputs(&self) -> Vec<&PlanExpr> {
+        vec![&self.input]
+    }
+}
@@ -4,17 +4,23 @@ use std::collections::VecDeque;
 
 use elio_common::schema::Schema;
 use elio_common::variable::VariableName;
+use elio_common::{IrToken, LabelId};
 use indexmap::IndexSet;
 use itertools::Itertools;
 
-use super::index_selection::{find_index_candidates, remove_index_conditions};
+use super::index_selection::{
+    find_index_candidates, find_range_index_candidate, remove_index_conditions, remove_range_index_conditions,
+};
 use super::*;
-use crate::expr::FilterExprs;
-use crate::ir::node_connection::RelPattern;
+use crate::expr::{Expr, FilterExprs, HasLabel};
+use crate::ir::node_connection::{QuantifiedPathPattern, RelPattern};
+use crate::ir::path_pattern::SelectivePathPattern;
 use crate::ir::query_graph::QueryGraph;
 use crate::plan_node::{
-    AllNodeScan, AllNodeScanInner, Argument, ArgumentInner, Expand, ExpandInner, ExpandKind, Filter, FilterInner,
-    NodeIndexSeek, NodeIndexSeekInner, PathMode, VarExpand, VarExpandInner,
+    AllNodeScan, AllNodeScanInner, Apply, ApplyInner, ApplyKind, Argument, ArgumentInner, Expand, ExpandInner,
+    ExpandKind, Filter, FilterInner, NodeByLabelScan, NodeByLabelScanInner, NodeIndexRangeSeek,
+    NodeIndexRangeSeekInner, NodeIndexSeek, NodeIndexSeekInner, QuantifiedExpand, QuantifiedExpandInner, RelScan,
+    RelScanInner, ShortestPath, ShortestPathInner, VarExpand, VarExpandInner,
 };
 
 // This is an simple implementation of planning an query graph.
@@ -26,16 +32,15 @@ pub(crate) fn plan_qg_simple(ctx: &mut PlannerContext, qg: &QueryGraph) -> Resul
     }
 
     // Try to find an index that can be used for the first node
-    let (solver, remaining_filter) = TraversalSolver::new_with_index_selection(ctx, qg);
-    let mut solver = solver;
+    let mut solver = TraversalSolver::new_with_index_selection(ctx, qg);
     solver.solve()?;
     let mut root = solver.root;
 
     // solve remaining filter (after index conditions removed)
-    if !remaining_filter.is_true() {
+    if !solver.filter.is_true() {
         root = Filter::new(FilterInner {
             input: root,
-            condition: remaining_filter,
+            condition: solver.filter,
         })
         .into();
     }
@@ -46,26 +51,33 @@ pub(crate) fn plan_qg_simple(ctx: &mut PlannerContext, qg: &QueryGraph) -> Resul
 /// Solve the query graph by Traversal strategy:
 /// 1. select start node to traversal, generate an plan leaf
 ///   - Argument
-///   - NodeScan
+///   - NodeScan, NodeByLabelScan when the node has a label, nodes with a label are preferred
+///   - RelScan, when the relationship is more selective than any node
 /// 2. select node connection by the given node
 ///   - Expand
 /// 3. if the node have multiple node connections, we have two strategy 3.1 DFS: this is what we currently doing 3.2 BFS
+///   - quantified path patterns are expanded after the relationships reachable from the solved nodes
+///   - VarExpand if the quantified path pattern is a single relationship without predicates, otherwise QuantifiedExpand
+/// 4. select selective path pattern when both endpoints are solved
+///   - ShortestPath, an unsolved endpoint is scanned for each solved row by Apply first
 ///
 /// This only solves graph traversal, filter and get properties not solved by this class.
 ///
 /// Topology is our first class citizon, purly solve the graph traversal.
 struct TraversalSolver<'a> {
-    pub _ctx: &'a mut PlannerContext,
+    pub ctx: &'a mut PlannerContext,
     pub qg: &'a QueryGraph,
     solved: IndexSet<VariableName>,
     stack: VecDeque<&'a RelPattern>,
     root: Box<PlanExpr>,
+    // filter not solved by index seek
+    filter: FilterExprs,
 }
 
 impl<'a> TraversalSolver<'a> {
     /// Create solver with index selection optimization
-    /// Returns (solver, remaining_filter) where remaining_filter has index conditions removed
-    fn new_with_index_selection(ctx: &'a mut PlannerContext, qg: &'a QueryGraph) -> (Self, FilterExprs) {
+    /// The remaining filter of the solver has index conditions removed
+    fn new_with_index_selection(ctx: &'a mut PlannerContext, qg: &'a QueryGraph) -> Self {
         assert!(!qg.nodes.is_empty() || !qg.imported().is_empty());
         let imported = qg.imported().iter().cloned().collect_vec();
         let mut solved = IndexSet::new();
@@ -98,20 +110,45 @@ impl<'a> TraversalSolver<'a> {
             None
         };
 
-        if stack.is_empty() && !qg.nodes.is_empty() {
-            // Try to find an index for the first node
-            let first = qg_nodes.next().unwrap();
+        if stack.is_empty()
+            && !qg.nodes.is_empty()
+            && let Some(rel) = Self::select_rel_scan(qg, &solved)
+        {
+            // no node can be located cheaply, start from the relationships of the given types
+            let inner = RelScanInner {
+                left: rel.endpoints.0.clone(),
+                rel: rel.variable.clone(),
+                right: rel.endpoints.1.clone(),
+                direction: rel.dir,
+                types: rel.types.clone(),
+                arguments: imported,
+                ctx: ctx.ctx.clone(),
+            };
+            root = Some(RelScan::new(inner).into());
+            solved.insert(rel.variable.clone());
+
+            // push connections on stack, the connections of left are solved first
+            for node in [&rel.endpoints.1, &rel.endpoints.0] {
+                solved.insert(node.clone());
+                for conn in qg.connections(node).rev() {
+                    stack.push_back(conn);
+                }
+            }
+        } else if stack.is_empty()
+            && !qg
+                .quantified_paths()
+                .iter()
+                .any(|qpp| qpp.endpoint_nodes().iter().any(|node| solved.contains(*node)))
+            && let Some(first) = qg_nodes
+                .clone()
+                .find(|node| !solved.contains(*node) && has_label_predicate(&qg.filter, node))
+                .or_else(|| qg_nodes.find(|node| !solved.contains(*node)))
+        {
+            // Try to find an index for the first node not solved by arguments, nodes with a label first
 
             // Check if we can use an index for this node
-            let (plan, filter) = Self::try_create_index_seek(ctx, first, &qg.filter, &imported).unwrap_or_else(|| {
-                // Fallback to AllNodeScan
-                let inner = AllNodeScanInner {
-                    variable: first.clone(),
-                    arguments: imported,
-                    ctx: ctx.ctx.clone(),
-                };
-                (AllNodeScan::new(inner).into(), qg.filter.clone())
-            });
+            let (plan, filter) = Self::try_create_index_seek(ctx, first, &qg.filter, &imported)
+                .unwrap_or_else(|| Self::create_node_scan(ctx, first, &qg.filter, imported));
 
             root = Some(plan);
             remaining_filter = filter;
@@ -123,29 +160,48 @@ impl<'a> TraversalSolver<'a> {
             }
         }
 
-        (
-            Self {
-                _ctx: ctx,
-                qg,
-                solved,
-                stack,
-                // SAFETY: imported.is_empty() and !stack.is_empty() won't happen at the same time.
-                root: root.unwrap().into(),
-            },
-            remaining_filter,
-        )
+        Self {
+            ctx,
+            qg,
+            solved,
+            stack,
+            // SAFETY: imported.is_empty() and !stack.is_empty() won't happen at the same time.
+            root: root.unwrap().into(),
+            filter: remaining_filter,
+        }
+    }
+
+    /// Select a relationship to scan by type, when it is more selective than any node.
+    /// Without statistics, a node with a label is considered more selective than a relationship with types,
+    /// and a relationship without types is never scanned.
+    fn select_rel_scan(qg: &'a QueryGraph, solved: &IndexSet<VariableName>) -> Option<&'a RelPattern> {
+        if qg.nodes.iter().any(|node| has_label_predicate(&qg.filter, node)) {
+            return None;
+        }
+        qg.rels.iter().find(|rel| {
+            let (left, right) = &rel.endpoints;
+            rel.length.is_simple()
+                && !rel.types.is_empty()
+                // a self loop needs both endpoints to be the same node
+                && left != right
+                && !solved.contains(left)
+                && !solved.contains(right)
+        })
     }
 
-    /// Try to create a NodeIndexSeek for the given node variable
+    /// Try to create a NodeIndexSeek for the given node variable, or else a NodeIndexRangeSeek
     /// Returns Some((plan, remaining_filter)) if an index can be used, None otherwise
     fn try_create_index_seek(
         ctx: &PlannerContext,
         node_var: &VariableName,
         filter: &FilterExprs,
-        _arguments: &[elio_common::schema::Variable],
+        arguments: &[elio_common::schema::Variable],
     ) -> Option<(PlanExpr, FilterExprs)> {
         // Find index candidates for this node
-        let candidate = find_index_candidates(&ctx.ctx, filter, node_var)?;
+        let argument_names = arguments.iter().map(|x| x.name.clone()).collect();
+        let Some(candidate) = find_index_candidates(&ctx.ctx, filter, node_var, &argument_names) else {
+            return Self::try_create_index_range_seek(ctx, node_var, filter, arguments, &argument_names);
+        };
 
         // Create NodeIndexSeek plan
         let plan = NodeIndexSeek::new(NodeIndexSeekInner {
@@ -156,6 +212,7 @@ impl<'a> TraversalSolver<'a> {
             property_names: candidate.property_names.clone(),
             property_key_ids: candidate.property_key_ids.clone(),
             property_values: candidate.property_values.clone(),
+            arguments: arguments.to_vec(),
             ctx: ctx.ctx.clone(),
         });
 
@@ -165,14 +222,211 @@ impl<'a> TraversalSolver<'a> {
         Some((plan.into(), remaining_filter))
     }
 
-    // DFS traversal
+    /// Try to create a NodeIndexRangeSeek on a secondary index for the given node variable
+    fn try_create_index_range_seek(
+        ctx: &PlannerContext,
+        node_var: &VariableName,
+        filter: &FilterExprs,
+        arguments: &[elio_common::schema::Variable],
+        argument_names: &IndexSet<VariableName>,
+    ) -> Option<(PlanExpr, FilterExprs)> {
+        let candidate = find_range_index_candidate(&ctx.ctx, filter, node_var, argument_names)?;
+        let remaining_filter = remove_range_index_conditions(filter, &candidate);
+
+        let plan = NodeIndexRangeSeek::new(NodeIndexRangeSeekInner {
+            variable: candidate.variable,
+            label_name: candidate.label_name,
+            label_id: candidate.label_id,
+            index_name: candidate.index_hint.constraint_name,
+            property_names: candidate.property_names,
+            property_key_ids: candidate.index_hint.property_key_ids,
+            prefix_values: candidate.prefix_values,
+            range: candidate.range,
+            arguments: arguments.to_vec(),
+            ctx: ctx.ctx.clone(),
+        });
+
+        Some((plan.into(), remaining_filter))
+    }
+
+    /// Scan the nodes with a label of the node by NodeByLabelScan, or else all the nodes by AllNodeScan
+    /// Returns the plan and the filter without the label predicate solved by the scan
+    fn create_node_scan(
+        ctx: &PlannerContext,
+        node_var: &VariableName,
+        filter: &FilterExprs,
+        arguments: Vec<elio_common::schema::Variable>,
+    ) -> (PlanExpr, FilterExprs) {
+        let Some((label_pred, label_id, label_name)) = find_label_predicate(filter, node_var) else {
+            let inner = AllNodeScanInner {
+                variable: node_var.clone(),
+                arguments,
+                ctx: ctx.ctx.clone(),
+            };
+            return (AllNodeScan::new(inner).into(), filter.clone());
+        };
+
+        let inner = NodeByLabelScanInner {
+            variable: node_var.clone(),
+            label_name,
+            label_id,
+            arguments,
+            ctx: ctx.ctx.clone(),
+        };
+        let remaining_filter = FilterExprs::from_iter(filter.iter().filter(|expr| *expr != label_pred).cloned());
+        (NodeByLabelScan::new(inner).into(), remaining_filter)
+    }
+
+    // DFS traversal, quantified paths and selective paths are solved after the traversal reaches them
     // the final plan will be placed at root
     fn solve(&mut self) -> Result<(), PlanError> {
-        while let Some(rel) = self.stack.pop_back() {
-            if !self.solved.contains(&rel.variable) {
-                self.solve_node_connection_by_expand(rel)?;
+        let mut quantified_paths = self.qg.quantified_paths().iter().collect_vec();
+        let mut selective_paths = self.qg.selective_paths().iter().collect_vec();
+        loop {
+            while let Some(rel) = self.stack.pop_back() {
+                if !self.solved.contains(&rel.variable) {
+                    self.solve_node_connection_by_expand(rel)?;
+                }
+            }
+
+            if let Some(pos) = quantified_paths
+                .iter()
+                .position(|qpp| qpp.endpoint_nodes().iter().any(|node| self.solved.contains(*node)))
+            {
+                let qpp = quantified_paths.remove(pos);
+                self.solve_quantified_path(qpp)?;
+                continue;
+            }
+
+            let solved_endpoints = |spp: &SelectivePathPattern| {
+                spp.endpoint_nodes()
+                    .into_iter()
+                    .filter(|node| self.solved.contains(*node))
+                    .count()
+            };
+            if let Some(pos) = selective_paths.iter().position(|spp| solved_endpoints(spp) == 2) {
+                let spp = selective_paths.remove(pos);
+                self.solve_selective_path(spp)?;
+            } else if let Some(spp) = selective_paths.iter().find(|spp| solved_endpoints(spp) == 1) {
+                let node = spp
+                    .endpoint_nodes()
+                    .into_iter()
+                    .find(|node| !self.solved.contains(*node))
+                    .unwrap();
+                self.solve_node_by_apply(node);
+            } else {
+                break;
             }
         }
+
+        if !selective_paths.is_empty() {
+            return Err(PlanError::not_supported(
+                "selective path pattern disconnected from the query graph",
+            ));
+        }
+        Ok(())
+    }
+
+    // scan the node for each row solved so far, then continue traversal from it
+    fn solve_node_by_apply(&mut self, node: &'a VariableName) {
+        self.solve_filter();
+        let arguments = self.root.schema().fields.clone();
+        let (rhs, filter) = Self::try_create_index_seek(self.ctx, node, &self.filter, &arguments)
+            .unwrap_or_else(|| Self::create_node_scan(self.ctx, node, &self.filter, arguments));
+        self.filter = filter;
+
+        let empty = PlanExpr::empty(Schema::empty(), self.root.ctx()).boxed();
+        self.root = Apply::new(ApplyInner {
+            left: std::mem::replace(&mut self.root, empty),
+            right: rhs.boxed(),
+            kind: ApplyKind::Inner,
+        })
+        .into();
+
+        self.solved.insert(node.clone());
+        for conn in self.qg.connections(node).rev() {
+            self.stack.push_back(conn);
+        }
+    }
+
+    // solve the predicates only depend on solved variables
+    fn solve_filter(&mut self) {
+        let (solved, remaining) = std::mem::take(&mut self.filter).partition_by(|e| e.depend_only_on(&self.solved));
+        self.filter = remaining;
+        if !solved.is_true() {
+            let empty = PlanExpr::empty(Schema::empty(), self.root.ctx()).boxed();
+            self.root = Filter::new(FilterInner {
+                input: std::mem::replace(&mut self.root, empty),
+                condition: solved,
+            })
+            .into();
+        }
+    }
+
+    fn solve_selective_path(&mut self, spp: &'a SelectivePathPattern) -> Result<(), PlanError> {
+        let rel = spp
+            .as_single_rel()
+            .ok_or_else(|| PlanError::not_supported("selective path pattern with multiple relationships"))?;
+
+        // filter the rows before searching paths, paths are selected for each pair of endpoints
+        self.solve_filter();
+        let empty = PlanExpr::empty(Schema::empty(), self.root.ctx()).boxed();
+        let inner = ShortestPathInner {
+            input: std::mem::replace(&mut self.root, empty),
+            rel_pattern: rel.clone(),
+            path: spp.path.clone(),
+            selector: spp.selector,
+        };
+        self.root = ShortestPath::new(inner).into();
+        self.solved.insert(rel.variable.clone());
+        self.solved.insert(spp.path.clone());
+        Ok(())
+    }
+
+    // repeat the inner pattern of the quantified path pattern from the solved endpoint
+    fn solve_quantified_path(&mut self, qpp: &'a QuantifiedPathPattern) -> Result<(), PlanError> {
+        // predicates of each iteration are evaluated on the solved rows and the inner variables
+        let mut visible = self.solved.clone();
+        visible.extend(qpp.inner_nodes());
+        visible.extend(qpp.rels.iter().map(|r| r.variable.clone()));
+        if !qpp.filter.iter().all(|e| e.depend_only_on(&visible)) {
+            return Err(PlanError::not_supported(
+                "quantified path pattern predicate referencing unsolved variables",
+            ));
+        }
+
+        // filter the rows before repeating the pattern
+        self.solve_filter();
+        if let Some(rel) = qpp.as_var_length_rel() {
+            return self.solve_node_connection_by_expand(&rel);
+        }
+
+        let (left, right) = (&qpp.left_binding.outer, &qpp.right_binding.outer);
+        let (kind, from, to, expanded_node) = match (self.solved.contains(left), self.solved.contains(right)) {
+            (true, true) => (ExpandKind::Into, left, right, None),
+            (true, false) => (ExpandKind::All, left, right, Some(right)),
+            (false, true) => (ExpandKind::All, right, left, Some(left)),
+            (false, false) => unreachable!(),
+        };
+
+        let empty = PlanExpr::empty(Schema::empty(), self.root.ctx()).boxed();
+        let inner = QuantifiedExpandInner {
+            input: std::mem::replace(&mut self.root, empty),
+            from: from.clone(),
+            to: to.clone(),
+            qpp: qpp.clone(),
+            kind,
+        };
+        self.root = QuantifiedExpand::new(inner).into();
+        self.solved.extend(qpp.group_variables().map(|v| v.name));
+
+        if let Some(expanded) = expanded_node {
+            self.solved.insert(expanded.clone());
+            self.qg
+                .connections(expanded)
+                .rev()
+                .for_each(|rel| self.stack.push_back(rel));
+        }
         Ok(())
     }
 
@@ -184,7 +438,8 @@ impl<'a> TraversalSolver<'a> {
             dir,
             types,
             length,
-        }: &'a RelPattern,
+            mode,
+        }: &RelPattern,
     ) -> Result<(), PlanError> {
         // if !length.is_simple() {
         //     return Err(PlanError::not_supported(
@@ -224,8 +479,7 @@ impl<'a> TraversalSolver<'a> {
                 node_filter: FilterExprs::default(),
                 rel_filter: FilterExprs::default(),
                 kind,
-                // TODO(pgao): path mode support
-                path_mode: PathMode::default(),
+                path_mode: *mode,
             };
             self.root = VarExpand::new(inner).into();
         }
@@ -244,3 +498,27 @@ impl<'a> TraversalSolver<'a> {
         Ok(())
     }
 }
+
+// the predicate of the filter requiring the node to have a label known to the catalog, with the label
+fn find_label_predicate<'e>(filter: &'e FilterExprs, node: &VariableName) -> Option<(&'e Expr, LabelId, String)> {
+    filter.iter().find_map(|expr| match expr {
+        Expr::HasLabel(HasLabel {
+            entity,
+            label_or_rel: IrToken::Resolved { name, token },
+        }) if matches!(entity.as_ref(), Expr::VariableRef(var_ref) if &var_ref.name == node) => {
+            Some((expr, *token, name.to_string()))
+        }
+        _ => None,
+    })
+}
+
+// whether the filter requires the node to have a label
+fn has_label_predicate(filter: &FilterExprs, node: &VariableName) -> bool {
+    filter.iter().any(|expr| {
+        matches!(
+            expr,
+            Expr::HasLabel(HasLabel { entity, .. })
+                if matches!(entity.as_ref(), Expr::VariableRef(var_ref) if &var_ref.name == node)
+        )
+    })
+}
@@ -0,0 +1,29 @@
+use crate::error::PlanError;
+use crate::plan_node::{DeleteNode, DeleteNodeInner, DeleteRel, DeleteRelInner, PlanExpr};
+use crate::planner::PlannerContext;
+
+/// Relationships are deleted before nodes, so that `DELETE n, r` deletes
+/// the relationships of `n` first.
+pub(super) fn plan_delete(
+    _ctx: &mut PlannerContext,
+    mut root: Box<PlanExpr>,
+    _delete @ crate::ir::DeletePattern { detach, nodes, rels }: &crate::ir::DeletePattern,
+) -> Result<Box<PlanExpr>, PlanError> {
+    if !rels.is_empty() {
+        let inner = DeleteRelInner {
+            input: root,
+            rels: rels.clone(),
+        };
+        root = PlanExpr::DeleteRel(DeleteRel::new(inner)).boxed();
+    }
+
+    if !nodes.is_empty() {
+        let inner = DeleteNodeInner {
+            input: root,
+            nodes: nodes.clone(),
+            detach: *detach,
+        };
+        root = PlanExpr::DeleteNode(DeleteNode::new(inner)).boxed();
+    }
+    Ok(root)
+}
@@ -1,16 +1,17 @@
 //! Index selection logic for query optimization
 //!
-//! This module analyzes filters and determines if a unique index can be used
-//! to directly lookup nodes instead of scanning all nodes.
+//! This module analyzes filters and determines if a unique index, or else a secondary
+//! index, can be used to directly lookup nodes instead of scanning all nodes.
 
 use std::sync::Arc;
 
 use elio_common::variable::VariableName;
 use elio_common::{IrToken, LabelId, PropertyKeyId};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 
-use crate::expr::{Expr, FilterExprs, HasLabel};
+use crate::expr::{Expr, FilterExprs, HasLabel, PropertyAccess};
 use crate::plan_context::PlanContext;
+use crate::plan_node::RangePredicate;
 use crate::session::IndexHint;
 
 /// Information extracted from a filter that can potentially use an index
@@ -22,7 +23,7 @@ pub struct IndexCandidate {
     pub label_id: LabelId,
     pub property_names: Vec<String>,
     pub property_key_ids: Vec<PropertyKeyId>,
-    /// constant property values
+    /// property values, which are constants or only depend on the arguments
     pub property_values: Vec<Expr>,
     /// The matching index hint
     pub index_hint: IndexHint,
@@ -33,6 +34,7 @@ pub fn find_index_candidates(
     ctx: &Arc<PlanContext>,
     filter: &FilterExprs,
     node_var: &VariableName,
+    arguments: &IndexSet<VariableName>,
 ) -> Option<IndexCandidate> {
     // Extract label and property conditions for this variable
     let mut label_id: Option<LabelId> = None;
@@ -41,9 +43,9 @@ pub fn find_index_candidates(
 
     for expr in filter.iter() {
         extract_index_info(
-            ctx,
             expr,
             node_var,
+            arguments,
             &mut label_id,
             &mut label_name,
             &mut property_conditions,
@@ -91,9 +93,9 @@ pub fn find_index_candidates(
 
 /// Extract label and property equality conditions from a filter expression
 fn extract_index_info(
-    ctx: &Arc<PlanContext>,
     expr: &Expr,
     target_var: &VariableName,
+    arguments: &IndexSet<VariableName>,
     label_id: &mut Option<LabelId>,
     label_name: &mut Option<String>,
     property_conditions: &mut IndexMap<PropertyKeyId, (String, Expr)>,
@@ -115,7 +117,7 @@ fn extract_index_info(
             // Check for AND - recurse into both sides
             if func_call.func == "and" {
                 for arg in &func_call.args {
-                    extract_index_info(ctx, arg, target_var, label_id, label_name, property_conditions);
+                    extract_index_info(arg, target_var, arguments, label_id, label_name, property_conditions);
                 }
                 return;
             }
@@ -132,8 +134,8 @@ fn extract_index_info(
                 if let Expr::VariableRef(var_ref) = prop_access.expr.as_ref()
                     && &var_ref.name == target_var
                 {
-                    // Check if value is a constant (we can only use index for constant values)
-                    if matches!(value, Expr::Constant(_))
+                    // The value must be known before the lookup, i.e. a constant or only depends on the arguments
+                    if value.depend_only_on(arguments)
                         && let IrToken::Resolved { name, token } = &prop_access.property
                     {
                         property_conditions.insert(*token, (name.to_string(), value.clone()));
@@ -172,17 +174,19 @@ fn is_covered_by_index(expr: &Expr, candidate: &IndexCandidate) -> bool {
         Expr::FuncCall(func_call) => {
             if func_call.func == "eq" && func_call.args.len() == 2 {
                 let prop_access = match (&func_call.args[0], &func_call.args[1]) {
-                    (Expr::PropertyAccess(pa), Expr::Constant(_)) => Some(pa),
-                    (Expr::Constant(_), Expr::PropertyAccess(pa)) => Some(pa),
+                    (Expr::PropertyAccess(pa), val) => Some((pa, val)),
+                    (val, Expr::PropertyAccess(pa)) => Some((pa, val)),
                     _ => None,
                 };
 
-                if let Some(pa) = prop_access
+                // the condition is covered if the index looks up the same value
+                if let Some((pa, value)) = prop_access
                     && let Expr::VariableRef(var_ref) = pa.expr.as_ref()
                     && var_ref.name == candidate.variable
                     && let IrToken::Resolved { token, .. } = &pa.property
+                    && let Some(idx) = candidate.property_key_ids.iter().position(|id| id == token)
                 {
-                    return candidate.property_key_ids.contains(token);
+                    return &candidate.property_values[idx] == value;
                 }
             }
             false
@@ -191,3 +195,181 @@ fn is_covered_by_index(expr: &Expr, candidate: &IndexCandidate) -> bool {
         _ => false,
     }
 }
+
+/// A secondary index lookup: equality on the leading properties of the index, then range predicates on the next one.
+/// Every property of the index has a condition in the filter, the ones not used by the lookup stay in the filter.
+#[derive(Debug)]
+pub struct RangeIndexCandidate {
+    pub variable: VariableName,
+    pub label_name: String,
+    pub label_id: LabelId,
+    /// Names of the properties used by the lookup, in index order
+    pub property_names: Vec<String>,
+    /// Values of the leading properties, which are constants or only depend on the arguments
+    pub prefix_values: Vec<Expr>,
+    /// Predicates on the property following the leading properties
+    pub range: Vec<RangePredicate>,
+    pub index_hint: IndexHint,
+    /// Conditions of the filter solved by the lookup
+    covered: Vec<Expr>,
+}
+
+// A condition on a property of the node that a secondary index can solve
+enum PropertyCondition {
+    Eq(Expr),
+    Range(RangePredicate),
+}
+
+// The conditions by property, with the name of the property and the conjunct each condition comes from
+type PropertyConditions<'a> = IndexMap<PropertyKeyId, (String, Vec<(PropertyCondition, &'a Expr)>)>;
+
+/// Analyze the filter to find the secondary index solving the most properties
+pub fn find_range_index_candidate(
+    ctx: &Arc<PlanContext>,
+    filter: &FilterExprs,
+    node_var: &VariableName,
+    arguments: &IndexSet<VariableName>,
+) -> Option<RangeIndexCandidate> {
+    let mut labels: Vec<(LabelId, String, &Expr)> = Vec::new();
+    let mut conditions: PropertyConditions = IndexMap::new();
+
+    for expr in filter.iter().flat_map(conjuncts) {
+        if let Expr::HasLabel(HasLabel { entity, label_or_rel }) = expr
+            && let Expr::VariableRef(var_ref) = entity.as_ref()
+            && &var_ref.name == node_var
+            && let IrToken::Resolved { name, token } = label_or_rel
+        {
+            labels.push((*token, name.to_string(), expr));
+        } else if let Some((token, name, condition)) = extract_property_condition(expr, node_var, arguments) {
+            conditions
+                .entry(token)
+                .or_insert_with(|| (name, vec![]))
+                .1
+                .push((condition, expr));
+        }
+    }
+    if conditions.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(usize, RangeIndexCandidate)> = None;
+    for (label_id, label_name, label_expr) in labels {
+        for index_hint in ctx.session().find_indexes(label_id) {
+            // nodes missing any property of the index are not in it, so every property needs a condition, which is
+            // never true on a missing property
+            if !index_hint.property_key_ids.iter().all(|id| conditions.contains_key(id)) {
+                continue;
+            }
+            let mut property_names = vec![];
+            let mut prefix_values = vec![];
+            let mut range = vec![];
+            let mut covered = vec![label_expr.clone()];
+
+            for prop_id in &index_hint.property_key_ids {
+                let Some((name, prop_conditions)) = conditions.get(prop_id) else {
+                    break;
+                };
+                let eq = prop_conditions.iter().find_map(|(condition, expr)| match condition {
+                    PropertyCondition::Eq(value) => Some((value, expr)),
+                    PropertyCondition::Range(_) => None,
+                });
+                property_names.push(name.clone());
+                if let Some((value, expr)) = eq {
+                    prefix_values.push(value.clone());
+                    covered.push((*expr).clone());
+                    continue;
+                }
+                for (condition, expr) in prop_conditions {
+                    if let PropertyCondition::Range(pred) = condition {
+                        range.push(pred.clone());
+                        covered.push((*expr).clone());
+                    }
+                }
+                break;
+            }
+
+            let score = prefix_values.len() + usize::from(!range.is_empty());
+            if score == 0 || best.as_ref().is_some_and(|(best_score, _)| *best_score >= score) {
+                continue;
+            }
+            best = Some((
+                score,
+                RangeIndexCandidate {
+                    variable: node_var.clone(),
+                    label_name: label_name.clone(),
+                    label_id,
+                    property_names,
+                    prefix_values,
+                    range,
+                    index_hint,
+                    covered,
+                },
+            ));
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+// The property of the node and the condition on it, when the compared value is known before the lookup
+fn extract_property_condition(
+    expr: &Expr,
+    target_var: &VariableName,
+    arguments: &IndexSet<VariableName>,
+) -> Option<(PropertyKeyId, String, PropertyCondition)> {
+    let Expr::FuncCall(func_call) = expr else {
+        return None;
+    };
+    if func_call.args.len() != 2 {
+        return None;
+    }
+
+    // the property of the node is on the left, or on the right of a comparison
+    let (prop_access, value, flipped) = match (&func_call.args[0], &func_call.args[1]) {
+        (Expr::PropertyAccess(pa), val) if is_property_of(pa, target_var) => (pa, val, false),
+        (val, Expr::PropertyAccess(pa)) if is_property_of(pa, target_var) => (pa, val, true),
+        _ => return None,
+    };
+    if !value.depend_only_on(arguments) {
+        return None;
+    }
+    let IrToken::Resolved { name, token } = &prop_access.property else {
+        return None;
+    };
+
+    let value = value.clone();
+    let condition = match (func_call.func.as_str(), flipped) {
+        ("eq", _) => PropertyCondition::Eq(value),
+        ("lt", false) | ("gt", true) => PropertyCondition::Range(RangePredicate::Lt(value)),
+        ("lt_eq", false) | ("gt_eq", true) => PropertyCondition::Range(RangePredicate::LtEq(value)),
+        ("gt", false) | ("lt", true) => PropertyCondition::Range(RangePredicate::Gt(value)),
+        ("gt_eq", false) | ("lt_eq", true) => PropertyCondition::Range(RangePredicate::GtEq(value)),
+        ("starts_with", false) => PropertyCondition::Range(RangePredicate::StartsWith(value)),
+        ("in", false) => PropertyCondition::Range(RangePredicate::In(value)),
+        _ => return None,
+    };
+    Some((*token, name.to_string(), condition))
+}
+
+fn is_property_of(prop_access: &PropertyAccess, target_var: &VariableName) -> bool {
+    matches!(prop_access.expr.as_ref(), Expr::VariableRef(var_ref) if &var_ref.name == target_var)
+}
+
+// the operands of nested ANDs, or the expression itself
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::FuncCall(func_call) if func_call.func == "and" => func_call.args.iter().flat_map(conjuncts).collect(),
+        _ => vec![expr],
+    }
+}
+
+/// Remove conditions that are solved by the secondary index lookup from the filter, the remaining conditions are
+/// split into conjuncts
+pub fn remove_range_index_conditions(filter: &FilterExprs, candidate: &RangeIndexCandidate) -> FilterExprs {
+    let remaining: Vec<Expr> = filter
+        .iter()
+        .flat_map(conjuncts)
+        .filter(|expr| !candidate.covered.contains(*expr))
+        .cloned()
+        .collect();
+    FilterExprs::from_iter(remaining)
+}
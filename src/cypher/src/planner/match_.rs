@@ -1,13 +1,16 @@
 use elio_common::schema::Schema;
 
 use super::*;
+use crate::expr::AggCall;
 use crate::ir::query_graph::QueryGraph;
-use crate::plan_node::{Argument, ArgumentInner, Empty, Unit};
+use crate::plan_node::{
+    Aggregate, AggregateInner, Apply, ApplyInner, ApplyKind, Argument, ArgumentInner, Empty, Filter, FilterInner, Unit,
+};
 use crate::planner::component::plan_qg_simple;
 
 // plan the query graph in following order:
 // 1. plan match parts
-// 2. plan optional match parts
+// 2. plan optional match parts, each by a left outer Apply
 pub fn plan_match(
     ctx: &mut PlannerContext,
     _part @ IrSingleQueryPart {
@@ -20,7 +23,11 @@ pub fn plan_match(
     plan_query_graph(ctx, query_graph, is_rhs)
 }
 
-fn plan_query_graph(ctx: &mut PlannerContext, qg: &QueryGraph, is_rhs: bool) -> Result<Box<PlanExpr>, PlanError> {
+pub(super) fn plan_query_graph(
+    ctx: &mut PlannerContext,
+    qg: &QueryGraph,
+    is_rhs: bool,
+) -> Result<Box<PlanExpr>, PlanError> {
     // get connected component
     let qgs = qg.connected_component();
     if qgs.len() > 1 {
@@ -28,20 +35,28 @@ fn plan_query_graph(ctx: &mut PlannerContext, qg: &QueryGraph, is_rhs: bool) ->
     }
 
     if qgs.is_empty() {
-        // if qg have imported variable, just put an argument here.
+        // if qg have imported variable, just put an argument here, the filter only depends on the arguments.
         if !qg.imported().is_empty() {
-            let root = PlanExpr::Argument(Argument::new(ArgumentInner {
+            let mut root = PlanExpr::Argument(Argument::new(ArgumentInner {
                 variables: qg.imported().into_iter().cloned().collect_vec(),
                 ctx: ctx.ctx.clone(),
-            }));
-            return Ok(root.boxed());
+            }))
+            .boxed();
+            if !qg.filter.is_true() {
+                root = Filter::new(FilterInner {
+                    input: root,
+                    condition: qg.filter.clone(),
+                })
+                .into();
+            }
+            return plan_optional_matches(ctx, root, qg);
         }
 
         // if qg does not have any imported variable and this is an lhs query graph, put an Unit node here to drive the
         // execution.
         if !is_rhs {
-            let root = PlanExpr::Unit(Unit::new(ctx.ctx.clone()));
-            return Ok(root.boxed());
+            let root = PlanExpr::Unit(Unit::new(ctx.ctx.clone())).boxed();
+            return plan_optional_matches(ctx, root, qg);
         }
 
         // other cases, put an empty
@@ -52,13 +67,60 @@ fn plan_query_graph(ctx: &mut PlannerContext, qg: &QueryGraph, is_rhs: bool) ->
     // plan component
     let plans = qgs
         .iter()
-        .map(move |qg| plan_component(ctx, qg))
+        .map(|qg| plan_component(ctx, qg))
         .collect::<Result<Vec<_>, _>>()?;
 
     // TODO(pgao): connect component by cartisen product
+    plan_optional_matches(ctx, plans[0].clone(), qg)
+}
 
-    // TODO(pgao): plan optional match
-    Ok(plans[0].clone())
+// each optional match is solved for every input row, rows without a match are padded with nulls
+fn plan_optional_matches(
+    ctx: &mut PlannerContext,
+    mut root: Box<PlanExpr>,
+    qg: &QueryGraph,
+) -> Result<Box<PlanExpr>, PlanError> {
+    for optional in qg.optional_matches.iter() {
+        let rhs = plan_query_graph(ctx, optional, true)?;
+        root = Apply::new(ApplyInner {
+            left: root,
+            right: rhs,
+            kind: ApplyKind::LeftOuter,
+        })
+        .into();
+    }
+    Ok(root)
+}
+
+// each pattern comprehension is solved for every input row, the projections of the matches are collected into a
+// list, which is an empty list if there's no match
+pub fn plan_pattern_comprehensions(
+    ctx: &mut PlannerContext,
+    mut root: Box<PlanExpr>,
+    qg: &QueryGraph,
+) -> Result<Box<PlanExpr>, PlanError> {
+    for pc in qg.pattern_comprehensions.iter() {
+        let rhs = plan_query_graph(ctx, &pc.qg, true)?;
+        let collect = AggCall::new_unchecked(
+            "collect".to_string(),
+            vec![pc.projection.clone()],
+            false,
+            pc.variable.typ.clone(),
+        );
+        let rhs = Aggregate::new(AggregateInner {
+            input: rhs,
+            group_by: vec![],
+            aggregates: vec![(pc.variable.name.clone(), collect)],
+        })
+        .into();
+        root = Apply::new(ApplyInner {
+            left: root,
+            right: rhs,
+            kind: ApplyKind::Inner,
+        })
+        .into();
+    }
+    Ok(root)
 }
 
 fn plan_component(ctx: &mut PlannerContext, qg: &QueryGraph) -> Result<Box<PlanExpr>, PlanError> {
@@ -0,0 +1,70 @@
+use elio_common::IrToken;
+use itertools::Itertools;
+
+use crate::error::PlanError;
+use crate::ir::MergePattern;
+use crate::plan_node::{Argument, ArgumentInner, Merge, MergeInner, PlanExpr, Unit};
+use crate::planner::PlannerContext;
+use crate::planner::create::plan_create;
+use crate::planner::match_::plan_query_graph;
+
+// plan MERGE as match-or-create, both sides take the imported variables as arguments
+pub(super) fn plan_merge(
+    ctx: &mut PlannerContext,
+    root: Box<PlanExpr>,
+    merge @ MergePattern {
+        qg,
+        create,
+        on_match,
+        on_create,
+    }: &MergePattern,
+) -> Result<Box<PlanExpr>, PlanError> {
+    let arguments = qg.imported().iter().cloned().collect_vec();
+    let match_plan = plan_query_graph(ctx, qg, true)?;
+
+    let leaf = if arguments.is_empty() {
+        PlanExpr::Unit(Unit::new(ctx.ctx.clone()))
+    } else {
+        PlanExpr::Argument(Argument::new(ArgumentInner {
+            variables: arguments.clone(),
+            ctx: ctx.ctx.clone(),
+        }))
+    };
+    let create_plan = plan_create(ctx, leaf.boxed(), create)?;
+
+    Ok(PlanExpr::Merge(Merge::new(MergeInner {
+        input: root,
+        match_plan,
+        create_plan,
+        arguments,
+        on_match: on_match.clone(),
+        on_create: on_create.clone(),
+        lock_labels: lock_labels(merge),
+        lock_reltypes: lock_reltypes(merge),
+    }))
+    .boxed())
+}
+
+// labels of the created nodes, concurrent merges on them must not interleave
+fn lock_labels(merge: &MergePattern) -> Vec<IrToken> {
+    merge
+        .create
+        .nodes
+        .iter()
+        .flat_map(|node| node.labels.iter().cloned())
+        .sorted_by_key(|label| label.name().clone())
+        .dedup()
+        .collect()
+}
+
+// types of the created relationships, the end nodes may be bound before MERGE so no label is locked for them
+fn lock_reltypes(merge: &MergePattern) -> Vec<IrToken> {
+    merge
+        .create
+        .rels
+        .iter()
+        .map(|rel| rel.reltype.clone())
+        .sorted_by_key(|reltype| reltype.name().clone())
+        .dedup()
+        .collect()
+}
@@ -6,20 +6,24 @@ use itertools::Itertools;
 use pretty_xmlish::{Pretty, PrettyConfig};
 
 use crate::error::PlanError;
-use crate::ir::query::{IrQuery, IrQueryRoot, IrSingleQueryPart};
+use crate::ir::query::{IrQueryRoot, IrSingleQueryPart};
 use crate::plan_context::PlanContext;
 use crate::plan_node::{PlanExpr, ProduceResult, ProduceResultInner};
 use crate::planner::single_query::plan_single_query;
+use crate::planner::union::plan_union;
 use crate::session::PlannerSession;
 
 mod component;
 mod create;
+mod delete;
 mod index_selection;
 mod load;
 mod match_;
+mod merge;
 mod project;
 mod single_query;
 mod tail;
+mod union;
 
 // planner temporaray state
 pub struct PlannerContext {
@@ -69,13 +73,12 @@ pub fn plan_root(
         _config: Default::default(),
     };
 
-    let IrQuery { queries, union_all: _ } = inner;
-    assert!(!queries.is_empty());
-    if queries.len() > 1 {
-        return Err(PlanError::not_supported("Union all is not supported yet".to_string()));
-    }
-
-    let plan = plan_single_query(&mut ctx, &queries[0])?;
+    assert!(!inner.queries.is_empty());
+    let plan = if inner.is_union() {
+        plan_union(&mut ctx, inner)?
+    } else {
+        plan_single_query(&mut ctx, &inner.queries[0])?
+    };
 
     // plan produce result
     let plan = {
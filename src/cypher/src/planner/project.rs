@@ -5,12 +5,16 @@ use elio_common::schema::Schema;
 use itertools::Itertools;
 
 use crate::error::PlanError;
-use crate::expr::FilterExprs;
+use crate::expr::{Expr, FilterExprs};
 use crate::ir::order::SortItem;
 use crate::ir::query_project::{
-    AggregateProjection, DistinctProjection, Pagination, Projection, QueryProjection, RegularProjection, Unwind,
+    AggregateProjection, DistinctProjection, Pagination, Projection, QueryProjection, RegularProjection,
+    Unwind as IrUnwind,
+};
+use crate::plan_node::{
+    Aggregate, AggregateInner, Filter, FilterInner, PaginationInner, PlanExpr, Project, ProjectInner, Sort, SortInner,
+    Unwind, UnwindInner,
 };
-use crate::plan_node::{Filter, FilterInner, PaginationInner, PlanExpr, Project, ProjectInner, Sort, SortInner};
 use crate::planner::PlannerContext;
 
 pub fn plan_query_projection(
@@ -45,26 +49,14 @@ fn plan_project(
         input: root,
         projections: items.clone().into_iter().collect_vec(),
     };
-    let mut root: Box<PlanExpr> = Project::new(inner).into();
-
-    if !filter.is_true() {
-        root = plan_selection(ctx, root, filter)?;
-    }
-
-    if !order_by.is_empty() {
-        root = plan_sort(ctx, root, order_by)?;
-    }
-
-    if !pagination.is_empty() {
-        root = plan_pagination(ctx, root, pagination)?;
-    }
+    let root: Box<PlanExpr> = Project::new(inner).into();
 
-    Ok(root)
+    plan_projection_tail(ctx, root, order_by, pagination, filter)
 }
 
 fn plan_aggregate(
-    _ctx: &mut PlannerContext,
-    _root: Box<PlanExpr>,
+    ctx: &mut PlannerContext,
+    root: Box<PlanExpr>,
     _project @ AggregateProjection {
         group_by,
         aggregate,
@@ -73,12 +65,30 @@ fn plan_aggregate(
         filter,
     }: &AggregateProjection,
 ) -> Result<Box<PlanExpr>, PlanError> {
-    Err(PlanError::not_supported("aggregate clause not implemented yet."))
+    let aggregates = aggregate
+        .iter()
+        .map(|(var, expr)| match expr {
+            Expr::AggCall(agg_call) => Ok((var.clone(), agg_call.clone())),
+            _ => Err(PlanError::not_supported(format!(
+                "non-aggregate expression {}",
+                expr.pretty()
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let inner = AggregateInner {
+        input: root,
+        group_by: group_by.clone().into_iter().collect_vec(),
+        aggregates,
+    };
+    let root: Box<PlanExpr> = Aggregate::new(inner).into();
+
+    plan_projection_tail(ctx, root, order_by, pagination, filter)
 }
 
+// DISTINCT is an aggregate with only group keys
 fn plan_distinct(
-    _ctx: &mut PlannerContext,
-    _root: Box<PlanExpr>,
+    ctx: &mut PlannerContext,
+    root: Box<PlanExpr>,
     _project @ DistinctProjection {
         group_by,
         order_by,
@@ -86,15 +96,50 @@ fn plan_distinct(
         filter,
     }: &DistinctProjection,
 ) -> Result<Box<PlanExpr>, PlanError> {
-    Err(PlanError::not_supported("distinct clause not implemented yet."))
+    let inner = AggregateInner {
+        input: root,
+        group_by: group_by.clone().into_iter().collect_vec(),
+        aggregates: vec![],
+    };
+    let root: Box<PlanExpr> = Aggregate::new(inner).into();
+
+    plan_projection_tail(ctx, root, order_by, pagination, filter)
+}
+
+// plan the WHERE, ORDER BY and SKIP/LIMIT subclauses of a projection
+fn plan_projection_tail(
+    ctx: &mut PlannerContext,
+    mut root: Box<PlanExpr>,
+    order_by: &[SortItem],
+    pagination: &Pagination,
+    filter: &FilterExprs,
+) -> Result<Box<PlanExpr>, PlanError> {
+    if !filter.is_true() {
+        root = plan_selection(ctx, root, filter)?;
+    }
+
+    if !order_by.is_empty() {
+        root = plan_sort(ctx, root, order_by)?;
+    }
+
+    if !pagination.is_empty() {
+        root = plan_pagination(ctx, root, pagination)?;
+    }
+
+    Ok(root)
 }
 
 fn plan_unwind(
     _ctx: &mut PlannerContext,
-    _root: Box<PlanExpr>,
-    _unwind @ Unwind { variable, expr }: &Unwind,
+    root: Box<PlanExpr>,
+    _unwind @ IrUnwind { variable, expr }: &IrUnwind,
 ) -> Result<Box<PlanExpr>, PlanError> {
-    Err(PlanError::not_supported("unwind clause not implemented yet."))
+    let inner = UnwindInner {
+        input: root,
+        variable: variable.clone(),
+        expr: expr.clone(),
+    };
+    Ok(Unwind::new(inner).into())
 }
 
 // WITH a, a.id + 1 AS b, c ORDER BY c.id + 1 ASC
@@ -165,8 +210,8 @@ fn plan_pagination(
 ) -> Result<Box<PlanExpr>, PlanError> {
     let inner = PaginationInner {
         input: root,
-        offset: offset.unwrap_or(0),
-        limit: limit.unwrap_or(-1),
+        offset: offset.clone(),
+        limit: limit.clone(),
     };
     Ok(crate::plan_node::Pagination::new(inner).into())
 }
@@ -2,16 +2,18 @@ use crate::error::PlanError;
 use crate::ir::mutating_pattern::MutatingPattern;
 use crate::ir::query::{IrSingleQuery, IrSingleQueryPart};
 use crate::ir::query_project::QueryProjection;
-use crate::plan_node::{Apply, ApplyInner, PlanExpr};
+use crate::plan_node::{Apply, ApplyInner, ApplyKind, PlanExpr, Set, SetInner};
 use crate::planner::PlannerContext;
 use crate::planner::create::plan_create;
+use crate::planner::delete::plan_delete;
 use crate::planner::load::plan_load;
-use crate::planner::match_::plan_match;
+use crate::planner::match_::{plan_match, plan_pattern_comprehensions};
+use crate::planner::merge::plan_merge;
 use crate::planner::project::plan_query_projection;
 
 pub fn plan_single_query(
     ctx: &mut PlannerContext,
-    _single_query @ IrSingleQuery { parts }: &IrSingleQuery,
+    _single_query @ IrSingleQuery { parts, .. }: &IrSingleQuery,
 ) -> Result<Box<PlanExpr>, PlanError> {
     assert!(!parts.is_empty());
     let mut part_iter = parts.iter();
@@ -50,6 +52,8 @@ fn plan_head(
     for mutating_pattern in query_graph.mutating_patterns.iter() {
         root = plan_mutating_pattern(ctx, root, mutating_pattern)?;
     }
+    // plan pattern comprehensions used by the projection
+    root = plan_pattern_comprehensions(ctx, root, query_graph)?;
     // plan projection
     if let Some(proj) = query_project {
         root = plan_query_projection(ctx, root, proj)?;
@@ -64,6 +68,13 @@ fn plan_mutating_pattern(
 ) -> Result<Box<PlanExpr>, PlanError> {
     match mutating_pattern {
         MutatingPattern::Create(create) => plan_create(ctx, root, create),
+        MutatingPattern::Delete(delete) => plan_delete(ctx, root, delete),
+        MutatingPattern::Set(set) => Ok(PlanExpr::Set(Set::new(SetInner {
+            input: root,
+            items: set.items.clone(),
+        }))
+        .boxed()),
+        MutatingPattern::Merge(merge) => plan_merge(ctx, root, merge),
     }
 }
 
@@ -85,6 +96,8 @@ fn plan_tail_part(
     for mutating_pattern in query_graph.mutating_patterns.iter() {
         root = plan_mutating_pattern(ctx, root, mutating_pattern)?;
     }
+    // plan pattern comprehensions used by the projection
+    root = plan_pattern_comprehensions(ctx, root, query_graph)?;
     // plan projection
     if let Some(proj) = query_project {
         root = plan_query_projection(ctx, root, proj)?;
@@ -100,6 +113,7 @@ fn plan_apply(
     Ok(PlanExpr::Apply(Apply::new(ApplyInner {
         left: lhs_plan,
         right: rhs_plan,
+        kind: ApplyKind::Inner,
     }))
     .boxed())
 }
@@ -0,0 +1,40 @@
+use itertools::Itertools;
+
+use crate::error::PlanError;
+use crate::expr::Expr;
+use crate::ir::query::IrQuery;
+use crate::plan_node::{PlanExpr, Project, ProjectInner, Union, UnionInner};
+use crate::planner::PlannerContext;
+use crate::planner::single_query::plan_single_query;
+
+// Each branch is projected to the output variables of the first branch, so that all inputs
+// of the union have the same columns. The first branch usually outputs them already.
+pub fn plan_union(
+    ctx: &mut PlannerContext,
+    _query @ IrQuery { queries, union_all }: &IrQuery,
+) -> Result<Box<PlanExpr>, PlanError> {
+    let head_output = &queries[0].output;
+    let inputs = queries
+        .iter()
+        .map(|query| {
+            let root = plan_single_query(ctx, query)?;
+            let schema = root.schema();
+            if schema.fields.iter().map(|f| &f.name).eq(head_output.iter()) {
+                return Ok(root);
+            }
+            let name2col = schema.name_to_col_map();
+            let projections = head_output
+                .iter()
+                .zip_eq(query.output.iter())
+                .map(|(out, var)| (out.clone(), Expr::from_variable(&schema.fields[name2col[var]])))
+                .collect_vec();
+            let inner = ProjectInner {
+                input: root,
+                projections,
+            };
+            Ok(Project::new(inner).into())
+        })
+        .collect::<Result<Vec<_>, PlanError>>()?;
+
+    Ok(Union::new(UnionInner::new(inputs, !union_all)?).into())
+}
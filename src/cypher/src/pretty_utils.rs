@@ -17,6 +17,10 @@ pub(crate) fn pretty_project_items<'a>(items: impl Iterator<Item = (&'a Variable
     )
 }
 
+pub(crate) fn pretty_exprs<'a>(exprs: impl Iterator<Item = &'a Box<Expr>>) -> Pretty<'static> {
+    Pretty::Array(exprs.map(|e| Pretty::display(&e.pretty())).collect_vec())
+}
+
 pub(crate) fn pretty_order_items(order_by: &[SortItem]) -> Pretty<'_> {
     Pretty::Array(order_by.iter().map(Pretty::display).collect::<Vec<_>>())
 }
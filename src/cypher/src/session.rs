@@ -1,8 +1,10 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use elio_catalog::FunctionCatalog;
 use elio_catalog::error::CatalogError;
+use elio_common::scalar::ScalarValue;
 use elio_common::{LabelId, PropertyKeyId, TokenId, TokenKind};
 use elio_parser::ast;
 use elio_parser::parser::cypher_parser;
@@ -37,6 +39,9 @@ pub trait PlannerSession: Debug + Send + Sync {
     /// Returns Some(IndexHint) if a matching index exists, None otherwise.
     fn find_unique_index(&self, label_id: LabelId, property_key_ids: &[PropertyKeyId]) -> Option<IndexHint>;
 
+    /// Find the secondary indexes of the given label, their entries are ordered by the property values.
+    fn find_indexes(&self, label_id: LabelId) -> Vec<IndexHint>;
+
     // TODO(impl send notification)
     fn send_notification(&self, notification: String);
 }
@@ -57,14 +62,22 @@ pub fn parse_statement(stmt: &str) -> Result<ast::Statement, PlanError> {
     cypher_parser::statement(stmt).map_err(PlanError::parse_error)
 }
 
-pub fn plan_query(ctx: Arc<dyn PlannerSession>, query: &ast::RegularQuery) -> Result<RootPlan, PlanError> {
+pub fn plan_query(
+    ctx: Arc<dyn PlannerSession>,
+    query: &ast::RegularQuery,
+    params: HashMap<String, ScalarValue>,
+) -> Result<RootPlan, PlanError> {
     // bind
-    let ir = bind_root_query(ctx.clone(), query)?;
+    let ir = bind_root_query(ctx.clone(), query, params)?;
     // plan
     let plan = plan_root(ctx, &ir)?;
     Ok(plan)
 }
 
-pub fn bind_query(ctx: Arc<dyn PlannerSession>, query: &ast::RegularQuery) -> Result<IrQueryRoot, PlanError> {
-    bind_root_query(ctx, query)
+pub fn bind_query(
+    ctx: Arc<dyn PlannerSession>,
+    query: &ast::RegularQuery,
+    params: HashMap<String, ScalarValue>,
+) -> Result<IrQueryRoot, PlanError> {
+    bind_root_query(ctx, query, params)
 }
@@ -1,15 +1,25 @@
+use std::backtrace::Backtrace;
+
 use elio_common::data_type::DataType;
-use elio_common::schema::{Name2ColumnMap, Schema};
+use elio_common::scalar::ScalarValue;
+use elio_common::schema::{Name2ColumnMap, Schema, Variable};
 use elio_cypher::expr;
-use elio_cypher::expr::{Constant, CreateList, CreateStruct, Expr, ExprNode, PropertyAccess, VariableRef};
+use elio_cypher::expr::{
+    Case, Constant, CreateList, CreateStruct, Expr, ExprNode, ListComprehension, ListPredicate, PropertyAccess, Reduce,
+    VariableRef,
+};
 use elio_expr::func::FUNCTION_REGISTRY;
+use elio_expr::func::string::compile_regex;
+use elio_expr::impl_::case::CaseExpr;
 use elio_expr::impl_::constant::ConstantExpr;
 use elio_expr::impl_::create_list::CreateListExpr;
 use elio_expr::impl_::create_struct::CreateStructExpr;
 use elio_expr::impl_::field_access::FieldAccessExpr;
 use elio_expr::impl_::func_call::FuncCallExpr;
 use elio_expr::impl_::label::HasLabelExpr;
+use elio_expr::impl_::lambda::{ListComprehensionExpr, ListPredicateExpr, ListPredicateKind, ReduceExpr};
 use elio_expr::impl_::project_path::ProjectPathExpr;
+use elio_expr::impl_::regex_match::RegexMatchExpr;
 use elio_expr::impl_::variable_ref::VariableRefExpr;
 use elio_expr::impl_::{Expression, SharedExpression};
 
@@ -34,11 +44,18 @@ pub(crate) fn build_expression(ctx: &BuildExprContext<'_>, expr: &Expr) -> Resul
         Expr::PropertyAccess(property_access) => build_property_access(ctx, property_access),
         Expr::Constant(constant) => build_constant(ctx, constant),
         Expr::FuncCall(func_call) => build_func_call(ctx, func_call),
-        Expr::AggCall(_agg_call) => todo!(),
+        Expr::AggCall(agg_call) => Err(BuildError::MalformedPlan(
+            format!("aggregate {} must be computed by Aggregate", agg_call.pretty()),
+            Backtrace::capture(),
+        )),
         Expr::Subquery(_subquery) => todo!(),
         Expr::HasLabel(has_label) => build_has_label(ctx, has_label),
         Expr::CreateStruct(create_map) => build_create_map(ctx, create_map),
         Expr::CreateList(create_list) => build_create_list(ctx, create_list),
+        Expr::Case(case) => build_case(ctx, case),
+        Expr::ListComprehension(list_comprehension) => build_list_comprehension(ctx, list_comprehension),
+        Expr::ListPredicate(list_predicate) => build_list_predicate(ctx, list_predicate),
+        Expr::Reduce(reduce) => build_reduce(ctx, reduce),
         Expr::ProjectPath(project_path) => build_project_path(ctx, project_path),
     }
 }
@@ -78,6 +95,21 @@ fn build_func_call(ctx: &BuildExprContext<'_>, func_call: &expr::FuncCall) -> Re
         .map(|expr| build_expression(ctx, expr))
         .collect::<Result<Vec<_>, _>>()?;
 
+    // a constant regex is compiled only once, an invalid one is reported when the function is evaluated
+    if func_call.func == "regex_match"
+        && let Expr::Constant(Constant {
+            data: Some(ScalarValue::String(pattern)),
+            ..
+        }) = &func_call.args[1]
+        && let Ok(regex) = compile_regex(pattern)
+    {
+        return Ok(RegexMatchExpr {
+            input: args[0].clone(),
+            regex,
+        }
+        .into_shared());
+    }
+
     let func_impl = FUNCTION_REGISTRY.get_func_impl(&func_call.func_id);
 
     Ok(FuncCallExpr {
@@ -122,6 +154,88 @@ fn build_create_list(ctx: &BuildExprContext<'_>, create_list: &CreateList) -> Re
     Ok(CreateListExpr::new(elements, create_list.typ()).into_shared())
 }
 
+fn build_case(ctx: &BuildExprContext<'_>, case: &Case) -> Result<SharedExpression, BuildError> {
+    let when_then = case
+        .when_then
+        .iter()
+        .map(|(when, then)| Ok((build_expression(ctx, when)?, build_expression(ctx, then)?)))
+        .collect::<Result<Vec<_>, BuildError>>()?;
+    let else_ = case.else_.as_ref().map(|x| build_expression(ctx, x)).transpose()?;
+
+    Ok(CaseExpr {
+        when_then,
+        else_,
+        typ: case.typ(),
+    }
+    .into_shared())
+}
+
+// the lambda body is evaluated on the input columns followed by the lambda variables
+fn build_lambda_body(
+    ctx: &BuildExprContext<'_>,
+    lambda: &[&Variable],
+    body: &Expr,
+) -> Result<SharedExpression, BuildError> {
+    let mut schema = ctx.schema.clone();
+    lambda.iter().for_each(|var| schema.add_column((*var).clone()));
+    build_expression(&BuildExprContext::new(&schema, ctx.ctx), body)
+}
+
+fn build_list_comprehension(
+    ctx: &BuildExprContext<'_>,
+    list_comprehension: &ListComprehension,
+) -> Result<SharedExpression, BuildError> {
+    let variable = &list_comprehension.variable;
+    let filter = list_comprehension
+        .filter
+        .as_ref()
+        .map(|x| build_lambda_body(ctx, &[variable], x))
+        .transpose()?;
+    let map = list_comprehension
+        .map
+        .as_ref()
+        .map(|x| build_lambda_body(ctx, &[variable], x))
+        .transpose()?;
+    Ok(ListComprehensionExpr {
+        list: build_expression(ctx, &list_comprehension.list)?,
+        filter,
+        map,
+        elem_type: variable.typ.clone(),
+        typ: list_comprehension.typ(),
+    }
+    .into_shared())
+}
+
+fn build_list_predicate(
+    ctx: &BuildExprContext<'_>,
+    list_predicate: &ListPredicate,
+) -> Result<SharedExpression, BuildError> {
+    let kind = match list_predicate.kind {
+        expr::ListPredicateKind::All => ListPredicateKind::All,
+        expr::ListPredicateKind::Any => ListPredicateKind::Any,
+        expr::ListPredicateKind::None => ListPredicateKind::None,
+        expr::ListPredicateKind::Single => ListPredicateKind::Single,
+    };
+    Ok(ListPredicateExpr {
+        kind,
+        list: build_expression(ctx, &list_predicate.list)?,
+        predicate: build_lambda_body(ctx, &[&list_predicate.variable], &list_predicate.predicate)?,
+        elem_type: list_predicate.variable.typ.clone(),
+    }
+    .into_shared())
+}
+
+fn build_reduce(ctx: &BuildExprContext<'_>, reduce: &Reduce) -> Result<SharedExpression, BuildError> {
+    Ok(ReduceExpr {
+        init: build_expression(ctx, &reduce.init)?,
+        list: build_expression(ctx, &reduce.list)?,
+        expr: build_lambda_body(ctx, &[&reduce.accumulator, &reduce.variable], &reduce.expr)?,
+        elem_type: reduce.variable.typ.clone(),
+        typ: reduce.typ(),
+    }
+    .into_shared())
+}
+
 fn build_project_path(
     ctx: &BuildExprContext<'_>,
     project_path: &expr::ProjectPath,
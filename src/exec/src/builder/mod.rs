@@ -1,10 +1,11 @@
 use std::backtrace::Backtrace;
 use std::sync::Arc;
 
-use elio_common::mapb::IndexKeyCodec;
-use elio_common::scalar::ScalarValue;
-use elio_common::schema::Name2ColumnMap;
+use elio_common::data_type::DataType;
+use elio_common::order::{ColumnOrder, SortDirection};
+use elio_common::schema::{Name2ColumnMap, Schema, Variable};
 use elio_common::variable::VariableName;
+use elio_cypher::ir::path_pattern::Selector;
 use elio_cypher::ir::query_project::LoadFormat;
 use elio_cypher::plan_node::{self, CreateNode, PlanExpr, PlanNode, Project};
 use elio_cypher::planner::RootPlan;
@@ -16,15 +17,31 @@ use crate::executor::apply::{ApplyExecutor, ArgumentContext, OutputColumnSource}
 use crate::executor::argument::ArgumentExecutor;
 use crate::executor::create_node::{CreateNodeExectuor, CreateNodeItem};
 use crate::executor::create_rel::{CreateRelExectuor, CreateRelItem};
+use crate::executor::delete_node::DeleteNodeExecutor;
+use crate::executor::delete_rel::DeleteRelExecutor;
 use crate::executor::expand::ExpandExecutor;
 use crate::executor::filter::FilterExecutor;
+use crate::executor::hash_agg::{AggregateItem, HashAggregateExecutor};
 use crate::executor::load_csv::LoadCsvExecutor;
+use crate::executor::merge::MergeExecutor;
+use crate::executor::node_by_label_scan::NodeByLabelScanExecutor;
+use crate::executor::node_index_range_seek::{NodeIndexRangeSeekExecutor, RangeOp};
 use crate::executor::node_index_seek::NodeIndexSeekExecutor;
+use crate::executor::pagination::PaginationExecutor;
 use crate::executor::produce_result::ProduceResultExecutor;
 use crate::executor::project::ProjectExecutor;
+use crate::executor::quantified_expand::{QuantifiedExpandExecutor, QuantifiedStep};
+use crate::executor::relscan::RelScanExecutor;
+use crate::executor::set::{SetExecutor, SetItem, SetItemKind};
+use crate::executor::shortest_path::{PathSelector, ShortestPathExecutor};
+use crate::executor::sort::SortExecutor;
+use crate::executor::top_n::TopNExecutor;
+use crate::executor::union::UnionExecutor;
 use crate::executor::unit::UnitExecutor;
+use crate::executor::unwind::UnwindExecutor;
 use crate::executor::var_expand::{
-    ExpandAllImpl, ExpandIntoImpl, TRAIL_PATH_MODE_FACTORY, VarExpandExecutor, WALK_PATH_MODE_FACTORY,
+    ACYCLIC_PATH_MODE_FACTORY, ExpandAllImpl, ExpandIntoImpl, TRAIL_PATH_MODE_FACTORY, VarExpandExecutor,
+    WALK_PATH_MODE_FACTORY,
 };
 use crate::executor::{Executor, SharedExecutor};
 use crate::task::TaskExecContext;
@@ -83,6 +100,19 @@ fn build_node(ctx: &mut ExecutorBuildContext, node: &PlanExpr) -> Result<SharedE
         return build_argument(ctx, argument);
     }
 
+    // Handle Merge specially - match plan and create plan are executed for each input row
+    if let PlanExpr::Merge(merge) = node {
+        return build_merge(ctx, merge);
+    }
+
+    // Handle Pagination directly on top of Sort specially - fuse them into a Top-N sort
+    if let PlanExpr::Pagination(pagination) = node
+        && let PlanExpr::Sort(sort) = pagination.inner().input.as_ref()
+        && pagination.inner().limit.is_some()
+    {
+        return build_top_n(ctx, pagination, sort);
+    }
+
     // For all other nodes, build children first
     let inputs = node
         .inputs()
@@ -92,84 +122,182 @@ fn build_node(ctx: &mut ExecutorBuildContext, node: &PlanExpr) -> Result<SharedE
 
     match node {
         PlanExpr::AllNodeScan(all_node_scan) => build_all_node_scan(ctx, all_node_scan, inputs),
+        PlanExpr::NodeByLabelScan(label_scan) => build_node_by_label_scan(ctx, label_scan, inputs),
         PlanExpr::NodeIndexSeek(node_index_seek) => build_node_index_seek(ctx, node_index_seek, inputs),
+        PlanExpr::NodeIndexRangeSeek(range_seek) => build_node_index_range_seek(ctx, range_seek, inputs),
+        PlanExpr::RelScan(rel_scan) => build_rel_scan(ctx, rel_scan, inputs),
         PlanExpr::GetProperty(_get_property) => todo!(),
         PlanExpr::Expand(expand) => build_expand(ctx, expand, inputs),
         PlanExpr::VarExpand(var_expand) => build_var_expand(ctx, var_expand, inputs),
+        PlanExpr::QuantifiedExpand(quantified_expand) => build_quantified_expand(ctx, quantified_expand, inputs),
+        PlanExpr::ShortestPath(shortest_path) => build_shortest_path(ctx, shortest_path, inputs),
         PlanExpr::Apply(_) => unreachable!("Apply is handled above"),
         PlanExpr::Argument(_) => unreachable!("Argument is handled above"),
+        PlanExpr::Merge(_) => unreachable!("Merge is handled above"),
         PlanExpr::Unit(_unit) => Ok(UnitExecutor::default().into_shared()),
         PlanExpr::ProduceResult(produce_result) => build_produce_result(ctx, produce_result, inputs),
         PlanExpr::CreateNode(create_node) => build_create_node(ctx, create_node, inputs),
         PlanExpr::CreateRel(create_rel) => build_create_rel(ctx, create_rel, inputs),
+        PlanExpr::DeleteNode(delete_node) => build_delete_node(ctx, delete_node, inputs),
+        PlanExpr::DeleteRel(delete_rel) => build_delete_rel(ctx, delete_rel, inputs),
+        PlanExpr::Set(set) => build_set(ctx, set, inputs),
         PlanExpr::Load(load) => build_load(ctx, load, inputs),
+        PlanExpr::Unwind(unwind) => build_unwind(ctx, unwind, inputs),
         PlanExpr::Project(project) => build_project(ctx, project, inputs),
-        PlanExpr::Sort(_sort) => todo!(),
+        PlanExpr::Sort(sort) => build_sort(ctx, sort, inputs),
         PlanExpr::Filter(filter) => build_filter(ctx, filter, inputs),
-        PlanExpr::Pagination(_pagination) => todo!(),
+        PlanExpr::Aggregate(aggregate) => build_aggregate(ctx, aggregate, inputs),
+        PlanExpr::Pagination(pagination) => build_pagination(ctx, pagination, inputs),
+        PlanExpr::Union(union) => build_union(ctx, union, inputs),
         PlanExpr::Empty(_empty) => todo!(),
     }
 }
 
 fn build_all_node_scan(
-    _ctx: &mut ExecutorBuildContext,
+    ctx: &mut ExecutorBuildContext,
     all_node_scan: &plan_node::AllNodeScan,
     inputs: Vec<SharedExecutor>,
 ) -> Result<SharedExecutor, BuildError> {
     assert_eq!(inputs.len(), 0);
     let schema = all_node_scan.schema();
-    Ok(AllNodeScanExectuor::new(schema).into_shared())
+    let arguments = all_node_scan.inner().arguments.clone();
+    let argument_ctx = argument_ctx_for(ctx, &arguments)?;
+    Ok(AllNodeScanExectuor::new(schema, arguments, argument_ctx).into_shared())
+}
+
+fn build_node_by_label_scan(
+    ctx: &mut ExecutorBuildContext,
+    label_scan: &plan_node::NodeByLabelScan,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 0);
+    let schema = label_scan.schema();
+    let arguments = label_scan.inner().arguments.clone();
+    let argument_ctx = argument_ctx_for(ctx, &arguments)?;
+    Ok(NodeByLabelScanExecutor::new(schema, label_scan.inner().label_id, arguments, argument_ctx).into_shared())
 }
 
 fn build_node_index_seek(
-    _ctx: &mut ExecutorBuildContext,
+    ctx: &mut ExecutorBuildContext,
     node_index_seek: &plan_node::NodeIndexSeek,
     inputs: Vec<SharedExecutor>,
 ) -> Result<SharedExecutor, BuildError> {
     assert_eq!(inputs.len(), 0);
 
     let schema = node_index_seek.schema();
+    let arguments = node_index_seek.inner().arguments.clone();
+    let argument_ctx = argument_ctx_for(ctx, &arguments)?;
 
-    // Extract constant values and encode them for index lookup
-    // The encoding must match how values are stored in the index (using IndexKeyCodec)
-    let property_values: Vec<Vec<u8>> = node_index_seek
+    // Property values are constants or only depend on the arguments
+    let argument_schema = arguments.iter().cloned().collect::<Schema>();
+    let ectx = BuildExprContext::new(&argument_schema, ctx);
+    let property_values = node_index_seek
         .inner()
         .property_values
         .iter()
-        .map(|expr| {
-            // For index lookup, the expression must be a constant
-            match expr {
-                elio_cypher::expr::Expr::Constant(constant) => {
-                    // Encode using IndexKeyCodec (same as index storage)
-                    let encoded = match &constant.data {
-                        Some(value) => IndexKeyCodec::encode_single(&value.as_scalar_ref()),
-                        None => IndexKeyCodec::encode_single(&ScalarValue::Unknown.as_scalar_ref()),
-                    };
-                    Ok(encoded)
-                }
-                _ => {
-                    // Non-constant expressions not supported for index seek
-                    // This shouldn't happen in practice, but handle gracefully
-                    Err(BuildError::MalformedPlan(
-                        format!("NodeIndexSeek requires constant property values, got: {:?}", expr),
-                        Backtrace::capture(),
-                    ))
-                }
-            }
-        })
-        .collect::<Result<Vec<_>, BuildError>>()?;
+        .map(|expr| build_expression(&ectx, expr))
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(NodeIndexSeekExecutor::new(
         schema,
         node_index_seek.inner().label_id,
         node_index_seek.inner().property_key_ids.clone(),
         property_values,
+        arguments,
+        argument_ctx,
     )
     .into_shared())
 }
 
+fn build_node_index_range_seek(
+    ctx: &mut ExecutorBuildContext,
+    range_seek: &plan_node::NodeIndexRangeSeek,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 0);
+
+    let schema = range_seek.schema();
+    let inner = range_seek.inner();
+    let arguments = inner.arguments.clone();
+    let argument_ctx = argument_ctx_for(ctx, &arguments)?;
+
+    // Values and range bounds are constants or only depend on the arguments
+    let argument_schema = arguments.iter().cloned().collect::<Schema>();
+    let ectx = BuildExprContext::new(&argument_schema, ctx);
+    let prefix_values = inner
+        .prefix_values
+        .iter()
+        .map(|expr| build_expression(&ectx, expr))
+        .collect::<Result<Vec<_>, _>>()?;
+    let range = inner
+        .range
+        .iter()
+        .map(|pred| {
+            let op = match pred {
+                plan_node::RangePredicate::Lt(_) => RangeOp::Lt,
+                plan_node::RangePredicate::LtEq(_) => RangeOp::LtEq,
+                plan_node::RangePredicate::Gt(_) => RangeOp::Gt,
+                plan_node::RangePredicate::GtEq(_) => RangeOp::GtEq,
+                plan_node::RangePredicate::StartsWith(_) => RangeOp::StartsWith,
+                plan_node::RangePredicate::In(_) => RangeOp::In,
+            };
+            Ok((op, build_expression(&ectx, pred.expr())?))
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    Ok(NodeIndexRangeSeekExecutor::new(
+        schema,
+        inner.index_name.clone(),
+        inner.label_id,
+        inner.property_key_ids.clone(),
+        prefix_values,
+        range,
+        arguments,
+        argument_ctx,
+    )
+    .into_shared())
+}
+
+fn build_rel_scan(
+    ctx: &mut ExecutorBuildContext,
+    rel_scan: &plan_node::RelScan,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 0);
+    let schema = rel_scan.schema();
+    let arguments = rel_scan.inner().arguments.clone();
+    let argument_ctx = argument_ctx_for(ctx, &arguments)?;
+
+    // a type that is not created yet has no relationship to scan
+    let rtype = rel_scan
+        .inner()
+        .types
+        .iter()
+        .filter_map(|x| match x {
+            elio_common::IrToken::Resolved { token, .. } => Some(*token),
+            elio_common::IrToken::Unresolved(name) => ctx.ctx.store().token_store().get_reltype_id(name),
+        })
+        .collect();
+
+    Ok(RelScanExecutor::new(schema, rel_scan.inner().direction, rtype, arguments, argument_ctx).into_shared())
+}
+
+// leaf nodes with arguments must be built inside Apply or Merge
+fn argument_ctx_for(ctx: &ExecutorBuildContext, arguments: &[Variable]) -> Result<Option<ArgumentContext>, BuildError> {
+    if arguments.is_empty() {
+        return Ok(None);
+    }
+    let argument_ctx = ctx.argument_ctx.clone().ok_or_else(|| {
+        BuildError::MalformedPlan(
+            "arguments require argument context (must be inside Apply or Merge)".to_string(),
+            Backtrace::capture(),
+        )
+    })?;
+    Ok(Some(argument_ctx))
+}
+
 fn build_expand(
-    _ctx: &mut ExecutorBuildContext,
+    ctx: &mut ExecutorBuildContext,
     expand: &plan_node::Expand,
     inputs: Vec<SharedExecutor>,
 ) -> Result<SharedExecutor, BuildError> {
@@ -200,7 +328,13 @@ fn build_expand(
         .iter()
         .map(|x| match x {
             elio_common::IrToken::Resolved { token, .. } => Ok(*token),
-            elio_common::IrToken::Unresolved(name) => Err(BuildError::unresolved_token(name.to_string())),
+            // the type may be created after planning
+            elio_common::IrToken::Unresolved(name) => ctx
+                .ctx
+                .store()
+                .token_store()
+                .get_reltype_id(name)
+                .ok_or_else(|| BuildError::unresolved_token(name.to_string())),
         })
         .collect::<Result<Vec<_>, _>>()?;
 
@@ -227,7 +361,7 @@ fn build_expand(
 }
 
 fn build_var_expand(
-    _ctx: &mut ExecutorBuildContext,
+    ctx: &mut ExecutorBuildContext,
     expand: &plan_node::VarExpand,
     inputs: Vec<SharedExecutor>,
 ) -> Result<SharedExecutor, BuildError> {
@@ -259,17 +393,31 @@ fn build_var_expand(
         .iter()
         .map(|x| match x {
             elio_common::IrToken::Resolved { token, .. } => Ok(*token),
-            elio_common::IrToken::Unresolved(name) => Err(BuildError::unresolved_token(name.to_string())),
+            // the type may be created after planning
+            elio_common::IrToken::Unresolved(name) => ctx
+                .ctx
+                .store()
+                .token_store()
+                .get_reltype_id(name)
+                .ok_or_else(|| BuildError::unresolved_token(name.to_string())),
         })
         .collect::<Result<Vec<_>, _>>()?;
 
     let (len_min, len_max) = expand.inner().rel_pattern.length.as_range().unwrap();
+    // expanded from the right endpoint, the relationships are followed backwards
+    let reversed = expand.inner().from != *expand.inner().rel_pattern.left();
+    let dir = if reversed {
+        expand.inner().rel_pattern.dir.rev()
+    } else {
+        expand.inner().rel_pattern.dir
+    };
 
     match (expand.inner().path_mode, to) {
         (plan_node::PathMode::Trail, None) => Ok(VarExpandExecutor {
             input,
             from,
-            dir: expand.inner().rel_pattern.dir,
+            dir,
+            reversed,
             rel_types: rtype.into_boxed_slice().into(),
             len_min,
             len_max: len_max.unwrap_or(usize::MAX),
@@ -283,7 +431,8 @@ fn build_var_expand(
         (plan_node::PathMode::Trail, Some(to_idx)) => Ok(VarExpandExecutor {
             input,
             from,
-            dir: expand.inner().rel_pattern.dir,
+            dir,
+            reversed,
             rel_types: rtype.into_boxed_slice().into(),
             len_min,
             len_max: len_max.unwrap_or(usize::MAX),
@@ -297,7 +446,8 @@ fn build_var_expand(
         (plan_node::PathMode::Walk, None) => Ok(VarExpandExecutor {
             input,
             from,
-            dir: expand.inner().rel_pattern.dir,
+            dir,
+            reversed,
             rel_types: rtype.into_boxed_slice().into(),
             len_min,
             len_max: len_max.unwrap_or(usize::MAX),
@@ -312,7 +462,8 @@ fn build_var_expand(
         (plan_node::PathMode::Walk, Some(to_idx)) => Ok(VarExpandExecutor {
             input,
             from,
-            dir: expand.inner().rel_pattern.dir,
+            dir,
+            reversed,
             rel_types: rtype.into_boxed_slice().into(),
             len_min,
             len_max: len_max.unwrap_or(usize::MAX),
@@ -323,7 +474,190 @@ fn build_var_expand(
             expand_kind_filter: ExpandIntoImpl { to_idx },
         }
         .into_shared()),
+        (plan_node::PathMode::Acyclic, None) => Ok(VarExpandExecutor {
+            input,
+            from,
+            dir,
+            reversed,
+            rel_types: rtype.into_boxed_slice().into(),
+            len_min,
+            len_max: len_max.unwrap_or(usize::MAX),
+            node_filter: None,
+            rel_filter: None,
+            schema: expand.schema().clone(),
+            path_container_factory: &ACYCLIC_PATH_MODE_FACTORY,
+            expand_kind_filter: ExpandAllImpl,
+        }
+        .into_shared()),
+        (plan_node::PathMode::Acyclic, Some(to_idx)) => Ok(VarExpandExecutor {
+            input,
+            from,
+            dir,
+            reversed,
+            rel_types: rtype.into_boxed_slice().into(),
+            len_min,
+            len_max: len_max.unwrap_or(usize::MAX),
+            node_filter: None,
+            rel_filter: None,
+            schema: expand.schema().clone(),
+            path_container_factory: &ACYCLIC_PATH_MODE_FACTORY,
+            expand_kind_filter: ExpandIntoImpl { to_idx },
+        }
+        .into_shared()),
+    }
+}
+
+fn build_quantified_expand(
+    ctx: &mut ExecutorBuildContext,
+    expand: &plan_node::QuantifiedExpand,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+    let name2col = input.schema().name_to_col_map();
+    let qpp = &expand.inner().qpp;
+
+    let col_of = |name: &VariableName| {
+        name2col
+            .get(name)
+            .copied()
+            .ok_or_else(|| BuildError::variable_not_found(name.clone()))
+    };
+    let from = col_of(&expand.inner().from)?;
+    let to = match expand.inner().kind {
+        plan_node::ExpandKind::All => None,
+        plan_node::ExpandKind::Into => Some(col_of(&expand.inner().to)?),
+    };
+
+    // the inner pattern is traversed backward when expanding from the right endpoint
+    let reversed = expand.inner().from != qpp.left_binding.outer;
+    let inner_nodes = qpp.inner_nodes();
+    let slot_of = |node: &VariableName| inner_nodes.get_index_of(node).unwrap();
+    let mut steps = qpp
+        .rels
+        .iter()
+        .enumerate()
+        .map(|(rel_slot, rel)| {
+            // a type that is not created yet has no relationship to traverse
+            let rtype = rel
+                .types
+                .iter()
+                .filter_map(|x| match x {
+                    elio_common::IrToken::Resolved { token, .. } => Some(*token),
+                    elio_common::IrToken::Unresolved(name) => ctx.ctx.store().token_store().get_reltype_id(name),
+                })
+                .collect::<Vec<_>>();
+            let rel_types = (rel.types.is_empty() || !rtype.is_empty()).then(|| rtype.into());
+            let (from_slot, to_slot, dir) = if reversed {
+                (slot_of(rel.right()), slot_of(rel.left()), rel.dir.rev())
+            } else {
+                (slot_of(rel.left()), slot_of(rel.right()), rel.dir)
+            };
+            QuantifiedStep {
+                from_slot,
+                to_slot,
+                rel_slot,
+                dir,
+                rel_types,
+            }
+        })
+        .collect::<Vec<_>>();
+    if reversed {
+        steps.reverse();
     }
+
+    // predicates of each iteration are evaluated on [input, inner nodes, inner rels]
+    let filter = if qpp.filter.is_true() {
+        None
+    } else {
+        let mut schema = input.schema().clone();
+        inner_nodes
+            .iter()
+            .for_each(|node| schema.add_column(Variable::new(node, &DataType::VirtualNode)));
+        qpp.rels
+            .iter()
+            .for_each(|rel| schema.add_column(Variable::new(&rel.variable, &DataType::Rel)));
+        let ectx = BuildExprContext::new(&schema, ctx);
+        let expr: elio_cypher::expr::Expr = qpp.filter.clone().into();
+        Some(build_expression(&ectx, &expr)?)
+    };
+
+    let node_groups = qpp.node_grouping.iter().map(|vg| slot_of(&vg.singleton)).collect();
+    let rel_groups = qpp
+        .rel_grouping
+        .iter()
+        .map(|vg| qpp.rels.iter().position(|rel| rel.variable == vg.singleton).unwrap())
+        .collect();
+
+    Ok(QuantifiedExpandExecutor {
+        input,
+        from,
+        to,
+        steps,
+        node_slots: inner_nodes.len(),
+        rel_slots: qpp.rels.len(),
+        filter,
+        node_groups,
+        rel_groups,
+        reversed,
+        unique_rels: !matches!(qpp.mode, plan_node::PathMode::Walk),
+        min: qpp.repetition.min as usize,
+        max: qpp.repetition.max.map(|max| max as usize),
+        schema: expand.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_shortest_path(
+    ctx: &mut ExecutorBuildContext,
+    shortest_path: &plan_node::ShortestPath,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+    let name2col = input.schema().name_to_col_map();
+    let rel_pattern = &shortest_path.inner().rel_pattern;
+
+    let [from, to] = [rel_pattern.left(), rel_pattern.right()].map(|name| {
+        name2col
+            .get(name)
+            .copied()
+            .ok_or_else(|| BuildError::variable_not_found(name.clone()))
+    });
+
+    // a type that is not created yet has no relationship to traverse
+    let rtype = rel_pattern
+        .types
+        .iter()
+        .filter_map(|x| match x {
+            elio_common::IrToken::Resolved { token, .. } => Some(*token),
+            elio_common::IrToken::Unresolved(name) => ctx.ctx.store().token_store().get_reltype_id(name),
+        })
+        .collect::<Vec<_>>();
+    let rel_types = if !rel_pattern.types.is_empty() && rtype.is_empty() {
+        None
+    } else {
+        Some(rtype.into())
+    };
+
+    let (len_min, len_max) = rel_pattern.length.as_range().unwrap_or((1, Some(1)));
+    let selector = match shortest_path.inner().selector {
+        Selector::AnyK(k) | Selector::ShortestK(k) => PathSelector::Shortest(k as usize),
+        Selector::ShortestKGroup(k) => PathSelector::ShortestGroups(k as usize),
+    };
+
+    Ok(ShortestPathExecutor {
+        input,
+        from: from?,
+        to: to?,
+        dir: rel_pattern.dir,
+        rel_types,
+        len_min,
+        len_max: len_max.unwrap_or(usize::MAX),
+        selector,
+        schema: shortest_path.schema().clone(),
+    }
+    .into_shared())
 }
 
 fn build_apply(ctx: &mut ExecutorBuildContext, apply: &plan_node::Apply) -> Result<SharedExecutor, BuildError> {
@@ -356,12 +690,9 @@ fn build_apply(ctx: &mut ExecutorBuildContext, apply: &plan_node::Apply) -> Resu
         })
         .collect::<Result<Vec<_>, _>>()?;
 
-    if argument_mapping.is_empty() {
-        return Err(BuildError::MalformedPlan(
-            "Apply node requires argument variables".to_string(),
-            Backtrace::capture(),
-        ));
-    }
+    // the right side may not depend on the left side, e.g. OPTIONAL MATCH (n) as the first clause or a pattern
+    // comprehension without variables of the input rows, it is solved again for each left row
+    let left_outer = apply.inner().kind == plan_node::ApplyKind::LeftOuter;
 
     // Compute output column mapping: for each output column, determine if it comes from left or right
     let right_schema = right.schema();
@@ -393,6 +724,7 @@ fn build_apply(ctx: &mut ExecutorBuildContext, apply: &plan_node::Apply) -> Resu
         schema: apply.schema().clone(),
         argument_mapping,
         output_mapping,
+        left_outer,
     }
     .into_shared())
 }
@@ -402,6 +734,21 @@ fn build_apply(ctx: &mut ExecutorBuildContext, apply: &plan_node::Apply) -> Resu
 fn find_argument_variables(plan: &PlanExpr) -> Vec<VariableName> {
     match plan {
         PlanExpr::Argument(arg) => arg.schema().columns().iter().map(|f| f.name.clone()).collect(),
+        PlanExpr::AllNodeScan(scan) if !scan.inner().arguments.is_empty() => {
+            scan.inner().arguments.iter().map(|f| f.name.clone()).collect()
+        }
+        PlanExpr::NodeByLabelScan(scan) if !scan.inner().arguments.is_empty() => {
+            scan.inner().arguments.iter().map(|f| f.name.clone()).collect()
+        }
+        PlanExpr::NodeIndexSeek(seek) if !seek.inner().arguments.is_empty() => {
+            seek.inner().arguments.iter().map(|f| f.name.clone()).collect()
+        }
+        PlanExpr::NodeIndexRangeSeek(seek) if !seek.inner().arguments.is_empty() => {
+            seek.inner().arguments.iter().map(|f| f.name.clone()).collect()
+        }
+        PlanExpr::RelScan(scan) if !scan.inner().arguments.is_empty() => {
+            scan.inner().arguments.iter().map(|f| f.name.clone()).collect()
+        }
         _ => plan
             .inputs()
             .iter()
@@ -538,6 +885,154 @@ fn build_create_rel(
     .into_shared())
 }
 
+fn build_delete_node(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::DeleteNode,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let nodes = node
+        .inner()
+        .nodes
+        .iter()
+        .map(|x| build_expression(&ectx, x))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DeleteNodeExecutor {
+        input,
+        nodes,
+        detach: node.inner().detach,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_delete_rel(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::DeleteRel,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let rels = node
+        .inner()
+        .rels
+        .iter()
+        .map(|x| build_expression(&ectx, x))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DeleteRelExecutor {
+        input,
+        rels,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_set(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Set,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+    let items = build_set_items(ctx, input.schema(), &node.inner().items)?;
+
+    Ok(SetExecutor {
+        input,
+        items,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_set_items(
+    ctx: &ExecutorBuildContext,
+    schema: &Schema,
+    items: &[elio_cypher::ir::SetItem],
+) -> Result<Vec<SetItem>, BuildError> {
+    let name2col = schema.name_to_col_map();
+    let ectx = BuildExprContext::new(schema, ctx);
+
+    let mut set_items = vec![];
+    for item in items {
+        let variable = &item.variable().name;
+        let column = name2col
+            .get(variable)
+            .copied()
+            .ok_or_else(|| BuildError::variable_not_found(variable.clone()))?;
+        let value = item.value().map(|expr| build_expression(&ectx, expr)).transpose()?;
+        let kind = match item {
+            elio_cypher::ir::SetItem::Property { key, .. } => SetItemKind::Property(key.name().clone()),
+            elio_cypher::ir::SetItem::Properties { merge, .. } => SetItemKind::Properties { merge: *merge },
+            elio_cypher::ir::SetItem::Labels { labels, remove, .. } => SetItemKind::Labels {
+                labels: labels.iter().map(|label| label.name().clone()).collect(),
+                remove: *remove,
+            },
+        };
+        set_items.push(SetItem { column, value, kind });
+    }
+    Ok(set_items)
+}
+
+fn build_merge(ctx: &mut ExecutorBuildContext, merge: &plan_node::Merge) -> Result<SharedExecutor, BuildError> {
+    let inner = merge.inner();
+    let input = build_node(ctx, &inner.input)?;
+
+    // match plan and create plan share the arguments of the current input row
+    let argument_ctx = ArgumentContext::default();
+    let mut sub_ctx = ExecutorBuildContext {
+        ctx: ctx.ctx.clone(),
+        argument_ctx: Some(argument_ctx.clone()),
+    };
+    let match_plan = build_node(&mut sub_ctx, &inner.match_plan)?;
+    let create_plan = build_node(&mut sub_ctx, &inner.create_plan)?;
+
+    let find_columns = |schema: &Schema, variables: &[&VariableName]| {
+        let name2col = schema.name_to_col_map();
+        variables
+            .iter()
+            .map(|name| {
+                name2col
+                    .get(*name)
+                    .copied()
+                    .ok_or_else(|| BuildError::variable_not_found((*name).clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let arguments = inner.arguments.iter().map(|x| &x.name).collect::<Vec<_>>();
+    let argument_mapping = find_columns(input.schema(), &arguments)?;
+
+    // the output is the input row followed by the introduced variables
+    let schema = merge.schema();
+    let introduced = schema.columns()[input.schema().len()..]
+        .iter()
+        .map(|x| &x.name)
+        .collect::<Vec<_>>();
+    let match_columns = find_columns(match_plan.schema(), &introduced)?;
+    let create_columns = find_columns(create_plan.schema(), &introduced)?;
+
+    let on_match = build_set_items(ctx, &schema, &inner.on_match)?;
+    let on_create = build_set_items(ctx, &schema, &inner.on_create)?;
+
+    Ok(MergeExecutor {
+        input,
+        match_plan,
+        create_plan,
+        argument_ctx,
+        argument_mapping,
+        match_columns,
+        create_columns,
+        on_match,
+        on_create,
+        schema,
+    }
+    .into_shared())
+}
+
 fn build_project(
     ctx: &mut ExecutorBuildContext,
     node: &Project,
@@ -602,6 +1097,161 @@ fn build_filter(
     .into_shared())
 }
 
+fn build_column_orders(schema: &Schema, items: &[ColumnOrder]) -> Result<Vec<(usize, SortDirection)>, BuildError> {
+    let name2col = schema.name_to_col_map();
+    items
+        .iter()
+        .map(|item| {
+            name2col
+                .get(&item.column)
+                .map(|idx| (*idx, item.direction))
+                .ok_or_else(|| BuildError::variable_not_found(item.column.clone()))
+        })
+        .collect()
+}
+
+fn build_unwind(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Unwind,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let expr = build_expression(&ectx, &node.inner().expr)?;
+
+    Ok(UnwindExecutor {
+        input,
+        expr,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_sort(
+    _ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Sort,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+
+    let column_orders = build_column_orders(input.schema(), &node.inner().items)?;
+
+    Ok(SortExecutor {
+        input,
+        column_orders: column_orders.into(),
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_aggregate(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Aggregate,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let group_by = node
+        .inner()
+        .group_by
+        .iter()
+        .map(|(_, expr)| build_expression(&ectx, expr))
+        .collect::<Result<Vec<_>, _>>()?;
+    let aggregates = node
+        .inner()
+        .aggregates
+        .iter()
+        .map(|(_, agg_call)| {
+            let args = agg_call
+                .args
+                .iter()
+                .map(|expr| build_expression(&ectx, expr))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(AggregateItem {
+                func: agg_call.func.clone(),
+                args,
+                distinct: agg_call.distinct,
+            })
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    Ok(HashAggregateExecutor {
+        input,
+        group_by,
+        aggregates,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_pagination(
+    ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Pagination,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    assert_eq!(inputs.len(), 1);
+    let [input]: [SharedExecutor; 1] = inputs.try_into().unwrap();
+
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let offset = node
+        .inner()
+        .offset
+        .as_ref()
+        .map(|x| build_expression(&ectx, x))
+        .transpose()?;
+    let limit = node
+        .inner()
+        .limit
+        .as_ref()
+        .map(|x| build_expression(&ectx, x))
+        .transpose()?;
+
+    Ok(PaginationExecutor {
+        input,
+        offset,
+        limit,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
+
+fn build_top_n(
+    ctx: &mut ExecutorBuildContext,
+    pagination: &plan_node::Pagination,
+    sort: &plan_node::Sort,
+) -> Result<SharedExecutor, BuildError> {
+    let input = build_node(ctx, &sort.inner().input)?;
+    let column_orders = build_column_orders(input.schema(), &sort.inner().items)?;
+
+    let schema = input.schema().clone();
+    let ectx = BuildExprContext::new(&schema, ctx);
+    let offset = pagination
+        .inner()
+        .offset
+        .as_ref()
+        .map(|x| build_expression(&ectx, x))
+        .transpose()?;
+    // SAFETY: only pagination with limit is fused
+    let limit = build_expression(&ectx, pagination.inner().limit.as_ref().unwrap())?;
+
+    Ok(TopNExecutor {
+        input,
+        column_orders: column_orders.into(),
+        offset,
+        limit,
+        schema: pagination.schema().clone(),
+    }
+    .into_shared())
+}
+
 fn build_load(
     _ctx: &mut ExecutorBuildContext,
     load: &plan_node::Load,
@@ -619,3 +1269,16 @@ fn build_load(
     }
     .into_shared())
 }
+
+fn build_union(
+    _ctx: &mut ExecutorBuildContext,
+    node: &plan_node::Union,
+    inputs: Vec<SharedExecutor>,
+) -> Result<SharedExecutor, BuildError> {
+    Ok(UnionExecutor {
+        inputs,
+        distinct: node.inner().distinct,
+        schema: node.schema().clone(),
+    }
+    .into_shared())
+}
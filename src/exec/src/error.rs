@@ -25,6 +25,8 @@ pub enum ExecError {
     },
     #[error("channel error: {0}")]
     ChannelError(String, #[backtrace] Backtrace),
+    #[error("transaction failed by an earlier statement, it has been rolled back")]
+    TransactionFailed(#[backtrace] Backtrace),
     #[error("constraint violation: {constraint} - {reason}")]
     ConstraintViolation {
         constraint: String,
@@ -47,4 +49,8 @@ impl ExecError {
     pub fn io_error<T: ToString>(msg: T) -> Self {
         Self::IoError(msg.to_string(), Backtrace::capture())
     }
+
+    pub fn transaction_failed() -> Self {
+        Self::TransactionFailed(Backtrace::capture())
+    }
 }
@@ -1,23 +1,30 @@
 use async_stream::try_stream;
+use elio_common::schema::Variable;
 use elio_storage::transaction::NodeScanOptions;
 use futures::StreamExt;
 use tokio::sync::mpsc;
 
+use super::apply::ArgumentContext;
 use super::*;
 use crate::executor::Executor;
 
 const CHANNEL_BUFFER_SIZE: usize = 128;
 
-// TODO(pgao): support argument
-// AllNodeScan is an leaf node and produces NodeId as results
+// AllNodeScan is an leaf node and produces NodeId as results, the arguments are appended to each row
 #[derive(Debug)]
 pub struct AllNodeScanExectuor {
     schema: Arc<Schema>,
+    arguments: Vec<Variable>,
+    argument_ctx: Option<ArgumentContext>,
 }
 
 impl AllNodeScanExectuor {
-    pub fn new(schema: Arc<Schema>) -> Self {
-        Self { schema }
+    pub fn new(schema: Arc<Schema>, arguments: Vec<Variable>, argument_ctx: Option<ArgumentContext>) -> Self {
+        Self {
+            schema,
+            arguments,
+            argument_ctx,
+        }
     }
 }
 
@@ -57,9 +64,17 @@ impl Executor for AllNodeScanExectuor {
             }
         });
 
+        let arguments = self.arguments.clone();
+        let argument_ctx = self.argument_ctx.clone();
         let stream = try_stream! {
             while let Some(item) = rx.recv().await{
-                yield item?;
+                let mut chunk = item?;
+                if let Some(argument_ctx) = &argument_ctx {
+                    for column in argument_ctx.build_columns(&arguments, chunk.len()) {
+                        chunk.add_column(column);
+                    }
+                }
+                yield chunk;
             }
         }
         .boxed();
@@ -1,8 +1,10 @@
 use std::sync::{Arc, RwLock};
 
 use async_stream::try_stream;
+use elio_common::array::ArrayImpl;
 use elio_common::array::chunk::DataChunkBuilder;
 use elio_common::scalar::ScalarValue;
+use elio_common::schema::Variable;
 use futures::StreamExt;
 
 use super::*;
@@ -22,6 +24,23 @@ impl ArgumentContext {
     pub fn get_value(&self, idx: usize) -> Option<ScalarValue> {
         self.inner.read().unwrap().as_ref()?.get(idx).cloned()
     }
+
+    /// Build a column for each argument of the current row, the value is repeated `len` times
+    pub fn build_columns(&self, arguments: &[Variable], len: usize) -> Vec<Arc<ArrayImpl>> {
+        arguments
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                // null arguments are stored as unknown
+                let value = self.get_value(idx).filter(|v| !matches!(v, ScalarValue::Unknown));
+                let mut builder = field.typ.physical_type().array_builder(len);
+                for _ in 0..len {
+                    builder.push(value.as_ref().map(|v| v.as_scalar_ref()));
+                }
+                Arc::new(builder.finish())
+            })
+            .collect()
+    }
 }
 
 /// Specifies where an output column comes from
@@ -41,6 +60,8 @@ pub struct ApplyExecutor {
     pub argument_mapping: Vec<usize>,
     /// Maps each output column to its source (left or right) and index
     pub output_mapping: Vec<OutputColumnSource>,
+    /// Keep the left rows without right rows, the right columns are null
+    pub left_outer: bool,
 }
 
 impl Executor for ApplyExecutor {
@@ -52,6 +73,7 @@ impl Executor for ApplyExecutor {
         let schema = self.schema.clone();
         let argument_mapping = self.argument_mapping.clone();
         let output_mapping = self.output_mapping.clone();
+        let left_outer = self.left_outer;
 
         let left_stream = left.open(ctx.clone())?;
 
@@ -78,11 +100,13 @@ impl Executor for ApplyExecutor {
 
                     // Open a new stream from the right executor (reuses executor, just new stream)
                     let mut right_stream = right.open(ctx.clone())?;
+                    let mut matched = false;
 
                     while let Some(right_chunk_result) = right_stream.next().await {
                         let right_chunk = right_chunk_result?.compact();
 
                         for right_row in right_chunk.iter() {
+                            matched = true;
                             let mut output_row = Vec::with_capacity(output_mapping.len());
                             for source in &output_mapping {
                                 let val = match source {
@@ -96,6 +120,19 @@ impl Executor for ApplyExecutor {
                             }
                         }
                     }
+
+                    if left_outer && !matched {
+                        let output_row = output_mapping
+                            .iter()
+                            .map(|source| match source {
+                                OutputColumnSource::Left(idx) => left_row.get(*idx).cloned().flatten(),
+                                OutputColumnSource::Right(_) => None,
+                            })
+                            .collect();
+                        if let Some(chunk) = out_builder.append_row(output_row) {
+                            yield chunk;
+                        }
+                    }
                 }
 
                 if let Some(chunk) = out_builder.yield_chunk() {
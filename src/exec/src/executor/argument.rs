@@ -1,6 +1,5 @@
 use async_stream::try_stream;
 use bitvec::vec::BitVec;
-use elio_common::array::ArrayImpl;
 use elio_common::array::chunk::DataChunk;
 use futures::StreamExt;
 
@@ -19,16 +18,7 @@ impl Executor for ArgumentExecutor {
         let argument_ctx = self.argument_ctx.clone();
 
         let stream = try_stream! {
-            let mut columns: Vec<Arc<ArrayImpl>> = Vec::with_capacity(schema.len());
-
-            for (idx, field) in schema.columns().iter().enumerate() {
-                let value = argument_ctx.get_value(idx);
-                let physical_type = field.typ.physical_type();
-                let mut builder = physical_type.array_builder(1);
-                builder.push(value.as_ref().map(|v| v.as_scalar_ref()));
-                columns.push(Arc::new(builder.finish()));
-            }
-
+            let columns = argument_ctx.build_columns(schema.columns(), 1);
             let mut visibility = BitVec::with_capacity(1);
             visibility.push(true);
             let chunk = DataChunk::new(columns, visibility);
@@ -3,16 +3,19 @@
 //! This module provides reusable functions for:
 //! - Checking unique constraints before data modifications
 //! - Updating unique indexes after data modifications
+//! - Checking NOT NULL constraints of the created or updated nodes and relationships
 
 use std::backtrace::Backtrace;
 use std::sync::Arc;
 
-use elio_common::TokenKind;
 use elio_common::array::{Array, NodeArray, StructArray};
 use elio_common::mapb::IndexKeyCodec;
+use elio_common::scalar::{NodeValue, RelValueRef, ScalarRef, StructValue, StructValueRef};
+use elio_common::{NodeId, TokenKind};
 use elio_storage::constraint::{ConstraintKind, ConstraintMeta};
 use elio_storage::graph::GraphStore;
 use elio_storage::transaction::TransactionImpl;
+use itertools::Itertools;
 
 use crate::error::ExecError;
 
@@ -26,9 +29,22 @@ impl LabelConstraints {
     pub fn is_empty(&self) -> bool {
         self.constraints.is_empty()
     }
+
+    /// The UNIQUE/NODE KEY constraints, which are backed by a unique index
+    fn unique(&self) -> impl Iterator<Item = &(u16, ConstraintMeta)> {
+        self.constraints
+            .iter()
+            .filter(|(_, c)| matches!(c.constraint_kind, ConstraintKind::Unique | ConstraintKind::NodeKey))
+    }
+
+    fn not_null(&self) -> impl Iterator<Item = &(u16, ConstraintMeta)> {
+        self.constraints
+            .iter()
+            .filter(|(_, c)| matches!(c.constraint_kind, ConstraintKind::NotNull))
+    }
 }
 
-/// Fetch all UNIQUE/NODE KEY constraints for the given labels
+/// Fetch all constraints for the given labels
 pub fn fetch_constraints_for_labels(
     store: &Arc<GraphStore>,
     tx: &Arc<TransactionImpl>,
@@ -39,11 +55,7 @@ pub fn fetch_constraints_for_labels(
     for label in labels {
         if let Some(label_id) = store.token_store().get_label_id(label) {
             let label_constraints = tx.get_constraints_for_label(label_id)?;
-            for c in label_constraints {
-                if matches!(c.constraint_kind, ConstraintKind::Unique | ConstraintKind::NodeKey) {
-                    constraints.push((label_id, c));
-                }
-            }
+            constraints.extend(label_constraints.into_iter().map(|c| (label_id, c)));
         }
     }
 
@@ -60,7 +72,7 @@ pub fn check_unique_constraints(
     label_constraints: &LabelConstraints,
     props: &StructArray,
 ) -> Result<(), ExecError> {
-    for (label_id, constraint) in &label_constraints.constraints {
+    for (label_id, constraint) in label_constraints.unique() {
         let is_node_key = matches!(constraint.constraint_kind, ConstraintKind::NodeKey);
 
         for row_idx in 0..props.len() {
@@ -116,7 +128,7 @@ pub fn update_unique_indexes(
     props: &StructArray,
     nodes: &NodeArray,
 ) -> Result<(), ExecError> {
-    for (label_id, constraint) in &label_constraints.constraints {
+    for (label_id, constraint) in label_constraints.unique() {
         for (row_idx, node_opt) in nodes.iter().enumerate() {
             if let Some(node) = node_opt {
                 let prop_values = extract_property_values(store, props, &constraint.property_key_ids, row_idx);
@@ -185,3 +197,195 @@ fn extract_property_values(
         _ => None,
     }
 }
+
+/// Move the unique index entries of an updated node from its old labels and properties to the new ones.
+///
+/// Returns an error if the new values violate a UNIQUE or NODE KEY constraint.
+pub fn update_unique_indexes_for_node(
+    store: &Arc<GraphStore>,
+    tx: &Arc<TransactionImpl>,
+    old: &NodeValue,
+    new: &NodeValue,
+) -> Result<(), ExecError> {
+    let labels = old
+        .labels
+        .iter()
+        .chain(new.labels.iter())
+        .unique()
+        .cloned()
+        .collect::<Vec<_>>();
+    let label_constraints = fetch_constraints_for_labels(store, tx, &labels)?;
+
+    for (label_id, constraint) in label_constraints.unique() {
+        let label = store.token_store().get_token_val(*label_id, TokenKind::Label)?;
+        let old_values = old
+            .labels
+            .contains(&label)
+            .then(|| extract_node_property_values(store, &old.props, &constraint.property_key_ids))
+            .flatten();
+        let new_values = if new.labels.contains(&label) {
+            let values = extract_node_property_values(store, &new.props, &constraint.property_key_ids);
+            if values.is_none() && matches!(constraint.constraint_kind, ConstraintKind::NodeKey) {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint.name.clone(),
+                    reason: "Properties are required by NODE KEY constraint".to_string(),
+                    trace: Backtrace::capture(),
+                });
+            }
+            values
+        } else {
+            None
+        };
+        if old_values == new_values {
+            continue;
+        }
+
+        if let Some(old_values) = &old_values {
+            let prop_value_refs: Vec<&[u8]> = old_values.iter().map(|v| v.as_slice()).collect();
+            tx.delete_unique_index(*label_id, &constraint.property_key_ids, &prop_value_refs)?;
+        }
+        if let Some(new_values) = &new_values {
+            let prop_value_refs: Vec<&[u8]> = new_values.iter().map(|v| v.as_slice()).collect();
+            if let Some(node_id) = tx.get_unique_index(*label_id, &constraint.property_key_ids, &prop_value_refs)?
+                && node_id != new.id
+            {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint.name.clone(),
+                    reason: "Node with this property value already exists".to_string(),
+                    trace: Backtrace::capture(),
+                });
+            }
+            tx.put_unique_index(*label_id, &constraint.property_key_ids, &prop_value_refs, new.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract encoded property values of a node, returns None if any of the properties is missing or NULL
+fn extract_node_property_values(
+    store: &Arc<GraphStore>,
+    props: &StructValue,
+    prop_key_ids: &[u16],
+) -> Option<Vec<Vec<u8>>> {
+    prop_key_ids
+        .iter()
+        .map(|prop_key_id| {
+            let prop_name = store
+                .token_store()
+                .get_token_val(*prop_key_id, TokenKind::PropertyKey)
+                .ok()?;
+            match props.field_at(&prop_name)? {
+                ScalarRef::Null => None,
+                val => Some(IndexKeyCodec::encode_single(&val)),
+            }
+        })
+        .collect()
+}
+
+/// Check the NOT NULL constraints of the labels against the created nodes
+pub fn check_not_null_constraints(
+    store: &Arc<GraphStore>,
+    label_constraints: &LabelConstraints,
+    nodes: &NodeArray,
+) -> Result<(), ExecError> {
+    for (label_id, constraint) in label_constraints.not_null() {
+        let label = store.token_store().get_token_val(*label_id, TokenKind::Label)?;
+        for node in nodes.iter().flatten() {
+            if let Some(prop_name) = find_missing_property(store, &node.props, &constraint.property_key_ids)? {
+                return Err(node_missing_property(constraint, node.id, &label, &prop_name));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the NOT NULL constraints of the labels of an updated node
+pub fn check_not_null_constraints_for_node(
+    store: &Arc<GraphStore>,
+    tx: &Arc<TransactionImpl>,
+    node: &NodeValue,
+) -> Result<(), ExecError> {
+    let label_constraints = fetch_constraints_for_labels(store, tx, &node.labels)?;
+    for (label_id, constraint) in label_constraints.not_null() {
+        let props = node.props.as_scalar_ref();
+        if let Some(prop_name) = find_missing_property(store, &props, &constraint.property_key_ids)? {
+            let label = store.token_store().get_token_val(*label_id, TokenKind::Label)?;
+            return Err(node_missing_property(constraint, node.id, &label, &prop_name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch all constraints for the given relationship type
+pub fn fetch_constraints_for_reltype(
+    store: &Arc<GraphStore>,
+    tx: &Arc<TransactionImpl>,
+    reltype: &str,
+) -> Result<Vec<ConstraintMeta>, ExecError> {
+    match store.token_store().get_reltype_id(reltype) {
+        Some(reltype_id) => Ok(tx.get_constraints_for_reltype(reltype_id)?),
+        None => Ok(vec![]),
+    }
+}
+
+/// Check the NOT NULL constraints of a relationship type against the created or updated relationships
+pub fn check_rel_not_null_constraints<'a>(
+    store: &Arc<GraphStore>,
+    constraints: &[ConstraintMeta],
+    rels: impl IntoIterator<Item = RelValueRef<'a>>,
+) -> Result<(), ExecError> {
+    let not_null = constraints
+        .iter()
+        .filter(|c| matches!(c.constraint_kind, ConstraintKind::NotNull))
+        .collect::<Vec<_>>();
+    if not_null.is_empty() {
+        return Ok(());
+    }
+    for rel in rels {
+        for constraint in &not_null {
+            if let Some(prop_name) = find_missing_property(store, &rel.props, &constraint.property_key_ids)? {
+                return Err(ExecError::ConstraintViolation {
+                    constraint: constraint.name.clone(),
+                    reason: format!(
+                        "Relationship({}) with type '{}' must have property '{}'",
+                        rel.id, rel.reltype, prop_name
+                    ),
+                    trace: Backtrace::capture(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The first of the properties which is missing or NULL
+fn find_missing_property(
+    store: &Arc<GraphStore>,
+    props: &StructValueRef<'_>,
+    prop_key_ids: &[u16],
+) -> Result<Option<Arc<str>>, ExecError> {
+    for prop_key_id in prop_key_ids {
+        let prop_name = store
+            .token_store()
+            .get_token_val(*prop_key_id, TokenKind::PropertyKey)?;
+        if matches!(props.field_at(&prop_name), None | Some(ScalarRef::Null)) {
+            return Ok(Some(prop_name));
+        }
+    }
+    Ok(None)
+}
+
+fn node_missing_property(constraint: &ConstraintMeta, node_id: NodeId, label: &str, prop_name: &str) -> ExecError {
+    ExecError::ConstraintViolation {
+        constraint: constraint.name.clone(),
+        reason: format!(
+            "Node({}) with label '{}' must have property '{}'",
+            node_id, label, prop_name
+        ),
+        trace: Backtrace::capture(),
+    }
+}
@@ -4,7 +4,9 @@ use elio_common::schema::Variable;
 use elio_expr::impl_::SharedExpression;
 use futures::StreamExt;
 
-use super::constraint::{check_unique_constraints, fetch_constraints_for_labels, update_unique_indexes};
+use super::constraint::{
+    check_not_null_constraints, check_unique_constraints, fetch_constraints_for_labels, update_unique_indexes,
+};
 use super::*;
 
 // input: Schema
@@ -50,7 +52,7 @@ impl Executor for CreateNodeExectuor {
                 .iter()
                 .flat_map(|labels| labels.iter().filter_map(|l| ctx.store().token_store().get_label_id(l)))
                 .collect();
-            let _locks = ctx.store().acquire_labels_read(&all_label_ids);
+            let _locks = ctx.acquire_labels_read(&all_label_ids);
 
             // Execute the stream
             while let Some(chunk) = input_stream.next().await {
@@ -75,6 +77,9 @@ impl Executor for CreateNodeExectuor {
                     // Update unique indexes for the created nodes
                     update_unique_indexes(ctx.store(), ctx.tx(), &label_constraints[i], prop_struct, &output)?;
 
+                    // Check the required properties, the error names the created node
+                    check_not_null_constraints(ctx.store(), &label_constraints[i], &output)?;
+
                     chunk.add_column(Arc::new(output.into()));
                 }
 
@@ -1,8 +1,9 @@
 use async_stream::try_stream;
-use elio_common::array::ArrayImpl;
+use elio_common::array::{Array, ArrayImpl};
 use elio_expr::impl_::SharedExpression;
 use futures::StreamExt;
 
+use super::constraint::{check_rel_not_null_constraints, fetch_constraints_for_reltype};
 use super::*;
 
 // input: Schema
@@ -31,6 +32,12 @@ impl Executor for CreateRelExectuor {
         let stream = try_stream! {
             let eval_ctx = ctx.derive_eval_ctx();
 
+            // Pre-fetch constraints for all relationship types
+            let rel_constraints: Vec<_> = items
+                .iter()
+                .map(|item| fetch_constraints_for_reltype(ctx.store(), ctx.tx(), &item.rtype))
+                .collect::<Result<_, _>>()?;
+
             // execute the stream
             for await chunk in input_stream {
                 let chunk = chunk?;
@@ -75,6 +82,7 @@ impl Executor for CreateRelExectuor {
                             ))
                         }
                     }?;
+                    check_rel_not_null_constraints(ctx.store(), &rel_constraints[i], output.iter().flatten())?;
                     chunk.add_column(Arc::new(output.into()));
                 }
                 yield chunk;
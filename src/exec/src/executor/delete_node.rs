@@ -0,0 +1,61 @@
+use async_stream::try_stream;
+use elio_common::scalar::ScalarRef;
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+
+use super::*;
+
+// input: Schema
+// output: Schema
+#[derive(Debug)]
+pub struct DeleteNodeExecutor {
+    pub(crate) input: SharedExecutor,
+    // evaluated to nodes, nulls are ignored
+    pub(crate) nodes: Vec<SharedExpression>,
+    pub(crate) detach: bool,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for DeleteNodeExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let nodes = self.nodes.clone();
+        let detach = self.detach;
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+
+            for await chunk in input_stream {
+                let chunk = chunk?;
+                let mut node_ids = vec![];
+                for (i, expr) in nodes.iter().enumerate() {
+                    let array = expr.eval_batch(&chunk, &eval_ctx)?;
+                    for row_idx in chunk.visibility().iter_ones() {
+                        match array.get(row_idx) {
+                            None | Some(ScalarRef::Null) => {}
+                            Some(ScalarRef::VirtualNode(id)) => node_ids.push(id),
+                            Some(ScalarRef::Node(node)) => node_ids.push(node.id),
+                            Some(_) => Err(ExecError::type_mismatch(
+                                format!("delete node item {}", i),
+                                "node or virtual node",
+                                array.physical_type(),
+                            ))?,
+                        }
+                    }
+                }
+                ctx.tx().node_delete(&node_ids, detach)?;
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteNode"
+    }
+}
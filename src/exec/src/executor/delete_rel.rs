@@ -0,0 +1,64 @@
+use async_stream::try_stream;
+use elio_common::scalar::{ScalarRef, VirtualRelRef};
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+
+use super::*;
+
+// input: Schema
+// output: Schema
+#[derive(Debug)]
+pub struct DeleteRelExecutor {
+    pub(crate) input: SharedExecutor,
+    // evaluated to relationships, nulls are ignored
+    pub(crate) rels: Vec<SharedExpression>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for DeleteRelExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let rels = self.rels.clone();
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+
+            for await chunk in input_stream {
+                let chunk = chunk?;
+                let arrays = rels.iter().map(|expr| expr.eval_batch(&chunk, &eval_ctx)).collect::<Result<Vec<_>, _>>()?;
+                let mut to_delete = vec![];
+                for (i, array) in arrays.iter().enumerate() {
+                    for row_idx in chunk.visibility().iter_ones() {
+                        match array.get(row_idx) {
+                            None | Some(ScalarRef::Null) => {}
+                            Some(ScalarRef::VirtualRel(rel)) => to_delete.push(rel),
+                            Some(ScalarRef::Rel(rel)) => to_delete.push(VirtualRelRef {
+                                id: rel.id,
+                                reltype: rel.reltype,
+                                start_id: rel.start_id,
+                                end_id: rel.end_id,
+                            }),
+                            Some(_) => Err(ExecError::type_mismatch(
+                                format!("delete rel item {}", i),
+                                "rel or virtual rel",
+                                array.physical_type(),
+                            ))?,
+                        }
+                    }
+                }
+                ctx.tx().relationship_delete(&to_delete)?;
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteRel"
+    }
+}
@@ -0,0 +1,97 @@
+use async_stream::try_stream;
+use elio_common::array::chunk::DataChunkBuilder;
+use elio_common::order::equivalence_key;
+use elio_common::scalar::{Row, ScalarRef};
+use elio_expr::func::agg::{Accumulator, create_accumulator};
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct AggregateItem {
+    pub(crate) func: String,
+    pub(crate) args: Vec<SharedExpression>,
+    pub(crate) distinct: bool,
+}
+
+impl AggregateItem {
+    fn create_accumulator(&self) -> Box<dyn Accumulator> {
+        create_accumulator(&self.func, self.args.len(), self.distinct)
+    }
+}
+
+/// Groups the input rows in a hash table, the output columns are group keys followed by aggregates.
+#[derive(Debug)]
+pub struct HashAggregateExecutor {
+    pub(crate) input: SharedExecutor,
+    pub(crate) group_by: Vec<SharedExpression>,
+    pub(crate) aggregates: Vec<AggregateItem>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for HashAggregateExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let group_by = self.group_by.clone();
+        let aggregates = self.aggregates.clone();
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+            // groups in the order they first appear, keyed by equivalence so that `1` and `1.0` are one group,
+            // the group keys are output as they first appear
+            let mut groups: IndexMap<Row, (Row, Vec<Box<dyn Accumulator>>)> = IndexMap::new();
+
+            for await chunk in input_stream {
+                let chunk = chunk?;
+                let keys = group_by.iter().map(|expr| expr.eval_batch(&chunk, &eval_ctx)).collect::<Result<Vec<_>, _>>()?;
+                // aggregates have at most one argument
+                let args = aggregates.iter().map(|agg| agg.args.first().map(|expr| expr.eval_batch(&chunk, &eval_ctx)).transpose()).collect::<Result<Vec<_>, _>>()?;
+
+                for row_idx in chunk.visibility().iter_ones() {
+                    let key = keys.iter().map(|col| col.get(row_idx).map(equivalence_key)).collect_vec();
+                    let (_, accs) = groups.entry(key).or_insert_with(|| {
+                        let group = keys.iter().map(|col| col.get(row_idx).map(|v| v.to_owned_scalar())).collect_vec();
+                        (group, aggregates.iter().map(|agg| agg.create_accumulator()).collect())
+                    });
+                    for (acc, arg) in accs.iter_mut().zip_eq(args.iter()) {
+                        let value = arg.as_ref().and_then(|col| col.get(row_idx)).filter(|v| !matches!(v, ScalarRef::Null));
+                        acc.update(value)?;
+                    }
+                }
+            }
+
+            // aggregation without group keys always returns one row, even if there's no input
+            if group_by.is_empty() && groups.is_empty() {
+                groups.insert(vec![], (vec![], aggregates.iter().map(|agg| agg.create_accumulator()).collect()));
+            }
+
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+            for (_, (group, accs)) in groups {
+                let mut row = group;
+                for acc in accs {
+                    row.push(acc.finish()?);
+                }
+                if let Some(chunk) = out_builder.append_row(row.iter().map(|x| x.as_ref().map(|v| v.as_scalar_ref())).collect_vec()) {
+                    yield chunk;
+                }
+            }
+            if let Some(chunk) = out_builder.yield_chunk() {
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "HashAggregate"
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use async_stream::try_stream;
+use elio_common::array::ArrayImpl;
+use elio_common::array::chunk::{DataChunk, DataChunkBuilder};
+use elio_common::scalar::ScalarValue;
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::apply::ArgumentContext;
+use super::set::{SetItem, apply_set_items, refresh_column};
+use super::*;
+
+// input: Schema
+// output: input Schema + the variables introduced by the merge pattern
+#[derive(Debug)]
+pub struct MergeExecutor {
+    pub input: SharedExecutor,
+    pub match_plan: SharedExecutor,
+    pub create_plan: SharedExecutor,
+    pub argument_ctx: ArgumentContext,
+    /// Maps argument variable indices to input column indices
+    pub argument_mapping: Vec<usize>,
+    /// Columns of the introduced variables in the output of match plan
+    pub match_columns: Vec<usize>,
+    /// Columns of the introduced variables in the output of create plan
+    pub create_columns: Vec<usize>,
+    pub on_match: Vec<SetItem>,
+    pub on_create: Vec<SetItem>,
+    pub schema: Arc<Schema>,
+}
+
+impl Executor for MergeExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let match_plan = self.match_plan.clone();
+        let create_plan = self.create_plan.clone();
+        let argument_ctx = self.argument_ctx.clone();
+        let argument_mapping = self.argument_mapping.clone();
+        let match_columns = self.match_columns.clone();
+        let create_columns = self.create_columns.clone();
+        let on_match = self.on_match.clone();
+        let on_create = self.on_create.clone();
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            // the match plan may not see the entities created in this transaction, so the created rows are remembered
+            // by the arguments
+            let mut created: HashMap<Vec<ScalarValue>, Vec<DataChunk>> = HashMap::new();
+
+            for await chunk in input_stream {
+                let chunk = chunk?.compact();
+
+                for row in chunk.iter() {
+                    let arguments = argument_mapping
+                        .iter()
+                        .map(|&idx| row[idx].map(|v| v.to_owned_scalar()).unwrap_or_default())
+                        .collect_vec();
+                    argument_ctx.set_row(arguments.clone());
+
+                    let mut is_create = false;
+                    let mut groups = collect_columns(&ctx, match_plan.open(ctx.clone())?, &match_columns).await?;
+                    if groups.is_empty() {
+                        if let Some(rows) = created.get(&arguments) {
+                            groups = rows.iter().map(|rows| refresh_chunk(&ctx, rows)).try_collect()?;
+                        } else {
+                            groups = collect_columns(&ctx, create_plan.open(ctx.clone())?, &create_columns).await?;
+                            created.insert(arguments, groups.clone());
+                            is_create = true;
+                        }
+                    }
+
+                    let items = if is_create { &on_create } else { &on_match };
+                    for group in groups {
+                        let mut builder = DataChunkBuilder::new(
+                            schema.columns().iter().map(|col| col.typ.physical_type()),
+                            group.len(),
+                        );
+                        let mut output = None;
+                        for new_row in group.iter() {
+                            output = builder.append_row(row.iter().cloned().chain(new_row).collect());
+                        }
+                        if let Some(output) = output {
+                            yield apply_set_items(&ctx, items, &output)?;
+                        }
+                    }
+                }
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Merge"
+    }
+}
+
+// collect the given columns of the non-empty chunks, virtual nodes are materialized
+async fn collect_columns(
+    ctx: &TaskExecContext,
+    mut stream: DataChunkStream,
+    columns: &[usize],
+) -> Result<Vec<DataChunk>, ExecError> {
+    let mut chunks = vec![];
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?.compact();
+        if chunk.len() == 0 {
+            continue;
+        }
+        let columns = columns
+            .iter()
+            .map(|&idx| match chunk.column(idx).as_ref() {
+                ArrayImpl::VirtualNode(nodes) => {
+                    Ok(Arc::new(ctx.tx().materialize_node(nodes, chunk.visibility())?.into()))
+                }
+                _ => Ok::<_, ExecError>(chunk.column(idx)),
+            })
+            .try_collect()?;
+        chunks.push(DataChunk::new(columns, chunk.visibility().clone()));
+    }
+    Ok(chunks)
+}
+
+fn refresh_chunk(ctx: &TaskExecContext, chunk: &DataChunk) -> Result<DataChunk, ExecError> {
+    let columns = chunk
+        .columns()
+        .iter()
+        .map(|column| refresh_column(ctx, column))
+        .try_collect()?;
+    Ok(DataChunk::new(columns, chunk.visibility().clone()))
+}
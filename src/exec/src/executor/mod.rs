@@ -15,14 +15,28 @@ pub mod argument;
 pub mod constraint;
 pub mod create_node;
 pub mod create_rel;
+pub mod delete_node;
+pub mod delete_rel;
 pub mod expand;
 pub mod filter;
+pub mod hash_agg;
 pub mod load_csv;
+pub mod merge;
+pub mod node_by_label_scan;
+pub mod node_index_range_seek;
 pub mod node_index_seek;
+pub mod pagination;
 pub mod produce_result;
 pub mod project;
+pub mod quantified_expand;
 pub mod relscan;
+pub mod set;
+pub mod shortest_path;
+pub mod sort;
+pub mod top_n;
+pub mod union;
 pub mod unit;
+pub mod unwind;
 pub mod var_expand;
 
 pub type DataChunkStream = Pin<Box<dyn Stream<Item = Result<DataChunk, ExecError>> + Send>>;
@@ -0,0 +1,103 @@
+use async_stream::try_stream;
+use elio_common::LabelId;
+use elio_common::schema::Variable;
+use elio_storage::transaction::LabelScanOptions;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+use super::apply::ArgumentContext;
+use super::*;
+use crate::executor::Executor;
+
+const CHANNEL_BUFFER_SIZE: usize = 128;
+
+// NodeByLabelScan is an leaf node and produces the NodeId of the nodes with the label through the label index,
+// the arguments are appended to each row
+#[derive(Debug)]
+pub struct NodeByLabelScanExecutor {
+    schema: Arc<Schema>,
+    label_id: LabelId,
+    arguments: Vec<Variable>,
+    argument_ctx: Option<ArgumentContext>,
+}
+
+impl NodeByLabelScanExecutor {
+    pub fn new(
+        schema: Arc<Schema>,
+        label_id: LabelId,
+        arguments: Vec<Variable>,
+        argument_ctx: Option<ArgumentContext>,
+    ) -> Self {
+        Self {
+            schema,
+            label_id,
+            arguments,
+            argument_ctx,
+        }
+    }
+}
+
+impl Executor for NodeByLabelScanExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let (tx, mut rx) = mpsc::channel::<Result<DataChunk, ExecError>>(CHANNEL_BUFFER_SIZE);
+        let txn = ctx.tx().clone();
+        let label_id = self.label_id;
+        // io task
+        tokio::task::spawn_blocking(move || {
+            let opts = LabelScanOptions {
+                batch_size: 1024,
+                label_id,
+            };
+            let mut iter = match txn.label_scan(opts) {
+                Ok(iter) => iter,
+                Err(e) => {
+                    tracing::error!("label scan error: {:?}", e);
+                    if tx.blocking_send(Err(e.into())).is_err() {
+                        tracing::warn!("recv dropped, could not send scan error.");
+                    }
+                    return;
+                }
+            };
+            loop {
+                match iter.next_batch() {
+                    Ok(Some(chunk)) => {
+                        if tx.blocking_send(Ok(chunk)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = tx.blocking_send(Err(e.into()));
+                        break;
+                    }
+                }
+            }
+        });
+
+        let arguments = self.arguments.clone();
+        let argument_ctx = self.argument_ctx.clone();
+        let stream = try_stream! {
+            while let Some(item) = rx.recv().await{
+                let mut chunk = item?;
+                if let Some(argument_ctx) = &argument_ctx {
+                    for column in argument_ctx.build_columns(&arguments, chunk.len()) {
+                        chunk.add_column(column);
+                    }
+                }
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "NodeByLabelScan"
+    }
+}
@@ -0,0 +1,272 @@
+//! NodeIndexRangeSeek executor - seeks a secondary index for the nodes in a range
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use bitvec::vec::BitVec;
+use elio_common::array::chunk::DataChunk;
+use elio_common::array::{ArrayImpl, VirtualNodeArrayBuilder};
+use elio_common::mapb::IndexKeyCodec;
+use elio_common::scalar::{ScalarPartialOrd, ScalarRef, ScalarValue};
+use elio_common::schema::{Schema, Variable};
+use elio_common::{LabelId, NodeId, PropertyKeyId};
+use elio_expr::error::EvalError;
+use elio_expr::impl_::SharedExpression;
+use elio_storage::index::{IndexCodec, IndexMeta};
+use futures::StreamExt;
+
+use super::apply::ArgumentContext;
+use super::*;
+use crate::error::ExecError;
+use crate::executor::Executor;
+
+const BATCH_SIZE: usize = 1024;
+
+/// Comparison of the range property with the value of a range predicate
+#[derive(Debug, Clone, Copy)]
+pub enum RangeOp {
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    StartsWith,
+    In,
+}
+
+impl RangeOp {
+    // the predicate holds, null and values of incomparable types never match
+    fn matches(self, value: &ScalarRef<'_>, operand: &ScalarRef<'_>) -> Result<bool, EvalError> {
+        let ord = || value.scalar_partial_cmp(operand);
+        Ok(match self {
+            RangeOp::Lt => ord() == Some(Ordering::Less),
+            RangeOp::LtEq => matches!(ord(), Some(Ordering::Less | Ordering::Equal)),
+            RangeOp::Gt => ord() == Some(Ordering::Greater),
+            RangeOp::GtEq => matches!(ord(), Some(Ordering::Greater | Ordering::Equal)),
+            RangeOp::StartsWith => match (value, operand) {
+                (ScalarRef::String(value), ScalarRef::String(prefix)) => value.starts_with(prefix),
+                _ => false,
+            },
+            RangeOp::In => match operand {
+                ScalarRef::List(list) => list
+                    .iter()
+                    .any(|elem| value.scalar_partial_cmp(&elem) == Some(Ordering::Equal)),
+                ScalarRef::Null => false,
+                other => return Err(EvalError::type_error(format!("IN expects a list, got {:?}", other))),
+            },
+        })
+    }
+}
+
+type SeekRange = (Bound<Vec<u8>>, Bound<Vec<u8>>);
+
+/// The ranges of encoded values of the range property to seek, in index order. The values in the ranges still need
+/// to be checked against the predicates.
+fn seek_ranges(operands: &[(RangeOp, ScalarValue)]) -> Result<Vec<SeekRange>, EvalError> {
+    // a point lookup for each element of the list
+    if let Some((_, operand)) = operands.iter().find(|(op, _)| matches!(op, RangeOp::In)) {
+        let elems: BTreeSet<Vec<u8>> = match operand.as_scalar_ref() {
+            ScalarRef::List(list) => list.iter().filter_map(|elem| IndexCodec::encode_value(&elem)).collect(),
+            ScalarRef::Null => BTreeSet::new(),
+            other => return Err(EvalError::type_error(format!("IN expects a list, got {:?}", other))),
+        };
+        return Ok(elems
+            .into_iter()
+            .map(|elem| (Bound::Included(elem.clone()), Bound::Included(elem)))
+            .collect());
+    }
+
+    let mut lower = Bound::Unbounded;
+    let mut upper = Bound::Unbounded;
+    for (op, operand) in operands {
+        let operand = operand.as_scalar_ref();
+        // null is not comparable, and the values of the other types are not in the range
+        let Some(value) = IndexCodec::encode_value(&operand) else {
+            return Ok(vec![]);
+        };
+        let tag = IndexKeyCodec::type_tag(&operand);
+        let (op_lower, op_upper) = match op {
+            RangeOp::Lt => (Bound::Included(vec![tag]), Bound::Excluded(value)),
+            RangeOp::LtEq => (Bound::Included(vec![tag]), Bound::Included(value)),
+            RangeOp::Gt => (Bound::Excluded(value), Bound::Excluded(vec![tag + 1])),
+            RangeOp::GtEq => (Bound::Included(value), Bound::Excluded(vec![tag + 1])),
+            RangeOp::StartsWith => match operand {
+                ScalarRef::String(prefix) => {
+                    let prefix = IndexKeyCodec::encode_string_prefix(prefix);
+                    (Bound::Included(prefix.clone()), Bound::Included(prefix))
+                }
+                _ => return Ok(vec![]),
+            },
+            RangeOp::In => unreachable!(),
+        };
+        lower = max_lower(lower, op_lower);
+        upper = min_upper(upper, op_upper);
+    }
+    Ok(vec![(lower, upper)])
+}
+
+// the higher of two lower bounds
+fn max_lower(lhs: Bound<Vec<u8>>, rhs: Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    match (&lhs, &rhs) {
+        (Bound::Unbounded, _) => rhs,
+        (_, Bound::Unbounded) => lhs,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(r) | Bound::Excluded(r)) => match l.cmp(r) {
+            Ordering::Less => rhs,
+            Ordering::Greater => lhs,
+            Ordering::Equal if matches!(rhs, Bound::Excluded(_)) => rhs,
+            Ordering::Equal => lhs,
+        },
+    }
+}
+
+// the lower of two upper bounds
+fn min_upper(lhs: Bound<Vec<u8>>, rhs: Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    match (&lhs, &rhs) {
+        (Bound::Unbounded, _) => rhs,
+        (_, Bound::Unbounded) => lhs,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(r) | Bound::Excluded(r)) => match l.cmp(r) {
+            Ordering::Less => lhs,
+            Ordering::Greater => rhs,
+            Ordering::Equal if matches!(rhs, Bound::Excluded(_)) => rhs,
+            Ordering::Equal => lhs,
+        },
+    }
+}
+
+/// Executor that seeks the entries of a secondary index
+#[derive(Debug)]
+pub struct NodeIndexRangeSeekExecutor {
+    pub schema: Arc<Schema>,
+    pub index: IndexMeta,
+    /// Values of the leading properties, evaluated on the arguments
+    pub prefix_values: Vec<SharedExpression>,
+    /// Predicates on the property following the leading properties, evaluated on the arguments
+    pub range: Vec<(RangeOp, SharedExpression)>,
+    pub arguments: Vec<Variable>,
+    pub argument_ctx: Option<ArgumentContext>,
+}
+
+impl NodeIndexRangeSeekExecutor {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        schema: Arc<Schema>,
+        index_name: String,
+        label_id: LabelId,
+        property_key_ids: Vec<PropertyKeyId>,
+        prefix_values: Vec<SharedExpression>,
+        range: Vec<(RangeOp, SharedExpression)>,
+        arguments: Vec<Variable>,
+        argument_ctx: Option<ArgumentContext>,
+    ) -> Self {
+        Self {
+            schema,
+            index: IndexMeta {
+                name: index_name,
+                label_id,
+                property_key_ids,
+            },
+            prefix_values,
+            range,
+            arguments,
+            argument_ctx,
+        }
+    }
+}
+
+impl Executor for NodeIndexRangeSeekExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let index = self.index.clone();
+        let prefix_values = self.prefix_values.clone();
+        let range = self.range.clone();
+        let argument_vars = self.arguments.clone();
+        let argument_ctx = self.argument_ctx.clone();
+        let arguments = match &argument_ctx {
+            Some(argument_ctx) => {
+                DataChunk::new(argument_ctx.build_columns(&argument_vars, 1), BitVec::repeat(true, 1))
+            }
+            None => DataChunk::unit(),
+        };
+        // the nodes with the arguments of the lookup
+        let build_chunk = move |node_ids: Vec<NodeId>| {
+            let len = node_ids.len();
+            let mut builder = VirtualNodeArrayBuilder::with_capacity(len);
+            node_ids.into_iter().for_each(|node_id| builder.push(Some(node_id)));
+            let nodes = ArrayImpl::VirtualNode(builder.finish());
+            let mut chunk = DataChunk::new(vec![Arc::new(nodes)], BitVec::repeat(true, len));
+            if let Some(argument_ctx) = &argument_ctx {
+                for column in argument_ctx.build_columns(&argument_vars, len) {
+                    chunk.add_column(column);
+                }
+            }
+            chunk
+        };
+
+        let stream = try_stream! {
+            let tx = ctx.tx();
+            let eval_ctx = ctx.derive_eval_ctx();
+
+            // Encode the leading values as the index does, null or a value that can not be indexed never matches
+            let mut encoded = Vec::with_capacity(prefix_values.len());
+            for expr in prefix_values.iter() {
+                let value = expr.eval_batch(&arguments, &eval_ctx)?;
+                match value.get(0).and_then(|value| IndexCodec::encode_value(&value)) {
+                    Some(value) => encoded.push(value),
+                    None => break,
+                }
+            }
+            let mut operands = Vec::with_capacity(range.len());
+            for (op, expr) in range.iter() {
+                let value = expr.eval_batch(&arguments, &eval_ctx)?;
+                let operand = value.get(0).map_or(ScalarValue::Unknown, |v| v.to_owned_scalar());
+                operands.push((*op, operand));
+            }
+
+            if encoded.len() == prefix_values.len() {
+                let prefix = encoded.iter().map(|v| v.as_slice()).collect::<Vec<_>>();
+                let range_pos = prefix.len();
+                let mut node_ids = Vec::with_capacity(BATCH_SIZE);
+                for (lower, upper) in seek_ranges(&operands)? {
+                    let lower = lower.as_ref().map(Vec::as_slice);
+                    let upper = upper.as_ref().map(Vec::as_slice);
+                    for entry in tx.index_seek(&index, &prefix, lower, upper) {
+                        let (node_id, values) = entry?;
+                        // the seek range may be wider than the predicates, e.g. for NaN
+                        if let Some(value) = values.get(range_pos) {
+                            let value = value.as_scalar_ref();
+                            let mut matched = true;
+                            for (op, operand) in operands.iter() {
+                                if !op.matches(&value, &operand.as_scalar_ref())? {
+                                    matched = false;
+                                    break;
+                                }
+                            }
+                            if !matched {
+                                continue;
+                            }
+                        }
+                        node_ids.push(node_id);
+
+                        if node_ids.len() == BATCH_SIZE {
+                            yield build_chunk(std::mem::replace(&mut node_ids, Vec::with_capacity(BATCH_SIZE)));
+                        }
+                    }
+                }
+                if !node_ids.is_empty() {
+                    yield build_chunk(node_ids);
+                }
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "NodeIndexRangeSeek"
+    }
+}
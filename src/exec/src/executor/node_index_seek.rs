@@ -6,10 +6,14 @@ use async_stream::try_stream;
 use bitvec::vec::BitVec;
 use elio_common::array::chunk::DataChunk;
 use elio_common::array::{ArrayImpl, VirtualNodeArrayBuilder};
-use elio_common::schema::Schema;
+use elio_common::mapb::IndexKeyCodec;
+use elio_common::scalar::ScalarRef;
+use elio_common::schema::{Schema, Variable};
 use elio_common::{LabelId, PropertyKeyId};
+use elio_expr::impl_::SharedExpression;
 use futures::StreamExt;
 
+use super::apply::ArgumentContext;
 use super::*;
 use crate::error::ExecError;
 use crate::executor::Executor;
@@ -20,8 +24,10 @@ pub struct NodeIndexSeekExecutor {
     pub schema: Arc<Schema>,
     pub label_id: LabelId,
     pub property_key_ids: Vec<PropertyKeyId>,
-    /// Serialized property values for lookup
-    pub property_values: Vec<Vec<u8>>,
+    /// Property values for lookup, evaluated on the arguments
+    pub property_values: Vec<SharedExpression>,
+    pub arguments: Vec<Variable>,
+    pub argument_ctx: Option<ArgumentContext>,
 }
 
 impl NodeIndexSeekExecutor {
@@ -29,13 +35,17 @@ impl NodeIndexSeekExecutor {
         schema: Arc<Schema>,
         label_id: LabelId,
         property_key_ids: Vec<PropertyKeyId>,
-        property_values: Vec<Vec<u8>>,
+        property_values: Vec<SharedExpression>,
+        arguments: Vec<Variable>,
+        argument_ctx: Option<ArgumentContext>,
     ) -> Self {
         Self {
             schema,
             label_id,
             property_key_ids,
             property_values,
+            arguments,
+            argument_ctx,
         }
     }
 }
@@ -45,28 +55,44 @@ impl Executor for NodeIndexSeekExecutor {
         let label_id = self.label_id;
         let property_key_ids = self.property_key_ids.clone();
         let property_values = self.property_values.clone();
+        let arguments = match &self.argument_ctx {
+            Some(argument_ctx) => {
+                DataChunk::new(argument_ctx.build_columns(&self.arguments, 1), BitVec::repeat(true, 1))
+            }
+            None => DataChunk::unit(),
+        };
 
         let stream = try_stream! {
             let tx = ctx.tx();
-            let prop_value_refs: Vec<&[u8]> = property_values.iter().map(|v| v.as_slice()).collect();
+            let eval_ctx = ctx.derive_eval_ctx();
+
+            // Encode using IndexKeyCodec (same as index storage), null never matches
+            let mut encoded = Vec::with_capacity(property_values.len());
+            for expr in property_values.iter() {
+                let value = expr.eval_batch(&arguments, &eval_ctx)?;
+                match value.get(0) {
+                    None | Some(ScalarRef::Null) => break,
+                    Some(value) => encoded.push(IndexKeyCodec::encode_single(&value)),
+                }
+            }
 
             // Perform single index lookup
-            let node_id = tx.get_unique_index(
-                label_id,
-                &property_key_ids,
-                &prop_value_refs,
-            )?;
+            let node_id = if encoded.len() == property_values.len() {
+                let prop_value_refs: Vec<&[u8]> = encoded.iter().map(|v| v.as_slice()).collect();
+                tx.get_unique_index(label_id, &property_key_ids, &prop_value_refs)?
+            } else {
+                None
+            };
 
             if let Some(node_id) = node_id {
-                // Found a node - create a single-row result with VirtualNodeArray
+                // Found a node - create a single-row result with VirtualNodeArray and the arguments
                 let mut builder = VirtualNodeArrayBuilder::with_capacity(1);
                 builder.push(Some(node_id));
                 let node_array = builder.finish();
 
-                let chunk = DataChunk::new(
-                    vec![Arc::new(ArrayImpl::VirtualNode(node_array))],
-                    BitVec::repeat(true, 1),
-                );
+                let mut columns = vec![Arc::new(ArrayImpl::VirtualNode(node_array))];
+                columns.extend(arguments.columns().iter().cloned());
+                let chunk = DataChunk::new(columns, BitVec::repeat(true, 1));
                 yield chunk;
             }
             // If no node found, yield nothing (empty result)
@@ -0,0 +1,164 @@
+use async_stream::try_stream;
+use elio_common::scalar::ScalarRef;
+use elio_expr::error::EvalError;
+use elio_expr::impl_::{EvalCtx, SharedExpression};
+use futures::StreamExt;
+
+use super::*;
+
+#[derive(Debug)]
+pub struct PaginationExecutor {
+    pub(crate) input: SharedExecutor,
+    pub(crate) offset: Option<SharedExpression>,
+    pub(crate) limit: Option<SharedExpression>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for PaginationExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let offset = self.offset.clone();
+        let limit = self.limit.clone();
+        let input_stream = self.input.open(ctx.clone())?;
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+            let mut offset = eval_pagination_arg(offset.as_ref(), &eval_ctx, "SKIP")?.unwrap_or(0);
+            let mut limit = eval_pagination_arg(limit.as_ref(), &eval_ctx, "LIMIT")?;
+
+            let mut input_stream = input_stream;
+            // stop pulling from input once the limit is reached,
+            // dropping the input stream cancels the upstream executors.
+            while limit != Some(0) && let Some(chunk) = input_stream.next().await {
+                let mut chunk = chunk?;
+                let visibility = chunk.visibility_mut();
+                for idx in 0..visibility.len() {
+                    if !visibility[idx] {
+                        continue;
+                    }
+                    if offset > 0 {
+                        offset -= 1;
+                        visibility.set(idx, false);
+                    } else {
+                        match limit.as_mut() {
+                            Some(0) => visibility.set(idx, false),
+                            Some(n) => *n -= 1,
+                            None => {}
+                        }
+                    }
+                }
+                if chunk.visible_row_len() > 0 {
+                    yield chunk;
+                }
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Pagination"
+    }
+}
+
+/// Evaluate SKIP or LIMIT argument, which must be a non-negative integer.
+pub(crate) fn eval_pagination_arg(
+    expr: Option<&SharedExpression>,
+    eval_ctx: &dyn EvalCtx,
+    clause: &str,
+) -> Result<Option<usize>, ExecError> {
+    let Some(expr) = expr else {
+        return Ok(None);
+    };
+    // the expression does not depend on any variable, evaluate on a single empty row
+    let result = expr.eval_batch(&DataChunk::unit(), eval_ctx)?;
+    match result.get(0) {
+        Some(ScalarRef::Integer(i)) if i >= 0 => Ok(Some(i as usize)),
+        other => Err(EvalError::invalid_argument(
+            clause,
+            "a non-negative integer",
+            other.map_or_else(|| "null".to_string(), |x| x.to_string()),
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use elio_catalog::Catalog;
+    use elio_common::data_type::DataType;
+    use elio_common::scalar::ScalarValue;
+    use elio_expr::impl_::constant::ConstantExpr;
+    use elio_storage::graph::GraphStore;
+
+    use super::*;
+    use crate::task::{ExecConfig, ExecContext};
+
+    // an endless input of single row chunks, counting the chunks pulled from it
+    #[derive(Debug, Default)]
+    struct EndlessExecutor {
+        pulled: Arc<AtomicUsize>,
+        schema: Schema,
+    }
+
+    impl Executor for EndlessExecutor {
+        fn open(&self, _ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+            let pulled = self.pulled.clone();
+            let stream = futures::stream::repeat_with(move || {
+                pulled.fetch_add(1, Ordering::Relaxed);
+                Ok(DataChunk::unit())
+            });
+            Ok(stream.boxed())
+        }
+
+        fn schema(&self) -> &Schema {
+            &self.schema
+        }
+
+        fn name(&self) -> &'static str {
+            "Endless"
+        }
+    }
+
+    fn integer(i: i64) -> SharedExpression {
+        Arc::new(ConstantExpr {
+            value: Some(ScalarValue::Integer(i)),
+            typ: DataType::Integer,
+        })
+    }
+
+    async fn pull_rows(offset: Option<i64>, limit: Option<i64>) -> (usize, usize) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(GraphStore::open(dir.path().to_str().unwrap()).unwrap());
+        let catalog = Arc::new(Catalog::new(store.token_store().clone()));
+        let ectx = Arc::new(ExecContext::new(catalog, store, ExecConfig::default()));
+        let ctx = Arc::new(TaskExecContext::standalone(ectx));
+
+        let input = EndlessExecutor::default();
+        let pulled = input.pulled.clone();
+        let pagination = PaginationExecutor {
+            input: input.into_shared(),
+            offset: offset.map(integer),
+            limit: limit.map(integer),
+            schema: Arc::new(Schema::empty()),
+        };
+        let mut rows = 0;
+        let mut stream = pagination.open(ctx).unwrap();
+        while let Some(chunk) = stream.next().await {
+            rows += chunk.unwrap().visible_row_len();
+        }
+        (rows, pulled.load(Ordering::Relaxed))
+    }
+
+    #[tokio::test]
+    async fn test_limit_stops_pulling_input() {
+        assert_eq!(pull_rows(None, Some(3)).await, (3, 3));
+        assert_eq!(pull_rows(Some(2), Some(1)).await, (1, 3));
+        assert_eq!(pull_rows(Some(5), Some(0)).await, (0, 0));
+    }
+}
@@ -50,6 +50,14 @@ impl Executor for ProduceResultExecutor {
 
                         let mz_path = PathArray::from_parts(path_nodes, path_rels, valid);
                         out_cols.push(Arc::new(mz_path.into()));
+                    } else if let Some(list) = col.as_list() && list.child().as_virtual_node().is_some() {
+                        // list of virtual nodes, e.g. collect(n)
+                        let (offsets, child, valid) = list.clone().into_parts();
+                        let child_nodes = child.as_virtual_node().unwrap();
+                        let vis = BitVec::repeat(true, child_nodes.len());
+                        let mz_nodes = ctx.tx().materialize_node(child_nodes, &vis)?;
+                        let mz_list = ListArray::from_parts(offsets, Arc::new(mz_nodes.into()), valid);
+                        out_cols.push(Arc::new(mz_list.into()));
                     } else {
                         out_cols.push(col.clone());
                     }
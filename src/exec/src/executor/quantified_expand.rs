@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+
+use async_stream::try_stream;
+use elio_common::array::{ArrayImpl, DataChunkBuilder, RelArrayBuilder, VirtualNodeArrayBuilder};
+use elio_common::data_type::DataType;
+use elio_common::scalar::{ListValueRef, RelValue, ScalarRef, ScalarVTable};
+use elio_common::{NodeId, RelationshipId, SemanticDirection, TokenId};
+use elio_expr::impl_::{EvalCtx, SharedExpression};
+use futures::StreamExt;
+
+use super::var_expand::decode_rel;
+use super::*;
+
+// one relationship of the inner pattern, in the order of expansion
+#[derive(Debug, Clone)]
+pub struct QuantifiedStep {
+    pub from_slot: usize,
+    pub to_slot: usize,
+    pub rel_slot: usize,
+    pub dir: SemanticDirection,
+    // None when none of the relationship types exists, then the step never matches
+    pub rel_types: Option<Arc<[TokenId]>>,
+}
+
+// the nodes and relationships bound by one iteration, indexed by slot
+#[derive(Debug, Clone)]
+struct Iteration {
+    nodes: Vec<NodeId>,
+    rels: Vec<RelValue>,
+}
+
+// the iterations repeated so far
+#[derive(Debug, Clone)]
+struct Repeated {
+    end: NodeId,
+    iterations: Vec<Iteration>,
+}
+
+// output schema: [input, node groups, rel groups, to]
+// The inner pattern is repeated level by level, the predicates of the iterations at the same level are evaluated
+// in a batch on [input, inner nodes, inner rels]. Relationships are not repeated in a match unless in WALK mode.
+#[derive(Debug)]
+pub struct QuantifiedExpandExecutor {
+    pub input: SharedExecutor,
+    pub from: usize,
+    // Some for expand into
+    pub to: Option<usize>,
+    pub steps: Vec<QuantifiedStep>,
+    pub node_slots: usize,
+    pub rel_slots: usize,
+    pub filter: Option<SharedExpression>,
+    pub node_groups: Vec<usize>,
+    pub rel_groups: Vec<usize>,
+    // expanded from the right endpoint, the groups are reversed to follow the pattern order
+    pub reversed: bool,
+    // false for WALK, the relationships can repeat across the iterations
+    pub unique_rels: bool,
+    pub min: usize,
+    pub max: Option<usize>,
+    pub schema: Arc<Schema>,
+}
+
+impl Executor for QuantifiedExpandExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let schema = self.schema.clone();
+        let expander = Expander {
+            steps: self.steps.clone(),
+            node_slots: self.node_slots,
+            rel_slots: self.rel_slots,
+            filter: self.filter.clone(),
+            unique_rels: self.unique_rels,
+            filter_types: self
+                .input
+                .schema()
+                .columns()
+                .iter()
+                .map(|col| col.typ.clone())
+                .chain(std::iter::repeat_n(DataType::VirtualNode, self.node_slots))
+                .chain(std::iter::repeat_n(DataType::Rel, self.rel_slots))
+                .collect(),
+        };
+        let from = self.from;
+        let to = self.to;
+        let node_groups = self.node_groups.clone();
+        let rel_groups = self.rel_groups.clone();
+        let reversed = self.reversed;
+        let min = self.min;
+        let max = self.max;
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+            for await chunk in input_stream {
+                let outer = chunk?;
+                let outer = outer.compact();
+                for row in outer.iter() {
+                    // if from is null, then remove this row
+                    let Some(from_id) = row[from].and_then(|id| id.get_node_id()) else {
+                        continue;
+                    };
+                    let to_id = match to {
+                        Some(to) => match row[to].and_then(|id| id.get_node_id()) {
+                            Some(id) => Some(id),
+                            None => continue,
+                        },
+                        None => None,
+                    };
+
+                    let mut level = vec![Repeated { end: from_id, iterations: vec![] }];
+                    let mut count = 0;
+                    loop {
+                        if count >= min {
+                            for repeated in level.iter().filter(|r| to_id.is_none_or(|id| id == r.end)) {
+                                let group_arrays = group_arrays(repeated, &node_groups, &rel_groups, reversed);
+                                let mut row = row.clone();
+                                for array in group_arrays.iter() {
+                                    row.push(Some(ScalarRef::List(ListValueRef::from_array(array, 0, array.len()))));
+                                }
+                                if to.is_none() {
+                                    row.push(Some(ScalarRef::VirtualNode(repeated.end)));
+                                }
+                                if let Some(chunk) = out_builder.append_row(row) {
+                                    yield chunk;
+                                }
+                            }
+                        }
+                        if max == Some(count) {
+                            break;
+                        }
+                        level = expander.expand(&ctx, &eval_ctx, &row, level)?;
+                        if level.is_empty() {
+                            break;
+                        }
+                        count += 1;
+                    }
+                }
+
+                if let Some(chunk) = out_builder.yield_chunk() {
+                    yield chunk;
+                }
+            }
+        }
+        .boxed();
+
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "QuantifiedExpand"
+    }
+}
+
+// the group lists of the repeated iterations, node groups first
+fn group_arrays(repeated: &Repeated, node_groups: &[usize], rel_groups: &[usize], reversed: bool) -> Vec<ArrayImpl> {
+    let mut iterations = repeated.iterations.iter().collect::<Vec<_>>();
+    if reversed {
+        iterations.reverse();
+    }
+
+    let mut arrays = vec![];
+    for slot in node_groups {
+        let mut builder = VirtualNodeArrayBuilder::with_capacity(iterations.len());
+        iterations.iter().for_each(|it| builder.push(Some(it.nodes[*slot])));
+        arrays.push(builder.finish().into());
+    }
+    for slot in rel_groups {
+        let mut builder = RelArrayBuilder::with_capacity(iterations.len());
+        iterations
+            .iter()
+            .for_each(|it| builder.push(Some(it.rels[*slot].as_scalar_ref())));
+        arrays.push(builder.finish().into());
+    }
+    arrays
+}
+
+struct Expander {
+    steps: Vec<QuantifiedStep>,
+    node_slots: usize,
+    rel_slots: usize,
+    filter: Option<SharedExpression>,
+    unique_rels: bool,
+    // types of the rows the filter evaluated on
+    filter_types: Vec<DataType>,
+}
+
+impl Expander {
+    // repeat the inner pattern once more for each of the repeated paths
+    fn expand(
+        &self,
+        ctx: &TaskExecContext,
+        eval_ctx: &dyn EvalCtx,
+        row: &[Option<ScalarRef>],
+        level: Vec<Repeated>,
+    ) -> Result<Vec<Repeated>, ExecError> {
+        let mut next = vec![];
+        for repeated in level {
+            let mut used = repeated
+                .iterations
+                .iter()
+                .flat_map(|it| it.rels.iter().map(|rel| rel.id))
+                .collect::<HashSet<_>>();
+            let mut nodes = vec![None; self.node_slots];
+            let mut rels = vec![None; self.rel_slots];
+            nodes[self.steps[0].from_slot] = Some(repeated.end);
+            let mut matched = vec![];
+            self.match_steps(ctx, 0, &mut nodes, &mut rels, &mut used, &mut matched)?;
+
+            for iteration in matched {
+                // the next iteration starts from the node this iteration ends at
+                let end = iteration.nodes[self.steps.last().unwrap().to_slot];
+                let mut iterations = repeated.iterations.clone();
+                iterations.push(iteration);
+                next.push(Repeated { end, iterations });
+            }
+        }
+
+        let Some(filter) = &self.filter else {
+            return Ok(next);
+        };
+        if next.is_empty() {
+            return Ok(next);
+        }
+        // evaluate the predicates on the last iteration of each path
+        let mut builder =
+            DataChunkBuilder::new(self.filter_types.iter().map(|typ| typ.physical_type()), next.len() + 1);
+        for repeated in next.iter() {
+            let iteration = repeated.iterations.last().unwrap();
+            let mut filter_row = row.to_vec();
+            filter_row.extend(iteration.nodes.iter().map(|node| Some(ScalarRef::VirtualNode(*node))));
+            filter_row.extend(
+                iteration
+                    .rels
+                    .iter()
+                    .map(|rel| Some(ScalarRef::Rel(rel.as_scalar_ref()))),
+            );
+            builder.append_row(filter_row);
+        }
+        let chunk = builder.yield_chunk().unwrap();
+        let res = filter.eval_batch(&chunk, eval_ctx)?;
+        let mask = res
+            .as_bool()
+            .expect("filter should result in bool array")
+            .to_filter_mask();
+        Ok(next
+            .into_iter()
+            .zip(mask.iter().by_vals())
+            .filter_map(|(repeated, keep)| keep.then_some(repeated))
+            .collect())
+    }
+
+    // match the steps of one iteration by DFS, the relationships of the iteration are not repeated either
+    fn match_steps(
+        &self,
+        ctx: &TaskExecContext,
+        step: usize,
+        nodes: &mut Vec<Option<NodeId>>,
+        rels: &mut Vec<Option<RelValue>>,
+        used: &mut HashSet<RelationshipId>,
+        matched: &mut Vec<Iteration>,
+    ) -> Result<(), ExecError> {
+        let Some(s) = self.steps.get(step) else {
+            // safety: all the slots are bound by the steps
+            matched.push(Iteration {
+                nodes: nodes.iter().map(|node| node.unwrap()).collect(),
+                rels: rels.iter().map(|rel| rel.clone().unwrap()).collect(),
+            });
+            return Ok(());
+        };
+        let Some(rel_types) = &s.rel_types else {
+            return Ok(());
+        };
+
+        let current = nodes[s.from_slot].unwrap();
+        for rel_kv in ctx.tx().rel_iter_for_node(current, s.dir, rel_types)? {
+            let rel_kv = rel_kv?;
+            let (other, rel_id) = (rel_kv.3, rel_kv.4);
+            // the node may be bound by the previous steps, e.g. ((a)-->(b)-->(a))
+            let bound = nodes[s.to_slot];
+            if (self.unique_rels && used.contains(&rel_id)) || bound.is_some_and(|node| node != other) {
+                continue;
+            }
+            nodes[s.to_slot] = Some(other);
+            rels[s.rel_slot] = Some(decode_rel(ctx, rel_kv)?);
+            used.insert(rel_id);
+            self.match_steps(ctx, step + 1, nodes, rels, used, matched)?;
+            used.remove(&rel_id);
+            nodes[s.to_slot] = bound;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,107 @@
+use async_stream::try_stream;
+use elio_common::schema::Variable;
+use elio_common::{SemanticDirection, TokenId};
+use elio_storage::transaction::RelScanOptions;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+use super::apply::ArgumentContext;
 use super::*;
 
+const CHANNEL_BUFFER_SIZE: usize = 128;
+
+// RelScan is an leaf node and produces (left, rel, right) as results, the arguments are appended to each row
 #[derive(Debug)]
-pub struct AllRelScanExecutor {
-    schema: Schema,
+pub struct RelScanExecutor {
+    schema: Arc<Schema>,
+    direction: SemanticDirection,
+    rtype: Vec<TokenId>,
+    arguments: Vec<Variable>,
+    argument_ctx: Option<ArgumentContext>,
+}
+
+impl RelScanExecutor {
+    pub fn new(
+        schema: Arc<Schema>,
+        direction: SemanticDirection,
+        rtype: Vec<TokenId>,
+        arguments: Vec<Variable>,
+        argument_ctx: Option<ArgumentContext>,
+    ) -> Self {
+        Self {
+            schema,
+            direction,
+            rtype,
+            arguments,
+            argument_ctx,
+        }
+    }
 }
 
-impl Executor for AllRelScanExecutor {
-    fn open(&self, _ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
-        todo!()
+impl Executor for RelScanExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let (tx, mut rx) = mpsc::channel::<Result<DataChunk, ExecError>>(CHANNEL_BUFFER_SIZE);
+        let txn = ctx.tx().clone();
+        let reltypes = self.rtype.clone();
+        // io task
+        tokio::task::spawn_blocking(move || {
+            let opts = RelScanOptions {
+                batch_size: 1024,
+                reltypes,
+            };
+            let mut iter = match txn.rel_scan(opts) {
+                Ok(iter) => iter,
+                Err(e) => {
+                    tracing::error!("rel scan error: {:?}", e);
+                    if tx.blocking_send(Err(e.into())).is_err() {
+                        tracing::warn!("recv dropped, could not send scan error.");
+                    }
+                    return;
+                }
+            };
+            loop {
+                match iter.next_batch() {
+                    Ok(Some(chunk)) => {
+                        if tx.blocking_send(Ok(chunk)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = tx.blocking_send(Err(e.into()));
+                        break;
+                    }
+                }
+            }
+        });
+
+        // the scanned chunks are (rel, start, end), reorder them to (left, rel, right)
+        let orders: &[[usize; 3]] = match self.direction {
+            SemanticDirection::Outgoing => &[[1, 0, 2]],
+            SemanticDirection::Incoming => &[[2, 0, 1]],
+            SemanticDirection::Both => &[[1, 0, 2], [2, 0, 1]],
+        };
+        let arguments = self.arguments.clone();
+        let argument_ctx = self.argument_ctx.clone();
+        let stream = try_stream! {
+            while let Some(item) = rx.recv().await {
+                let scanned = item?;
+                for order in orders {
+                    let columns = order.iter().map(|&idx| scanned.column(idx)).collect();
+                    let mut chunk = DataChunk::new(columns, scanned.visibility().clone());
+                    if let Some(argument_ctx) = &argument_ctx {
+                        for column in argument_ctx.build_columns(&arguments, chunk.len()) {
+                            chunk.add_column(column);
+                        }
+                    }
+                    yield chunk;
+                }
+            }
+        }
+        .boxed();
+        Ok(stream)
     }
 
     fn schema(&self) -> &Schema {
@@ -15,6 +109,6 @@ impl Executor for AllRelScanExecutor {
     }
 
     fn name(&self) -> &'static str {
-        "AllRelScan"
+        "RelScan"
     }
 }
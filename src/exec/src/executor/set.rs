@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+
+use async_stream::try_stream;
+use elio_common::NodeId;
+use elio_common::array::chunk::DataChunk;
+use elio_common::array::{Array, ArrayImpl, NodeArrayBuilder, RelArrayBuilder};
+use elio_common::scalar::{ScalarRef, ScalarVTable, ScalarValue, StructValue, VirtualRelRef};
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+
+use super::constraint::{
+    check_not_null_constraints_for_node, check_rel_not_null_constraints, fetch_constraints_for_reltype,
+    update_unique_indexes_for_node,
+};
+use super::*;
+
+// input: Schema
+// output: Schema, node and relationship columns are refreshed with the updated values
+#[derive(Debug)]
+pub struct SetExecutor {
+    pub(crate) input: SharedExecutor,
+    pub(crate) items: Vec<SetItem>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetItem {
+    // index of the updated entity
+    pub(crate) column: usize,
+    pub(crate) value: Option<SharedExpression>,
+    pub(crate) kind: SetItemKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum SetItemKind {
+    // set the property to value, null removes the property
+    Property(Arc<str>),
+    // replace or merge all the properties with value
+    Properties { merge: bool },
+    Labels { labels: Vec<Arc<str>>, remove: bool },
+}
+
+impl Executor for SetExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let items = self.items.clone();
+
+        let stream = try_stream! {
+            for await chunk in input_stream {
+                yield apply_set_items(&ctx, &items, &chunk?.compact())?;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Set"
+    }
+}
+
+/// Apply the items to each row of the compacted chunk in order, the updated columns are refreshed in the output.
+pub(crate) fn apply_set_items(
+    ctx: &TaskExecContext,
+    items: &[SetItem],
+    chunk: &DataChunk,
+) -> Result<DataChunk, ExecError> {
+    let eval_ctx = ctx.derive_eval_ctx();
+    let columns = items.iter().map(|item| item.column).collect::<HashSet<_>>();
+    let values = items
+        .iter()
+        .map(|item| {
+            item.value
+                .as_ref()
+                .map(|expr| expr.eval_batch(chunk, &eval_ctx))
+                .transpose()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for row_idx in 0..chunk.len() {
+        for (i, (item, value)) in items.iter().zip(values.iter()).enumerate() {
+            let value = value.as_ref().map(|v| (v.as_ref(), row_idx));
+            let entity = chunk.column(item.column);
+            match entity.get(row_idx) {
+                None | Some(ScalarRef::Null) => {}
+                Some(ScalarRef::VirtualNode(id)) => update_node(ctx, id, &item.kind, value)?,
+                Some(ScalarRef::Node(node)) => update_node(ctx, node.id, &item.kind, value)?,
+                Some(ScalarRef::VirtualRel(rel)) => update_rel(ctx, &rel, &item.kind, value)?,
+                Some(ScalarRef::Rel(rel)) => {
+                    let rel = VirtualRelRef {
+                        id: rel.id,
+                        reltype: rel.reltype,
+                        start_id: rel.start_id,
+                        end_id: rel.end_id,
+                    };
+                    update_rel(ctx, &rel, &item.kind, value)?
+                }
+                Some(_) => Err(ExecError::type_mismatch(
+                    format!("set item {}", i),
+                    "node or rel",
+                    entity.physical_type(),
+                ))?,
+            }
+        }
+    }
+
+    // virtual nodes are materialized with the updates of the transaction
+    let mut out_columns = vec![];
+    for (idx, column) in chunk.columns().iter().enumerate() {
+        if columns.contains(&idx) {
+            out_columns.push(refresh_column(ctx, column)?);
+        } else {
+            out_columns.push(column.clone());
+        }
+    }
+    Ok(DataChunk::new(out_columns, chunk.visibility().clone()))
+}
+
+fn update_node(
+    ctx: &TaskExecContext,
+    node_id: NodeId,
+    kind: &SetItemKind,
+    value: Option<(&ArrayImpl, usize)>,
+) -> Result<(), ExecError> {
+    // node has been deleted
+    let Some(old) = ctx.tx().get_node(node_id)? else {
+        return Ok(());
+    };
+    let mut new = old.clone();
+    match kind {
+        SetItemKind::Property(key) => new.props = set_property(&new.props, key, value_of(value)),
+        SetItemKind::Properties { merge } => {
+            let Some(props) = properties_of(ctx, value)? else {
+                return Ok(());
+            };
+            new.props = merge_properties(if *merge { Some(&new.props) } else { None }, props);
+        }
+        SetItemKind::Labels { labels, remove: false } => {
+            for label in labels {
+                if !new.labels.contains(label) {
+                    new.labels.push(label.clone());
+                }
+            }
+        }
+        SetItemKind::Labels { labels, remove: true } => new.labels.retain(|l| !labels.contains(l)),
+    }
+
+    // Acquire read locks for all labels (to prevent concurrent CREATE CONSTRAINT)
+    let label_ids: Vec<_> = old
+        .labels
+        .iter()
+        .chain(new.labels.iter())
+        .filter_map(|l| ctx.store().token_store().get_label_id(l))
+        .collect();
+    let _locks = ctx.acquire_labels_read(&label_ids);
+    update_unique_indexes_for_node(ctx.store(), ctx.tx(), &old, &new)?;
+    check_not_null_constraints_for_node(ctx.store(), ctx.tx(), &new)?;
+    ctx.tx().node_update(&new)?;
+    Ok(())
+}
+
+fn update_rel(
+    ctx: &TaskExecContext,
+    rel: &VirtualRelRef<'_>,
+    kind: &SetItemKind,
+    value: Option<(&ArrayImpl, usize)>,
+) -> Result<(), ExecError> {
+    // relationship has been deleted
+    let Some(mut new) = ctx.tx().get_relationship(rel)? else {
+        return Ok(());
+    };
+    match kind {
+        SetItemKind::Property(key) => new.props = set_property(&new.props, key, value_of(value)),
+        SetItemKind::Properties { merge } => {
+            let Some(props) = properties_of(ctx, value)? else {
+                return Ok(());
+            };
+            new.props = merge_properties(if *merge { Some(&new.props) } else { None }, props);
+        }
+        SetItemKind::Labels { .. } => unreachable!("labels can not be set on relationships"),
+    }
+    let constraints = fetch_constraints_for_reltype(ctx.store(), ctx.tx(), &new.reltype)?;
+    check_rel_not_null_constraints(ctx.store(), &constraints, [new.as_scalar_ref()])?;
+    ctx.tx().relationship_update(&new)?;
+    Ok(())
+}
+
+// replace the property in place, a null value removes the property
+fn set_property(props: &StructValue, key: &Arc<str>, value: ScalarValue) -> StructValue {
+    let mut fields = props
+        .iter()
+        .filter(|(k, _)| *k != key)
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Vec<_>>();
+    if !matches!(value, ScalarValue::Unknown) {
+        match props.iter().position(|(k, _)| k == key) {
+            Some(pos) => fields.insert(pos, (key.clone(), value)),
+            None => fields.push((key.clone(), value)),
+        }
+    }
+    StructValue::new(fields)
+}
+
+fn merge_properties(base: Option<&StructValue>, props: StructValue) -> StructValue {
+    let mut merged = base.cloned().unwrap_or_default();
+    for (key, value) in props.iter() {
+        merged = set_property(&merged, key, value.clone());
+    }
+    merged
+}
+
+fn value_of(value: Option<(&ArrayImpl, usize)>) -> ScalarValue {
+    value
+        .and_then(|(array, idx)| array.get(idx))
+        .map(|v| v.to_owned_scalar())
+        .unwrap_or_default()
+}
+
+// properties of a map, node or relationship, None for null
+fn properties_of(ctx: &TaskExecContext, value: Option<(&ArrayImpl, usize)>) -> Result<Option<StructValue>, ExecError> {
+    let Some((array, idx)) = value else {
+        return Ok(None);
+    };
+    let to_owned = |props: elio_common::scalar::StructValueRef<'_>| {
+        StructValue::new(props.iter().map(|(k, v)| (k.clone(), v.to_owned_scalar())).collect())
+    };
+    let props = match array.get(idx) {
+        None | Some(ScalarRef::Null) => None,
+        Some(ScalarRef::Struct(props)) => Some(to_owned(props)),
+        Some(ScalarRef::Node(node)) => Some(to_owned(node.props)),
+        Some(ScalarRef::Rel(rel)) => Some(to_owned(rel.props)),
+        Some(ScalarRef::VirtualNode(id)) => ctx.tx().get_node(id)?.map(|node| node.props),
+        Some(ScalarRef::VirtualRel(rel)) => ctx.tx().get_relationship(&rel)?.map(|rel| rel.props),
+        Some(_) => Err(ExecError::type_mismatch(
+            "set properties",
+            "map, node or rel",
+            array.physical_type(),
+        ))?,
+    };
+    Ok(props)
+}
+
+// re-read the updated nodes and relationships
+pub(crate) fn refresh_column(ctx: &TaskExecContext, column: &Arc<ArrayImpl>) -> Result<Arc<ArrayImpl>, ExecError> {
+    match column.as_ref() {
+        ArrayImpl::Node(nodes) => {
+            let mut builder = NodeArrayBuilder::with_capacity(nodes.len());
+            for node in nodes.iter() {
+                match node.map(|node| ctx.tx().get_node(node.id)).transpose()?.flatten() {
+                    Some(updated) => builder.push(Some(updated.as_scalar_ref())),
+                    None => builder.push(node),
+                }
+            }
+            Ok(Arc::new(builder.finish().into()))
+        }
+        ArrayImpl::Rel(rels) => {
+            let mut builder = RelArrayBuilder::with_capacity(rels.len());
+            for rel in rels.iter() {
+                let updated = match rel {
+                    Some(rel) => ctx.tx().get_relationship(&VirtualRelRef {
+                        id: rel.id,
+                        reltype: rel.reltype,
+                        start_id: rel.start_id,
+                        end_id: rel.end_id,
+                    })?,
+                    None => None,
+                };
+                match updated {
+                    Some(updated) => builder.push(Some(updated.as_scalar_ref())),
+                    None => builder.push(rel),
+                }
+            }
+            Ok(Arc::new(builder.finish().into()))
+        }
+        _ => Ok(column.clone()),
+    }
+}
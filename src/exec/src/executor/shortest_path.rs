@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use async_stream::try_stream;
+use elio_common::array::{ArrayImpl, DataChunkBuilder, RelArrayBuilder, VirtualNodeArrayBuilder};
+use elio_common::scalar::{ListValueRef, RelValue, ScalarRef, ScalarVTable, VirtualPathRef};
+use elio_common::{NodeId, SemanticDirection, TokenId};
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::var_expand::decode_rel;
+use super::*;
+
+// nodes and relationships of a path, nodes.len() == rels.len() + 1
+type Path = (Vec<NodeId>, Vec<RelValue>);
+
+// parents of the nodes visited by BFS, the parent is the node one step closer to the BFS source
+type Parents = HashMap<NodeId, Vec<(NodeId, RelValue)>>;
+
+#[derive(Debug, Clone, Copy)]
+pub enum PathSelector {
+    // first k paths in length order, paths of the same length are picked in any order
+    Shortest(usize),
+    // all paths of the first k lengths
+    ShortestGroups(usize),
+}
+
+impl PathSelector {
+    // add a group of paths with the same length, return true on enough paths are selected
+    fn select(&self, paths: &mut Vec<Path>, groups: &mut usize, group: Vec<Path>) -> bool {
+        match *self {
+            PathSelector::Shortest(k) => {
+                let remaining = k.saturating_sub(paths.len());
+                paths.extend(group.into_iter().take(remaining));
+                paths.len() >= k
+            }
+            PathSelector::ShortestGroups(k) => {
+                paths.extend(group);
+                *groups += 1;
+                *groups >= k
+            }
+        }
+    }
+
+    // the number of paths of the next group worth finding, after `selected` paths are selected
+    fn limit(&self, selected: usize) -> usize {
+        match *self {
+            PathSelector::Shortest(k) => k.saturating_sub(selected),
+            PathSelector::ShortestGroups(_) => usize::MAX,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, PathSelector::Shortest(0) | PathSelector::ShortestGroups(0))
+    }
+}
+
+// output schema: [input, rel, path]
+#[derive(Debug)]
+pub struct ShortestPathExecutor {
+    pub input: SharedExecutor,
+    pub from: usize,
+    pub to: usize,
+    pub dir: SemanticDirection, // direction from `from` to `to`
+    // None when none of the relationship types exists, then there is no path
+    pub rel_types: Option<Arc<[TokenId]>>,
+    pub len_min: usize,
+    pub len_max: usize,
+    pub selector: PathSelector,
+    pub schema: Arc<Schema>,
+}
+
+impl Executor for ShortestPathExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let schema = self.schema.clone();
+        let from = self.from;
+        let to = self.to;
+        let Some(rel_types) = self.rel_types.clone() else {
+            return Ok(futures::stream::empty().boxed());
+        };
+        let finder = PathFinder {
+            dir: self.dir,
+            len_min: self.len_min,
+            len_max: self.len_max,
+            selector: self.selector,
+        };
+
+        let stream = try_stream! {
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+            for await chunk in input_stream {
+                let outer = chunk?;
+                let outer = outer.compact();
+                for row in outer.iter() {
+                    // if any endpoint is null, then there is no path
+                    let (Some(from_id), Some(to_id)) = (
+                        row[from].and_then(|id| id.get_node_id()),
+                        row[to].and_then(|id| id.get_node_id()),
+                    ) else {
+                        continue;
+                    };
+
+                    for (nodes, rels) in finder.find(&ctx, &rel_types, from_id, to_id)? {
+                        let mut node_array = VirtualNodeArrayBuilder::with_capacity(nodes.len());
+                        nodes.iter().for_each(|node| node_array.push(Some(*node)));
+                        let node_array: ArrayImpl = node_array.finish().into();
+                        let mut rel_array = RelArrayBuilder::with_capacity(rels.len());
+                        rels.iter().for_each(|rel| rel_array.push(Some(rel.as_scalar_ref())));
+                        let rel_array: ArrayImpl = rel_array.finish().into();
+
+                        let mut row = row.clone();
+                        row.push(Some(ScalarRef::List(ListValueRef::from_array(&rel_array, 0, rel_array.len()))));
+                        row.push(Some(ScalarRef::VirtualPath(VirtualPathRef {
+                            nodes: &node_array,
+                            node_start: 0,
+                            node_end: node_array.len(),
+                            rels: &rel_array,
+                            rel_start: 0,
+                            rel_end: rel_array.len(),
+                        })));
+                        if let Some(chunk) = out_builder.append_row(row) {
+                            yield chunk;
+                        }
+                    }
+                }
+
+                if let Some(chunk) = out_builder.yield_chunk() {
+                    yield chunk;
+                }
+            }
+        }
+        .boxed();
+
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "ShortestPath"
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PathFinder {
+    dir: SemanticDirection,
+    len_min: usize,
+    len_max: usize,
+    selector: PathSelector,
+}
+
+impl PathFinder {
+    // The shortest paths are found by bidirectional BFS. When the selector needs longer paths, or the shortest
+    // paths are shorter than the lower bound, the paths of each longer length are searched without repeated
+    // relationships, until enough paths are selected.
+    fn find(
+        &self,
+        ctx: &TaskExecContext,
+        rel_types: &[TokenId],
+        from: NodeId,
+        to: NodeId,
+    ) -> Result<Vec<Path>, ExecError> {
+        let mut paths = vec![];
+        let mut groups = 0;
+        if self.selector.is_empty() {
+            return Ok(paths);
+        }
+
+        let Some(shortest) = self.bidirectional_bfs(ctx, rel_types, from, to)? else {
+            // no path within the upper bound
+            return Ok(paths);
+        };
+        let shortest_len = shortest[0].1.len();
+        if shortest_len >= self.len_min && self.selector.select(&mut paths, &mut groups, shortest) {
+            return Ok(paths);
+        }
+
+        let distances = self.distances_to(ctx, rel_types, to)?;
+        for len in self.len_min.max(shortest_len + 1)..=self.len_max {
+            let mut group = vec![];
+            let limit = self.selector.limit(paths.len());
+            let longer = self.find_trails(ctx, rel_types, &distances, from, len, limit, &mut group)?;
+            if !group.is_empty() && self.selector.select(&mut paths, &mut groups, group) {
+                break;
+            }
+            if !longer {
+                break;
+            }
+        }
+        Ok(paths)
+    }
+
+    // Lengths of the shortest paths from the nodes to `to` within the upper bound, the nodes missing in the map
+    // can not reach `to`.
+    fn distances_to(
+        &self,
+        ctx: &TaskExecContext,
+        rel_types: &[TokenId],
+        to: NodeId,
+    ) -> Result<HashMap<NodeId, usize>, ExecError> {
+        let mut distances = HashMap::from([(to, 0)]);
+        let mut frontier = vec![to];
+        let mut len = 0;
+        while !frontier.is_empty() && len < self.len_max {
+            len += 1;
+            let mut next = vec![];
+            for node in frontier {
+                for rel_kv in ctx.tx().rel_iter_for_node(node, self.dir.rev(), rel_types)? {
+                    let other = rel_kv?.3;
+                    if let Entry::Vacant(entry) = distances.entry(other) {
+                        entry.insert(len);
+                        next.push(other);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        Ok(distances)
+    }
+
+    // Depth first search of the paths to the target, the node at distance 0, with `len` relationships and no
+    // repeated relationship, at most `limit` of them are added to `found`. A prefix is only extended if the distance
+    // to the target keeps it within `len`, so the search never leaves the nodes leading to the target. Return true
+    // if a prefix is cut by `len`, then longer paths may exist.
+    #[allow(clippy::too_many_arguments)]
+    fn find_trails(
+        &self,
+        ctx: &TaskExecContext,
+        rel_types: &[TokenId],
+        distances: &HashMap<NodeId, usize>,
+        from: NodeId,
+        len: usize,
+        limit: usize,
+        found: &mut Vec<Path>,
+    ) -> Result<bool, ExecError> {
+        let mut longer = false;
+        let (mut nodes, mut rels) = (vec![from], Vec::<RelValue>::new());
+        // iterators of the relationships of each node in the prefix
+        let mut iters = vec![ctx.tx().rel_iter_for_node(from, self.dir, rel_types)?];
+        while let Some(iter) = iters.last_mut() {
+            let Some(rel_kv) = iter.next() else {
+                iters.pop();
+                nodes.pop();
+                rels.pop();
+                continue;
+            };
+            let rel_kv = rel_kv?;
+            let Some(distance) = distances.get(&rel_kv.3) else {
+                continue;
+            };
+            if rels.len() + 1 + distance > len {
+                longer = true;
+                continue;
+            }
+            if rels.iter().any(|rel| rel.id == rel_kv.4) {
+                continue;
+            }
+            let other = rel_kv.3;
+            nodes.push(other);
+            rels.push(decode_rel(ctx, rel_kv)?);
+            if rels.len() < len {
+                iters.push(ctx.tx().rel_iter_for_node(other, self.dir, rel_types)?);
+                continue;
+            }
+            // the distance is 0, the path ends at the target, and may go on to return to it later
+            found.push((nodes.clone(), rels.clone()));
+            longer = true;
+            if found.len() >= limit {
+                break;
+            }
+            nodes.pop();
+            rels.pop();
+        }
+        Ok(longer)
+    }
+
+    // Return all the shortest paths within the upper bound, or None if there is no such path.
+    // Each round expands a whole level of the side with the smaller frontier, the searches meet at the nodes in
+    // both frontiers.
+    fn bidirectional_bfs(
+        &self,
+        ctx: &TaskExecContext,
+        rel_types: &[TokenId],
+        from: NodeId,
+        to: NodeId,
+    ) -> Result<Option<Vec<Path>>, ExecError> {
+        if from == to {
+            return Ok(Some(vec![(vec![from], vec![])]));
+        }
+
+        let mut forward = BfsSide::new(from, self.dir);
+        let mut backward = BfsSide::new(to, self.dir.rev());
+        let mut len = 0;
+        while len < self.len_max {
+            let (expand, other) = if forward.frontier.len() <= backward.frontier.len() {
+                (&mut forward, &backward)
+            } else {
+                (&mut backward, &forward)
+            };
+            expand.expand(ctx, rel_types)?;
+            len += 1;
+            if expand.frontier.is_empty() {
+                return Ok(None);
+            }
+
+            let meets = expand
+                .frontier
+                .iter()
+                .filter(|node| other.parents.contains_key(*node))
+                .copied()
+                .collect::<Vec<_>>();
+            if !meets.is_empty() {
+                let mut paths = vec![];
+                for meet in meets {
+                    let heads = unfold(&forward.parents, meet);
+                    let tails = unfold(&backward.parents, meet);
+                    for (head_nodes, head_rels) in heads.iter() {
+                        for (tail_nodes, tail_rels) in tails.iter() {
+                            let mut nodes = head_nodes.clone();
+                            nodes.extend(tail_nodes.iter().rev().skip(1));
+                            let mut rels = head_rels.clone();
+                            rels.extend(tail_rels.iter().rev().cloned());
+                            paths.push((nodes, rels));
+                        }
+                    }
+                }
+                return Ok(Some(paths));
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct BfsSide {
+    dir: SemanticDirection,
+    parents: Parents,
+    frontier: Vec<NodeId>,
+}
+
+impl BfsSide {
+    fn new(source: NodeId, dir: SemanticDirection) -> Self {
+        Self {
+            dir,
+            parents: HashMap::from([(source, vec![])]),
+            frontier: vec![source],
+        }
+    }
+
+    // expand the frontier by one level, all the parents of the same level are kept
+    fn expand(&mut self, ctx: &TaskExecContext, rel_types: &[TokenId]) -> Result<(), ExecError> {
+        let mut next: Parents = HashMap::new();
+        for node in self.frontier.iter() {
+            for rel_kv in ctx.tx().rel_iter_for_node(*node, self.dir, rel_types)? {
+                let rel_kv = rel_kv?;
+                let other = rel_kv.3;
+                if self.parents.contains_key(&other) {
+                    continue;
+                }
+                let rel = decode_rel(ctx, rel_kv)?;
+                next.entry(other).or_default().push((*node, rel));
+            }
+        }
+        // sort the frontier to find the paths in a deterministic order
+        self.frontier = next.keys().copied().sorted().collect();
+        self.parents.extend(next);
+        Ok(())
+    }
+}
+
+// all the paths from the BFS source to the node
+fn unfold(parents: &Parents, node: NodeId) -> Vec<Path> {
+    let node_parents = &parents[&node];
+    if node_parents.is_empty() {
+        return vec![(vec![node], vec![])];
+    }
+    let mut paths = vec![];
+    for (parent, rel) in node_parents {
+        for (mut nodes, mut rels) in unfold(parents, *parent) {
+            nodes.push(node);
+            rels.push(rel.clone());
+            paths.push((nodes, rels));
+        }
+    }
+    paths
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::path::PathBuf;
+
+use async_stream::try_stream;
+use elio_common::array::chunk::DataChunkBuilder;
+use elio_common::order::{SortDirection, cmp_datum_with_direction};
+use elio_common::scalar::Row;
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::*;
+use crate::spill::{SpillReader, SpillWriter, estimated_row_size};
+
+#[derive(Debug)]
+pub struct SortExecutor {
+    pub(crate) input: SharedExecutor,
+    // (column index, direction) of each sort key
+    pub(crate) column_orders: Arc<[(usize, SortDirection)]>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for SortExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let column_orders = self.column_orders.clone();
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            let config = ctx.config();
+            let mut sorter = ExternalSorter::new(column_orders, config.sort_memory_budget, config.spill_dir.clone());
+
+            for await chunk in input_stream {
+                let chunk = chunk?;
+                for row in chunk.iter() {
+                    sorter.push(row.into_iter().map(|x| x.map(|v| v.to_owned_scalar())).collect_vec())?;
+                }
+            }
+
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+            let mut sorted = sorter.finish()?;
+            while let Some(row) = sorted.next_row()? {
+                if let Some(chunk) = out_builder.append_row(row.iter().map(|x| x.as_ref().map(|v| v.as_scalar_ref())).collect_vec()) {
+                    yield chunk;
+                }
+            }
+            if let Some(chunk) = out_builder.yield_chunk() {
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Sort"
+    }
+}
+
+pub(crate) fn cmp_rows(column_orders: &[(usize, SortDirection)], lhs: &Row, rhs: &Row) -> Ordering {
+    for (idx, direction) in column_orders {
+        let ord = cmp_datum_with_direction(
+            lhs[*idx].as_ref().map(|x| x.as_scalar_ref()),
+            rhs[*idx].as_ref().map(|x| x.as_scalar_ref()),
+            *direction,
+        );
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Buffers rows in memory and sorts them. Once the buffered rows exceed the memory budget,
+/// they are sorted and written to disk as a run, the runs are k-way merged at the end.
+pub(crate) struct ExternalSorter {
+    column_orders: Arc<[(usize, SortDirection)]>,
+    memory_budget: usize,
+    spill_dir: PathBuf,
+    rows: Vec<Row>,
+    memory_used: usize,
+    runs: Vec<SpillReader>,
+}
+
+impl ExternalSorter {
+    pub(crate) fn new(column_orders: Arc<[(usize, SortDirection)]>, memory_budget: usize, spill_dir: PathBuf) -> Self {
+        Self {
+            column_orders,
+            memory_budget,
+            spill_dir,
+            rows: vec![],
+            memory_used: 0,
+            runs: vec![],
+        }
+    }
+
+    pub(crate) fn push(&mut self, row: Row) -> Result<(), ExecError> {
+        self.memory_used += estimated_row_size(&row);
+        self.rows.push(row);
+        if self.memory_used > self.memory_budget {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn sort_rows(&mut self) {
+        // stable sort, rows with equal keys keep their input order
+        self.rows.sort_by(|a, b| cmp_rows(&self.column_orders, a, b));
+    }
+
+    fn spill(&mut self) -> Result<(), ExecError> {
+        self.sort_rows();
+        let mut writer = SpillWriter::create_in(&self.spill_dir)?;
+        for row in self.rows.drain(..) {
+            writer.write_row(&row)?;
+        }
+        self.runs.push(writer.finish()?);
+        self.memory_used = 0;
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> Result<SortedRows, ExecError> {
+        if self.runs.is_empty() {
+            self.sort_rows();
+            return Ok(SortedRows::InMemory(self.rows.into_iter()));
+        }
+
+        if !self.rows.is_empty() {
+            self.spill()?;
+        }
+
+        let mut heap = BinaryHeap::with_capacity(self.runs.len());
+        for (run, reader) in self.runs.iter_mut().enumerate() {
+            if let Some(row) = reader.read_row()? {
+                heap.push(MergeEntry {
+                    row,
+                    run,
+                    column_orders: self.column_orders.clone(),
+                });
+            }
+        }
+        Ok(SortedRows::Merge { runs: self.runs, heap })
+    }
+}
+
+pub(crate) enum SortedRows {
+    InMemory(std::vec::IntoIter<Row>),
+    Merge {
+        runs: Vec<SpillReader>,
+        heap: BinaryHeap<MergeEntry>,
+    },
+}
+
+impl SortedRows {
+    pub(crate) fn next_row(&mut self) -> Result<Option<Row>, ExecError> {
+        match self {
+            SortedRows::InMemory(rows) => Ok(rows.next()),
+            SortedRows::Merge { runs, heap } => {
+                let Some(MergeEntry {
+                    row,
+                    run,
+                    column_orders,
+                }) = heap.pop()
+                else {
+                    return Ok(None);
+                };
+                if let Some(next) = runs[run].read_row()? {
+                    heap.push(MergeEntry {
+                        row: next,
+                        run,
+                        column_orders,
+                    });
+                }
+                Ok(Some(row))
+            }
+        }
+    }
+}
+
+/// Head row of a spilled run.
+pub(crate) struct MergeEntry {
+    row: Row,
+    run: usize,
+    column_orders: Arc<[(usize, SortDirection)]>,
+}
+
+impl Ord for MergeEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max heap, reverse to pop the smallest row first.
+        // Earlier runs hold earlier input rows, break ties by run to keep the sort stable.
+        cmp_rows(&self.column_orders, &self.row, &other.row)
+            .then(self.run.cmp(&other.run))
+            .reverse()
+    }
+}
+
+impl PartialOrd for MergeEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MergeEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MergeEntry {}
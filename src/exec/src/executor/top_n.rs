@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use async_stream::try_stream;
+use elio_common::array::chunk::DataChunkBuilder;
+use elio_common::order::SortDirection;
+use elio_common::scalar::Row;
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::*;
+use crate::executor::pagination::eval_pagination_arg;
+use crate::executor::sort::{ExternalSorter, SortedRows, cmp_rows};
+use crate::spill::estimated_row_size;
+use crate::task::ExecConfig;
+
+/// Sort fused with pagination, only keeps the first `offset + limit` rows in memory. Once they exceed the sort
+/// memory budget, it falls back to the external sort.
+#[derive(Debug)]
+pub struct TopNExecutor {
+    pub(crate) input: SharedExecutor,
+    // (column index, direction) of each sort key
+    pub(crate) column_orders: Arc<[(usize, SortDirection)]>,
+    pub(crate) offset: Option<SharedExpression>,
+    pub(crate) limit: SharedExpression,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for TopNExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let column_orders = self.column_orders.clone();
+        let offset = self.offset.clone();
+        let limit = self.limit.clone();
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+            let offset = eval_pagination_arg(offset.as_ref(), &eval_ctx, "SKIP")?.unwrap_or(0);
+            // SAFETY: limit is always present
+            let limit = eval_pagination_arg(Some(&limit), &eval_ctx, "LIMIT")?.unwrap();
+            let n = offset.saturating_add(limit);
+
+            // max heap holding the smallest n rows seen so far, replaced by an external sort of the remaining input once
+            // it exceeds the memory budget
+            let config = ctx.config();
+            let mut heap = BinaryHeap::new();
+            let mut memory_used = 0;
+            let mut sorter: Option<ExternalSorter> = None;
+            let mut seq = 0;
+            if limit > 0 {
+                for await chunk in input_stream {
+                    let chunk = chunk?;
+                    for row in chunk.iter() {
+                        let row = row.into_iter().map(|x| x.map(|v| v.to_owned_scalar())).collect_vec();
+                        if let Some(sorter) = sorter.as_mut() {
+                            sorter.push(row)?;
+                            continue;
+                        }
+                        let size = estimated_row_size(&row);
+                        let entry = TopNEntry {
+                            row,
+                            seq,
+                            column_orders: column_orders.clone(),
+                        };
+                        seq += 1;
+                        if heap.len() < n {
+                            memory_used += size;
+                            heap.push(entry);
+                        } else if let Some(mut top) = heap.peek_mut() && entry < *top {
+                            memory_used = memory_used + size - estimated_row_size(&top.row);
+                            *top = entry;
+                        }
+                        if memory_used > config.sort_memory_budget {
+                            sorter = Some(spill_heap(std::mem::take(&mut heap), &column_orders, config)?);
+                        }
+                    }
+                }
+            }
+
+            let mut sorted = match sorter {
+                Some(sorter) => sorter.finish()?,
+                None => SortedRows::InMemory(heap.into_sorted_vec().into_iter().map(|entry| entry.row).collect_vec().into_iter()),
+            };
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+            let mut skipped = 0;
+            let mut taken = 0;
+            while taken < limit {
+                let Some(row) = sorted.next_row()? else {
+                    break;
+                };
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                taken += 1;
+                if let Some(chunk) = out_builder.append_row(row.iter().map(|x| x.as_ref().map(|v| v.as_scalar_ref())).collect_vec()) {
+                    yield chunk;
+                }
+            }
+            if let Some(chunk) = out_builder.yield_chunk() {
+                yield chunk;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "TopN"
+    }
+}
+
+// Move the rows of the heap to an external sort in their input order, so that the sort stays stable
+fn spill_heap(
+    heap: BinaryHeap<TopNEntry>,
+    column_orders: &Arc<[(usize, SortDirection)]>,
+    config: &ExecConfig,
+) -> Result<ExternalSorter, ExecError> {
+    let mut sorter = ExternalSorter::new(
+        column_orders.clone(),
+        config.sort_memory_budget,
+        config.spill_dir.clone(),
+    );
+    for entry in heap.into_vec().into_iter().sorted_by_key(|entry| entry.seq) {
+        sorter.push(entry.row)?;
+    }
+    Ok(sorter)
+}
+
+struct TopNEntry {
+    row: Row,
+    // input position, rows with equal keys keep their input order
+    seq: usize,
+    column_orders: Arc<[(usize, SortDirection)]>,
+}
+
+impl Ord for TopNEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_rows(&self.column_orders, &self.row, &other.row).then(self.seq.cmp(&other.seq))
+    }
+}
+
+impl PartialOrd for TopNEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for TopNEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TopNEntry {}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use async_stream::try_stream;
+use elio_common::array::chunk::DataChunkBuilder;
+use elio_common::order::equivalence_key;
+use elio_common::scalar::Row;
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::*;
+
+/// Outputs the rows of all inputs one after another. With `distinct`, rows are deduplicated
+/// by hashing their equivalence keys, otherwise chunks are passed through as long as the column types match.
+#[derive(Debug)]
+pub struct UnionExecutor {
+    pub(crate) inputs: Vec<SharedExecutor>,
+    pub(crate) distinct: bool,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for UnionExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_streams = self
+            .inputs
+            .iter()
+            .map(|input| input.open(ctx.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        // inputs whose columns differ from the output types are rebuilt row by row
+        let rebuild = self
+            .inputs
+            .iter()
+            .map(|input| {
+                self.distinct
+                    || !input
+                        .schema()
+                        .columns()
+                        .iter()
+                        .map(|col| col.typ.physical_type())
+                        .eq(self.schema.columns().iter().map(|col| col.typ.physical_type()))
+            })
+            .collect_vec();
+        let distinct = self.distinct;
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            let mut seen: HashSet<Row> = HashSet::new();
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+
+            for (input_stream, rebuild) in input_streams.into_iter().zip_eq(rebuild) {
+                for await chunk in input_stream {
+                    let chunk = chunk?;
+                    if !rebuild {
+                        yield chunk;
+                        continue;
+                    }
+                    for row in chunk.iter() {
+                        if distinct && !seen.insert(row.iter().map(|x| x.map(equivalence_key)).collect_vec()) {
+                            continue;
+                        }
+                        if let Some(out) = out_builder.append_row(row) {
+                            yield out;
+                        }
+                    }
+                }
+            }
+            if let Some(out) = out_builder.yield_chunk() {
+                yield out;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Union"
+    }
+}
@@ -0,0 +1,63 @@
+use async_stream::try_stream;
+use elio_common::array::chunk::DataChunkBuilder;
+use elio_common::scalar::ScalarRef;
+use elio_expr::impl_::SharedExpression;
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::*;
+
+/// Repeats each input row once for every element of the evaluated list, the element is appended
+/// as the last column. A null or empty list produces no row, a non-list value produces one row.
+#[derive(Debug)]
+pub struct UnwindExecutor {
+    pub(crate) input: SharedExecutor,
+    pub(crate) expr: SharedExpression,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl Executor for UnwindExecutor {
+    fn open(&self, ctx: Arc<TaskExecContext>) -> Result<DataChunkStream, ExecError> {
+        let input_stream = self.input.open(ctx.clone())?;
+        let expr = self.expr.clone();
+        let schema = self.schema.clone();
+
+        let stream = try_stream! {
+            let eval_ctx = ctx.derive_eval_ctx();
+            let mut out_builder = DataChunkBuilder::new(schema.columns().iter().map(|col| col.typ.physical_type()), CHUNK_SIZE);
+
+            for await chunk in input_stream {
+                let chunk = chunk?;
+                let lists = expr.eval_batch(&chunk, &eval_ctx)?;
+
+                for row_idx in chunk.visibility().iter_ones() {
+                    let elems = match lists.get(row_idx) {
+                        None | Some(ScalarRef::Null) => vec![],
+                        Some(ScalarRef::List(list)) => list.iter().collect_vec(),
+                        Some(value) => vec![value],
+                    };
+                    for elem in elems {
+                        let mut row = chunk.columns().iter().map(|col| col.get(row_idx)).collect_vec();
+                        row.push(Some(elem).filter(|v| !matches!(v, ScalarRef::Null)));
+                        if let Some(out) = out_builder.append_row(row) {
+                            yield out;
+                        }
+                    }
+                }
+            }
+            if let Some(out) = out_builder.yield_chunk() {
+                yield out;
+            }
+        }
+        .boxed();
+        Ok(stream)
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn name(&self) -> &'static str {
+        "Unwind"
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::LazyLock;
 
 use async_stream::try_stream;
@@ -6,7 +6,7 @@ use educe::Educe;
 use elio_common::array::{ArrayImpl, DataChunkBuilder, RelArrayBuilder};
 use elio_common::scalar::{ListValueRef, RelValue, ScalarRef, ScalarVTable, StructValue};
 use elio_common::store_types::RelDirection;
-use elio_common::{NodeId, SemanticDirection, TokenId, TokenKind};
+use elio_common::{NodeId, RelationshipId, SemanticDirection, TokenId, TokenKind};
 use elio_expr::impl_::SharedExpression;
 use elio_storage::codec::RelFormat;
 use futures::StreamExt;
@@ -21,6 +21,8 @@ pub struct VarExpandExecutor<PATHMODE: PathContainer, EXPANDKIND: ExpandKindStra
     pub input: SharedExecutor,
     pub from: usize,
     pub dir: SemanticDirection, // expansion direction
+    // expanded from the right endpoint, the rels are reversed to follow the pattern order
+    pub reversed: bool,
     pub rel_types: Arc<[TokenId]>,
     pub len_min: usize,
     pub len_max: usize,
@@ -39,6 +41,7 @@ impl<PATHMODE: PathContainer, EXPANDKIND: ExpandKindStrategy> Executor for VarEx
         let schema = self.schema.clone();
         let from = self.from;
         let dir = self.dir;
+        let reversed = self.reversed;
         let rel_types = self.rel_types.clone();
         let len_min = self.len_min;
         let len_max = self.len_max;
@@ -74,7 +77,11 @@ impl<PATHMODE: PathContainer, EXPANDKIND: ExpandKindStrategy> Executor for VarEx
                         if path.len() >= len_min && expand_kind_filter.is_valid(&row, to_node) {
                             // push path rels list
                             let mut rel_array = RelArrayBuilder::with_capacity(path.len());
-                            path.iter().for_each(|rel|
+                            let mut rels = path.iter().collect::<Vec<_>>();
+                            if reversed {
+                                rels.reverse();
+                            }
+                            rels.iter().for_each(|rel|
                                 rel_array.push(Some(rel.as_scalar_ref()))
                             );
                             let rel_array: ArrayImpl= rel_array.finish().into();
@@ -137,48 +144,10 @@ impl<PATHMODE: PathContainer> Iterator for VarExpandIter<PATHMODE> {
                     Ok(kv) => kv,
                     Err(e) => return Some(Err(e.into())),
                 };
-                let (from_id, rel_dir, token_id, to_id, rel_id, value) = rel_kv;
-                // TODO(pgao): avoid get token value for each rel
-                // maybe we can cache all the token value on the execution context
-                let rel_type = match self.ctx.catalog().get_token_val(token_id, TokenKind::RelationshipType) {
-                    Ok(rel_type) => rel_type,
-                    Err(e) => return Some(Err(e.into())),
-                };
-
-                // TODO(pgao): we can know the start id and end id at planning time
-                let (start_id, end_id) = match rel_dir {
-                    RelDirection::Out => (from_id, to_id),
-                    RelDirection::In => (to_id, from_id),
-                };
-
-                // TODO(pgao): lazy deserialize
-                let prop_map = RelFormat::decode_value(&value);
-                // TODO(pgao): avoid clone
-                let struct_value = {
-                    let mut fileds = vec![];
-                    for entry in prop_map.iter() {
-                        let key = match self
-                            .ctx
-                            .store()
-                            .token_store()
-                            .get_token_val(entry.key(), TokenKind::PropertyKey)
-                        {
-                            Ok(key) => key,
-                            Err(e) => return Some(Err(e.into())),
-                        };
-                        // TODO(pgao): avoid clone
-                        fileds.push((key, entry.value().to_owned_scalar()));
-                    }
-                    StructValue::new(fileds)
-                };
-
-                // TODO(pgao): expand into and filter etc
-                let rel_value = RelValue {
-                    id: rel_id,
-                    reltype: rel_type,
-                    start_id,
-                    end_id,
-                    props: struct_value,
+                let to_id = rel_kv.3;
+                let rel_value = match decode_rel(&self.ctx, rel_kv) {
+                    Ok(rel_value) => rel_value,
+                    Err(e) => return Some(Err(e)),
                 };
 
                 if expanded_path.can_add_rel(&rel_value) {
@@ -192,12 +161,52 @@ impl<PATHMODE: PathContainer> Iterator for VarExpandIter<PATHMODE> {
     }
 }
 
+pub type RelKeyValue = (NodeId, RelDirection, TokenId, NodeId, RelationshipId, Box<[u8]>);
+
+// decode the relationship returned by `rel_iter_for_node`
+pub(crate) fn decode_rel(
+    ctx: &TaskExecContext,
+    (from_id, rel_dir, token_id, to_id, rel_id, value): RelKeyValue,
+) -> Result<RelValue, ExecError> {
+    // TODO(pgao): avoid get token value for each rel
+    // maybe we can cache all the token value on the execution context
+    let rel_type = ctx.catalog().get_token_val(token_id, TokenKind::RelationshipType)?;
+
+    // TODO(pgao): we can know the start id and end id at planning time
+    let (start_id, end_id) = match rel_dir {
+        RelDirection::Out => (from_id, to_id),
+        RelDirection::In => (to_id, from_id),
+    };
+
+    // TODO(pgao): lazy deserialize
+    let prop_map = RelFormat::decode_value(&value);
+    // TODO(pgao): avoid clone
+    let mut fileds = vec![];
+    for entry in prop_map.iter() {
+        let key = ctx
+            .store()
+            .token_store()
+            .get_token_val(entry.key(), TokenKind::PropertyKey)?;
+        fileds.push((key, entry.value().to_owned_scalar()));
+    }
+
+    Ok(RelValue {
+        id: rel_id,
+        reltype: rel_type,
+        start_id,
+        end_id,
+        props: StructValue::new(fileds),
+    })
+}
+
 pub type PathContainerFactory<P> = Box<dyn Fn() -> P + Sync + Send>;
 
 pub static TRAIL_PATH_MODE_FACTORY: LazyLock<PathContainerFactory<TrailPathContainer>> =
     LazyLock::new(|| Box::new(TrailPathContainer::default));
 pub static WALK_PATH_MODE_FACTORY: LazyLock<PathContainerFactory<WalkPathContainer>> =
     LazyLock::new(|| Box::new(WalkPathContainer::default));
+pub static ACYCLIC_PATH_MODE_FACTORY: LazyLock<PathContainerFactory<AcyclicPathContainer>> =
+    LazyLock::new(|| Box::new(AcyclicPathContainer::default));
 
 #[allow(clippy::len_without_is_empty)]
 pub trait PathContainer: 'static + Sync + Send + Clone {
@@ -290,3 +299,35 @@ impl PathContainer for TrailPathContainer {
         self.path.len()
     }
 }
+
+#[derive(Default, Clone)]
+pub struct AcyclicPathContainer {
+    pub(crate) path: Vec<RelValue>,
+    // the nodes on the path
+    pub(crate) nodes: HashSet<NodeId>,
+}
+
+impl PathContainer for AcyclicPathContainer {
+    // the rel starts at the last node of the path, it reaches a new node unless both of its endpoints are visited
+    fn can_add_rel(&self, step: &RelValue) -> bool {
+        if self.nodes.is_empty() {
+            step.start_id != step.end_id
+        } else {
+            !(self.nodes.contains(&step.start_id) && self.nodes.contains(&step.end_id))
+        }
+    }
+
+    fn add_rel(&mut self, step: RelValue) {
+        self.nodes.insert(step.start_id);
+        self.nodes.insert(step.end_id);
+        self.path.push(step);
+    }
+
+    fn into_list(self) -> Vec<RelValue> {
+        self.path
+    }
+
+    fn len(&self) -> usize {
+        self.path.len()
+    }
+}
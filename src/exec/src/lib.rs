@@ -3,4 +3,5 @@
 pub mod builder;
 pub mod error;
 pub mod executor;
+pub mod spill;
 pub mod task;
@@ -0,0 +1,405 @@
+//! Spill rows to local files when an operator runs out of its memory budget.
+//!
+//! Rows are written as length prefixed records, each datum is encoded with a leading tag byte
+//! followed by a little endian payload. The encoding is private to a single query, it is never
+//! persisted across processes.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use elio_common::array::{ArrayImpl, PhysicalType};
+use elio_common::data_type::F64;
+use elio_common::scalar::*;
+use elio_common::{NodeId, RelationshipId};
+
+use crate::error::ExecError;
+
+const NULL_TAG: u8 = 0;
+const BOOL_TAG: u8 = 1;
+const INTEGER_TAG: u8 = 2;
+const FLOAT_TAG: u8 = 3;
+const DATE_TAG: u8 = 4;
+const LOCAL_TIME_TAG: u8 = 5;
+const LOCAL_DATE_TIME_TAG: u8 = 6;
+const ZONED_DATE_TIME_TAG: u8 = 7;
+const DURATION_TAG: u8 = 8;
+const STRING_TAG: u8 = 9;
+const VIRTUAL_NODE_TAG: u8 = 10;
+const VIRTUAL_REL_TAG: u8 = 11;
+const VIRTUAL_PATH_TAG: u8 = 12;
+const NODE_TAG: u8 = 13;
+const REL_TAG: u8 = 14;
+const PATH_TAG: u8 = 15;
+const LIST_TAG: u8 = 16;
+const STRUCT_TAG: u8 = 17;
+
+/// Rough in-memory footprint of a row, used to decide when to spill.
+pub fn estimated_row_size(row: &Row) -> usize {
+    row.iter()
+        .map(|datum| {
+            std::mem::size_of::<Datum>() + datum.as_ref().map_or(0, |x| estimated_heap_size(x.as_scalar_ref()))
+        })
+        .sum()
+}
+
+fn estimated_heap_size(value: ScalarRef<'_>) -> usize {
+    const ENTRY_SIZE: usize = 64;
+    match value {
+        ScalarRef::String(s) => s.len(),
+        ScalarRef::VirtualRel(rel) => rel.reltype.len(),
+        ScalarRef::VirtualPath(path) => (path.node_end - path.node_start + path.rel_end - path.rel_start) * ENTRY_SIZE,
+        ScalarRef::Node(node) => {
+            node.labels.iter().map(|l| l.len()).sum::<usize>() + estimated_heap_size(ScalarRef::Struct(node.props))
+        }
+        ScalarRef::Rel(rel) => rel.reltype.len() + estimated_heap_size(ScalarRef::Struct(rel.props)),
+        ScalarRef::Path(path) => (path.node_end - path.node_start + path.rel_end - path.rel_start) * ENTRY_SIZE * 4,
+        ScalarRef::List(list) => list
+            .iter()
+            .map(|item| std::mem::size_of::<ScalarValue>() + estimated_heap_size(item))
+            .sum(),
+        ScalarRef::Struct(s) => s
+            .iter()
+            .map(|(k, v)| k.len() + std::mem::size_of::<ScalarValue>() + estimated_heap_size(v))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// An anonymous spill file, removed by the OS once dropped.
+///
+/// Rows are appended with [`SpillWriter`], then read back in the same order with [`SpillReader`].
+pub struct SpillWriter {
+    writer: BufWriter<File>,
+    buf: Vec<u8>,
+}
+
+impl SpillWriter {
+    pub fn create_in(dir: &Path) -> Result<Self, ExecError> {
+        let file = tempfile::tempfile_in(dir).map_err(ExecError::io_error)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            buf: vec![],
+        })
+    }
+
+    pub fn write_row(&mut self, row: &Row) -> Result<(), ExecError> {
+        self.buf.clear();
+        for datum in row {
+            encode_value(&mut self.buf, datum.as_ref().map(|x| x.as_scalar_ref()));
+        }
+        self.writer
+            .write_all(&(self.buf.len() as u32).to_le_bytes())
+            .and_then(|_| self.writer.write_all(&self.buf))
+            .map_err(ExecError::io_error)
+    }
+
+    /// Flush buffered rows and rewind the file for reading.
+    pub fn finish(self) -> Result<SpillReader, ExecError> {
+        let mut file = self.writer.into_inner().map_err(ExecError::io_error)?;
+        file.seek(SeekFrom::Start(0)).map_err(ExecError::io_error)?;
+        Ok(SpillReader {
+            reader: BufReader::new(file),
+            buf: vec![],
+        })
+    }
+}
+
+pub struct SpillReader {
+    reader: BufReader<File>,
+    buf: Vec<u8>,
+}
+
+impl SpillReader {
+    pub fn read_row(&mut self) -> Result<Option<Row>, ExecError> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(ExecError::io_error(e)),
+        }
+        self.buf.resize(u32::from_le_bytes(len) as usize, 0);
+        self.reader.read_exact(&mut self.buf).map_err(ExecError::io_error)?;
+
+        let mut decoder = Decoder { buf: &self.buf };
+        let mut row = vec![];
+        while !decoder.buf.is_empty() {
+            let value = decoder.value()?;
+            row.push((value != ScalarValue::Unknown).then_some(value));
+        }
+        Ok(Some(row))
+    }
+}
+
+fn encode_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn encode_struct(buf: &mut Vec<u8>, value: StructValueRef<'_>) {
+    buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    for (k, v) in value.iter() {
+        encode_str(buf, k);
+        encode_value(buf, Some(v));
+    }
+}
+
+fn encode_node(buf: &mut Vec<u8>, node: NodeValueRef<'_>) {
+    buf.extend_from_slice(&node.id.0.to_le_bytes());
+    buf.extend_from_slice(&(node.labels.len() as u32).to_le_bytes());
+    for label in node.labels {
+        encode_str(buf, label);
+    }
+    encode_struct(buf, node.props);
+}
+
+fn encode_rel(buf: &mut Vec<u8>, rel: RelValueRef<'_>) {
+    buf.extend_from_slice(&rel.id.0.to_le_bytes());
+    encode_str(buf, rel.reltype);
+    buf.extend_from_slice(&rel.start_id.0.to_le_bytes());
+    buf.extend_from_slice(&rel.end_id.0.to_le_bytes());
+    encode_struct(buf, rel.props);
+}
+
+fn encode_value(buf: &mut Vec<u8>, value: Option<ScalarRef<'_>>) {
+    let Some(value) = value else {
+        buf.push(NULL_TAG);
+        return;
+    };
+    match value {
+        ScalarRef::Null => buf.push(NULL_TAG),
+        ScalarRef::Bool(b) => {
+            buf.push(BOOL_TAG);
+            buf.push(b as u8);
+        }
+        ScalarRef::Integer(i) => {
+            buf.push(INTEGER_TAG);
+            buf.extend_from_slice(&i.to_le_bytes());
+        }
+        ScalarRef::Float(f) => {
+            buf.push(FLOAT_TAG);
+            buf.extend_from_slice(&f.0.to_le_bytes());
+        }
+        ScalarRef::Date(d) => {
+            buf.push(DATE_TAG);
+            buf.extend_from_slice(&d.to_le_bytes());
+        }
+        ScalarRef::LocalTime(t) => {
+            buf.push(LOCAL_TIME_TAG);
+            buf.extend_from_slice(&t.to_le_bytes());
+        }
+        ScalarRef::LocalDateTime(dt) => {
+            buf.push(LOCAL_DATE_TIME_TAG);
+            buf.extend_from_slice(&dt.to_le_bytes());
+        }
+        ScalarRef::ZonedDateTime(zdt) => {
+            buf.push(ZONED_DATE_TIME_TAG);
+            buf.extend_from_slice(&zdt.to_le_bytes());
+        }
+        ScalarRef::Duration(d) => {
+            buf.push(DURATION_TAG);
+            for part in [d.months, d.days, d.seconds, d.nanoseconds] {
+                buf.extend_from_slice(&part.to_le_bytes());
+            }
+        }
+        ScalarRef::String(s) => {
+            buf.push(STRING_TAG);
+            encode_str(buf, s);
+        }
+        ScalarRef::VirtualNode(id) => {
+            buf.push(VIRTUAL_NODE_TAG);
+            buf.extend_from_slice(&id.0.to_le_bytes());
+        }
+        ScalarRef::VirtualRel(rel) => {
+            buf.push(VIRTUAL_REL_TAG);
+            buf.extend_from_slice(&rel.id.0.to_le_bytes());
+            encode_str(buf, rel.reltype);
+            buf.extend_from_slice(&rel.start_id.0.to_le_bytes());
+            buf.extend_from_slice(&rel.end_id.0.to_le_bytes());
+        }
+        ScalarRef::VirtualPath(path) => {
+            buf.push(VIRTUAL_PATH_TAG);
+            buf.extend_from_slice(&(path.node_end - path.node_start).to_le_bytes());
+            for node in path.node_iter() {
+                // SAFETY: path elements are never null
+                buf.extend_from_slice(&node.unwrap().0.to_le_bytes());
+            }
+            buf.extend_from_slice(&(path.rel_end - path.rel_start).to_le_bytes());
+            for rel in path.rel_iter() {
+                encode_rel(buf, rel.unwrap());
+            }
+        }
+        ScalarRef::Node(node) => {
+            buf.push(NODE_TAG);
+            encode_node(buf, node);
+        }
+        ScalarRef::Rel(rel) => {
+            buf.push(REL_TAG);
+            encode_rel(buf, rel);
+        }
+        ScalarRef::Path(path) => {
+            buf.push(PATH_TAG);
+            buf.extend_from_slice(&(path.node_end - path.node_start).to_le_bytes());
+            for node in path.node_iter() {
+                encode_node(buf, node.unwrap());
+            }
+            buf.extend_from_slice(&(path.rel_end - path.rel_start).to_le_bytes());
+            for rel in path.rel_iter() {
+                encode_rel(buf, rel.unwrap());
+            }
+        }
+        ScalarRef::List(list) => {
+            buf.push(LIST_TAG);
+            buf.extend_from_slice(&(list.len() as u32).to_le_bytes());
+            for item in list.iter() {
+                encode_value(buf, Some(item));
+            }
+        }
+        ScalarRef::Struct(s) => {
+            buf.push(STRUCT_TAG);
+            encode_struct(buf, s);
+        }
+    }
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ExecError> {
+        if self.buf.len() < N {
+            return Err(ExecError::io_error("truncated spill record"));
+        }
+        let (head, tail) = self.buf.split_at(N);
+        self.buf = tail;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<usize, ExecError> {
+        Ok(u32::from_le_bytes(self.bytes()?) as usize)
+    }
+
+    fn usize(&mut self) -> Result<usize, ExecError> {
+        Ok(usize::from_le_bytes(self.bytes()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ExecError> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, ExecError> {
+        Ok(i64::from_le_bytes(self.bytes()?))
+    }
+
+    fn str(&mut self) -> Result<&'a str, ExecError> {
+        let len = self.u32()?;
+        if self.buf.len() < len {
+            return Err(ExecError::io_error("truncated spill record"));
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        std::str::from_utf8(head).map_err(ExecError::io_error)
+    }
+
+    fn struct_(&mut self) -> Result<StructValue, ExecError> {
+        let len = self.u32()?;
+        let mut fields = Vec::with_capacity(len);
+        for _ in 0..len {
+            let name: Arc<str> = self.str()?.into();
+            fields.push((name, self.value()?));
+        }
+        Ok(StructValue::new(fields))
+    }
+
+    fn node(&mut self) -> Result<NodeValue, ExecError> {
+        let id = NodeId(self.u64()?);
+        let len = self.u32()?;
+        let labels = (0..len).map(|_| self.str().map(Arc::from)).collect::<Result<_, _>>()?;
+        let props = self.struct_()?;
+        Ok(NodeValue { id, labels, props })
+    }
+
+    fn rel(&mut self) -> Result<RelValue, ExecError> {
+        Ok(RelValue {
+            id: RelationshipId(self.u64()?),
+            reltype: self.str()?.into(),
+            start_id: NodeId(self.u64()?),
+            end_id: NodeId(self.u64()?),
+            props: self.struct_()?,
+        })
+    }
+
+    fn rel_array(&mut self) -> Result<ArrayImpl, ExecError> {
+        let len = self.usize()?;
+        let rels = (0..len).map(|_| self.rel()).collect::<Result<Vec<_>, _>>()?;
+        let mut builder = PhysicalType::Rel.array_builder(len);
+        rels.iter()
+            .for_each(|rel| builder.push(Some(ScalarRef::Rel(rel.as_scalar_ref()))));
+        Ok(builder.finish())
+    }
+
+    fn value(&mut self) -> Result<ScalarValue, ExecError> {
+        let [tag] = self.bytes()?;
+        let value = match tag {
+            NULL_TAG => ScalarValue::Unknown,
+            BOOL_TAG => ScalarValue::Bool(self.bytes::<1>()?[0] != 0),
+            INTEGER_TAG => ScalarValue::Integer(self.i64()?),
+            FLOAT_TAG => ScalarValue::Float(F64::from(f64::from_le_bytes(self.bytes()?))),
+            DATE_TAG => ScalarValue::Date(Date::from_le_bytes(self.bytes()?)),
+            LOCAL_TIME_TAG => ScalarValue::LocalTime(LocalTime::from_le_bytes(self.bytes()?)),
+            LOCAL_DATE_TIME_TAG => ScalarValue::LocalDateTime(LocalDateTime::from_le_bytes(self.bytes()?)),
+            ZONED_DATE_TIME_TAG => ScalarValue::ZonedDateTime(ZonedDateTime::from_le_bytes(self.bytes()?)),
+            DURATION_TAG => ScalarValue::Duration(Duration {
+                months: self.i64()?,
+                days: self.i64()?,
+                seconds: self.i64()?,
+                nanoseconds: self.i64()?,
+            }),
+            STRING_TAG => ScalarValue::String(self.str()?.to_string()),
+            VIRTUAL_NODE_TAG => ScalarValue::VirtualNode(NodeId(self.u64()?)),
+            VIRTUAL_REL_TAG => ScalarValue::VirtualRel(VirtualRel {
+                id: RelationshipId(self.u64()?),
+                reltype: self.str()?.into(),
+                start_id: NodeId(self.u64()?),
+                end_id: NodeId(self.u64()?),
+            }),
+            VIRTUAL_PATH_TAG => {
+                let len = self.usize()?;
+                let mut nodes = PhysicalType::VirtualNode.array_builder(len);
+                for _ in 0..len {
+                    nodes.push(Some(ScalarRef::VirtualNode(NodeId(self.u64()?))));
+                }
+                let rels = self.rel_array()?;
+                ScalarValue::VirtualPath(VirtualPath {
+                    nodes: Arc::new(nodes.finish()),
+                    rels: Arc::new(rels),
+                })
+            }
+            NODE_TAG => ScalarValue::Node(Box::new(self.node()?)),
+            REL_TAG => ScalarValue::Rel(Box::new(self.rel()?)),
+            PATH_TAG => {
+                let len = self.usize()?;
+                let node_values = (0..len).map(|_| self.node()).collect::<Result<Vec<_>, _>>()?;
+                let mut nodes = PhysicalType::Node.array_builder(len);
+                node_values
+                    .iter()
+                    .for_each(|node| nodes.push(Some(ScalarRef::Node(node.as_scalar_ref()))));
+                let rels = self.rel_array()?;
+                ScalarValue::Path(Box::new(PathValue {
+                    nodes: Arc::new(nodes.finish()),
+                    rels: Arc::new(rels),
+                }))
+            }
+            LIST_TAG => {
+                let len = self.u32()?;
+                let values = (0..len).map(|_| self.value()).collect::<Result<Vec<_>, _>>()?;
+                ScalarValue::List(Box::new(ListValue::new(values)))
+            }
+            STRUCT_TAG => ScalarValue::Struct(Box::new(self.struct_()?)),
+            tag => return Err(ExecError::io_error(format!("unknown spill value tag {tag}"))),
+        };
+        Ok(value)
+    }
+}
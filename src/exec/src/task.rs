@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use bitvec::vec::BitVec;
 use educe::Educe;
@@ -6,20 +9,41 @@ use elio_catalog::Catalog;
 use elio_common::array::chunk::DataChunk;
 use elio_common::array::{NodeArray, VirtualNodeArray};
 use elio_common::schema::Schema;
-use elio_common::{TokenId, TokenKind};
+use elio_common::{LabelId, RelationshipTypeId, TokenId, TokenKind};
+use elio_cypher::plan_node::{PlanExpr, PlanNode};
 use elio_cypher::planner::RootPlan;
 use elio_expr::error::EvalError;
 use elio_expr::impl_::EvalCtx;
-use elio_storage::graph::GraphStore;
+use elio_storage::error::GraphStoreError;
+use elio_storage::graph::{GraphStore, LabelReadGuard, LabelWriteGuard};
 use elio_storage::transaction::TransactionImpl;
 use futures::StreamExt;
 use itertools::Itertools;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
 
 use crate::builder::{ExecutorBuildContext, build_executor};
 use crate::error::ExecError;
 use crate::executor::SharedExecutor;
 
+/// Execution tunables shared by all tasks
+#[derive(Debug, Clone)]
+pub struct ExecConfig {
+    /// memory budget in bytes of a single sort, input beyond the budget is spilled to disk
+    pub sort_memory_budget: usize,
+    /// directory to hold spill files
+    pub spill_dir: PathBuf,
+}
+
+impl Default for ExecConfig {
+    fn default() -> Self {
+        Self {
+            sort_memory_budget: 64 * 1024 * 1024,
+            spill_dir: std::env::temp_dir(),
+        }
+    }
+}
+
 // global execution context
 #[derive(Educe)]
 #[educe(Debug)]
@@ -28,11 +52,12 @@ pub struct ExecContext {
     // global resources here
     #[educe(Debug(ignore))]
     store: Arc<GraphStore>,
+    config: ExecConfig,
 }
 
 impl ExecContext {
-    pub fn new(catalog: Arc<Catalog>, store: Arc<GraphStore>) -> Self {
-        Self { catalog, store }
+    pub fn new(catalog: Arc<Catalog>, store: Arc<GraphStore>, config: ExecConfig) -> Self {
+        Self { catalog, store, config }
     }
 }
 
@@ -44,6 +69,10 @@ impl ExecContext {
     pub fn store(&self) -> &Arc<GraphStore> {
         &self.store
     }
+
+    pub fn config(&self) -> &ExecConfig {
+        &self.config
+    }
 }
 
 pub struct EvalCtxImpl {
@@ -71,9 +100,25 @@ pub struct TaskExecContext {
     // task specific context here
     // TODO(pgao): maybe we should transaction also into catalog api?
     tx: Arc<TransactionImpl>,
+    // labels locked exclusively by MERGE until the transaction ends
+    locked_labels: HashSet<LabelId>,
+    // labels written under a read lock by the tasks of the transaction, see [`commit_checked`]
+    written_labels: Arc<Mutex<HashSet<LabelId>>>,
 }
 
 impl TaskExecContext {
+    /// A context running in a transaction of its own, without any locks
+    #[cfg(test)]
+    pub(crate) fn standalone(exec_ctx: Arc<ExecContext>) -> Self {
+        let tx = exec_ctx.store().transaction();
+        Self {
+            exec_ctx,
+            tx,
+            locked_labels: HashSet::new(),
+            written_labels: Default::default(),
+        }
+    }
+
     pub fn catalog(&self) -> &Arc<Catalog> {
         self.exec_ctx.catalog()
     }
@@ -86,6 +131,22 @@ impl TaskExecContext {
         &self.tx
     }
 
+    pub fn config(&self) -> &ExecConfig {
+        self.exec_ctx.config()
+    }
+
+    /// Acquire read locks for the labels, labels already locked exclusively by this task are skipped. The labels are
+    /// checked again at commit, as the locks are released before it.
+    pub fn acquire_labels_read(&self, label_ids: &[LabelId]) -> Vec<LabelReadGuard> {
+        let label_ids = label_ids
+            .iter()
+            .filter(|id| !self.locked_labels.contains(id))
+            .copied()
+            .collect_vec();
+        self.written_labels.lock().unwrap().extend(label_ids.iter().copied());
+        self.store().acquire_labels_read(&label_ids)
+    }
+
     pub fn derive_eval_ctx(&self) -> EvalCtxImpl {
         EvalCtxImpl {
             catalog: self.exec_ctx.catalog().clone(),
@@ -94,6 +155,112 @@ impl TaskExecContext {
     }
 }
 
+/// A transaction spanning the tasks of several statements, it ends when the session commits or rolls it back.
+/// Every task sees the writes of the tasks before it.
+#[derive(Educe)]
+#[educe(Debug)]
+pub struct ExplicitTransaction {
+    #[educe(Debug(ignore))]
+    store: Arc<GraphStore>,
+    #[educe(Debug(ignore))]
+    tx: Arc<TransactionImpl>,
+    // locked exclusively by MERGE of the statements so far, released when the transaction ends
+    #[educe(Debug(ignore))]
+    merge_locks: Mutex<MergeLocks>,
+    // labels written by the statements so far, checked at commit
+    #[educe(Debug(ignore))]
+    written_labels: Arc<Mutex<HashSet<LabelId>>>,
+    // running tasks hold a read guard, so that the transaction ends after them
+    #[educe(Debug(ignore))]
+    running: Arc<RwLock<()>>,
+    // a statement failed with its writes partially applied, the transaction can only be rolled back
+    failed: AtomicBool,
+}
+
+impl ExplicitTransaction {
+    pub fn begin(ectx: &ExecContext) -> Self {
+        Self {
+            store: ectx.store.clone(),
+            tx: ectx.store.transaction(),
+            merge_locks: Default::default(),
+            written_labels: Default::default(),
+            running: Default::default(),
+            failed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.failed.load(Ordering::Acquire)
+    }
+
+    /// Commit the writes of all the statements, a failed transaction is rolled back instead
+    pub async fn commit(&self) -> Result<(), ExecError> {
+        let _running = self.running.write().await;
+        let result = if self.is_failed() {
+            self.tx.abort()?;
+            Err(ExecError::transaction_failed())
+        } else {
+            // the labels locked exclusively are kept from DDL already
+            let written_labels = {
+                let locks = self.merge_locks.lock().unwrap();
+                let written_labels = self.written_labels.lock().unwrap();
+                written_labels.difference(&locks.labels).copied().collect_vec()
+            };
+            commit_checked(&self.store, &self.tx, &written_labels).map_err(Into::into)
+        };
+        if result.is_ok() {
+            record_merge_commit(&self.store, &mut self.merge_locks.lock().unwrap().guards);
+        }
+        self.release_locks();
+        result
+    }
+
+    pub async fn rollback(&self) -> Result<(), ExecError> {
+        let _running = self.running.write().await;
+        self.tx.abort()?;
+        self.release_locks();
+        Ok(())
+    }
+
+    // discard the writes of all the statements, later statements are rejected
+    fn fail(&self) {
+        self.failed.store(true, Ordering::Release);
+        let _ = self.tx.abort();
+    }
+
+    fn release_locks(&self) {
+        *self.merge_locks.lock().unwrap() = MergeLocks::default();
+    }
+}
+
+// Commit unless a DDL changed the constraints or indexes of the written labels after the transaction started, since
+// the writes were only checked against the ones of its snapshot. The read locks keep DDL out until the commit is done.
+fn commit_checked(store: &GraphStore, tx: &TransactionImpl, written_labels: &[LabelId]) -> Result<(), GraphStoreError> {
+    let guards = store.acquire_labels_read(written_labels);
+    if guards.iter().any(|guard| guard.schema_seq() > tx.start_seq()) {
+        tx.abort()?;
+        return Err(GraphStoreError::WriteConflict(
+            "constraints or indexes changed on labels written after the transaction started".to_string(),
+        ));
+    }
+    tx.commit()
+}
+
+// record the commit on the MERGE locks, so that the transactions started before it do not merge on them
+fn record_merge_commit(store: &GraphStore, guards: &mut [LabelWriteGuard]) {
+    // the latest commit may be later than the own one, which only fails more transactions
+    let seq = store.last_commit_seq();
+    guards.iter_mut().for_each(|guard| guard.set_last_commit_seq(seq));
+}
+
+/// Labels and relationship types locked exclusively by MERGE, with the guards holding the locks
+#[derive(Default)]
+struct MergeLocks {
+    labels: HashSet<LabelId>,
+    reltypes: HashSet<RelationshipTypeId>,
+    guards: Vec<LabelWriteGuard>,
+}
+
 // TODO(pgao): task manager
 
 /// receiver side of task
@@ -120,12 +287,87 @@ impl TaskHandle {
     }
 }
 
-/// create task and spawn running task execution
-pub async fn create_task(ectx: &Arc<ExecContext>, query_id: Arc<str>, plan: RootPlan) -> Result<TaskHandle, ExecError> {
-    let tx = ectx.store.transaction();
+/// create task and spawn running task execution. Without an explicit transaction, the task runs in its own
+/// transaction which commits when the task succeeds.
+pub async fn create_task(
+    ectx: &Arc<ExecContext>,
+    query_id: Arc<str>,
+    plan: RootPlan,
+    explicit: Option<&Arc<ExplicitTransaction>>,
+) -> Result<TaskHandle, ExecError> {
+    // MERGE locks the labels and relationship types before the snapshot is taken, so that it sees the nodes and
+    // relationships merged by other transactions. The snapshot of an explicit transaction is taken by BEGIN, its
+    // MERGE fails with a conflict if another transaction merged on the locks after that.
+    // The relationship types are created ahead, so that the match plan of MERGE can be built.
+    let (mut labels, mut lock_reltypes, mut reltypes) = (vec![], vec![], vec![]);
+    collect_merge_tokens(&plan.plan, &mut labels, &mut lock_reltypes, &mut reltypes);
+    let token_store = ectx.store.token_store();
+    for reltype in reltypes.iter() {
+        token_store.get_or_create_reltype_id(reltype)?;
+    }
+    let mut locked_labels = labels
+        .iter()
+        .map(|label| token_store.get_or_create_label_id(label))
+        .collect::<Result<HashSet<_>, _>>()?;
+    let mut locked_reltypes = lock_reltypes
+        .iter()
+        .map(|reltype| token_store.get_or_create_reltype_id(reltype))
+        .collect::<Result<HashSet<_>, _>>()?;
+    // tokens locked by the earlier statements of the transaction are not locked again
+    if let Some(explicit) = explicit {
+        let locks = explicit.merge_locks.lock().unwrap();
+        locked_labels.retain(|id| !locks.labels.contains(id));
+        locked_reltypes.retain(|id| !locks.reltypes.contains(id));
+    }
+    let merge_locks = {
+        let store = ectx.store.clone();
+        let label_ids = locked_labels.iter().copied().collect_vec();
+        let reltype_ids = locked_reltypes.iter().copied().collect_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut guards = store.acquire_labels_write(&label_ids);
+            guards.extend(store.acquire_reltypes_write(&reltype_ids));
+            guards
+        })
+        .await
+        .map_err(ExecError::io_error)?
+    };
+
+    let (tx, end) = match explicit {
+        Some(explicit) => {
+            if merge_locks
+                .iter()
+                .any(|guard| guard.last_commit_seq() > explicit.tx.start_seq())
+            {
+                // the transaction can only be rolled back, it would not see what was merged
+                drop(merge_locks);
+                explicit.fail();
+                return Err(GraphStoreError::WriteConflict(
+                    "MERGE on labels or relationship types merged after the transaction started".to_string(),
+                )
+                .into());
+            }
+            // the locks are held until the transaction ends
+            locked_labels = {
+                let mut locks = explicit.merge_locks.lock().unwrap();
+                locks.labels.extend(locked_labels.iter().copied());
+                locks.reltypes.extend(locked_reltypes);
+                locks.guards.extend(merge_locks);
+                locks.labels.clone()
+            };
+            let running = explicit.running.clone().read_owned().await;
+            (explicit.tx.clone(), TaskEnd::Explicit(explicit.clone(), running))
+        }
+        None => (ectx.store.transaction(), TaskEnd::AutoCommit(merge_locks)),
+    };
+    let written_labels = match explicit {
+        Some(explicit) => explicit.written_labels.clone(),
+        None => Default::default(),
+    };
     let task_context = Arc::new(TaskExecContext {
         exec_ctx: ectx.clone(),
         tx,
+        locked_labels,
+        written_labels,
     });
 
     // compile to executor
@@ -147,6 +389,7 @@ pub async fn create_task(ectx: &Arc<ExecContext>, query_id: Arc<str>, plan: Root
         ctx: task_context,
         tx,
         root_executor,
+        end,
     };
 
     runner.start();
@@ -154,18 +397,60 @@ pub async fn create_task(ectx: &Arc<ExecContext>, query_id: Arc<str>, plan: Root
     Ok(handle)
 }
 
+// labels and relationship types locked by MERGE, and relationship types created by MERGE
+fn collect_merge_tokens(
+    plan: &PlanExpr,
+    labels: &mut Vec<Arc<str>>,
+    lock_reltypes: &mut Vec<Arc<str>>,
+    reltypes: &mut Vec<Arc<str>>,
+) {
+    if let PlanExpr::Merge(merge) = plan {
+        labels.extend(merge.inner().lock_labels.iter().map(|label| label.name().clone()));
+        lock_reltypes.extend(merge.inner().lock_reltypes.iter().map(|reltype| reltype.name().clone()));
+        collect_create_reltypes(&merge.inner().create_plan, reltypes);
+    }
+    for input in plan.inputs() {
+        collect_merge_tokens(input, labels, lock_reltypes, reltypes);
+    }
+}
+
+fn collect_create_reltypes(plan: &PlanExpr, reltypes: &mut Vec<Arc<str>>) {
+    if let PlanExpr::CreateRel(create) = plan {
+        reltypes.extend(create.inner().rels.iter().map(|rel| rel.reltype.name().clone()));
+    }
+    for input in plan.inputs() {
+        collect_create_reltypes(input, reltypes);
+    }
+}
+
+/// What a task does with its transaction when it ends
+pub enum TaskEnd {
+    /// commit on success or abort, then release the MERGE locks
+    AutoCommit(Vec<LabelWriteGuard>),
+    /// the session ends the transaction, a failed task fails the whole transaction
+    Explicit(Arc<ExplicitTransaction>, OwnedRwLockReadGuard<()>),
+}
+
 pub struct TaskRunner {
     ctx: Arc<TaskExecContext>,
     tx: UnboundedSender<Result<DataChunk, ExecError>>,
     root_executor: SharedExecutor,
+    end: TaskEnd,
     // TODO(pgao): cancellation token
 }
 
 impl TaskRunner {
     pub fn start(self) {
         // spawn task and drive task to finish
-        let TaskRunner { ctx, tx, root_executor } = self;
+        let TaskRunner {
+            ctx,
+            tx,
+            root_executor,
+            end,
+        } = self;
         let txn = ctx.tx().clone();
+        let store = ctx.store().clone();
+        let written_labels = ctx.written_labels.clone();
         let stream = match root_executor.open(ctx) {
             Ok(s) => s,
             Err(e) => {
@@ -179,6 +464,10 @@ impl TaskRunner {
             // TODO(pgao): cancellation token
             while let Some(chunk) = stream.next().await {
                 let is_err = chunk.is_err();
+                // the session must see the transaction failed once it receives the error
+                if is_err && let TaskEnd::Explicit(explicit, _) = &end {
+                    explicit.fail();
+                }
                 if tx.send(chunk).is_err() {
                     success = false;
                     break;
@@ -189,12 +478,27 @@ impl TaskRunner {
                 }
             }
 
-            if success {
-                if let Err(e) = txn.commit() {
-                    let _ = tx.send(Err(e.into()));
+            match end {
+                TaskEnd::AutoCommit(mut merge_locks) => {
+                    if success {
+                        let written_labels = written_labels.lock().unwrap().iter().copied().collect_vec();
+                        match commit_checked(&store, &txn, &written_labels) {
+                            Ok(()) => record_merge_commit(&store, &mut merge_locks),
+                            Err(e) => {
+                                let _ = tx.send(Err(e.into()));
+                            }
+                        }
+                    } else {
+                        let _ = txn.abort();
+                    }
+                    drop(merge_locks);
+                }
+                TaskEnd::Explicit(explicit, running) => {
+                    if !success {
+                        explicit.fail();
+                    }
+                    drop(running);
                 }
-            } else {
-                let _ = txn.abort();
             }
         });
     }
@@ -0,0 +1,308 @@
+//! Aggregate functions
+//!
+//! - count: number of non-null values, `count(*)` counts rows
+//! - sum: sum of numbers or durations, 0 if there's no value
+//! - avg: average of numbers or durations, null if there's no value
+//! - min / max: smallest / largest value in cypher orderability, null if there's no value
+//! - collect: list of non-null values
+//!
+//! Nulls are ignored by all aggregate functions except `count(*)`.
+//! With DISTINCT, duplicated values are only aggregated once.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use bitvec::vec::BitVec;
+use elio_common::array::{ArrayImpl, ArrayRef};
+use elio_common::data_type::{DataType, F64};
+use elio_common::order::{cmp_datum, equivalence_key};
+use elio_common::scalar::{ListValue, ScalarRef, ScalarValue};
+
+use crate::define_function;
+use crate::error::EvalError;
+use crate::func::FunctionRegistry;
+use crate::func::sig::{FuncDef, FuncImpl, FuncImplArg, FuncImplReturn};
+
+/// Per-group state of an aggregate function.
+pub trait Accumulator: Send {
+    /// Accumulate one value of the argument, `None` for null.
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError>;
+
+    /// Aggregated result, `None` for null.
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError>;
+}
+
+/// Create the accumulator of aggregate function `name`, `num_args` is 0 for `count(*)`.
+///
+/// # Panics
+/// if `name` is not an aggregate function, planner guarantees it is.
+pub fn create_accumulator(name: &str, num_args: usize, distinct: bool) -> Box<dyn Accumulator> {
+    let acc: Box<dyn Accumulator> = match (name, num_args) {
+        ("count", 0) => Box::<CountStar>::default(),
+        ("count", _) => Box::<Count>::default(),
+        ("sum", _) => Box::<Sum>::default(),
+        ("avg", _) => Box::<Avg>::default(),
+        ("min", _) => Box::new(MinMax::new(Ordering::Less)),
+        ("max", _) => Box::new(MinMax::new(Ordering::Greater)),
+        ("collect", _) => Box::<Collect>::default(),
+        _ => unreachable!("unknown aggregate function {}", name),
+    };
+    if distinct { Box::new(Distinct::new(acc)) } else { acc }
+}
+
+#[derive(Default)]
+struct CountStar {
+    count: i64,
+}
+
+impl Accumulator for CountStar {
+    fn update(&mut self, _value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        Ok(Some(ScalarValue::Integer(self.count)))
+    }
+}
+
+#[derive(Default)]
+struct Count {
+    count: i64,
+}
+
+impl Accumulator for Count {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        if value.is_some() {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        Ok(Some(ScalarValue::Integer(self.count)))
+    }
+}
+
+#[derive(Default)]
+struct Sum {
+    sum: Option<ScalarValue>,
+}
+
+impl Accumulator for Sum {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        if let Some(value) = value {
+            self.sum = Some(add_value("sum", self.sum.take(), value)?);
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        Ok(Some(self.sum.unwrap_or(ScalarValue::Integer(0))))
+    }
+}
+
+#[derive(Default)]
+struct Avg {
+    sum: Option<ScalarValue>,
+    count: i64,
+}
+
+impl Accumulator for Avg {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        if let Some(value) = value {
+            self.sum = Some(add_value("avg", self.sum.take(), value)?);
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        let count = self.count as f64;
+        match self.sum {
+            None => Ok(None),
+            Some(ScalarValue::Integer(i)) => Ok(Some(ScalarValue::Float(F64::from(i as f64 / count)))),
+            Some(ScalarValue::Float(f)) => Ok(Some(ScalarValue::Float(f / count))),
+            Some(ScalarValue::Duration(d)) => d
+                .checked_div(count)
+                .map(|d| Some(ScalarValue::Duration(d)))
+                .ok_or_else(|| EvalError::arithmetic_overflow("avg", vec![d.to_string(), count.to_string()])),
+            Some(other) => unreachable!("avg accumulated non-numeric value {}", other),
+        }
+    }
+}
+
+// add a non-null value to the sum, only numbers and durations can be summed up
+fn add_value(func: &str, sum: Option<ScalarValue>, value: ScalarRef<'_>) -> Result<ScalarValue, EvalError> {
+    let Some(sum) = sum else {
+        return match value {
+            ScalarRef::Integer(_) | ScalarRef::Float(_) | ScalarRef::Duration(_) => Ok(value.to_owned_scalar()),
+            _ => Err(EvalError::invalid_argument(func, "Integer | Float | Duration", value)),
+        };
+    };
+    match (sum.as_scalar_ref(), value) {
+        (ScalarRef::Integer(a), ScalarRef::Integer(b)) => a
+            .checked_add(b)
+            .map(ScalarValue::Integer)
+            .ok_or_else(|| EvalError::arithmetic_overflow(func, vec![a.to_string(), b.to_string()])),
+        (ScalarRef::Float(a), ScalarRef::Float(b)) => Ok(ScalarValue::Float(a + b)),
+        (ScalarRef::Integer(a), ScalarRef::Float(b)) => Ok(ScalarValue::Float(F64::from(a as f64) + b)),
+        (ScalarRef::Float(a), ScalarRef::Integer(b)) => Ok(ScalarValue::Float(a + F64::from(b as f64))),
+        (ScalarRef::Duration(a), ScalarRef::Duration(b)) => a
+            .checked_add(&b)
+            .map(ScalarValue::Duration)
+            .ok_or_else(|| EvalError::arithmetic_overflow(func, vec![a.to_string(), b.to_string()])),
+        (ScalarRef::Duration(_), _) => Err(EvalError::invalid_argument(func, "Duration", value)),
+        _ => Err(EvalError::invalid_argument(func, "Integer | Float", value)),
+    }
+}
+
+struct MinMax {
+    // keep the new value if it compares to the current one as `keep`
+    keep: Ordering,
+    value: Option<ScalarValue>,
+}
+
+impl MinMax {
+    fn new(keep: Ordering) -> Self {
+        Self { keep, value: None }
+    }
+}
+
+impl Accumulator for MinMax {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        let Some(value) = value else {
+            return Ok(());
+        };
+        let replace = match &self.value {
+            Some(current) => cmp_datum(Some(value), Some(current.as_scalar_ref())) == self.keep,
+            None => true,
+        };
+        if replace {
+            self.value = Some(value.to_owned_scalar());
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        Ok(self.value)
+    }
+}
+
+#[derive(Default)]
+struct Collect {
+    values: Vec<ScalarValue>,
+}
+
+impl Accumulator for Collect {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        if let Some(value) = value {
+            self.values.push(value.to_owned_scalar());
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        Ok(Some(ScalarValue::List(Box::new(ListValue::new(self.values)))))
+    }
+}
+
+/// Only passes the first occurrence of each value to the inner accumulator, equivalent values like `1` and `1.0`
+/// count as the same value.
+struct Distinct {
+    seen: HashSet<ScalarValue>,
+    inner: Box<dyn Accumulator>,
+}
+
+impl Distinct {
+    fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            seen: HashSet::new(),
+            inner,
+        }
+    }
+}
+
+impl Accumulator for Distinct {
+    fn update(&mut self, value: Option<ScalarRef<'_>>) -> Result<(), EvalError> {
+        match value {
+            Some(value) if !self.seen.insert(equivalence_key(value)) => Ok(()),
+            _ => self.inner.update(value),
+        }
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<ScalarValue>, EvalError> {
+        self.inner.finish()
+    }
+}
+
+// aggregate functions are evaluated by the aggregate executor through accumulators,
+// they are never invoked as scalar functions.
+fn agg_batch(_args: &[ArrayRef], _vis: &BitVec, _len: usize) -> Result<ArrayImpl, EvalError> {
+    Err(EvalError::type_error(
+        "aggregate function can not be evaluated as scalar function",
+    ))
+}
+
+pub(crate) fn register(registry: &mut FunctionRegistry) {
+    let count = define_function!(
+        name: "count",
+        impls: [
+            {args: [], ret: Integer, func: agg_batch},
+            {args: [{exact Any}], ret: Integer, func: agg_batch}
+        ],
+        is_agg: true
+    );
+    registry.insert(count);
+
+    let sum = define_function!(
+        name: "sum",
+        impls: [
+            {args: [{exact Integer}], ret: Integer, func: agg_batch},
+            {args: [{exact Float}], ret: Float, func: agg_batch},
+            {args: [{exact Duration}], ret: Duration, func: agg_batch},
+            {args: [{exact Any}], ret: Any, func: agg_batch}
+        ],
+        is_agg: true
+    );
+    registry.insert(sum);
+
+    let avg = define_function!(
+        name: "avg",
+        impls: [
+            {args: [{anyof Integer | Float}], ret: Float, func: agg_batch},
+            {args: [{exact Duration}], ret: Duration, func: agg_batch},
+            {args: [{exact Any}], ret: Any, func: agg_batch}
+        ],
+        is_agg: true
+    );
+    registry.insert(avg);
+
+    // min(T) -> T, max(T) -> T
+    for name in ["min", "max"] {
+        let def = FuncDef {
+            name: name.to_string(),
+            impls: vec![FuncImpl::new(
+                name,
+                vec![FuncImplArg::Exact(DataType::Any)],
+                FuncImplReturn::SameAsArg(0),
+                agg_batch,
+            )],
+            is_agg: true,
+        };
+        registry.insert(def);
+    }
+
+    // collect(T) -> List<T>
+    let collect = FuncDef {
+        name: "collect".to_string(),
+        impls: vec![FuncImpl::new(
+            "collect",
+            vec![FuncImplArg::Exact(DataType::Any)],
+            FuncImplReturn::ListOf(0),
+            agg_batch,
+        )],
+        is_agg: true,
+    };
+    registry.insert(collect);
+}
@@ -2,11 +2,12 @@
 //!
 //! - list_index: Get element at index from list
 //! - list_slice: Get a slice of list
+//! - in: Check whether the list contains a value
 
 use bitvec::vec::BitVec;
 use elio_common::array::*;
 use elio_common::data_type::DataType;
-use elio_common::scalar::{ListValueRef, ScalarRef, ScalarVTable};
+use elio_common::scalar::{ListValueRef, ScalarPartialOrd, ScalarRef, ScalarVTable};
 
 use crate::error::EvalError;
 use crate::func::FunctionRegistry;
@@ -150,6 +151,45 @@ pub fn list_slice_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<A
     Ok(builder.finish().into())
 }
 
+/// in(value, list) -> bool
+/// Follows three-valued logic: true if any element equals the value, otherwise null if the value or any element
+/// is null, otherwise false.
+pub fn in_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let mut builder = BoolArrayBuilder::with_capacity(len);
+    for i in 0..len {
+        if !vis[i] {
+            builder.push(None);
+            continue;
+        }
+        let list = match args[1].get(i) {
+            Some(ScalarRef::List(list)) => list,
+            None | Some(ScalarRef::Null) => {
+                builder.push(None);
+                continue;
+            }
+            Some(other) => {
+                return Err(EvalError::type_error(format!("IN expects a list, got {:?}", other)));
+            }
+        };
+        let value = args[0].get(i).filter(|x| !matches!(x, ScalarRef::Null));
+        let mut result = Some(false);
+        for elem in list.iter() {
+            match (&value, elem) {
+                (Some(value), elem) if !matches!(elem, ScalarRef::Null) => {
+                    if value.scalar_partial_cmp(&elem) == Some(std::cmp::Ordering::Equal) {
+                        result = Some(true);
+                        break;
+                    }
+                }
+                // comparing with null is unknown
+                _ => result = None,
+            }
+        }
+        builder.push(result);
+    }
+    Ok(builder.finish().into())
+}
+
 pub(crate) fn register(registry: &mut FunctionRegistry) {
     // list_index(List<T>, Int|Any) -> T
     // Accept Any for index because unary operations (like -1) return Any type
@@ -201,4 +241,17 @@ pub(crate) fn register(registry: &mut FunctionRegistry) {
         is_agg: false,
     };
     registry.insert(list_slice_def);
+
+    // in(Any, List<T>|Any) -> Bool
+    let in_def = FuncDef {
+        name: "in".to_string(),
+        impls: vec![FuncImpl::new(
+            "in",
+            vec![FuncImplArg::Exact(DataType::Any), FuncImplArg::Exact(DataType::Any)],
+            FuncImplReturn::Exact(DataType::Bool),
+            in_batch,
+        )],
+        is_agg: false,
+    };
+    registry.insert(in_def);
 }
@@ -1,11 +1,13 @@
 pub mod sig;
 
+pub mod agg; // count / sum / avg / min / max / collect
 pub mod compare; // gt/eq/lt/le/ge/ne
 pub mod list;
 pub mod op_arith;
 pub mod op_bool; // and / or
 pub mod op_unary;
 pub mod path;
+pub mod string; // starts_with / ends_with / contains / regex_match
 pub mod temporal;
 
 use std::collections::HashMap;
@@ -45,6 +47,18 @@ impl FunctionRegistry {
         self.name2def.get("eq").unwrap().impls.first().unwrap()
     }
 
+    pub fn get_is_acyclic_func_impl(&self) -> &FuncImpl {
+        // SAFETY:
+        //    there's only 1 is_acyclic impl
+        self.name2def.get("is_acyclic").unwrap().impls.first().unwrap()
+    }
+
+    pub fn get_rels_disjoint_func_impl(&self) -> &FuncImpl {
+        // SAFETY:
+        //    there's only 1 rels_disjoint impl
+        self.name2def.get("rels_disjoint").unwrap().impls.first().unwrap()
+    }
+
     pub fn get_func_impl(&self, func_id: &str) -> &FuncImpl {
         // SAFETY:
         //    only called in expression builder, planner will guarantee the function exists
@@ -65,8 +79,11 @@ pub static FUNCTION_REGISTRY: LazyLock<FunctionRegistry> = LazyLock::new(|| {
     op_arith::register(&mut registry);
     op_unary::register(&mut registry);
     list::register(&mut registry);
+    path::register(&mut registry);
+    string::register(&mut registry);
 
     // register agg functions
+    agg::register(&mut registry);
 
     registry
 });
@@ -1 +1,203 @@
+//! Path functions
+//!
+//! - length: number of relationships in the path
+//! - nodes: list of the nodes in the path
+//! - relationships: list of the relationships in the path
+//! - startnode: the start node of the relationship
+//! - endnode: the end node of the relationship
+//!
+//! Uniqueness predicates of the match modes
+//! - is_acyclic: no node repeats in the path
+//! - rels_disjoint: two relationships or relationship lists share no relationship
 
+use std::collections::HashSet;
+
+use bitvec::prelude::*;
+use elio_common::array::*;
+use elio_common::data_type::DataType;
+use elio_common::scalar::*;
+use elio_common::{NodeId, RelationshipId};
+use expr_macros::cypher_func;
+use itertools::Itertools;
+
+use crate::error::EvalError;
+use crate::func::FunctionRegistry;
+use crate::func::sig::{FuncDef, FuncImpl, FuncImplArg, FuncImplReturn};
+
+#[cypher_func(batch_name = "path_length_batch", sig = "(pathref) -> any")]
+fn path_length(path: VirtualPathRef<'_>) -> Result<ScalarValue, EvalError> {
+    Ok(ScalarValue::Integer((path.rel_end - path.rel_start) as i64))
+}
+
+#[cypher_func(batch_name = "path_is_acyclic_batch", sig = "(pathref) -> bool")]
+fn path_is_acyclic(path: VirtualPathRef<'_>) -> Result<bool, EvalError> {
+    let mut visited = HashSet::new();
+    Ok(path.node_iter().flatten().all(|node| visited.insert(node)))
+}
+
+// the ids of a relationship or a list of relationships
+fn rel_ids(value: Option<ScalarRef<'_>>) -> Result<Vec<RelationshipId>, EvalError> {
+    match value {
+        None | Some(ScalarRef::Null) => Ok(vec![]),
+        Some(ScalarRef::Rel(rel)) => Ok(vec![rel.id]),
+        Some(ScalarRef::VirtualRel(rel)) => Ok(vec![rel.id]),
+        Some(ScalarRef::List(list)) => list.iter().map(|elem| rel_ids(Some(elem))).flatten_ok().collect(),
+        Some(other) => Err(EvalError::invalid_argument(
+            "rels_disjoint",
+            "Relationship | List<Relationship>",
+            other.to_string(),
+        )),
+    }
+}
+
+/// rels_disjoint(rels, rels) -> bool
+/// Null is taken as no relationship.
+pub fn rels_disjoint_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let mut builder = BoolArrayBuilder::with_capacity(len);
+    for i in 0..len {
+        if vis[i] {
+            let lhs = rel_ids(args[0].get(i))?;
+            let rhs = rel_ids(args[1].get(i))?;
+            builder.push(Some(lhs.iter().all(|id| !rhs.contains(id))));
+        } else {
+            builder.push(None);
+        }
+    }
+
+    Ok(builder.finish().into())
+}
+
+// the start and end node ids of a relationship, null is taken as no relationship
+fn rel_endpoints(name: &str, value: Option<ScalarRef<'_>>) -> Result<Option<(NodeId, NodeId)>, EvalError> {
+    match value {
+        None | Some(ScalarRef::Null) => Ok(None),
+        Some(ScalarRef::Rel(rel)) => Ok(Some((rel.start_id, rel.end_id))),
+        Some(ScalarRef::VirtualRel(rel)) => Ok(Some((rel.start_id, rel.end_id))),
+        Some(other) => Err(EvalError::invalid_argument(name, "Relationship", other.to_string())),
+    }
+}
+
+/// startNode(rel) -> node
+/// The argument may be a list element, so its array is not always a relationship array.
+pub fn rel_start_node_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let mut builder = VirtualNodeArrayBuilder::with_capacity(len);
+    for i in 0..len {
+        if vis[i] {
+            builder.push(rel_endpoints("startNode", args[0].get(i))?.map(|(start, _)| start));
+        } else {
+            builder.push(None);
+        }
+    }
+
+    Ok(builder.finish().into())
+}
+
+/// endNode(rel) -> node
+pub fn rel_end_node_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let mut builder = VirtualNodeArrayBuilder::with_capacity(len);
+    for i in 0..len {
+        if vis[i] {
+            builder.push(rel_endpoints("endNode", args[0].get(i))?.map(|(_, end)| end));
+        } else {
+            builder.push(None);
+        }
+    }
+
+    Ok(builder.finish().into())
+}
+
+/// nodes(path) -> list<node>
+pub fn path_nodes_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let path_arr = args[0]
+        .as_virtual_path()
+        .unwrap_or_else(|| panic!("expected virtual path array got {:?} array", args[0].physical_type()));
+    let mut builder = ListArrayBuilder::new(Box::new(VirtualNodeArrayBuilder::with_capacity(len).into()));
+
+    let valid_rows = vis.clone() & path_arr.valid_map().clone();
+    for i in 0..len {
+        if valid_rows[i] {
+            let path = unsafe { path_arr.get_unchecked(i) };
+            builder.push(Some(path.node_list_ref()));
+        } else {
+            builder.push(None);
+        }
+    }
+
+    Ok(builder.finish().into())
+}
+
+/// relationships(path) -> list<rel>
+pub fn path_relationships_batch(args: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let path_arr = args[0]
+        .as_virtual_path()
+        .unwrap_or_else(|| panic!("expected virtual path array got {:?} array", args[0].physical_type()));
+    let mut builder = ListArrayBuilder::new(Box::new(RelArrayBuilder::with_capacity(len).into()));
+
+    let valid_rows = vis.clone() & path_arr.valid_map().clone();
+    for i in 0..len {
+        if valid_rows[i] {
+            let path = unsafe { path_arr.get_unchecked(i) };
+            builder.push(Some(path.rel_list_ref()));
+        } else {
+            builder.push(None);
+        }
+    }
+
+    Ok(builder.finish().into())
+}
+
+pub(crate) fn register(registry: &mut FunctionRegistry) {
+    let path_fn = |name: &str, ret: DataType, func| FuncDef {
+        name: name.to_string(),
+        impls: vec![FuncImpl::new(
+            name,
+            vec![FuncImplArg::Exact(DataType::VirtualPath)],
+            FuncImplReturn::Exact(ret),
+            func,
+        )],
+        is_agg: false,
+    };
+
+    registry.insert(path_fn("length", DataType::Integer, path_length_batch));
+    registry.insert(path_fn(
+        "nodes",
+        DataType::new_list(DataType::VirtualNode),
+        path_nodes_batch,
+    ));
+    registry.insert(path_fn(
+        "relationships",
+        DataType::new_list(DataType::Rel),
+        path_relationships_batch,
+    ));
+    registry.insert(path_fn("is_acyclic", DataType::Bool, path_is_acyclic_batch));
+
+    let rel_fn = |name: &str, func| FuncDef {
+        name: name.to_string(),
+        impls: [DataType::Rel, DataType::VirtualRel]
+            .into_iter()
+            .map(|arg| {
+                FuncImpl::new(
+                    name,
+                    vec![FuncImplArg::Exact(arg)],
+                    FuncImplReturn::Exact(DataType::VirtualNode),
+                    func,
+                )
+            })
+            .collect(),
+        is_agg: false,
+    };
+    registry.insert(rel_fn("startnode", rel_start_node_batch));
+    registry.insert(rel_fn("endnode", rel_end_node_batch));
+
+    let rels_disjoint = FuncDef {
+        name: "rels_disjoint".to_string(),
+        impls: vec![FuncImpl::new(
+            "rels_disjoint",
+            vec![FuncImplArg::Exact(DataType::Any), FuncImplArg::Exact(DataType::Any)],
+            FuncImplReturn::Exact(DataType::Bool),
+            rels_disjoint_batch,
+        )],
+        is_agg: false,
+    };
+    registry.insert(rels_disjoint);
+}
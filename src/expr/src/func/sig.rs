@@ -170,6 +170,8 @@ pub enum FuncImplReturn {
     /// Return type is the element type of the nth argument (which must be a List),
     /// e.g. `list_index(List<T>, Int) -> T`
     ListElement(usize),
+    /// Return type is a list of the nth argument type, e.g. `collect(T) -> List<T>`
+    ListOf(usize),
 }
 
 impl FuncImplReturn {
@@ -189,6 +191,7 @@ impl FuncImplReturn {
                     DataType::Any
                 }
             }
+            FuncImplReturn::ListOf(idx) => DataType::new_list(args.get(*idx).cloned().unwrap_or(DataType::Any)),
         }
     }
 }
@@ -1,64 +1,110 @@
 //! String functions
-//! - uppper
+//! - upper
 //! - lower
+//! - starts_with
+//! - ends_with
+//! - contains
+//! - regex_match
 
 use std::collections::HashMap;
 
-use elio_common::array::ArrayImpl;
-use elio_common::array::chunk::DataChunk;
+use bitvec::prelude::*;
+use elio_common::array::*;
+use elio_common::data_type::DataType;
+use elio_common::scalar::*;
+use regex::Regex;
 
 use crate::define_function;
 use crate::error::EvalError;
-use crate::func::sig::FuncDef;
-use crate::impl_::EvalCtx;
-use crate::impl_::func_executor::UnaryExecutor;
+use crate::func::FunctionRegistry;
 
-fn string_upper(s: &str) -> String {
-    s.to_uppercase()
+fn do_string_map(inputs: &[ArrayRef], vis: &BitVec, len: usize, op: impl Fn(&str) -> String) -> ArrayImpl {
+    let mut builder = DataType::String.physical_type().array_builder(len);
+    for i in 0..len {
+        match inputs[0].get(i) {
+            Some(ScalarRef::String(s)) if vis[i] => builder.push(Some(ScalarRef::String(&op(s)))),
+            _ => builder.push(None),
+        }
+    }
+    builder.finish()
 }
 
-fn string_lower(s: &str) -> String {
-    s.to_lowercase()
+// Tenary Logic
+// if any side is null or not a string, then return NULL
+fn do_string_predicate(
+    inputs: &[ArrayRef],
+    vis: &BitVec,
+    len: usize,
+    mut op: impl FnMut(&str, &str) -> Result<bool, EvalError>,
+) -> Result<ArrayImpl, EvalError> {
+    assert_eq!(inputs.len(), 2);
+    let mut out_builder = BoolArrayBuilder::with_capacity(len);
+    for i in 0..len {
+        if !vis[i] {
+            out_builder.push(None);
+            continue;
+        }
+        match (inputs[0].get(i), inputs[1].get(i)) {
+            (Some(ScalarRef::String(lhs)), Some(ScalarRef::String(rhs))) => out_builder.push(Some(op(lhs, rhs)?)),
+            _ => out_builder.push(None),
+        }
+    }
+    Ok(out_builder.finish().into())
 }
 
-fn upper_function(input: &DataChunk, _ctx: &dyn EvalCtx) -> Result<ArrayImpl, EvalError> {
-    let res = UnaryExecutor::execute_simple::<String, String, _>(input.column(0), string_upper)?;
-    Ok(res)
+fn upper_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    Ok(do_string_map(inputs, vis, len, str::to_uppercase))
 }
 
-fn lower_function(input: &DataChunk, _ctx: &dyn EvalCtx) -> Result<ArrayImpl, EvalError> {
-    let res = UnaryExecutor::execute_simple::<String, String, _>(input.column(0), string_lower)?;
-    Ok(res)
+fn lower_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    Ok(do_string_map(inputs, vis, len, str::to_lowercase))
 }
 
-// register function here
+fn starts_with_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    do_string_predicate(inputs, vis, len, |lhs, rhs| Ok(lhs.starts_with(rhs)))
+}
+
+fn ends_with_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    do_string_predicate(inputs, vis, len, |lhs, rhs| Ok(lhs.ends_with(rhs)))
+}
+
+fn contains_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    do_string_predicate(inputs, vis, len, |lhs, rhs| Ok(lhs.contains(rhs)))
+}
+
+/// Compile the pattern of `=~`, which must match the whole string.
+pub fn compile_regex(pattern: &str) -> Result<Regex, EvalError> {
+    Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|e| EvalError::invalid_argument("=~", "a valid regular expression", e))
+}
+
+// the pattern may differ by rows, each distinct pattern is compiled once per batch. A constant pattern is compiled
+// only once by `RegexMatchExpr` instead.
+fn regex_match_batch(inputs: &[ArrayRef], vis: &BitVec, len: usize) -> Result<ArrayImpl, EvalError> {
+    let mut regexes: HashMap<String, Regex> = HashMap::new();
+    do_string_predicate(inputs, vis, len, |lhs, pattern| {
+        if !regexes.contains_key(pattern) {
+            regexes.insert(pattern.to_string(), compile_regex(pattern)?);
+        }
+        Ok(regexes[pattern].is_match(lhs))
+    })
+}
+
+pub(crate) fn register(registry: &mut FunctionRegistry) {
+    let upper =
+        define_function!( name: "upper", impls: [ {args: [{exact Any}], ret: String, func: upper_batch}],is_agg: false);
+    let lower =
+        define_function!( name: "lower", impls: [ {args: [{exact Any}], ret: String, func: lower_batch}],is_agg: false);
 
-pub(crate) fn register(registry: &mut HashMap<String, FuncDef>) {
-    // lower
-    let lower = define_function!(
-        name: "lower",
-        impls: [
-            {
-                args: [String],
-                ret: String,
-                func: lower_function,
-            }
-        ],
-        is_agg: false
-    );
-    // upper
-    let upper = define_function!(
-        name: "upper",
-        impls: [
-            {
-                args: [String],
-                ret: String,
-                func: upper_function,
-            }
-        ],
-        is_agg: false
-    );
+    let starts_with = define_function!( name: "starts_with", impls: [ {args: [{exact Any}, {exact Any}], ret: Bool, func: starts_with_batch}],is_agg: false);
+    let ends_with = define_function!( name: "ends_with", impls: [ {args: [{exact Any}, {exact Any}], ret: Bool, func: ends_with_batch}],is_agg: false);
+    let contains = define_function!( name: "contains", impls: [ {args: [{exact Any}, {exact Any}], ret: Bool, func: contains_batch}],is_agg: false);
+    let regex_match = define_function!( name: "regex_match", impls: [ {args: [{exact Any}, {exact Any}], ret: Bool, func: regex_match_batch}],is_agg: false);
 
-    registry.insert(lower.name.clone(), lower);
-    registry.insert(upper.name.clone(), upper);
+    registry.insert(upper);
+    registry.insert(lower);
+    registry.insert(starts_with);
+    registry.insert(ends_with);
+    registry.insert(contains);
+    registry.insert(regex_match);
 }
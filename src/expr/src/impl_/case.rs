@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use elio_common::array::ArrayRef;
+use elio_common::array::chunk::DataChunk;
+use elio_common::data_type::DataType;
+
+use crate::error::EvalError;
+use crate::impl_::{EvalCtx, Expression, SharedExpression};
+
+/// CASE WHEN c1 THEN r1 WHEN c2 THEN r2 ELSE d END
+///
+/// The branches are evaluated lazily: each condition only sees the rows not taken by the previous ones,
+/// and each result only sees the rows taking it, so an error in a branch no row takes never fires.
+#[derive(Debug)]
+pub struct CaseExpr {
+    pub when_then: Vec<(SharedExpression, SharedExpression)>,
+    pub else_: Option<SharedExpression>,
+    pub typ: DataType,
+}
+
+impl Expression for CaseExpr {
+    fn typ(&self) -> &DataType {
+        &self.typ
+    }
+
+    fn eval_batch(&self, chunk: &DataChunk, ctx: &dyn EvalCtx) -> Result<ArrayRef, EvalError> {
+        let len = chunk.len();
+        // the rows not taken by any branch yet
+        let mut remaining = chunk.visibility().clone();
+        // the result array of each branch, and the branch each row takes
+        let mut results = vec![];
+        let mut taken = vec![None; len];
+
+        let mut eval_branch = |result: &SharedExpression, rows: BitVec| -> Result<(), EvalError> {
+            let mut branch = chunk.clone();
+            *branch.visibility_mut() = rows.clone();
+            for idx in rows.iter_ones() {
+                taken[idx] = Some(results.len());
+            }
+            results.push(result.eval_batch(&branch, ctx)?);
+            Ok(())
+        };
+
+        for (when, then) in &self.when_then {
+            if remaining.not_any() {
+                break;
+            }
+            let mut cond_chunk = chunk.clone();
+            *cond_chunk.visibility_mut() = remaining.clone();
+            let cond = when.eval_batch(&cond_chunk, ctx)?;
+            let cond = cond.as_bool().expect("case condition should result in bool array");
+            // null is not true
+            let rows = cond.to_filter_mask() & remaining.clone();
+            if rows.any() {
+                remaining &= !rows.clone();
+                eval_branch(then, rows)?;
+            }
+        }
+        if let Some(else_) = &self.else_
+            && remaining.any()
+        {
+            eval_branch(else_, remaining)?;
+        }
+
+        let mut builder = self.typ.physical_type().array_builder(len);
+        for (idx, branch) in taken.into_iter().enumerate() {
+            builder.push(branch.and_then(|branch| results[branch].get(idx)));
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
@@ -61,13 +61,9 @@ impl Expression for FieldAccessExpr {
         // rel type
         if let ArrayImpl::Rel(input) = input.as_ref() {
             // the output must be Any
-            let mut builder = AnyArrayBuilder::with_capacity(input.len());
-            input.props_iter().for_each(|props| {
-                if let Some(props) = props {
-                    builder.push(props.field_at(key));
-                }
-            });
-            return Ok(Arc::new(builder.finish().into()));
+            let props_iter = input.props_iter().map(|props| props.map(|p| p.as_scalar_ref()));
+            let output = access_properties(props_iter, input.len(), key);
+            return Ok(Arc::new(output.into()));
         }
 
         // virtual node
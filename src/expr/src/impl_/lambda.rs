@@ -0,0 +1,239 @@
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use elio_common::array::chunk::DataChunk;
+use elio_common::array::{ArrayImpl, ArrayRef};
+use elio_common::data_type::DataType;
+use elio_common::scalar::{ListValue, ScalarRef, ScalarVTable, ScalarValue};
+
+use crate::error::EvalError;
+use crate::impl_::{EvalCtx, Expression, SharedExpression};
+
+// Expressions with a lambda are evaluated step by step, the k-th step evaluates the lambda on the k-th element of the
+// lists. The chunk of a step has the columns of the input chunk followed by the lambda variables, and only the rows
+// whose list has the k-th element are visible.
+
+// the elements of the list of each row, None if the row is invisible or the list is null
+fn list_elements<'a>(lists: &'a ArrayImpl, visibility: &BitVec) -> Result<Vec<Option<Vec<ScalarRef<'a>>>>, EvalError> {
+    let mut elements = vec![None; lists.len()];
+    for idx in visibility.iter_ones() {
+        match lists.get(idx) {
+            Some(ScalarRef::List(list)) => elements[idx] = Some(list.iter().collect()),
+            None | Some(ScalarRef::Null) => {}
+            Some(other) => return Err(EvalError::type_error(format!("expected a list, got {}", other))),
+        }
+    }
+    Ok(elements)
+}
+
+fn max_len(elements: &[Option<Vec<ScalarRef>>]) -> usize {
+    elements.iter().flatten().map(|x| x.len()).max().unwrap_or(0)
+}
+
+// the k-th element of each row, and the rows having it
+fn element_column(elements: &[Option<Vec<ScalarRef>>], k: usize, typ: &DataType) -> (ArrayRef, BitVec) {
+    let mut builder = typ.physical_type().array_builder(elements.len());
+    let mut visibility = BitVec::repeat(false, elements.len());
+    for (idx, list) in elements.iter().enumerate() {
+        match list.as_ref().and_then(|list| list.get(k)) {
+            Some(elem) => {
+                builder.push(not_null(*elem));
+                visibility.set(idx, true);
+            }
+            None => builder.push(None),
+        }
+    }
+    (Arc::new(builder.finish()), visibility)
+}
+
+fn not_null(value: ScalarRef) -> Option<ScalarRef> {
+    match value {
+        ScalarRef::Null => None,
+        value => Some(value),
+    }
+}
+
+fn step_chunk(chunk: &DataChunk, lambda_columns: Vec<ArrayRef>, visibility: BitVec) -> DataChunk {
+    let mut columns = chunk.columns().to_vec();
+    columns.extend(lambda_columns);
+    DataChunk::new(columns, visibility)
+}
+
+fn eval_predicate(
+    predicate: &SharedExpression,
+    chunk: &DataChunk,
+    ctx: &dyn EvalCtx,
+) -> Result<Vec<Option<bool>>, EvalError> {
+    let result = predicate.eval_batch(chunk, ctx)?;
+    let mut values = vec![None; chunk.len()];
+    for idx in chunk.visibility().iter_ones() {
+        values[idx] = match result.get(idx) {
+            Some(ScalarRef::Bool(b)) => Some(b),
+            _ => None,
+        };
+    }
+    Ok(values)
+}
+
+/// [x IN list WHERE filter | map]
+#[derive(Debug)]
+pub struct ListComprehensionExpr {
+    pub list: SharedExpression,
+    pub filter: Option<SharedExpression>,
+    pub map: Option<SharedExpression>,
+    // type of the lambda variable
+    pub elem_type: DataType,
+    pub typ: DataType,
+}
+
+impl Expression for ListComprehensionExpr {
+    fn typ(&self) -> &DataType {
+        &self.typ
+    }
+
+    fn eval_batch(&self, chunk: &DataChunk, ctx: &dyn EvalCtx) -> Result<ArrayRef, EvalError> {
+        let lists = self.list.eval_batch(chunk, ctx)?;
+        let elements = list_elements(&lists, chunk.visibility())?;
+        let mut values: Vec<Vec<ScalarValue>> = vec![vec![]; chunk.len()];
+
+        for k in 0..max_len(&elements) {
+            let (elem, visibility) = element_column(&elements, k, &self.elem_type);
+            let mut step = step_chunk(chunk, vec![elem.clone()], visibility);
+            if let Some(filter) = &self.filter {
+                let kept = eval_predicate(filter, &step, ctx)?;
+                let visibility = kept.iter().map(|x| *x == Some(true)).collect();
+                *step.visibility_mut() = visibility;
+            }
+            if step.visibility().not_any() {
+                continue;
+            }
+            let mapped = match &self.map {
+                Some(map) => map.eval_batch(&step, ctx)?,
+                None => elem,
+            };
+            for idx in step.visibility().iter_ones() {
+                values[idx].push(mapped.get(idx).map(|x| x.to_owned_scalar()).unwrap_or_default());
+            }
+        }
+
+        let mut builder = self.typ.physical_type().array_builder(chunk.len()).into_list().unwrap();
+        for (list, values) in elements.iter().zip(values) {
+            match list {
+                Some(_) => builder.push(Some(ListValue::new(values).as_scalar_ref())),
+                None => builder.push(None),
+            }
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ListPredicateKind {
+    All,
+    Any,
+    None,
+    Single,
+}
+
+/// all/any/none/single(x IN list WHERE predicate), null if the result depends on a null predicate
+#[derive(Debug)]
+pub struct ListPredicateExpr {
+    pub kind: ListPredicateKind,
+    pub list: SharedExpression,
+    pub predicate: SharedExpression,
+    // type of the lambda variable
+    pub elem_type: DataType,
+}
+
+impl Expression for ListPredicateExpr {
+    fn typ(&self) -> &DataType {
+        &DataType::Bool
+    }
+
+    fn eval_batch(&self, chunk: &DataChunk, ctx: &dyn EvalCtx) -> Result<ArrayRef, EvalError> {
+        let lists = self.list.eval_batch(chunk, ctx)?;
+        let elements = list_elements(&lists, chunk.visibility())?;
+        // number of the elements whose predicate is true or null
+        let mut trues = vec![0; chunk.len()];
+        let mut nulls = vec![0; chunk.len()];
+
+        for k in 0..max_len(&elements) {
+            let (elem, visibility) = element_column(&elements, k, &self.elem_type);
+            let step = step_chunk(chunk, vec![elem], visibility);
+            let result = eval_predicate(&self.predicate, &step, ctx)?;
+            for idx in step.visibility().iter_ones() {
+                match result[idx] {
+                    Some(true) => trues[idx] += 1,
+                    Some(false) => {}
+                    None => nulls[idx] += 1,
+                }
+            }
+        }
+
+        let mut builder = DataType::Bool.physical_type().array_builder(chunk.len());
+        for (idx, list) in elements.iter().enumerate() {
+            let Some(list) = list else {
+                builder.push(None);
+                continue;
+            };
+            let (trues, nulls, falses) = (trues[idx], nulls[idx], list.len() - trues[idx] - nulls[idx]);
+            let value = match self.kind {
+                ListPredicateKind::All if falses > 0 => Some(false),
+                ListPredicateKind::Any if trues > 0 => Some(true),
+                ListPredicateKind::None if trues > 0 => Some(false),
+                ListPredicateKind::Single if trues > 1 => Some(false),
+                _ if nulls > 0 => None,
+                ListPredicateKind::All | ListPredicateKind::None => Some(true),
+                ListPredicateKind::Any => Some(false),
+                ListPredicateKind::Single => Some(trues == 1),
+            };
+            builder.push(value.map(ScalarRef::Bool));
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+/// reduce(acc = init, x IN list | expr), the lambda columns are the accumulator followed by the element
+#[derive(Debug)]
+pub struct ReduceExpr {
+    pub init: SharedExpression,
+    pub list: SharedExpression,
+    pub expr: SharedExpression,
+    // type of the lambda variable
+    pub elem_type: DataType,
+    pub typ: DataType,
+}
+
+impl Expression for ReduceExpr {
+    fn typ(&self) -> &DataType {
+        &self.typ
+    }
+
+    fn eval_batch(&self, chunk: &DataChunk, ctx: &dyn EvalCtx) -> Result<ArrayRef, EvalError> {
+        let mut acc = self.init.eval_batch(chunk, ctx)?;
+        let lists = self.list.eval_batch(chunk, ctx)?;
+        let elements = list_elements(&lists, chunk.visibility())?;
+
+        for k in 0..max_len(&elements) {
+            let (elem, visibility) = element_column(&elements, k, &self.elem_type);
+            let step = step_chunk(chunk, vec![acc.clone(), elem], visibility);
+            let result = self.expr.eval_batch(&step, ctx)?;
+            // the rows whose list is exhausted keep the accumulated value
+            let mut builder = self.typ.physical_type().array_builder(chunk.len());
+            for idx in 0..chunk.len() {
+                if step.visibility()[idx] {
+                    builder.push(result.get(idx));
+                } else {
+                    builder.push(acc.get(idx));
+                }
+            }
+            acc = Arc::new(builder.finish());
+        }
+
+        let mut builder = self.typ.physical_type().array_builder(chunk.len());
+        for (idx, list) in elements.iter().enumerate() {
+            builder.push(list.as_ref().and_then(|_| acc.get(idx)));
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
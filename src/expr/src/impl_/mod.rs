@@ -8,13 +8,16 @@ use elio_common::{TokenId, TokenKind};
 
 use crate::error::EvalError;
 
+pub mod case;
 pub mod constant;
 pub mod create_list;
 pub mod create_struct;
 pub mod field_access;
 pub mod func_call;
 pub mod label;
+pub mod lambda;
 pub mod project_path;
+pub mod regex_match;
 pub mod variable_ref;
 
 pub trait EvalCtx {
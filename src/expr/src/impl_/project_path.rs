@@ -35,64 +35,72 @@ impl Expression for ProjectPathExpr {
         let mut node_builder = ListArrayBuilder::new(Box::new(VirtualNodeArrayBuilder::with_capacity(len).into()));
         let mut rel_builder = ListArrayBuilder::new(Box::new(RelArrayBuilder::with_capacity(len).into()));
 
-        let mut nodes = Vec::with_capacity(steps.len() / 2 + 1);
-        let mut rels = Vec::with_capacity(steps.len() / 2);
         for (i, step) in steps.iter().enumerate() {
-            if i % 2 == 0 {
-                match step.as_ref() {
-                    ArrayImpl::VirtualNode(_) | ArrayImpl::Node(_) => nodes.push(step.clone()),
-                    other => {
-                        return Err(EvalError::TypeError(format!(
-                            "Expected node array at step {}, found {:?}",
-                            i,
-                            other.physical_type()
-                        )));
+            let expected = match (i % 2, step.as_ref()) {
+                (0, ArrayImpl::VirtualNode(_) | ArrayImpl::Node(_)) => continue,
+                // a variable length relationship is a list of rels
+                (1, ArrayImpl::Rel(_) | ArrayImpl::List(_)) => continue,
+                (0, _) => "node",
+                _ => "rel",
+            };
+            return Err(EvalError::TypeError(format!(
+                "Expected {} array at step {}, found {:?}",
+                expected,
+                i,
+                step.physical_type()
+            )));
+        }
+
+        // the nodes and rels of the path at the row, none if any of the steps is null
+        fn walk_path(steps: &[ArrayRef], rowid: usize) -> Option<(Vec<NodeId>, Vec<RelValueRef<'_>>)> {
+            let mut nodes = Vec::with_capacity(steps.len() / 2 + 1);
+            let mut rels = Vec::with_capacity(steps.len() / 2);
+            // the node after a list of rels is already reached by walking the list
+            let mut walked = false;
+            for step in steps {
+                let node = match step.as_ref() {
+                    ArrayImpl::VirtualNode(virtual_node_array) => virtual_node_array.get(rowid)?,
+                    ArrayImpl::Node(node_array) => node_array.get(rowid)?.id,
+                    ArrayImpl::Rel(rel_array) => {
+                        rels.push(rel_array.get(rowid)?);
+                        continue;
                     }
-                }
-            } else {
-                match step.as_ref() {
-                    ArrayImpl::Rel(_) => rels.push(step.clone()),
-                    other => {
-                        return Err(EvalError::TypeError(format!(
-                            "Expected rel array at step {}, found {:?}",
-                            i,
-                            other.physical_type()
-                        )));
+                    ArrayImpl::List(list_array) => {
+                        // rels are in the pattern order, each one continues from the last node
+                        for rel in list_array.get(rowid)?.iter() {
+                            let rel = rel.into_rel().ok()?;
+                            let current = *nodes.last()?;
+                            nodes.push(if rel.start_id == current {
+                                rel.end_id
+                            } else {
+                                rel.start_id
+                            });
+                            rels.push(rel);
+                        }
+                        walked = true;
+                        continue;
                     }
+                    _ => unreachable!(),
+                };
+                if !std::mem::take(&mut walked) {
+                    nodes.push(node);
                 }
             }
-        }
-
-        fn get_nodes(node_steps: &[ArrayRef], rowid: usize) -> impl Iterator<Item = NodeId> + '_ {
-            node_steps.iter().filter_map(move |step| {
-                if let ArrayImpl::VirtualNode(virtual_node_array) = step.as_ref() {
-                    virtual_node_array.get(rowid)
-                } else if let ArrayImpl::Node(node_array) = step.as_ref() {
-                    node_array.get(rowid).map(|x| x.id)
-                } else {
-                    None
-                }
-            })
-        }
-
-        fn get_rels(rel_steps: &[ArrayRef], rowid: usize) -> impl Iterator<Item = RelValueRef<'_>> + '_ {
-            rel_steps.iter().filter_map(move |step| {
-                if let ArrayImpl::Rel(rel_array) = step.as_ref() {
-                    rel_array.get(rowid)
-                } else {
-                    None
-                }
-            })
+            Some((nodes, rels))
         }
 
         let mut valid = BitVec::with_capacity(len);
         // build node list
         for rowid in 0..len {
-            let nodes = get_nodes(&nodes, rowid);
-            let rels = get_rels(&rels, rowid);
-            node_builder.push_virtual_nodes(nodes);
-            rel_builder.push_rels(rels);
-            valid.push(true);
+            if let Some((nodes, rels)) = walk_path(&steps, rowid) {
+                node_builder.push_virtual_nodes(nodes.into_iter());
+                rel_builder.push_rels(rels.into_iter());
+                valid.push(true);
+            } else {
+                node_builder.push(None);
+                rel_builder.push(None);
+                valid.push(false);
+            }
         }
 
         let path = VirtualPathArray::from_parts(Arc::new(node_builder.finish()), Arc::new(rel_builder.finish()), valid);
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use elio_common::array::chunk::DataChunk;
+use elio_common::array::{ArrayRef, BoolArrayBuilder};
+use elio_common::data_type::DataType;
+use elio_common::scalar::ScalarRef;
+use regex::Regex;
+
+use crate::error::EvalError;
+use crate::impl_::{EvalCtx, Expression, SharedExpression};
+
+/// `input =~ pattern` with a constant pattern, the regex is compiled once when the expression is built.
+#[derive(Debug)]
+pub struct RegexMatchExpr {
+    pub input: SharedExpression,
+    pub regex: Regex,
+}
+
+impl Expression for RegexMatchExpr {
+    fn typ(&self) -> &DataType {
+        &DataType::Bool
+    }
+
+    fn eval_batch(&self, chunk: &DataChunk, ctx: &dyn EvalCtx) -> Result<ArrayRef, EvalError> {
+        let input = self.input.eval_batch(chunk, ctx)?;
+        let vis = chunk.visibility();
+        let mut builder = BoolArrayBuilder::with_capacity(chunk.len());
+        for i in 0..chunk.len() {
+            match input.get(i) {
+                Some(ScalarRef::String(s)) if vis[i] => builder.push(Some(self.regex.is_match(s))),
+                _ => builder.push(None),
+            }
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+}
@@ -1,9 +1,10 @@
+use std::collections::HashMap;
 use std::process::Command;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use elio_common::scalar::Row;
+use elio_common::scalar::{Row, ScalarValue};
 use elio_core::db_env::{DbConfig, DbEnv};
 use elio_core::error::Error as GraphDBError;
 use elio_core::session::Session;
@@ -11,25 +12,41 @@ use futures::stream::StreamExt;
 use sqllogictest::{AsyncDB, ColumnType, DBOutput};
 use tempfile::TempDir;
 
-pub struct EmbeddedGraphDB {
-    _db: Arc<DbEnv>,
-    sess: Arc<Session>,
+/// A database of a test file, every connection of the file is a session of it
+pub struct TestDatabase {
+    db: Arc<DbEnv>,
     // hold db files, references the temp file, in case of temp file is deleted during test
-    _temp_dir: TempDir,
+    temp_dir: Arc<TempDir>,
 }
 
-impl EmbeddedGraphDB {
+impl TestDatabase {
     pub fn open(temp_dir: TempDir) -> Result<Self, GraphDBError> {
-        let config = DbConfig::with_db_path(temp_dir.path());
+        // tiny sort budget, so that tests also cover sorts spilling to disk
+        let config = DbConfig::with_db_path(temp_dir.path()).with_sort_memory_budget(1024);
 
         let db = DbEnv::open(&config)?;
-        let sess = db.new_session();
         Ok(Self {
-            _db: db,
-            sess,
-            _temp_dir: temp_dir,
+            db,
+            temp_dir: Arc::new(temp_dir),
         })
     }
+
+    pub fn connect(&self) -> EmbeddedGraphDB {
+        EmbeddedGraphDB {
+            _db: self.db.clone(),
+            sess: self.db.new_session(),
+            params: HashMap::new(),
+            _temp_dir: self.temp_dir.clone(),
+        }
+    }
+}
+
+pub struct EmbeddedGraphDB {
+    _db: Arc<DbEnv>,
+    sess: Arc<Session>,
+    // parameters set by `:param`, passed to every query
+    params: HashMap<String, ScalarValue>,
+    _temp_dir: Arc<TempDir>,
 }
 
 fn convert_row(row: Row) -> Vec<String> {
@@ -74,7 +91,21 @@ impl AsyncDB for EmbeddedGraphDB {
 
     /// Async run a SQL query and return the output.
     async fn run(&mut self, sql: &str) -> Result<DBOutput<Self::ColumnType>, Self::Error> {
-        let mut stream = self.sess.execute(sql.to_string(), Default::default()).await?;
+        // `:param name => expr` sets parameter $name to the value of expr, like cypher-shell
+        if let Some((name, expr)) = sql.trim().strip_prefix(":param").and_then(|x| x.split_once("=>")) {
+            let mut stream = self
+                .sess
+                .execute(format!("RETURN {}", expr), self.params.clone())
+                .await?;
+            let value = match stream.next().await {
+                Some(row) => row?.pop().flatten(),
+                None => None,
+            };
+            self.params.insert(name.trim().to_string(), value.unwrap_or_default());
+            return Ok(DBOutput::StatementComplete(0));
+        }
+
+        let mut stream = self.sess.execute(sql.to_string(), self.params.clone()).await?;
         let mut rows = Vec::new();
         while let Some(row) = stream.next().await {
             let row = row?;
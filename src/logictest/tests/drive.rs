@@ -3,7 +3,7 @@ extern crate logictest;
 use std::path::Path;
 use std::sync::LazyLock;
 
-use logictest::graphdb::{EmbeddedGraphDB, graphdb_column_validator};
+use logictest::graphdb::{TestDatabase, graphdb_column_validator};
 use sqllogictest::runner::Runner;
 use sqllogictest::{default_normalizer, default_validator};
 use tempfile::tempdir;
@@ -18,10 +18,12 @@ static RT: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
 datatest_stable::harness! {{test=run_slt_file, root="tests/", pattern=r".*slt"},}
 
 fn run_slt_file(path: &Path) -> datatest_stable::Result<()> {
-    let make_conn = || async {
-        let temp_dir = tempdir().expect("failed to create temp dir for testing db");
-        let db = EmbeddedGraphDB::open(temp_dir)?;
-        Ok(db)
+    // the connections of a file share the database
+    let temp_dir = tempdir().expect("failed to create temp dir for testing db");
+    let db = TestDatabase::open(temp_dir)?;
+    let make_conn = || {
+        let conn = db.connect();
+        async { Ok(conn) }
     };
 
     let mut runner = Runner::new(make_conn);
@@ -3,6 +3,8 @@
 use derive_more::Display;
 use enum_as_inner::EnumAsInner;
 
+use crate::ast::PatternPart;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EnumAsInner)]
 pub enum Expr {
     Literal {
@@ -48,6 +50,54 @@ pub enum Expr {
         distinct: bool,
         args: Vec<Expr>,
     },
+    // count(*)
+    CountStar,
+    // CASE [operand] WHEN .. THEN .. [ELSE ..] END
+    Case {
+        operand: Option<Box<Expr>>,
+        alternatives: Vec<(Expr, Expr)>,
+        else_: Option<Box<Expr>>,
+    },
+    // [x IN list WHERE filter | map]
+    ListComprehension {
+        variable: String,
+        list: Box<Expr>,
+        filter: Option<Box<Expr>>,
+        map: Option<Box<Expr>>,
+    },
+    // all/any/none/single(x IN list WHERE predicate)
+    ListPredicate {
+        kind: ListPredicateKind,
+        variable: String,
+        list: Box<Expr>,
+        predicate: Box<Expr>,
+    },
+    // reduce(acc = init, x IN list | expr)
+    Reduce {
+        accumulator: String,
+        init: Box<Expr>,
+        variable: String,
+        list: Box<Expr>,
+        expr: Box<Expr>,
+    },
+    // [(a)-->(b) WHERE filter | map]
+    PatternComprehension {
+        pattern: Box<PatternPart>,
+        filter: Option<Box<Expr>>,
+        map: Box<Expr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Display, PartialEq, Eq, Hash)]
+pub enum ListPredicateKind {
+    #[display("all")]
+    All,
+    #[display("any")]
+    Any,
+    #[display("none")]
+    None,
+    #[display("single")]
+    Single,
 }
 
 impl Expr {
@@ -135,6 +185,50 @@ impl Expr {
     pub fn new_function_call(name: String, distinct: bool, args: Vec<Expr>) -> Self {
         Expr::FunctionCall { name, distinct, args }
     }
+
+    pub fn new_list_comprehension(variable: String, list: Expr, filter: Option<Expr>, map: Option<Expr>) -> Self {
+        Expr::ListComprehension {
+            variable,
+            list: Box::new(list),
+            filter: filter.map(Box::new),
+            map: map.map(Box::new),
+        }
+    }
+
+    pub fn new_list_predicate(kind: ListPredicateKind, variable: String, list: Expr, predicate: Expr) -> Self {
+        Expr::ListPredicate {
+            kind,
+            variable,
+            list: Box::new(list),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    pub fn new_reduce(accumulator: String, init: Expr, variable: String, list: Expr, expr: Expr) -> Self {
+        Expr::Reduce {
+            accumulator,
+            init: Box::new(init),
+            variable,
+            list: Box::new(list),
+            expr: Box::new(expr),
+        }
+    }
+
+    pub fn new_pattern_comprehension(pattern: PatternPart, filter: Option<Expr>, map: Expr) -> Self {
+        Expr::PatternComprehension {
+            pattern: Box::new(pattern),
+            filter: filter.map(Box::new),
+            map: Box::new(map),
+        }
+    }
+
+    pub fn new_case(operand: Option<Expr>, alternatives: Vec<(Expr, Expr)>, else_: Option<Expr>) -> Self {
+        Expr::Case {
+            operand: operand.map(Box::new),
+            alternatives,
+            else_: else_.map(Box::new),
+        }
+    }
 }
 
 impl std::fmt::Display for Expr {
@@ -186,6 +280,59 @@ impl std::fmt::Display for Expr {
                     args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().join(", ")
                 )
             }
+            Expr::CountStar => write!(f, "count(*)"),
+            Expr::Case {
+                operand,
+                alternatives,
+                else_,
+            } => {
+                write!(f, "CASE")?;
+                if let Some(operand) = operand {
+                    write!(f, " {operand}")?;
+                }
+                for (when, then) in alternatives {
+                    write!(f, " WHEN {when} THEN {then}")?;
+                }
+                if let Some(else_) = else_ {
+                    write!(f, " ELSE {else_}")?;
+                }
+                write!(f, " END")
+            }
+            Expr::ListComprehension {
+                variable,
+                list,
+                filter,
+                map,
+            } => {
+                write!(f, "[{variable} IN {list}")?;
+                if let Some(filter) = filter {
+                    write!(f, " WHERE {filter}")?;
+                }
+                if let Some(map) = map {
+                    write!(f, " | {map}")?;
+                }
+                write!(f, "]")
+            }
+            Expr::ListPredicate {
+                kind,
+                variable,
+                list,
+                predicate,
+            } => write!(f, "{kind}({variable} IN {list} WHERE {predicate})"),
+            Expr::Reduce {
+                accumulator,
+                init,
+                variable,
+                list,
+                expr,
+            } => write!(f, "reduce({accumulator} = {init}, {variable} IN {list} | {expr})"),
+            Expr::PatternComprehension { pattern, filter, map } => {
+                write!(f, "[{pattern}")?;
+                if let Some(filter) = filter {
+                    write!(f, " WHERE {filter}")?;
+                }
+                write!(f, " | {map}]")
+            }
         }
     }
 }
@@ -301,10 +448,11 @@ binary_operator! {
     StartsWith => "STARTS WITH", "starts_with",
     EndsWith => "ENDS WITH", "ends_with",
     Contains => "CONTAINS", "contains",
+    RegexMatch => "=~", "regex_match",
     In => "IN", "in",
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum LabelExpr {
     Label(String),
     // (n:A|B)
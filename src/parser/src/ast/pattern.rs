@@ -28,7 +28,7 @@ impl std::fmt::Display for UpdatePattern {
     }
 }
 
-#[derive(Default, Debug, Display, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Display, PartialEq, Eq, Hash)]
 #[display("{}", _0)]
 pub enum Selector {
     #[default]
@@ -59,7 +59,7 @@ impl Selector {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PatternPart {
     pub variable: Option<String>, // pattern part with name
     pub selector: Selector,
@@ -106,7 +106,7 @@ impl std::fmt::Display for PatternPart {
     }
 }
 
-#[derive(Debug, Display)]
+#[derive(Debug, Clone, Display, PartialEq, Eq, Hash)]
 #[display("{}", _0)]
 pub enum PathFactor {
     #[display("{}", _0)]
@@ -128,7 +128,7 @@ impl PathFactor {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SimplePathPattern {
     pub nodes: Vec<NodePattern>,
     pub relationships: Vec<RelationshipPattern>,
@@ -145,7 +145,7 @@ impl std::fmt::Display for SimplePathPattern {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct QuantifiedPathPattern {
     pub non_selective_part: Box<PatternPart>,
     pub quantifier: PatternQuantifier,
@@ -166,7 +166,7 @@ impl std::fmt::Display for QuantifiedPathPattern {
     }
 }
 
-#[derive(Debug, Display)]
+#[derive(Debug, Clone, Display, PartialEq, Eq, Hash)]
 #[display("{}", _0)]
 pub enum PatternQuantifier {
     #[display("+")]
@@ -179,7 +179,7 @@ pub enum PatternQuantifier {
     Interval { lower: Option<u32>, upper: Option<u32> }, // {n,m}
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NodePattern {
     pub variable: Option<String>,
     pub label_expr: Option<LabelExpr>,
@@ -199,7 +199,7 @@ impl std::fmt::Display for NodePattern {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RelationshipPattern {
     pub variable: Option<String>,
     pub label_expr: Option<LabelExpr>,
@@ -1,7 +1,7 @@
 use derive_more::Display;
 use itertools::{self, Itertools};
 
-use crate::ast::pattern::UpdatePattern;
+use crate::ast::pattern::{PatternPart, UpdatePattern};
 use crate::ast::{Expr, Literal, MatchPattern, OrderBy, ReturnItems};
 
 #[derive(Debug)]
@@ -36,10 +36,14 @@ pub enum Clause {
     #[display("CREATE {}", _0)]
     Create(CreateClause),
     // INSERT
-    // DELETE
-    // SET
-    // REMOVE
-    // MERGE
+    #[display("{}", _0)]
+    Delete(DeleteClause),
+    #[display("SET {}", _0.iter().join(", "))]
+    Set(Vec<SetItem>),
+    #[display("REMOVE {}", _0.iter().join(", "))]
+    Remove(Vec<RemoveItem>),
+    #[display("{}", _0)]
+    Merge(MergeClause),
     // LET
     #[display("{}", _0)]
     Match(MatchClause),
@@ -59,6 +63,78 @@ pub struct CreateClause {
     pub pattern: UpdatePattern,
 }
 
+/// [DETACH] DELETE expr, ...
+#[derive(Debug)]
+pub struct DeleteClause {
+    pub detach: bool,
+    pub exprs: Vec<Expr>,
+}
+
+impl std::fmt::Display for DeleteClause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.detach {
+            write!(f, "DETACH ")?;
+        }
+        write!(f, "DELETE {}", self.exprs.iter().join(", "))
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum SetItem {
+    /// n.p = expr
+    #[display("{variable}.{key} = {value}")]
+    Property {
+        variable: String,
+        key: String,
+        value: Box<Expr>,
+    },
+    /// n = expr, or n += expr if merge
+    #[display("{variable} {} {value}", if *merge { "+=" } else { "=" })]
+    Properties {
+        variable: String,
+        value: Box<Expr>,
+        merge: bool,
+    },
+    /// n:A:B
+    #[display("{variable}:{}", labels.join(":"))]
+    Labels { variable: String, labels: Vec<String> },
+}
+
+/// MERGE pattern [ON CREATE SET ...] [ON MATCH SET ...]
+#[derive(Debug)]
+pub struct MergeClause {
+    pub pattern: PatternPart,
+    pub actions: Vec<MergeAction>,
+}
+
+impl std::fmt::Display for MergeClause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MERGE {}", self.pattern)?;
+        for action in &self.actions {
+            write!(f, " {action}")?;
+        }
+        Ok(())
+    }
+}
+
+/// ON CREATE SET ... if on_create, otherwise ON MATCH SET ...
+#[derive(Debug, Display)]
+#[display("ON {} SET {}", if *on_create { "CREATE" } else { "MATCH" }, items.iter().join(", "))]
+pub struct MergeAction {
+    pub on_create: bool,
+    pub items: Vec<SetItem>,
+}
+
+#[derive(Debug, Display)]
+pub enum RemoveItem {
+    /// n.p
+    #[display("{variable}.{key}")]
+    Property { variable: String, key: String },
+    /// n:A:B
+    #[display("{variable}:{}", labels.join(":"))]
+    Labels { variable: String, labels: Vec<String> },
+}
+
 #[derive(Debug)]
 pub struct MatchClause {
     pub optional: bool,
@@ -85,10 +161,13 @@ impl std::fmt::Display for MatchClause {
 
 #[derive(Default, Debug, Display)]
 pub enum MatchMode {
+    #[display(" WALK")]
     WALK, // no constraint
     #[default]
     #[display("")]
     TRAIL, // different relationship
+    #[display(" ACYCLIC")]
+    ACYCLIC, // different node
 }
 
 #[derive(Debug)]
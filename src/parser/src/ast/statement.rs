@@ -13,6 +13,16 @@ pub enum Statement {
     CreateConstraint(Box<CreateConstraint>),
     #[display("{}", _0)]
     DropConstraint(Box<DropConstraint>),
+    #[display("{}", _0)]
+    CreateIndex(Box<CreateIndex>),
+    #[display("{}", _0)]
+    DropIndex(Box<DropIndex>),
+    #[display("BEGIN")]
+    Begin,
+    #[display("COMMIT")]
+    Commit,
+    #[display("ROLLBACK")]
+    Rollback,
 }
 
 /// CREATE CONSTRAINT constraint_name [IF NOT EXISTS]
@@ -63,6 +73,8 @@ pub enum ConstraintType {
     Unique { properties: Vec<PropertyRef> },
     /// Composite key constraint (properties form a unique key)
     NodeKey { properties: Vec<PropertyRef> },
+    /// Composite key constraint of a relationship type
+    RelationshipKey { properties: Vec<PropertyRef> },
     /// Property must not be null
     NotNull { property: PropertyRef },
 }
@@ -82,6 +94,10 @@ impl std::fmt::Display for ConstraintType {
                 let props = properties.iter().map(|p| p.to_string()).join(", ");
                 write!(f, "({}) IS NODE KEY", props)
             }
+            ConstraintType::RelationshipKey { properties } => {
+                let props = properties.iter().map(|p| p.to_string()).join(", ");
+                write!(f, "({}) IS RELATIONSHIP KEY", props)
+            }
             ConstraintType::NotNull { property } => {
                 write!(f, "{} IS NOT NULL", property)
             }
@@ -113,3 +129,42 @@ impl std::fmt::Display for DropConstraint {
         Ok(())
     }
 }
+
+/// CREATE INDEX index_name [IF NOT EXISTS]
+/// FOR (var:Label) ON (var.p1, var.p2, ...)
+#[derive(Debug)]
+pub struct CreateIndex {
+    pub name: String,
+    pub if_not_exists: bool,
+    pub variable: String,
+    pub label: String,
+    pub properties: Vec<PropertyRef>,
+}
+
+impl std::fmt::Display for CreateIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE INDEX {}", self.name)?;
+        if self.if_not_exists {
+            write!(f, " IF NOT EXISTS")?;
+        }
+        let props = self.properties.iter().map(|p| p.to_string()).join(", ");
+        write!(f, " FOR ({}:{}) ON ({})", self.variable, self.label, props)
+    }
+}
+
+/// DROP INDEX index_name [IF EXISTS]
+#[derive(Debug)]
+pub struct DropIndex {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl std::fmt::Display for DropIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP INDEX {}", self.name)?;
+        if self.if_exists {
+            write!(f, " IF EXISTS")?;
+        }
+        Ok(())
+    }
+}
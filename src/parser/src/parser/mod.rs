@@ -16,6 +16,9 @@ peg::parser! {
     pub rule statement() -> Statement
         = _? s:create_constraint_stmt() _? (";" _?)? { s }
         / _? s:drop_constraint_stmt() _? (";" _?)? { s }
+        / _? s:create_index_stmt() _? (";" _?)? { s }
+        / _? s:drop_index_stmt() _? (";" _?)? { s }
+        / _? s:transaction_stmt() _? (";" _?)? { s }
         / _? s:regular_query() _? (";" _?)? { s }
 
     /// CREATE CONSTRAINT constraint_name [IF NOT EXISTS]
@@ -48,11 +51,14 @@ peg::parser! {
             }
         }
 
-    /// IS UNIQUE | IS NODE KEY | IS NOT NULL
+    /// IS UNIQUE | IS NODE KEY | IS RELATIONSHIP KEY | IS NOT NULL
     rule constraint_type() -> ConstraintType
         = props:property_ref_list() _ IS() _ NODE() _ KEY() {
             ConstraintType::NodeKey { properties: props }
         }
+        / props:property_ref_list() _ IS() _ RELATIONSHIP() _ KEY() {
+            ConstraintType::RelationshipKey { properties: props }
+        }
         / props:property_ref_list() _ IS() _ UNIQUE() {
             ConstraintType::Unique { properties: props }
         }
@@ -83,6 +89,37 @@ peg::parser! {
             }))
         }
 
+    /// CREATE INDEX index_name [IF NOT EXISTS]
+    /// FOR (var:Label) ON (var.p1, var.p2)
+    rule create_index_stmt() -> Statement
+        = CREATE() _ INDEX() _ name:ident()
+          not_exists:(_ IF() _ NOT() _ EXISTS())?
+          _ FOR() _ "(" _? var:ident() _? ":" _? label:ident() _? ")"
+          _ ON() _? properties:property_ref_list() {
+            Statement::CreateIndex(Box::new(CreateIndex {
+                name: name.to_string(),
+                if_not_exists: not_exists.is_some(),
+                variable: var.to_string(),
+                label: label.to_string(),
+                properties,
+            }))
+        }
+
+    /// DROP INDEX index_name [IF EXISTS]
+    rule drop_index_stmt() -> Statement
+        = DROP() _ INDEX() _ name:ident() if_exists:(_ IF() _ EXISTS())? {
+            Statement::DropIndex(Box::new(DropIndex {
+                name: name.to_string(),
+                if_exists: if_exists.is_some(),
+            }))
+        }
+
+    /// BEGIN | COMMIT | ROLLBACK
+    rule transaction_stmt() -> Statement
+        = BEGIN() { Statement::Begin }
+        / COMMIT() { Statement::Commit }
+        / ROLLBACK() { Statement::Rollback }
+
     /// create database statement
     // pub rule create_database() -> Statement
     //     = CREATE() _ DATABASE() _ not_exists:if_not_exists() _ db_name:ident() _ options:with_attribute_list()? {
@@ -205,6 +242,18 @@ peg::parser! {
         / create:create_clause() {
             Clause::Create(create)
         }
+        / delete:delete_clause() {
+            Clause::Delete(delete)
+        }
+        / SET() _ items:(set_item() ++ comma_separator()) {
+            Clause::Set(items)
+        }
+        / REMOVE() _ items:(remove_item() ++ comma_separator()) {
+            Clause::Remove(items)
+        }
+        / merge:merge_clause() {
+            Clause::Merge(merge)
+        }
         / match_:match_clause() {
             Clause::Match(match_)
         }
@@ -227,6 +276,49 @@ peg::parser! {
             }
         }
 
+    rule delete_clause() -> DeleteClause
+        = detach:(DETACH() _)? DELETE() _ exprs:(expr() ++ comma_separator()) {
+            DeleteClause {
+                detach: detach.is_some(),
+                exprs,
+            }
+        }
+
+    rule set_item() -> SetItem
+        = variable:ident() _? "." _? key:ident() _? "=" _? value:expr() {
+            SetItem::Property { variable: variable.to_string(), key: key.to_string(), value: Box::new(value) }
+        }
+        / variable:ident() _? "+=" _? value:expr() {
+            SetItem::Properties { variable: variable.to_string(), value: Box::new(value), merge: true }
+        }
+        / variable:ident() _? "=" _? value:expr() {
+            SetItem::Properties { variable: variable.to_string(), value: Box::new(value), merge: false }
+        }
+        / variable:ident() labels:set_labels() {
+            SetItem::Labels { variable: variable.to_string(), labels }
+        }
+
+    rule merge_clause() -> MergeClause
+        = MERGE() _ pattern:pattern_part() actions:(_ action:merge_action() { action })* {
+            MergeClause { pattern, actions }
+        }
+
+    rule merge_action() -> MergeAction
+        = ON() _ on:(CREATE() / MATCH()) _ SET() _ items:(set_item() ++ comma_separator()) {
+            MergeAction { on_create: on == "CREATE", items }
+        }
+
+    rule remove_item() -> RemoveItem
+        = variable:ident() _? "." _? key:ident() {
+            RemoveItem::Property { variable: variable.to_string(), key: key.to_string() }
+        }
+        / variable:ident() labels:set_labels() {
+            RemoveItem::Labels { variable: variable.to_string(), labels }
+        }
+
+    rule set_labels() -> Vec<String>
+        = labels:(_? ":" _? label:ident() { label.to_string() })+ { labels }
+
     rule match_clause() -> MatchClause
         = optional:optional_match() mode:match_mode()? _ patterns:pattern() where_:where_clause()? {
             MatchClause {
@@ -318,26 +410,30 @@ peg::parser! {
     pub(crate) rule match_mode() -> MatchMode
         = _ WALK() { MatchMode::WALK }
         / _ TRAIL() { MatchMode::TRAIL }
+        / _ ACYCLIC() { MatchMode::ACYCLIC }
 
     pub(crate) rule selector() -> Selector
-        = _ ALL() _ PATH_OR_PATHS() {
+        = _? ALL() _ SHORTEST() (_ PATH_OR_PATHS())? {
+            Selector::AllShortest
+        }
+        / _? ALL() (_ PATH_OR_PATHS())? {
             Selector::AllPaths
         }
-        / _ ANY() _ count:integer_literal() _ PATH_OR_PATHS() {
+        / _? ANY() _ SHORTEST() (_ PATH_OR_PATHS())? {
+            Selector::AnyShortestPath
+        }
+        / _? ANY() _ count:integer_literal() (_ PATH_OR_PATHS())? {
             let count: u32 = count.parse().unwrap();
             Selector::AnyPath(count)
         }
-        / _ ALL() _ SHORTEST() _ PATH_OR_PATHS() {
-            Selector::AllShortest
+        / _? ANY() (_ PATH_OR_PATHS())? {
+            Selector::AnyPath(1)
         }
-        / _ ANY() _ SHORTEST() _ PATH_OR_PATHS() {
-            Selector::AnyShortestPath
-        }
-        / _ SHORTEST() _ count:integer_literal() _ PATH_OR_PATHS() _ GROUP_OR_GROUPS() {
+        / _? SHORTEST() _ count:integer_literal() (_ PATH_OR_PATHS())? _ GROUP_OR_GROUPS() {
             let count: u32 = count.parse().unwrap();
             Selector::CountedShortestGroup(count)
         }
-        / _ SHORTEST() _ count:integer_literal() _ PATH_OR_PATHS() {
+        / _? SHORTEST() _ count:integer_literal() (_ PATH_OR_PATHS())? {
             let count: u32 = count.parse().unwrap();
             Selector::CountedShortestPath(count)
         }
@@ -590,7 +686,7 @@ peg::parser! {
             --
                     _? NOT() _ right:@ {Expr::new_unary(UnaryOperator::Not, right)}
             --
-            left:(@) _? op:$("=" / "!=" / "<>" / "<=" / "<" / ">=" / ">") _? right:@ {
+            left:(@) _? op:$("=" !"~" / "!=" / "<>" / "<=" / "<" / ">=" / ">") _? right:@ {
                 let operator = match op {
                     "=" => BinaryOperator::Eq,
                     "!="  | "<>" => BinaryOperator::NotEq,
@@ -606,6 +702,11 @@ peg::parser! {
             left:(@) _ op:null_predicate() {
                 Expr::new_unary(op, left)
             }
+            left:(@) _ STARTS() _ WITH() _ right:@ { Expr::new_binary(left, BinaryOperator::StartsWith, right) }
+            left:(@) _ ENDS() _ WITH() _ right:@ { Expr::new_binary(left, BinaryOperator::EndsWith, right) }
+            left:(@) _ CONTAINS() _ right:@ { Expr::new_binary(left, BinaryOperator::Contains, right) }
+            left:(@) _ IN() _ right:@ { Expr::new_binary(left, BinaryOperator::In, right) }
+            left:(@) _? "=~" _? right:@ { Expr::new_binary(left, BinaryOperator::RegexMatch, right) }
             --
             left:(@) _? op:$("+" / "-" / "||" ) _? right:@ {
                 let operator = match op {
@@ -661,7 +762,13 @@ peg::parser! {
         / "$" v:ident() { Expr::new_parameter(v.to_string()) }
         / "(" _? e:expr() _? ")" { e }
         / m:map_expr() { m }
+        / l:list_comprehension() { l }
+        / p:pattern_comprehension() { p }
         / l:list_expr() { l }
+        / c:count_star() { c }
+        / c:case_expr() { c }
+        / p:list_predicate() { p }
+        / r:reduce() { r }
         / f:function_call() { f }
         / v:variable() { v }
 
@@ -673,6 +780,18 @@ peg::parser! {
         / n:null_literal() { n }
 
 
+    rule count_star() -> Expr
+        = COUNT() _? "(" _? "*" _? ")" { Expr::CountStar }
+
+    rule case_expr() -> Expr
+        = CASE() _ operand:(!(WHEN() _) e:expr() _ { e })?
+          alternatives:(case_alternative() ++ _)
+          else_:(_ ELSE() _ e:expr() { e })? _ END() {
+            Expr::new_case(operand, alternatives, else_)
+        }
+    rule case_alternative() -> (Expr, Expr)
+        = WHEN() _ when:expr() _ THEN() _ then:expr() { (when, then) }
+
     rule function_call() -> Expr
         = name:ident() _? "(" _? distinct:distinct()? _? args:(expr() ** comma_separator()) _? ")" {
             Expr::new_function_call(name.to_string(), distinct.unwrap_or(false), args)
@@ -711,6 +830,34 @@ peg::parser! {
             Expr::new_list_expression(items)
         }
 
+    rule list_comprehension() -> Expr
+        = "[" _? variable:ident() _ IN() _ list:expr()
+          filter:(_ WHERE() _ e:expr() { e })?
+          map:(_? "|" _? e:expr() { e })? _? "]" {
+            Expr::new_list_comprehension(variable.to_string(), list, filter, map)
+        }
+
+    rule pattern_comprehension() -> Expr
+        = "[" _? pattern:pattern_part() filter:(_ WHERE() _ e:expr() { e })? _? "|" _? map:expr() _? "]" {
+            Expr::new_pattern_comprehension(pattern, filter, map)
+        }
+
+    rule list_predicate() -> Expr
+        = kind:list_predicate_kind() _? "(" _? variable:ident() _ IN() _ list:expr() _ WHERE() _ predicate:expr() _? ")" {
+            Expr::new_list_predicate(kind, variable.to_string(), list, predicate)
+        }
+    rule list_predicate_kind() -> ListPredicateKind
+        = ALL() { ListPredicateKind::All }
+        / ANY() { ListPredicateKind::Any }
+        / NONE() { ListPredicateKind::None }
+        / SINGLE() { ListPredicateKind::Single }
+
+    rule reduce() -> Expr
+        = REDUCE() _? "(" _? accumulator:ident() _? "=" _? init:expr() comma_separator()
+          variable:ident() _ IN() _ list:expr() _? "|" _? expr:expr() _? ")" {
+            Expr::new_reduce(accumulator.to_string(), init, variable.to_string(), list, expr)
+        }
+
     /// ---------------------
     /// Label Expression
     /// ---------------------
@@ -804,13 +951,41 @@ peg::parser! {
     rule WHERE() -> &'static str
         = ['w' | 'W'] ['h' | 'H'] ['e' | 'E'] ['r' | 'R'] ['e' | 'E'] { "WHERE" }
     rule OPTIONAL() -> &'static str
-        = ['o' | 'O'] ['p' | 'P'] ['t' | 'T'] ['i' | 'I'] ['o' | 'O'] ['n' | 'N'] { "OPTIONAL" }
+        = ['o' | 'O'] ['p' | 'P'] ['t' | 'T'] ['i' | 'I'] ['o' | 'O'] ['n' | 'N'] ['a' | 'A'] ['l' | 'L'] { "OPTIONAL" }
     rule MATCH() -> &'static str
         = ['m' | 'M'] ['a' | 'A'] ['t' | 'T'] ['c' | 'C'] ['h' | 'H'] { "MATCH" }
     rule WALK() -> &'static str
         = ['w' | 'W'] ['a' | 'A'] ['l' | 'L'] ['k' | 'K'] { "WALK" }
     rule TRAIL() -> &'static str
         = ['t' | 'T'] ['r' | 'R'] ['a' | 'A'] ['i' | 'I'] ['l' | 'L'] { "TRAIL" }
+    rule ACYCLIC() -> &'static str
+        = ['a' | 'A'] ['c' | 'C'] ['y' | 'Y'] ['c' | 'C'] ['l' | 'L'] ['i' | 'I'] ['c' | 'C'] { "ACYCLIC" }
+    rule COUNT() -> &'static str
+        = ['c' | 'C'] ['o' | 'O'] ['u' | 'U'] ['n' | 'N'] ['t' | 'T'] { "COUNT" }
+    rule CASE() -> &'static str
+        = ['c' | 'C'] ['a' | 'A'] ['s' | 'S'] ['e' | 'E'] { "CASE" }
+    rule WHEN() -> &'static str
+        = ['w' | 'W'] ['h' | 'H'] ['e' | 'E'] ['n' | 'N'] { "WHEN" }
+    rule THEN() -> &'static str
+        = ['t' | 'T'] ['h' | 'H'] ['e' | 'E'] ['n' | 'N'] { "THEN" }
+    rule ELSE() -> &'static str
+        = ['e' | 'E'] ['l' | 'L'] ['s' | 'S'] ['e' | 'E'] { "ELSE" }
+    rule END() -> &'static str
+        = ['e' | 'E'] ['n' | 'N'] ['d' | 'D'] { "END" }
+    rule IN() -> &'static str
+        = ['i' | 'I'] ['n' | 'N'] { "IN" }
+    rule STARTS() -> &'static str
+        = ['s' | 'S'] ['t' | 'T'] ['a' | 'A'] ['r' | 'R'] ['t' | 'T'] ['s' | 'S'] { "STARTS" }
+    rule ENDS() -> &'static str
+        = ['e' | 'E'] ['n' | 'N'] ['d' | 'D'] ['s' | 'S'] { "ENDS" }
+    rule CONTAINS() -> &'static str
+        = ['c' | 'C'] ['o' | 'O'] ['n' | 'N'] ['t' | 'T'] ['a' | 'A'] ['i' | 'I'] ['n' | 'N'] ['s' | 'S'] { "CONTAINS" }
+    rule NONE() -> &'static str
+        = ['n' | 'N'] ['o' | 'O'] ['n' | 'N'] ['e' | 'E'] { "NONE" }
+    rule SINGLE() -> &'static str
+        = ['s' | 'S'] ['i' | 'I'] ['n' | 'N'] ['g' | 'G'] ['l' | 'L'] ['e' | 'E'] { "SINGLE" }
+    rule REDUCE() -> &'static str
+        = ['r' | 'R'] ['e' | 'E'] ['d' | 'D'] ['u' | 'U'] ['c' | 'C'] ['e' | 'E'] { "REDUCE" }
     rule DISTINCT() -> &'static str
         = ['d' | 'D'] ['i' | 'I'] ['s' | 'S'] ['t' | 'T'] ['i' | 'I'] ['n' | 'N'] ['c' | 'C'] ['t' | 'T'] { "DISTINCT" }
     rule ORDER() -> &'static str
@@ -833,6 +1008,18 @@ peg::parser! {
         = ['r' | 'R'] ['e' | 'E'] ['t' | 'T'] ['u' | 'U'] ['r' | 'R'] ['n' | 'N'] { "RETURN" }
     rule UNWIND() -> &'static str
         = ['u' | 'U'] ['n' | 'N'] ['w' | 'W'] ['i' | 'I'] ['n' | 'N'] ['d' | 'D'] { "UNWIND" }
+    rule SET() -> &'static str
+        = ['s' | 'S'] ['e' | 'E'] ['t' | 'T'] { "SET" }
+    rule REMOVE() -> &'static str
+        = ['r' | 'R'] ['e' | 'E'] ['m' | 'M'] ['o' | 'O'] ['v' | 'V'] ['e' | 'E'] { "REMOVE" }
+    rule MERGE() -> &'static str
+        = ['m' | 'M'] ['e' | 'E'] ['r' | 'R'] ['g' | 'G'] ['e' | 'E'] { "MERGE" }
+    rule ON() -> &'static str
+        = ['o' | 'O'] ['n' | 'N'] { "ON" }
+    rule DELETE() -> &'static str
+        = ['d' | 'D'] ['e' | 'E'] ['l' | 'L'] ['e' | 'E'] ['t' | 'T'] ['e' | 'E'] { "DELETE" }
+    rule DETACH() -> &'static str
+        = ['d' | 'D'] ['e' | 'E'] ['t' | 'T'] ['a' | 'A'] ['c' | 'C'] ['h' | 'H'] { "DETACH" }
     rule LOAD() -> &'static str
         = ['l' | 'L'] ['o' | 'O'] ['a' | 'A'] ['d' | 'D'] { "LOAD" }
     rule OPTIONS() -> &'static str
@@ -869,9 +1056,24 @@ peg::parser! {
         = ['u' | 'U'] ['n' | 'N'] ['i' | 'I'] ['q' | 'Q'] ['u' | 'U'] ['e' | 'E'] { "UNIQUE" }
     rule NODE() -> &'static str
         = ['n' | 'N'] ['o' | 'O'] ['d' | 'D'] ['e' | 'E'] { "NODE" }
+    rule RELATIONSHIP() -> &'static str
+        = ['r' | 'R'] ['e' | 'E'] ['l' | 'L'] ['a' | 'A'] ['t' | 'T'] ['i' | 'I'] ['o' | 'O'] ['n' | 'N']
+          ['s' | 'S'] ['h' | 'H'] ['i' | 'I'] ['p' | 'P'] { "RELATIONSHIP" }
     rule DROP() -> &'static str
         = ['d' | 'D'] ['r' | 'R'] ['o' | 'O'] ['p' | 'P'] { "DROP" }
 
+    // Index keywords
+    rule INDEX() -> &'static str
+        = ['i' | 'I'] ['n' | 'N'] ['d' | 'D'] ['e' | 'E'] ['x' | 'X'] { "INDEX" }
+
+    // Transaction keywords
+    rule BEGIN() -> &'static str
+        = ['b' | 'B'] ['e' | 'E'] ['g' | 'G'] ['i' | 'I'] ['n' | 'N'] { "BEGIN" }
+    rule COMMIT() -> &'static str
+        = ['c' | 'C'] ['o' | 'O'] ['m' | 'M'] ['m' | 'M'] ['i' | 'I'] ['t' | 'T'] { "COMMIT" }
+    rule ROLLBACK() -> &'static str
+        = ['r' | 'R'] ['o' | 'O'] ['l' | 'L'] ['l' | 'L'] ['b' | 'B'] ['a' | 'A'] ['c' | 'C'] ['k' | 'K'] { "ROLLBACK" }
+
     // operator
     rule OR() -> &'static str
         = ['o' | 'O'] ['r' | 'R'] { "OR" }
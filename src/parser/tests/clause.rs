@@ -33,6 +33,33 @@ fn test_match() {
     assert_snapshot!(clause!("MATCH (n:Person)"), @"MATCH (n:Person)");
     assert_snapshot!(clause!("MATCH (n:Person) WHERE n.name = 'Alice'"), @"MATCH (n:Person) WHERE (n.name) = ('Alice')");
     assert_snapshot!(clause!("MATCH (n:Person) WHERE n.name = 'Alice'"), @"MATCH (n:Person) WHERE (n.name) = ('Alice')");
+    assert_snapshot!(clause!("OPTIONAL MATCH (n)-[:MANAGED_BY]->(m) WHERE m.age > 30"), @"OPTIONAL MATCH (n)-[:MANAGED_BY]->(m) WHERE (m.age) > (30)");
+    assert_snapshot!(clause!("MATCH WALK (a)-[:KNOWS*1..3]->(b)"), @"MATCH WALK (a)-[:KNOWS*1..3]->(b)");
+    assert_snapshot!(clause!("MATCH TRAIL (a)-[:KNOWS]->(b)"), @"MATCH (a)-[:KNOWS]->(b)");
+    assert_snapshot!(clause!("MATCH ACYCLIC p = (a)-[:KNOWS*]->(b)"), @"MATCH ACYCLIC p = (a)-[:KNOWS*]->(b)");
+}
+
+#[test]
+fn test_delete() {
+    assert_snapshot!(clause!("DELETE n"), @"DELETE n");
+    assert_snapshot!(clause!("DELETE r, n"), @"DELETE r, n");
+    assert_snapshot!(clause!("detach delete n"), @"DETACH DELETE n");
+}
+
+#[test]
+fn test_set_remove() {
+    assert_snapshot!(clause!("SET n.age = 30"), @"SET n.age = 30");
+    assert_snapshot!(clause!("SET n.age = n.age + 1, n:Person:Adult"), @"SET n.age = (n.age) + (1), n:Person:Adult");
+    assert_snapshot!(clause!("SET n += {age: 30}"), @"SET n += {age: 30}");
+    assert_snapshot!(clause!("SET n = m"), @"SET n = m");
+    assert_snapshot!(clause!("REMOVE n.age, n:Person"), @"REMOVE n.age, n:Person");
+}
+
+#[test]
+fn test_merge() {
+    assert_snapshot!(clause!("MERGE (n:Person {id: 1})"), @"MERGE (n:Person{id: 1})");
+    assert_snapshot!(clause!("MERGE (n:Person {id: row.id}) ON CREATE SET n.created = true ON MATCH SET n.seen = n.seen + 1"), @"MERGE (n:Person{id: row.id}) ON CREATE SET n.created = TRUE ON MATCH SET n.seen = (n.seen) + (1)");
+    assert_snapshot!(clause!("MERGE (a)-[r:KNOWS]->(b) ON MATCH SET r.count = r.count + 1, r:Known"), @"MERGE (a)-[r:KNOWS]->(b) ON MATCH SET r.count = (r.count) + (1), r:Known");
 }
 
 #[test]
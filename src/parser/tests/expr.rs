@@ -29,6 +29,9 @@ fn test_literal() {
 #[test]
 fn test_func() {
     assert_snapshot!(expr!("func(1,2)"), @"func(1, 2)");
+    assert_snapshot!(expr!("count(*)"), @"count(*)");
+    assert_snapshot!(expr!("COUNT( * )"), @"count(*)");
+    assert_snapshot!(expr!("count(DISTINCT a)"), @"count(DISTINCT a)");
 }
 
 #[test]
@@ -52,6 +55,17 @@ fn test_compare() {
     assert_snapshot!(expr!("1 != 2"), @"(1) <> (2)");
 }
 
+#[test]
+fn test_string_predicate() {
+    assert_snapshot!(expr!("n.name STARTS WITH 'A'"), @"(n.name) STARTS WITH ('A')");
+    assert_snapshot!(expr!("n.name ends with 'x' + 'y'"), @"(n.name) ENDS WITH (('x') + ('y'))");
+    assert_snapshot!(expr!("n.name CONTAINS 'l' AND n.age > 1"), @"((n.name) CONTAINS ('l')) AND ((n.age) > (1))");
+    assert_snapshot!(expr!("n.name =~ 'A.*'"), @"(n.name) =~ ('A.*')");
+    assert_snapshot!(expr!("n.name='A'"), @"(n.name) = ('A')");
+    assert_snapshot!(expr!("1 IN [1, 2] = true"), @"((1) IN ([1, 2])) = (TRUE)");
+    assert_snapshot!(expr!("x IN list IS NULL"), @"((x) IN (list))IS NULL");
+}
+
 #[test]
 fn test_label_expr() {
     assert_snapshot!(label_expr!(":a|b"), @"(a|b)");
@@ -65,3 +79,31 @@ fn test_atom() {
     assert_snapshot!(expr!("n"), @"n");
     assert_snapshot!(expr!("n.name"), @"n.name");
 }
+
+#[test]
+fn test_case() {
+    assert_snapshot!(expr!("CASE n.age WHEN 1 THEN 'one' WHEN 2 THEN 'two' ELSE 'many' END"), @"CASE n.age WHEN 1 THEN 'one' WHEN 2 THEN 'two' ELSE 'many' END");
+    assert_snapshot!(expr!("case when n.age < 18 then 'minor' end"), @"CASE WHEN (n.age) < (18) THEN 'minor' END");
+    assert_snapshot!(expr!("CASE WHEN a THEN CASE b WHEN 1 THEN 2 END END + 1"), @"(CASE WHEN a THEN CASE b WHEN 1 THEN 2 END END) + (1)");
+    assert_snapshot!(expr!("CASE whenever WHEN x THEN endNode(p) ELSE elsewhere END"), @"CASE whenever WHEN x THEN endNode(p) ELSE elsewhere END");
+}
+
+#[test]
+fn test_list_comprehension() {
+    assert_snapshot!(expr!("[x IN [1, 2, 3] WHERE x > 1 | x * 2]"), @"[x IN [1, 2, 3] WHERE (x) > (1) | (x) * (2)]");
+    assert_snapshot!(expr!("[x IN list WHERE x > 1]"), @"[x IN list WHERE (x) > (1)]");
+    assert_snapshot!(expr!("[x IN list|x.name]"), @"[x IN list | x.name]");
+    assert_snapshot!(expr!("[x IN list]"), @"[x IN list]");
+    assert_snapshot!(expr!("[(a)-[:KNOWS]->(b) WHERE b.age > 20 | b.name]"), @"[(a)-[:KNOWS]->(b) WHERE (b.age) > (20) | b.name]");
+    assert_snapshot!(expr!("[p = (a)-->(b) | p]"), @"[p = (a)-[]->(b) | p]");
+    assert_snapshot!(expr!("[(a), b]"), @"[a, b]");
+}
+
+#[test]
+fn test_list_predicate() {
+    assert_snapshot!(expr!("all(x IN list WHERE x > 1)"), @"all(x IN list WHERE (x) > (1))");
+    assert_snapshot!(expr!("ANY(x IN list WHERE x > 1)"), @"any(x IN list WHERE (x) > (1))");
+    assert_snapshot!(expr!("none (x IN list WHERE x > 1)"), @"none(x IN list WHERE (x) > (1))");
+    assert_snapshot!(expr!("single(x IN list WHERE x > 1)"), @"single(x IN list WHERE (x) > (1))");
+    assert_snapshot!(expr!("reduce(acc = 0, x IN [1, 2] | acc + x)"), @"reduce(acc = 0, x IN [1, 2] | (acc) + (x))");
+}
@@ -58,6 +58,14 @@ fn test_pattern_with_selector() {
     assert_snapshot!(pattern_part!("p = ANY SHORTEST PATHS (a:Person)-[]-(b)"), @"p = ANY SHORTEST PATHS (a:Person)-[]-(b)");
     assert_snapshot!(pattern_part!("p = SHORTEST 42 PATHS (a:Person)-[]-(b)"), @"p = SHORTEST 42 PATHS (a:Person)-[]-(b)");
     assert_snapshot!(pattern_part!("p = SHORTEST 42 PATH GROUPS (a:Person)-[]-(b)"), @"p = SHORTEST 42 PATH GROUPS (a:Person)-[]-(b)");
+    // PATH or PATHS is optional
+    assert_snapshot!(pattern_part!("p = ALL (a:Person)-[]-(b)"), @"p = (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = ANY (a:Person)-[]-(b)"), @"p = ANY 1 PATHS (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = ANY 2 (a:Person)-[]-(b)"), @"p = ANY 2 PATHS (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = ALL SHORTEST (a:Person)-[]-(b)"), @"p = ALL SHORTEST PATHS (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = ANY SHORTEST (a:Person)-[]-(b)"), @"p = ANY SHORTEST PATHS (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = SHORTEST 2 (a:Person)-[]-(b)"), @"p = SHORTEST 2 PATHS (a:Person)-[]-(b)");
+    assert_snapshot!(pattern_part!("p = SHORTEST 2 GROUPS (a:Person)-[]-(b)"), @"p = SHORTEST 2 PATH GROUPS (a:Person)-[]-(b)");
 }
 
 #[test]
@@ -53,6 +53,12 @@ fn test_create_constraint() {
         stmt!("CREATE CONSTRAINT knows_since_unique FOR ()-[r:KNOWS]-() REQUIRE r.since IS UNIQUE"),
         @"CREATE CONSTRAINT knows_since_unique FOR ()-[r:KNOWS]-() REQUIRE r.since IS UNIQUE"
     );
+
+    // Relationship key constraint
+    assert_snapshot!(
+        stmt!("CREATE CONSTRAINT pays_key FOR ()-[r:PAYS]-() REQUIRE (r.ledger, r.tx_id) IS RELATIONSHIP KEY"),
+        @"CREATE CONSTRAINT pays_key FOR ()-[r:PAYS]-() REQUIRE (r.ledger, r.tx_id) IS RELATIONSHIP KEY"
+    );
 }
 
 #[test]
@@ -70,6 +76,30 @@ fn test_drop_constraint() {
     );
 }
 
+#[test]
+fn test_index() {
+    assert_snapshot!(
+        stmt!("CREATE INDEX event_ts FOR (e:Event) ON (e.ts)"),
+        @"CREATE INDEX event_ts FOR (e:Event) ON (e.ts)"
+    );
+
+    // Composite index with IF NOT EXISTS
+    assert_snapshot!(
+        stmt!("CREATE INDEX event_src_ts IF NOT EXISTS FOR (e:Event) ON (e.source, e.ts)"),
+        @"CREATE INDEX event_src_ts IF NOT EXISTS FOR (e:Event) ON (e.source, e.ts)"
+    );
+
+    assert_snapshot!(stmt!("DROP INDEX event_ts"), @"DROP INDEX event_ts");
+    assert_snapshot!(stmt!("DROP INDEX event_ts IF EXISTS"), @"DROP INDEX event_ts IF EXISTS");
+}
+
+#[test]
+fn test_transaction() {
+    assert_snapshot!(stmt!("BEGIN"), @"BEGIN");
+    assert_snapshot!(stmt!("commit;"), @"COMMIT");
+    assert_snapshot!(stmt!(" Rollback "), @"ROLLBACK");
+}
+
 #[test]
 fn test_load() {
     assert_snapshot!(
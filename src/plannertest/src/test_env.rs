@@ -32,6 +32,8 @@ pub struct MockCatalog {
     tokens: Mutex<HashMap<TokenKey, TokenId>>,
     /// Mock indexes: (label_id, property_key_ids) -> (constraint_name)
     indexes: Mutex<HashMap<MockIndexKey, String>>,
+    /// Mock secondary indexes, in creation order
+    range_indexes: Mutex<Vec<IndexHint>>,
 }
 
 impl Default for MockCatalog {
@@ -53,6 +55,7 @@ impl Default for MockCatalog {
             functions,
             tokens: Default::default(),
             indexes: Default::default(),
+            range_indexes: Default::default(),
         }
     }
 }
@@ -67,6 +70,15 @@ impl MockCatalog {
         self.indexes.lock().unwrap().insert(key, constraint_name.to_string());
     }
 
+    /// Add a mock secondary index for testing
+    pub fn add_range_index(&self, label_id: LabelId, property_key_ids: Vec<PropertyKeyId>, index_name: &str) {
+        self.range_indexes.lock().unwrap().push(IndexHint {
+            constraint_name: index_name.to_string(),
+            label_id,
+            property_key_ids,
+        });
+    }
+
     /// Find index matching the given label and properties
     pub fn find_index(
         &self,
@@ -99,17 +111,17 @@ impl MockPlannerSession {
     }
 
     pub fn bind_query(self: &Arc<Self>, ast: &ast::RegularQuery) -> anyhow::Result<String> {
-        let ir = elio_cypher::session::bind_query(self.clone(), ast)?;
+        let ir = elio_cypher::session::bind_query(self.clone(), ast, Default::default())?;
         Ok(ir.explain())
     }
 
     pub fn plan_query(self: &Arc<Self>, ast: &ast::RegularQuery) -> anyhow::Result<String> {
-        let plan = elio_cypher::session::plan_query(self.clone(), ast)?;
+        let plan = elio_cypher::session::plan_query(self.clone(), ast, Default::default())?;
         Ok(plan.explain())
     }
 
-    /// Execute a DDL statement (CREATE CONSTRAINT, DROP CONSTRAINT)
-    /// This records the constraint in the mock catalog for index selection
+    /// Execute a DDL statement (CREATE/DROP CONSTRAINT, CREATE/DROP INDEX)
+    /// This records the constraint or index in the mock catalog for index selection
     pub fn execute_ddl(self: &Arc<Self>, stmt: &ast::Statement) -> anyhow::Result<()> {
         match stmt {
             ast::Statement::CreateConstraint(constraint) => {
@@ -123,7 +135,8 @@ impl MockPlannerSession {
                     ast::ConstraintType::Unique { properties } => {
                         properties.iter().map(|p| p.property.clone()).collect()
                     }
-                    ast::ConstraintType::NodeKey { properties } => {
+                    ast::ConstraintType::NodeKey { properties }
+                    | ast::ConstraintType::RelationshipKey { properties } => {
                         properties.iter().map(|p| p.property.clone()).collect()
                     }
                     ast::ConstraintType::NotNull { property } => vec![property.property.clone()],
@@ -144,7 +157,20 @@ impl MockPlannerSession {
                 // For mock, we just ignore drop constraint
                 Ok(())
             }
-            _ => Err(Error::msg("Only DDL statements (CREATE/DROP CONSTRAINT) are supported")),
+            ast::Statement::CreateIndex(index) => {
+                let label_id = self.get_or_create_token(&index.label, TokenKind::Label)?;
+                let prop_key_ids: Vec<PropertyKeyId> = index
+                    .properties
+                    .iter()
+                    .map(|p| self.get_or_create_token(&p.property, TokenKind::PropertyKey))
+                    .collect::<Result<_, _>>()?;
+                self.catalog.add_range_index(label_id, prop_key_ids, &index.name);
+                Ok(())
+            }
+            ast::Statement::DropIndex(_) => Ok(()),
+            _ => Err(Error::msg(
+                "Only DDL statements (CREATE/DROP CONSTRAINT, CREATE/DROP INDEX) are supported",
+            )),
         }
     }
 }
@@ -165,7 +191,7 @@ impl PlannerSession for MockPlannerSession {
     }
 
     fn get_function_by_name(&self, name: &str) -> Option<&FunctionCatalog> {
-        self.catalog.functions.get(name)
+        self.catalog.functions.get(&name.to_lowercase())
     }
 
     fn get_token_id(&self, token: &str, kind: TokenKind) -> Option<TokenId> {
@@ -188,6 +214,15 @@ impl PlannerSession for MockPlannerSession {
             })
     }
 
+    fn find_indexes(&self, label_id: LabelId) -> Vec<IndexHint> {
+        let indexes = self.catalog.range_indexes.lock().unwrap();
+        indexes
+            .iter()
+            .filter(|index| index.label_id == label_id)
+            .cloned()
+            .collect()
+    }
+
     fn send_notification(&self, _notification: String) {
         todo!()
     }
@@ -0,0 +1,117 @@
+-- group by with count star
+MATCH (n) RETURN n.city, count(*)
+
+/*
+RootIR { names: [n.city, count(*)] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [ncity@1, anon@2] }
+  ├─Project { items: [ncity@1 AS ncity@1, count@3 AS anon@2] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { nodes: [n@0] }
+    └─Aggregate { group_by: [ncity@1 AS n@0.city], aggregate: [anon@2 AS count(*)] }
+RootPlan { names: [n.city, count(*)] }
+└─ProduceResult { return_columns: ncity@1,count@3 }
+  └─Project { exprs: [ncity@1 AS ncity@1, count@3 AS anon@2] }
+    └─Apply
+      ├─Aggregate { group_by: [ncity@1 AS n@0.city], aggregates: [anon@2 AS count(*)] }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Argument { variables: [ncity@1, anon@2] }
+*/
+
+-- aggregate without group keys
+MATCH (n) RETURN count(n), sum(n.age) + 1 AS total
+
+/*
+RootIR { names: [count(n), total] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [anon@1, anon@2] }
+  ├─Project { items: [countn@3 AS anon@1, total@4 AS add(anon@2, 1)] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { nodes: [n@0] }
+    └─Aggregate { aggregate: [anon@1 AS count(n@0), anon@2 AS sum(n@0.age)] }
+RootPlan { names: [count(n), total] }
+└─ProduceResult { return_columns: countn@3,total@4 }
+  └─Project { exprs: [countn@3 AS anon@1, total@4 AS add(anon@2, 1)] }
+    └─Apply
+      ├─Aggregate { aggregates: [anon@1 AS count(n@0), anon@2 AS sum(n@0.age)] }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Argument { variables: [anon@1, anon@2] }
+*/
+
+-- distinct aggregate in with clause
+MATCH (n) WITH n.city AS city, collect(DISTINCT n.name) AS names WHERE city <> 'Rome' RETURN city, names
+
+/*
+RootIR { names: [city, names] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [city@1, names@3] }
+  ├─Project { items: [city@4 AS city@1, names@5 AS names@3] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { imported: [city@1, anon@2] }
+    ├─Project { items: [city@1 AS city@1, names@3 AS anon@2], filter: not_eq(city@1, 'Rome') }
+    └─IrSingleQueryPart
+      ├─QueryGraph { nodes: [n@0] }
+      └─Aggregate { group_by: [city@1 AS n@0.city], aggregate: [anon@2 AS collect(DISTINCT n@0.name)] }
+RootPlan { names: [city, names] }
+└─ProduceResult { return_columns: city@4,names@5 }
+  └─Project { exprs: [city@4 AS city@1, names@5 AS names@3] }
+    └─Apply
+      ├─Filter { condition: not_eq(city@1, 'Rome') }
+      │ └─Project { exprs: [city@1 AS city@1, names@3 AS anon@2] }
+      │   └─Apply
+      │     ├─Aggregate { group_by: [city@1 AS n@0.city], aggregates: [anon@2 AS collect(DISTINCT n@0.name)] }
+      │     │ └─AllNodeScan { variable: n@0 }
+      │     └─Argument { variables: [city@1, anon@2] }
+      └─Argument { variables: [city@1, names@3] }
+*/
+
+-- order by on aggregate
+MATCH (n) WITH n.city AS city, count(*) AS c RETURN city, c ORDER BY c DESC LIMIT 3
+
+/*
+RootIR { names: [city, c] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [city@1, c@3] }
+  ├─Project { items: [city@4 AS city@1, c@5 AS c@3], order_by: [c@5 Desc], pagination: limit: 3 }
+  └─IrSingleQueryPart
+    ├─QueryGraph { imported: [city@1, anon@2] }
+    ├─Project { items: [city@1 AS city@1, c@3 AS anon@2] }
+    └─IrSingleQueryPart
+      ├─QueryGraph { nodes: [n@0] }
+      └─Aggregate { group_by: [city@1 AS n@0.city], aggregate: [anon@2 AS count(*)] }
+RootPlan { names: [city, c] }
+└─ProduceResult { return_columns: city@4,c@5 }
+  └─Pagination { limit: 3 }
+    └─Sort { items: [c@5 Desc] }
+      └─Project { exprs: [city@4 AS city@1, c@5 AS c@3] }
+        └─Apply
+          ├─Project { exprs: [city@1 AS city@1, c@3 AS anon@2] }
+          │ └─Apply
+          │   ├─Aggregate { group_by: [city@1 AS n@0.city], aggregates: [anon@2 AS count(*)] }
+          │   │ └─AllNodeScan { variable: n@0 }
+          │   └─Argument { variables: [city@1, anon@2] }
+          └─Argument { variables: [city@1, c@3] }
+*/
+
+-- distinct projection
+MATCH (n) RETURN DISTINCT n.city AS city
+
+/*
+RootIR { names: [city] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0] }
+  └─Distinct { group_by: [city@1 AS n@0.city] }
+RootPlan { names: [city] }
+└─ProduceResult { return_columns: city@1 }
+  └─Aggregate { group_by: [city@1 AS n@0.city] }
+    └─AllNodeScan { variable: n@0 }
+*/
+
+-- nested aggregate is not allowed
+MATCH (n) RETURN count(count(n))
+
+/*
+Error
+Aggregation function count is not allowed in expression Aggregation
+*/
+
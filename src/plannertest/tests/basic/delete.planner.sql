@@ -0,0 +1,78 @@
+-- delete matched nodes
+MATCH (n:Person) DELETE n
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [n@0], filter: n@0:Person }
+    └─mutating_pattern
+      └─DeletePattern { detach: false, nodes: [n@0] }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@0 }
+  └─DeleteNode { detach: false, nodes: [n@0] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- detach delete matched nodes
+MATCH (n:Person) DETACH DELETE n
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [n@0], filter: n@0:Person }
+    └─mutating_pattern
+      └─DeletePattern { detach: true, nodes: [n@0] }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@0 }
+  └─DeleteNode { detach: true, nodes: [n@0] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- relationships are deleted before nodes
+MATCH (a)-[r:KNOWS]->(b) DELETE r, a
+
+/*
+RootIR { names: [a, b, r] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [a@0, b@1], rels: [(a@0)-[r@2:]->(b@1)] }
+    └─mutating_pattern
+      └─DeletePattern { detach: false, nodes: [a@0], rels: [r@2] }
+RootPlan { names: [a, b, r] }
+└─ProduceResult { return_columns: a@0,b@1,r@2 }
+  └─DeleteNode { detach: false, nodes: [a@0] }
+    └─DeleteRel { rels: [r@2] }
+      └─RelScan { left: a@0, rel: r@2, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
+-- delete after with, then return
+MATCH (n) WITH n WHERE n.age > 30 DELETE n RETURN count(*)
+
+/*
+RootIR { names: [count(*)] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [anon@2] }
+  ├─Project { items: [count@3 AS anon@2] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { imported: [n@1] }
+    │ └─mutating_pattern
+    │   └─DeletePattern { detach: false, nodes: [n@1] }
+    ├─Aggregate { aggregate: [anon@2 AS count(*)] }
+    └─IrSingleQueryPart
+      ├─QueryGraph { nodes: [n@0] }
+      └─Project { items: [n@1 AS n@0], filter: gt(n@1.age, 30) }
+RootPlan { names: [count(*)] }
+└─ProduceResult { return_columns: count@3 }
+  └─Project { exprs: [count@3 AS anon@2] }
+    └─Apply
+      ├─Aggregate { aggregates: [anon@2 AS count(*)] }
+      │ └─DeleteNode { detach: false, nodes: [n@1] }
+      │   └─Apply
+      │     ├─Filter { condition: gt(n@1.age, 30) }
+      │     │ └─Project { exprs: [n@1 AS n@0] }
+      │     │   └─AllNodeScan { variable: n@0 }
+      │     └─Argument { variables: [n@1] }
+      └─Argument { variables: [anon@2] }
+*/
+
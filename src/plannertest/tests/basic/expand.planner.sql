@@ -9,8 +9,7 @@ RootIR { names: [a, b, r] }
 RootPlan { names: [a, b, r] }
 └─ProduceResult { return_columns: a@0,b@1,r@2 }
   └─Project { exprs: [a@0 AS a@0, b@1 AS b@1, r@2 AS r@2] }
-    └─ExpandAll { from: a@0, to: b@1, rel: r@2, direction: -, types: [KNOWS] }
-      └─AllNodeScan { variable: a@0 }
+    └─RelScan { left: a@0, rel: r@2, right: b@1, direction: -, types: [KNOWS] }
 */
 
 -- expand all
@@ -24,8 +23,7 @@ RootIR { names: [a, b, r] }
 RootPlan { names: [a, b, r] }
 └─ProduceResult { return_columns: a@0,b@1,r@2 }
   └─Project { exprs: [a@0 AS a@0, b@1 AS b@1, r@2 AS r@2] }
-    └─ExpandAll { from: a@0, to: b@1, rel: r@2, direction: <-, types: [KNOWS] }
-      └─AllNodeScan { variable: a@0 }
+    └─RelScan { left: a@0, rel: r@2, right: b@1, direction: <-, types: [KNOWS] }
 */
 
 -- variable expand 1..3
@@ -0,0 +1,84 @@
+-- make the label known to the catalog
+CREATE INDEX person_age FOR (p:Person) ON (p.age)
+
+/*
+
+*/
+
+-- make the label known to the catalog
+CREATE INDEX city_population FOR (c:City) ON (c.population)
+
+/*
+
+*/
+
+-- label predicate - uses NodeByLabelScan
+MATCH (n:Person) RETURN n
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Project { exprs: [n@1 AS n@0] }
+    └─NodeByLabelScan { variable: n@0, label: Person }
+*/
+
+-- property predicate stays in the filter
+MATCH (n:Person{name: 'alice'}) RETURN n
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Project { exprs: [n@1 AS n@0] }
+    └─Filter { condition: eq(n@0.name, 'alice') }
+      └─NodeByLabelScan { variable: n@0, label: Person }
+*/
+
+-- label not in the catalog - uses AllNodeScan
+MATCH (n:Unknown) RETURN n
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Project { exprs: [n@1 AS n@0] }
+    └─Filter { condition: n@0:Unknown }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- traversal starts from the node with a label
+MATCH (p)-[:LIVES_IN]->(c:City) RETURN p, c
+
+/*
+RootPlan { names: [p, c] }
+└─ProduceResult { return_columns: p@3,c@4 }
+  └─Project { exprs: [p@3 AS p@0, c@4 AS c@1] }
+    └─ExpandAll { from: c@1, to: p@0, rel: anon@2, direction: <-, types: [LIVES_IN] }
+      └─NodeByLabelScan { variable: c@1, label: City }
+*/
+
+-- label scan with arguments
+MATCH (p:Person) WITH p MATCH (c:City) RETURN p, c
+
+/*
+RootPlan { names: [p, c] }
+└─ProduceResult { return_columns: p@3,c@4 }
+  └─Project { exprs: [p@3 AS p@1, c@4 AS c@2] }
+    └─Apply
+      ├─Project { exprs: [p@1 AS p@0] }
+      │ └─NodeByLabelScan { variable: p@0, label: Person }
+      └─NodeByLabelScan { variable: c@2, label: City, arguments: [p@1] }
+*/
+
+-- optional match expands from the argument
+MATCH (p:Person) OPTIONAL MATCH (p)-[:LIVES_IN]->(c:City) RETURN p, c
+
+/*
+RootPlan { names: [p, c] }
+└─ProduceResult { return_columns: p@3,c@4 }
+  └─Project { exprs: [p@3 AS p@0, c@4 AS c@1] }
+    └─OptionalApply
+      ├─NodeByLabelScan { variable: p@0, label: Person }
+      └─Filter { condition: c@1:Resolved(City, 2) }
+        └─ExpandAll { from: p@0, to: c@1, rel: anon@2, direction: ->, types: [LIVES_IN] }
+          └─Argument { variables: [p@0] }
+*/
+
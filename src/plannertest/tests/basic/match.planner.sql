@@ -57,3 +57,46 @@ RootPlan { names: [n.name] }
       └─AllNodeScan { variable: n@0 }
 */
 
+-- relationships are not repeated across the patterns in TRAIL mode
+MATCH (a)-[r:KNOWS]->(b), (b)-[s:KNOWS*1..2]->(c) RETURN a, c
+
+/*
+RootPlan { names: [a, c] }
+└─ProduceResult { return_columns: a@5,c@6 }
+  └─Project { exprs: [a@5 AS a@0, c@6 AS c@3] }
+    └─Filter { condition: rels_disjoint(r@2, s@4) }
+      └─VarExpandAll { from: b@1, to: c@3, rel_pattern: (b@1)-[s@4:*1..2]->(c@3), path_mode: Trail }
+        └─RelScan { left: a@0, rel: r@2, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
+-- relationships can repeat in WALK mode
+MATCH WALK (a)-[r:KNOWS]->(b), (b)-[s:KNOWS*1..2]->(c) RETURN a, c
+
+/*
+RootPlan { names: [a, c] }
+└─ProduceResult { return_columns: a@5,c@6 }
+  └─Project { exprs: [a@5 AS a@0, c@6 AS c@3] }
+    └─VarExpandAll { from: b@1, to: c@3, rel_pattern: (b@1)-[s@4:*1..2]->(c@3), path_mode: Walk }
+      └─RelScan { left: a@0, rel: r@2, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
+-- variable length relationship must be bounded in WALK mode
+MATCH WALK (a)-[:KNOWS*]->(b) RETURN a, b
+
+/*
+Error
+Pattern -[:KNOWS*]-> must have an upper bound in WALK mode in MATCH WALK (a)-[:KNOWS*]->(b)
+*/
+
+-- nodes are not repeated in ACYCLIC mode
+MATCH ACYCLIC (a)-[:KNOWS]->(b)-[:KNOWS*1..3]->(c) RETURN a, c
+
+/*
+RootPlan { names: [a, c] }
+└─ProduceResult { return_columns: a@5,c@6 }
+  └─Project { exprs: [a@5 AS a@0, c@6 AS c@2] }
+    └─Filter { condition: is_acyclic((a@0)-[anon@3]->(b@1)-[anon@4*]->(c@2)) AND rels_disjoint(anon@3, anon@4) }
+      └─VarExpandAll { from: b@1, to: c@2, rel_pattern: (b@1)-[anon@4:*1..3]->(c@2), path_mode: Acyclic }
+        └─RelScan { left: a@0, rel: anon@3, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
@@ -0,0 +1,128 @@
+-- merge a node without index - AllNodeScan + Filter to match
+MERGE (n:Person {name: 'Alice'}) RETURN n
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  ├─QueryGraph
+  │ └─mutating_pattern
+  │   └─MergePattern
+  │     ├─QueryGraph { nodes: [n@0], filter: n@0:Person AND eq(n@0.name, 'Alice') }
+  │     └─CreatePattern { nodes: [(n@0):Person create_map{name: 'Alice'}], rels: [] }
+  └─Project { items: [n@1 AS n@0] }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Project { exprs: [n@1 AS n@0] }
+    └─Merge { lock_labels: [Person] }
+      ├─Unit
+      ├─Filter { condition: n@0:Person AND eq(n@0.name, 'Alice') }
+      │ └─AllNodeScan { variable: n@0 }
+      └─CreateNode { items: [CreateNodeItem { variable: n@0, labels: [Person], properties: create_map{name: 'Alice'} }] }
+        └─Unit
+*/
+
+-- merge a node with ON CREATE and ON MATCH
+MERGE (n:Person {name: 'Alice'}) ON CREATE SET n.created = true ON MATCH SET n.seen = n.seen + 1
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@0 }
+  └─Merge { lock_labels: [Person], on_match: [n@0.seen = add(n@0.seen, 1)], on_create: [n@0.created = true] }
+    ├─Unit
+    ├─Filter { condition: n@0:Person AND eq(n@0.name, 'Alice') }
+    │ └─AllNodeScan { variable: n@0 }
+    └─CreateNode { items: [CreateNodeItem { variable: n@0, labels: [Person], properties: create_map{name: 'Alice'} }] }
+      └─Unit
+*/
+
+-- merge with the key from the previous clause, x is passed as argument
+UNWIND [1, 2] AS x MERGE (n:Person {id: x}) RETURN n
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@2 }
+  └─Project { exprs: [n@2 AS n@1] }
+    └─Merge { arguments: [x@0], lock_labels: [Person] }
+      ├─Apply
+      │ ├─Unwind { variable: x@0, expr: [1, 2] }
+      │ │ └─Unit
+      │ └─Argument { variables: [x@0] }
+      ├─Filter { condition: n@1:Person AND eq(n@1.id, x@0) }
+      │ └─AllNodeScan { variable: n@1, arguments: [x@0] }
+      └─CreateNode { items: [CreateNodeItem { variable: n@1, labels: [Person], properties: create_map{id: x@0} }] }
+        └─Argument { variables: [x@0] }
+*/
+
+-- merge a relationship from a bound node
+MATCH (a:Person) MERGE (a)-[r:KNOWS]->(b:Person {name: 'Bob'}) RETURN r
+
+/*
+RootIR { names: [r] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [a@0], filter: a@0:Person }
+  │ └─mutating_pattern
+  │   └─MergePattern
+  │     ├─QueryGraph { imported: [a@0], nodes: [b@1, a@0], rels: [(a@0)-[r@2:]->(b@1)], filter: b@1:Person AND eq(b@1.name, 'Bob') }
+  │     └─CreatePattern { nodes: [(b@1):Person create_map{name: 'Bob'}], rels: [(a@0)-[r@2:KNOWS]->(b@1) create_map{}] }
+  └─Project { items: [r@3 AS r@2] }
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@3 }
+  └─Project { exprs: [r@3 AS r@2] }
+    └─Merge { arguments: [a@0], lock_labels: [Person], lock_reltypes: [KNOWS] }
+      ├─Filter { condition: a@0:Person }
+      │ └─AllNodeScan { variable: a@0 }
+      ├─Filter { condition: b@1:Person AND eq(b@1.name, 'Bob') }
+      │ └─ExpandAll { from: a@0, to: b@1, rel: r@2, direction: ->, types: [KNOWS] }
+      │   └─Argument { variables: [a@0] }
+      └─CreateRel { items: [CreateRelItem { variable: r@2, reltype: KNOWS, start_node: a@0, end_node: b@1, properties: create_map{} }] }
+        └─CreateNode { items: [CreateNodeItem { variable: b@1, labels: [Person], properties: create_map{name: 'Bob'} }] }
+          └─Argument { variables: [a@0] }
+*/
+
+-- nothing to merge
+MATCH (n:Person) MERGE (n)
+
+/*
+Error
+Invalid CREATE entity in MERGE (n)
+*/
+
+-- create unique constraint
+CREATE CONSTRAINT user_id_unique FOR (u:User) REQUIRE u.id IS UNIQUE
+
+/*
+
+*/
+
+-- merge on a unique key uses NodeIndexSeek
+MERGE (u:User {id: 1, name: 'Alice'}) RETURN u
+
+/*
+RootPlan { names: [u] }
+└─ProduceResult { return_columns: u@1 }
+  └─Project { exprs: [u@1 AS u@0] }
+    └─Merge { lock_labels: [User] }
+      ├─Unit
+      ├─Filter { condition: eq(u@0.name, 'Alice') }
+      │ └─NodeIndexSeek { variable: u@0, label: User, constraint: user_id_unique, properties: [id = 1] }
+      └─CreateNode { items: [CreateNodeItem { variable: u@0, labels: [Resolved(User, 0)], properties: create_map{id: 1, name: 'Alice'} }] }
+        └─Unit
+*/
+
+-- NodeIndexSeek looks up the key from the arguments
+UNWIND [1, 2] AS x MERGE (u:User {id: x}) ON MATCH SET u.seen = true RETURN u
+
+/*
+RootPlan { names: [u] }
+└─ProduceResult { return_columns: u@2 }
+  └─Project { exprs: [u@2 AS u@1] }
+    └─Merge { arguments: [x@0], lock_labels: [User], on_match: [u@1.seen = true] }
+      ├─Apply
+      │ ├─Unwind { variable: x@0, expr: [1, 2] }
+      │ │ └─Unit
+      │ └─Argument { variables: [x@0] }
+      ├─NodeIndexSeek { variable: u@1, label: User, constraint: user_id_unique, properties: [id = x@0], arguments: [x@0] }
+      └─CreateNode { items: [CreateNodeItem { variable: u@1, labels: [Resolved(User, 0)], properties: create_map{id: x@0} }] }
+        └─Argument { variables: [x@0] }
+*/
+
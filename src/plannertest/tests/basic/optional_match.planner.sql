@@ -0,0 +1,102 @@
+-- optional expand from the matched node
+MATCH (n:Person) OPTIONAL MATCH (n)-[r:MANAGED_BY]->(m) RETURN n, m
+
+/*
+RootIR { names: [n, m] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ └─optional_matches
+  │   └─QueryGraph { imported: [n@0], nodes: [n@0, m@1], rels: [(n@0)-[r@2:]->(m@1)] }
+  └─Project { items: [n@3 AS n@0, m@4 AS m@1] }
+RootPlan { names: [n, m] }
+└─ProduceResult { return_columns: n@3,m@4 }
+  └─Project { exprs: [n@3 AS n@0, m@4 AS m@1] }
+    └─OptionalApply
+      ├─Filter { condition: n@0:Person }
+      │ └─AllNodeScan { variable: n@0 }
+      └─ExpandAll { from: n@0, to: m@1, rel: r@2, direction: ->, types: [MANAGED_BY] }
+        └─Argument { variables: [n@0] }
+*/
+
+-- where is part of the optional pattern
+MATCH (n:Person) OPTIONAL MATCH (n)-[r:MANAGED_BY]->(m) WHERE m.age > 30 RETURN n, m
+
+/*
+RootIR { names: [n, m] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ └─optional_matches
+  │   └─QueryGraph { imported: [n@0], nodes: [n@0, m@1], rels: [(n@0)-[r@2:]->(m@1)], filter: gt(m@1.age, 30) }
+  └─Project { items: [n@3 AS n@0, m@4 AS m@1] }
+RootPlan { names: [n, m] }
+└─ProduceResult { return_columns: n@3,m@4 }
+  └─Project { exprs: [n@3 AS n@0, m@4 AS m@1] }
+    └─OptionalApply
+      ├─Filter { condition: n@0:Person }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Filter { condition: gt(m@1.age, 30) }
+        └─ExpandAll { from: n@0, to: m@1, rel: r@2, direction: ->, types: [MANAGED_BY] }
+          └─Argument { variables: [n@0] }
+*/
+
+-- optional match as the first clause
+OPTIONAL MATCH (n:Person) RETURN n
+
+/*
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Project { exprs: [n@1 AS n@0] }
+    └─OptionalApply
+      ├─Unit
+      └─Filter { condition: n@0:Person }
+        └─AllNodeScan { variable: n@0 }
+*/
+
+-- optional pattern connected by where
+MATCH (n:Person) OPTIONAL MATCH (m:Person) WHERE m.age = n.age RETURN n, m
+
+/*
+RootPlan { names: [n, m] }
+└─ProduceResult { return_columns: n@2,m@3 }
+  └─Project { exprs: [n@2 AS n@0, m@3 AS m@1] }
+    └─OptionalApply
+      ├─Filter { condition: n@0:Person }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Filter { condition: m@1:Person AND eq(m@1.age, n@0.age) }
+        └─AllNodeScan { variable: m@1, arguments: [n@0] }
+*/
+
+-- chained optional matches
+MATCH (n:Person) OPTIONAL MATCH (n)-[:MANAGED_BY]->(m) OPTIONAL MATCH (m)-[:MANAGED_BY]->(k) RETURN n, m, k
+
+/*
+RootPlan { names: [n, m, k] }
+└─ProduceResult { return_columns: n@5,m@6,k@7 }
+  └─Project { exprs: [n@5 AS n@0, m@6 AS m@1, k@7 AS k@3] }
+    └─OptionalApply
+      ├─OptionalApply
+      │ ├─Filter { condition: n@0:Person }
+      │ │ └─AllNodeScan { variable: n@0 }
+      │ └─ExpandAll { from: n@0, to: m@1, rel: anon@2, direction: ->, types: [MANAGED_BY] }
+      │   └─Argument { variables: [n@0] }
+      └─ExpandAll { from: m@1, to: k@3, rel: anon@4, direction: ->, types: [MANAGED_BY] }
+        └─Argument { variables: [m@1] }
+*/
+
+-- optional match after with
+MATCH (n:Person) WITH n OPTIONAL MATCH (n)-[:MANAGED_BY]->(m) RETURN n, m
+
+/*
+RootPlan { names: [n, m] }
+└─ProduceResult { return_columns: n@4,m@5 }
+  └─Project { exprs: [n@4 AS n@1, m@5 AS m@2] }
+    └─Apply
+      ├─Project { exprs: [n@1 AS n@0] }
+      │ └─Filter { condition: n@0:Person }
+      │   └─AllNodeScan { variable: n@0 }
+      └─OptionalApply
+        ├─Argument { variables: [n@1] }
+        └─ExpandAll { from: n@1, to: m@2, rel: anon@3, direction: ->, types: [MANAGED_BY] }
+          └─Argument { variables: [n@1] }
+*/
+
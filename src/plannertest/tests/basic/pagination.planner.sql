@@ -0,0 +1,49 @@
+-- skip and limit
+MATCH (n) RETURN n SKIP 1 LIMIT 2
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0] }
+  └─Project { items: [n@1 AS n@0], pagination: offset: 1 limit: 2 }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@1 }
+  └─Pagination { offset: 1, limit: 2 }
+    └─Project { exprs: [n@1 AS n@0] }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- limit on top of sort, limit is a constant expression
+MATCH (n) RETURN n.name AS name ORDER BY name DESC LIMIT 1 + 2
+
+/*
+RootIR { names: [name] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0] }
+  └─Project { items: [name@1 AS n@0.name], order_by: [name@1 Desc], pagination: limit: add(1, 2) }
+RootPlan { names: [name] }
+└─ProduceResult { return_columns: name@1 }
+  └─Pagination { limit: add(1, 2) }
+    └─Sort { items: [name@1 Desc] }
+      └─Project { exprs: [name@1 AS n@0.name] }
+        └─AllNodeScan { variable: n@0 }
+*/
+
+-- limit must not refer to variables
+MATCH (n) RETURN n LIMIT n.age
+
+/*
+Error
+Pagination limit must be a non-negative integer in n.age
+*/
+
+-- skip must be non-negative
+MATCH (n) RETURN n SKIP -1
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0] }
+  └─Project { items: [n@1 AS n@0], pagination: offset: unary_substract(1) }
+*/
+
@@ -0,0 +1,103 @@
+-- quantified path pattern with a predicate for each iteration
+MATCH (x:Person{id: 1}) ((a)-[r:KNOWS]->(b) WHERE a.age < b.age){1,3} (y) RETURN a, r, b, y
+
+/*
+RootIR { names: [a, r, b, y] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [x@0, y@1], quantified_paths: [(x@0) ((a@2)-[r@4:]->(b@3) WHERE lt(a@2.age, b@3.age)){1,3} (y@1)], filter: x@0:Person AND eq(x@0.id, 1) }
+  └─Project { items: [a@8 AS a@5, r@9 AS r@7, b@10 AS b@6, y@11 AS y@1] }
+RootPlan { names: [a, r, b, y] }
+└─ProduceResult { return_columns: a@8,r@9,b@10,y@11 }
+  └─Project { exprs: [a@8 AS a@5, r@9 AS r@7, b@10 AS b@6, y@11 AS y@1] }
+    └─QuantifiedExpandAll { from: x@0, to: y@1, qpp: (x@0) ((a@2)-[r@4:]->(b@3) WHERE lt(a@2.age, b@3.age)){1,3} (y@1), path_mode: Trail, groups: [a@5, b@6, r@7] }
+      └─Filter { condition: x@0:Person AND eq(x@0.id, 1) }
+        └─AllNodeScan { variable: x@0 }
+*/
+
+-- single relationship without predicates is solved by variable length expand
+MATCH (x:Person{id: 1}) (()-[r:KNOWS]->()){2,} (y) RETURN r, y
+
+/*
+RootPlan { names: [r, y] }
+└─ProduceResult { return_columns: r@6,y@7 }
+  └─Project { exprs: [r@6 AS r@5, y@7 AS y@1] }
+    └─VarExpandAll { from: x@0, to: y@1, rel_pattern: (x@0)-[r@5:*2..18446744073709551615]->(y@1), path_mode: Trail }
+      └─Filter { condition: x@0:Person AND eq(x@0.id, 1) }
+        └─AllNodeScan { variable: x@0 }
+*/
+
+-- label of the inner node is a predicate for each iteration
+MATCH (x:Person{id: 1}) ((:Person)-[:KNOWS]->()){2} (y) RETURN y
+
+/*
+RootPlan { names: [y] }
+└─ProduceResult { return_columns: y@6 }
+  └─Project { exprs: [y@6 AS y@1] }
+    └─QuantifiedExpandAll { from: x@0, to: y@1, qpp: (x@0) ((anon@2)-[anon@4:]->(anon@3) WHERE anon@2:Person){2,2} (y@1), path_mode: Trail, groups: [anon@5] }
+      └─Filter { condition: x@0:Person AND eq(x@0.id, 1) }
+        └─AllNodeScan { variable: x@0 }
+*/
+
+-- both endpoints are solved before the quantified path pattern
+MATCH (x:Person{id: 1})-[:LIVES_IN]->(c) MATCH (y)-[:LIVES_IN]->(c), (x) ((a)-[:KNOWS]-(b) WHERE b.age > 20)+ (y) RETURN x, a, y
+
+/*
+RootPlan { names: [x, a, y] }
+└─ProduceResult { return_columns: x@11,a@12,y@13 }
+  └─Project { exprs: [x@11 AS x@0, a@12 AS a@8, y@13 AS y@3] }
+    └─Filter { condition: rels_disjoint(anon@4, anon@10) }
+      └─QuantifiedExpandInto { from: x@0, to: y@3, qpp: (x@0) ((a@5)<-[anon@7:]->(b@6) WHERE gt(b@6.age, 20)){1,} (y@3), path_mode: Trail, groups: [a@8, b@9, anon@10] }
+        └─Filter { condition: x@0:Person AND eq(x@0.id, 1) }
+          └─ExpandAll { from: c@1, to: y@3, rel: anon@4, direction: <-, types: [LIVES_IN] }
+            └─ExpandAll { from: x@0, to: c@1, rel: anon@2, direction: ->, types: [LIVES_IN] }
+              └─AllNodeScan { variable: x@0 }
+*/
+
+-- multiple relationships in each iteration, expanded from the imported right endpoint
+MATCH (y:Person{id: 2}) WITH y MATCH (x) ((a)-[r1:KNOWS]->(m)-[r2:WORKS_WITH]->(b))* (y) RETURN a, m, b, x
+
+/*
+RootPlan { names: [a, m, b, x] }
+└─ProduceResult { return_columns: a@13,m@14,b@15,x@16 }
+  └─Project { exprs: [a@13 AS a@8, m@14 AS m@9, b@15 AS b@10, x@16 AS x@2] }
+    └─Apply
+      ├─Project { exprs: [y@1 AS y@0] }
+      │ └─Filter { condition: y@0:Person AND eq(y@0.id, 2) }
+      │   └─AllNodeScan { variable: y@0 }
+      └─Filter { condition: rels_disjoint(r1@11, r2@12) }
+        └─QuantifiedExpandAll { from: y@1, to: x@2, qpp: (x@2) ((a@3)-[r1@6:]->(m@4)(m@4)-[r2@7:]->(b@5)){0,} (y@1), path_mode: Trail, groups: [a@8, m@9, b@10, r1@11, r2@12] }
+          └─Argument { variables: [y@1] }
+*/
+
+-- singleton variables can not be bound outside
+MATCH (x) ((x)-[r:KNOWS]->(b))+ (y) RETURN x
+
+/*
+Error
+Variable 'x' is already defined in QuantifiedPathPattern
+*/
+
+-- path variable with quantified path pattern
+MATCH p = (x) ((a)-[r:KNOWS]->(b))+ (y) RETURN p
+
+/*
+Error
+Path variable with quantified path pattern not supported
+*/
+
+-- predicate referencing the other endpoint
+MATCH (x) ((a)-[r:KNOWS]->(b) WHERE a.age < y.age)+ (y) RETURN x
+
+/*
+Error
+quantified path pattern predicate referencing unsolved variables
+*/
+
+-- upper bound less than the lower bound
+MATCH (x) ((a)-[r:KNOWS]->(b)){3,1} (y) RETURN x
+
+/*
+Error
+Invalid quantifier {3,1}, the upper bound is less than the lower bound in QuantifiedPathPattern
+*/
+
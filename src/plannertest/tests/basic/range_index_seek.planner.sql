@@ -0,0 +1,122 @@
+-- create index on a single property
+CREATE INDEX event_ts FOR (e:Event) ON (e.ts)
+
+/*
+
+*/
+
+-- create composite index
+CREATE INDEX order_customer_day FOR (o:Order) ON (o.customer, o.day)
+
+/*
+
+*/
+
+-- range on the indexed property - uses NodeIndexRangeSeek
+MATCH (e:Event) WHERE e.ts >= 10 AND e.ts < 20 RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@1 }
+  └─Project { exprs: [e@1 AS e@0] }
+    └─NodeIndexRangeSeek { variable: e@0, label: Event, index: event_ts, properties: [ts >= 10, ts < 20] }
+*/
+
+-- property on the right side of the comparison
+MATCH (e:Event) WHERE 10 < e.ts RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@1 }
+  └─Project { exprs: [e@1 AS e@0] }
+    └─NodeIndexRangeSeek { variable: e@0, label: Event, index: event_ts, properties: [ts > 10] }
+*/
+
+-- equality on a secondary index
+MATCH (e:Event) WHERE e.ts = 15 RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@1 }
+  └─Project { exprs: [e@1 AS e@0] }
+    └─NodeIndexRangeSeek { variable: e@0, label: Event, index: event_ts, properties: [ts = 15] }
+*/
+
+-- IN on the indexed property, other predicates stay in the filter
+MATCH (e:Event) WHERE e.ts IN [1, 2, 3] AND e.name STARTS WITH 'a' RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@1 }
+  └─Project { exprs: [e@1 AS e@0] }
+    └─Filter { condition: starts_with(e@0.name, 'a') }
+      └─NodeIndexRangeSeek { variable: e@0, label: Event, index: event_ts, properties: [ts IN [1, 2, 3]] }
+*/
+
+-- equality on the leading property, range on the next one
+MATCH (o:Order) WHERE o.customer = 'c1' AND o.day >= 3 RETURN o
+
+/*
+RootPlan { names: [o] }
+└─ProduceResult { return_columns: o@1 }
+  └─Project { exprs: [o@1 AS o@0] }
+    └─NodeIndexRangeSeek { variable: o@0, label: Order, index: order_customer_day, properties: [customer = 'c1', day >= 3] }
+*/
+
+-- range on a non-leading property can not use the index
+MATCH (o:Order) WHERE o.day >= 3 RETURN o
+
+/*
+RootPlan { names: [o] }
+└─ProduceResult { return_columns: o@1 }
+  └─Project { exprs: [o@1 AS o@0] }
+    └─Filter { condition: gt_eq(o@0.Resolved(day, 4), 3) }
+      └─NodeByLabelScan { variable: o@0, label: Order }
+*/
+
+-- no condition on the trailing property can not use the index, nodes missing it are not indexed
+MATCH (o:Order) WHERE o.customer = 'c1' RETURN o
+
+/*
+RootPlan { names: [o] }
+└─ProduceResult { return_columns: o@1 }
+  └─Project { exprs: [o@1 AS o@0] }
+    └─Filter { condition: eq(o@0.Resolved(customer, 3), 'c1') }
+      └─NodeByLabelScan { variable: o@0, label: Order }
+*/
+
+-- prefix on the leading property, equality on the next one stays in the filter
+MATCH (o:Order) WHERE o.customer STARTS WITH 'c' AND o.day = 3 RETURN o
+
+/*
+RootPlan { names: [o] }
+└─ProduceResult { return_columns: o@1 }
+  └─Project { exprs: [o@1 AS o@0] }
+    └─Filter { condition: eq(o@0.Resolved(day, 4), 3) }
+      └─NodeIndexRangeSeek { variable: o@0, label: Order, index: order_customer_day, properties: [customer STARTS WITH 'c'] }
+*/
+
+-- range bound depending on the arguments
+MATCH (a:Event) WITH a MATCH (e:Event) WHERE e.ts > a.ts RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@3 }
+  └─Project { exprs: [e@3 AS e@2] }
+    └─Apply
+      ├─Project { exprs: [a@1 AS a@0] }
+      │ └─NodeByLabelScan { variable: a@0, label: Event }
+      └─NodeIndexRangeSeek { variable: e@2, label: Event, index: event_ts, properties: [ts > a@1.Resolved(ts, 1)], arguments: [a@1] }
+*/
+
+-- range bound depending on the node itself can not use the index
+MATCH (e:Event) WHERE e.ts > e.start RETURN e
+
+/*
+RootPlan { names: [e] }
+└─ProduceResult { return_columns: e@1 }
+  └─Project { exprs: [e@1 AS e@0] }
+    └─Filter { condition: gt(e@0.Resolved(ts, 1), e@0.start) }
+      └─NodeByLabelScan { variable: e@0, label: Event }
+*/
+
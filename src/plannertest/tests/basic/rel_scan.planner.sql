@@ -0,0 +1,79 @@
+-- scan relationships by type
+MATCH (a)-[r:KNOWS]->(b) RETURN r
+
+/*
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@3 }
+  └─Project { exprs: [r@3 AS r@2] }
+    └─RelScan { left: a@0, rel: r@2, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
+-- scan relationships of multiple types in both directions
+MATCH (a)-[r:KNOWS|LIKES]-(b) RETURN r
+
+/*
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@3 }
+  └─Project { exprs: [r@3 AS r@2] }
+    └─RelScan { left: a@0, rel: r@2, right: b@1, direction: -, types: [KNOWS, LIKES] }
+*/
+
+-- expand from the scanned relationship
+MATCH (a)-[r:KNOWS]->(b)-[s:LIKES]->(c) RETURN a, c
+
+/*
+RootPlan { names: [a, c] }
+└─ProduceResult { return_columns: a@5,c@6 }
+  └─Project { exprs: [a@5 AS a@0, c@6 AS c@2] }
+    └─Filter { condition: rels_disjoint(r@3, s@4) }
+      └─ExpandAll { from: b@1, to: c@2, rel: s@4, direction: ->, types: [LIKES] }
+        └─RelScan { left: a@0, rel: r@3, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
+-- labeled node is more selective
+MATCH (a:Person)-[r:KNOWS]->(b) RETURN r
+
+/*
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@3 }
+  └─Project { exprs: [r@3 AS r@2] }
+    └─Filter { condition: a@0:Person }
+      └─ExpandAll { from: a@0, to: b@1, rel: r@2, direction: ->, types: [KNOWS] }
+        └─AllNodeScan { variable: a@0 }
+*/
+
+-- relationship without type is not scanned
+MATCH (a)-[r]->(b) RETURN r
+
+/*
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@3 }
+  └─Project { exprs: [r@3 AS r@2] }
+    └─ExpandAll { from: a@0, to: b@1, rel: r@2, direction: ->, types: [] }
+      └─AllNodeScan { variable: a@0 }
+*/
+
+-- self loop is not scanned
+MATCH (a)-[r:KNOWS]->(a) RETURN r
+
+/*
+RootPlan { names: [r] }
+└─ProduceResult { return_columns: r@2 }
+  └─Project { exprs: [r@2 AS r@1] }
+    └─ExpandInto { from: a@0, to: a@0, rel: r@1, direction: ->, types: [KNOWS] }
+      └─AllNodeScan { variable: a@0 }
+*/
+
+-- scan with arguments
+UNWIND [1, 2] AS x MATCH (a)-[r:KNOWS]->(b) RETURN x, r
+
+/*
+RootPlan { names: [x, r] }
+└─ProduceResult { return_columns: x@4,r@5 }
+  └─Project { exprs: [x@4 AS x@0, r@5 AS r@3] }
+    └─Apply
+      ├─Unwind { variable: x@0, expr: [1, 2] }
+      │ └─Unit
+      └─RelScan { left: a@1, rel: r@3, right: b@2, direction: ->, types: [KNOWS], arguments: [x@0] }
+*/
+
@@ -0,0 +1,82 @@
+-- set a property and return the new value
+MATCH (n:Person) SET n.age = n.age + 1 RETURN n.age
+
+/*
+RootIR { names: [n.age] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ └─mutating_pattern
+  │   └─SetPattern { items: [n@0.age = add(n@0.age, 1)] }
+  └─Project { items: [nage@1 AS n@0.age] }
+RootPlan { names: [n.age] }
+└─ProduceResult { return_columns: nage@1 }
+  └─Project { exprs: [nage@1 AS n@0.age] }
+    └─Set { items: [n@0.age = add(n@0.age, 1)] }
+      └─Filter { condition: n@0:Person }
+        └─AllNodeScan { variable: n@0 }
+*/
+
+-- merge properties and add a label
+MATCH (n:Person) SET n += {age: 1}, n:Adult
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [n@0], filter: n@0:Person }
+    └─mutating_pattern
+      └─SetPattern { items: [n@0 += create_map{age: 1}, n@0:Adult] }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@0 }
+  └─Set { items: [n@0 += create_map{age: 1}, n@0:Adult] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- replace the properties with those of another node
+MATCH (a:Person)-[:KNOWS]->(b:Person) SET a = b
+
+/*
+RootIR { names: [a, b] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [a@0, b@1], rels: [(a@0)-[anon@2:]->(b@1)], filter: a@0:Person AND b@1:Person }
+    └─mutating_pattern
+      └─SetPattern { items: [a@0 = b@1] }
+RootPlan { names: [a, b] }
+└─ProduceResult { return_columns: a@0,b@1 }
+  └─Set { items: [a@0 = b@1] }
+    └─Filter { condition: a@0:Person AND b@1:Person }
+      └─ExpandAll { from: a@0, to: b@1, rel: anon@2, direction: ->, types: [KNOWS] }
+        └─AllNodeScan { variable: a@0 }
+*/
+
+-- remove a property and a label
+MATCH (n:Person) REMOVE n.age, n:Person
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [n@0], filter: n@0:Person }
+    └─mutating_pattern
+      └─SetPattern { items: [n@0.age = null, REMOVE n@0:Person] }
+RootPlan { names: [n] }
+└─ProduceResult { return_columns: n@0 }
+  └─Set { items: [n@0.age = null, REMOVE n@0:Person] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- set a relationship property
+MATCH (a)-[r:KNOWS]->(b) SET r.since = 2020
+
+/*
+RootIR { names: [a, b, r] }
+└─IrSingleQueryPart
+  └─QueryGraph { nodes: [a@0, b@1], rels: [(a@0)-[r@2:]->(b@1)] }
+    └─mutating_pattern
+      └─SetPattern { items: [r@2.since = 2020] }
+RootPlan { names: [a, b, r] }
+└─ProduceResult { return_columns: a@0,b@1,r@2 }
+  └─Set { items: [r@2.since = 2020] }
+    └─RelScan { left: a@0, rel: r@2, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
@@ -0,0 +1,97 @@
+-- shortest path between two nodes, the second node is scanned for each first node
+MATCH p = ANY SHORTEST (a:Account{id: 1})-[:TRANSFER*]->(b:Account{id: 2}) RETURN p
+
+/*
+RootIR { names: [p] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [a@0, b@1], selective_paths: [p@3 = SHORTEST 1 (a@0)-[anon@2:*1..18446744073709551615]->(b@1)], filter: a@0:Account AND eq(a@0.id, 1) AND b@1:Account AND eq(b@1.id, 2) }
+  └─Project { items: [p@4 AS p@3] }
+RootPlan { names: [p] }
+└─ProduceResult { return_columns: p@4 }
+  └─Project { exprs: [p@4 AS p@3] }
+    └─ShortestPath { from: a@0, to: b@1, rel_pattern: (a@0)-[anon@2:*1..18446744073709551615]->(b@1), path: p@3, selector: SHORTEST 1 }
+      └─Filter { condition: b@1:Account AND eq(b@1.id, 2) }
+        └─Apply
+          ├─Filter { condition: a@0:Account AND eq(a@0.id, 1) }
+          │ └─AllNodeScan { variable: a@0 }
+          └─AllNodeScan { variable: b@1, arguments: [a@0] }
+*/
+
+-- endpoint predicates are applied before searching paths
+MATCH p = ALL SHORTEST (a)-[r:TRANSFER|KNOWS*..5]-(b) WHERE a.id = 1 AND b.id = 2 RETURN p, r
+
+/*
+RootPlan { names: [p, r] }
+└─ProduceResult { return_columns: p@4,r@5 }
+  └─Project { exprs: [p@4 AS p@3, r@5 AS r@2] }
+    └─ShortestPath { from: a@0, to: b@1, rel_pattern: (a@0)<-[r@2:*1..5]->(b@1), path: p@3, selector: SHORTEST 1 GROUPS }
+      └─Filter { condition: and(eq(a@0.id, 1), eq(b@1.id, 2)) }
+        └─Apply
+          ├─AllNodeScan { variable: a@0 }
+          └─AllNodeScan { variable: b@1, arguments: [a@0] }
+*/
+
+-- both endpoints are solved by traversal
+MATCH (a)-[:OWNS]->(b) MATCH p = SHORTEST 2 GROUPS (a)-[:TRANSFER*]->(b) RETURN p
+
+/*
+RootPlan { names: [p] }
+└─ProduceResult { return_columns: p@5 }
+  └─Project { exprs: [p@5 AS p@4] }
+    └─ShortestPath { from: a@0, to: b@1, rel_pattern: (a@0)-[anon@3:*1..18446744073709551615]->(b@1), path: p@4, selector: SHORTEST 2 GROUPS }
+      └─RelScan { left: a@0, rel: anon@2, right: b@1, direction: ->, types: [OWNS] }
+*/
+
+-- selective path pattern with a single relationship only
+MATCH (c)-[:OWNS]->(a), p = ANY SHORTEST (a)-[:TRANSFER*]->(b)<-[:OWNS]-(d) RETURN p
+
+/*
+Error
+Selective path pattern must be a single relationship between two different nodes
+*/
+
+-- traverse from the scanned endpoint before searching paths
+MATCH (c)-[:OWNS]->(a), p = ANY SHORTEST (a)-[:TRANSFER*]->(b), (b)<-[:OWNS]-(d) RETURN c, p, d
+
+/*
+RootPlan { names: [c, p, d] }
+└─ProduceResult { return_columns: c@8,p@9,d@10 }
+  └─Project { exprs: [c@8 AS c@0, p@9 AS p@5, d@10 AS d@6] }
+    └─Filter { condition: rels_disjoint(anon@2, anon@4) AND rels_disjoint(anon@4, anon@7) }
+      └─ShortestPath { from: a@1, to: b@3, rel_pattern: (a@1)-[anon@4:*1..18446744073709551615]->(b@3), path: p@5, selector: SHORTEST 1 }
+        └─Filter { condition: rels_disjoint(anon@2, anon@7) }
+          └─ExpandAll { from: b@3, to: d@6, rel: anon@7, direction: <-, types: [OWNS] }
+            └─Apply
+              ├─RelScan { left: c@0, rel: anon@2, right: a@1, direction: ->, types: [OWNS] }
+              └─AllNodeScan { variable: b@3, arguments: [c@0, anon@2, a@1] }
+*/
+
+-- shortest path from an imported node
+MATCH (a:Account{id: 1}) WITH a MATCH p = ALL SHORTEST (a)-[:TRANSFER*]->(b:Account{id: 3}) RETURN p
+
+/*
+RootPlan { names: [p] }
+└─ProduceResult { return_columns: p@5 }
+  └─Project { exprs: [p@5 AS p@4] }
+    └─Apply
+      ├─Project { exprs: [a@1 AS a@0] }
+      │ └─Filter { condition: a@0:Account AND eq(a@0.id, 1) }
+      │   └─AllNodeScan { variable: a@0 }
+      └─ShortestPath { from: a@1, to: b@2, rel_pattern: (a@1)-[anon@3:*1..18446744073709551615]->(b@2), path: p@4, selector: SHORTEST 1 GROUPS }
+        └─Filter { condition: b@2:Account AND eq(b@2.id, 3) }
+          └─AllNodeScan { variable: b@2, arguments: [a@1] }
+*/
+
+-- anonymous path
+MATCH ANY SHORTEST (a)-[:TRANSFER*]->(b) RETURN b
+
+/*
+RootPlan { names: [b] }
+└─ProduceResult { return_columns: b@4 }
+  └─Project { exprs: [b@4 AS b@1] }
+    └─ShortestPath { from: a@0, to: b@1, rel_pattern: (a@0)-[anon@2:*1..18446744073709551615]->(b@1), path: anon@3, selector: SHORTEST 1 }
+      └─Apply
+        ├─AllNodeScan { variable: a@0 }
+        └─AllNodeScan { variable: b@1, arguments: [a@0] }
+*/
+
@@ -0,0 +1,53 @@
+-- union all of differently labelled nodes
+MATCH (n:Person) RETURN n.name AS name UNION ALL MATCH (m:Company) RETURN m.title AS name
+
+/*
+RootIR { names: [name] }
+└─UnionQuery
+  ├─inputs:
+  │ ┌─IrSingleQueryPart
+  │ │ ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ │ └─Project { items: [name@1 AS n@0.name] }
+  │ └─IrSingleQueryPart
+  │   ├─QueryGraph { nodes: [m@2], filter: m@2:Company }
+  │   └─Project { items: [name@3 AS m@2.title] }
+  ├─distinct: false
+
+RootPlan { names: [name] }
+└─ProduceResult { return_columns: name@1 }
+  └─Union { distinct: false }
+    ├─Project { exprs: [name@1 AS n@0.name] }
+    │ └─Filter { condition: n@0:Person }
+    │   └─AllNodeScan { variable: n@0 }
+    └─Project { exprs: [name@1 AS name@3] }
+      └─Project { exprs: [name@3 AS m@2.title] }
+        └─Filter { condition: m@2:Company }
+          └─AllNodeScan { variable: m@2 }
+*/
+
+-- union removes duplicated rows
+MATCH (n:Person) RETURN n.name AS name, n.age AS age UNION RETURN 'nobody' AS name, 0 AS age
+
+/*
+RootIR { names: [name, age] }
+└─UnionQuery
+  ├─inputs:
+  │ ┌─IrSingleQueryPart
+  │ │ ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ │ └─Project { items: [name@1 AS n@0.name, age@2 AS n@0.age] }
+  │ └─IrSingleQueryPart
+  │   ├─QueryGraph
+  │   └─Project { items: [name@3 AS 'nobody', age@4 AS 0] }
+  ├─distinct: true
+
+RootPlan { names: [name, age] }
+└─ProduceResult { return_columns: name@1,age@2 }
+  └─Union { distinct: true }
+    ├─Project { exprs: [name@1 AS n@0.name, age@2 AS n@0.age] }
+    │ └─Filter { condition: n@0:Person }
+    │   └─AllNodeScan { variable: n@0 }
+    └─Project { exprs: [name@1 AS name@3, age@2 AS age@4] }
+      └─Project { exprs: [name@3 AS 'nobody', age@4 AS 0] }
+        └─Unit
+*/
+
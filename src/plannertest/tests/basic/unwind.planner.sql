@@ -0,0 +1,87 @@
+-- unwind a list literal
+UNWIND [1, 2, 3] AS x RETURN x
+
+/*
+RootIR { names: [x] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [x@0] }
+  ├─Project { items: [x@1 AS x@0] }
+  └─IrSingleQueryPart
+    ├─QueryGraph
+    └─UnwindProjection { variable: x@0, expr: [1, 2, 3] }
+RootPlan { names: [x] }
+└─ProduceResult { return_columns: x@1 }
+  └─Project { exprs: [x@1 AS x@0] }
+    └─Apply
+      ├─Unwind { variable: x@0, expr: [1, 2, 3] }
+      │ └─Unit
+      └─Argument { variables: [x@0] }
+*/
+
+-- unwind after match
+MATCH (n) UNWIND n.tags AS tag RETURN n.name, tag
+
+/*
+RootIR { names: [n.name, tag] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [n@0, tag@1] }
+  ├─Project { items: [nname@2 AS n@0.name, tag@3 AS tag@1] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { nodes: [n@0] }
+    └─UnwindProjection { variable: tag@1, expr: n@0.tags }
+RootPlan { names: [n.name, tag] }
+└─ProduceResult { return_columns: nname@2,tag@3 }
+  └─Project { exprs: [nname@2 AS n@0.name, tag@3 AS tag@1] }
+    └─Apply
+      ├─Unwind { variable: tag@1, expr: n@0.tags }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Argument { variables: [n@0, tag@1] }
+*/
+
+-- unwind then create
+UNWIND [1, 2] AS x CREATE (n:Person {id: x})
+
+/*
+RootIR { names: [x, n] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [x@0] }
+  │ └─mutating_pattern
+  │   └─CreatePattern { nodes: [(n@1):Person create_map{id: x@0}], rels: [] }
+  └─IrSingleQueryPart
+    ├─QueryGraph
+    └─UnwindProjection { variable: x@0, expr: [1, 2] }
+RootPlan { names: [x, n] }
+└─ProduceResult { return_columns: x@0,n@1 }
+  └─CreateNode { items: [CreateNodeItem { variable: n@1, labels: [Person], properties: create_map{id: x@0} }] }
+    └─Apply
+      ├─Unwind { variable: x@0, expr: [1, 2] }
+      │ └─Unit
+      └─Argument { variables: [x@0] }
+*/
+
+-- nested unwind
+UNWIND [[1, 2], [3]] AS xs UNWIND xs AS x RETURN x
+
+/*
+RootIR { names: [x] }
+└─IrSingleQueryPart
+  ├─QueryGraph { imported: [xs@0, x@1] }
+  ├─Project { items: [x@2 AS x@1] }
+  └─IrSingleQueryPart
+    ├─QueryGraph { imported: [xs@0] }
+    ├─UnwindProjection { variable: x@1, expr: xs@0 }
+    └─IrSingleQueryPart
+      ├─QueryGraph
+      └─UnwindProjection { variable: xs@0, expr: [[1, 2], [3]] }
+RootPlan { names: [x] }
+└─ProduceResult { return_columns: x@2 }
+  └─Project { exprs: [x@2 AS x@1] }
+    └─Apply
+      ├─Unwind { variable: x@1, expr: xs@0 }
+      │ └─Apply
+      │   ├─Unwind { variable: xs@0, expr: [[1, 2], [3]] }
+      │   │ └─Unit
+      │   └─Argument { variables: [xs@0] }
+      └─Argument { variables: [xs@0, x@1] }
+*/
+
@@ -0,0 +1,34 @@
+-- generic case expression
+MATCH (n:Person) RETURN CASE WHEN n.age < 18 THEN 'minor' WHEN n.age < 65 THEN 'adult' ELSE 'senior' END
+
+/*
+RootIR { names: [CASE WHEN (n.age) < (18) THEN 'minor' WHEN (n.age) < (65) THEN 'adult' ELSE 'senior' END] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  └─Project { items: [CASEWHENnage18THENminorWHENnage65THENadultELSEseniorEND@1 AS CASE WHEN lt(n@0.age, 18) THEN 'minor' WHEN lt(n@0.age, 65) THEN 'adult' ELSE 'senior' END] }
+RootPlan { names: [CASE WHEN (n.age) < (18) THEN 'minor' WHEN (n.age) < (65) THEN 'adult' ELSE 'senior' END] }
+└─ProduceResult { return_columns: CASEWHENnage18THENminorWHENnage65THENadultELSEseniorEND@1 }
+  └─Project { exprs: [CASEWHENnage18THENminorWHENnage65THENadultELSEseniorEND@1 AS CASE WHEN lt(n@0.age, 18) THEN 'minor' WHEN lt(n@0.age, 65) THEN 'adult' ELSE 'senior' END] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- simple case expression is bound as equality conditions
+MATCH (n:Person) RETURN CASE n.age WHEN 0 THEN 'zero' WHEN 1 THEN 'one' END
+
+/*
+RootPlan { names: [CASE n.age WHEN 0 THEN 'zero' WHEN 1 THEN 'one' END] }
+└─ProduceResult { return_columns: CASEnageWHEN0THENzeroWHEN1THENoneEND@1 }
+  └─Project { exprs: [CASEnageWHEN0THENzeroWHEN1THENoneEND@1 AS CASE WHEN eq(n@0.age, 0) THEN 'zero' WHEN eq(n@0.age, 1) THEN 'one' END] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- case condition must be a boolean
+MATCH (n:Person) RETURN CASE WHEN n.age THEN 'minor' END
+
+/*
+Error
+CASE condition must be a boolean, got Any in Return Clause
+*/
+
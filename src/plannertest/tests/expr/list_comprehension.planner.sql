@@ -0,0 +1,46 @@
+-- lambda variables are bound inside the expressions
+MATCH (n:Person) RETURN [x IN n.scores WHERE x > 60 | x * 2], all(x IN n.scores WHERE x > 0), reduce(s = 0, x IN n.scores | s + x)
+
+/*
+RootIR { names: [[x IN n.scores WHERE (x) > (60) | (x) * (2)], all(x IN n.scores WHERE (x) > (0)), reduce(s = 0, x IN n.scores | (s) + (x))] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  └─Project { items: [xINnscoresWHEREx60x2@2 AS [x@1 IN n@0.scores WHERE gt(x@1, 60) | multiply(x@1, 2)], allxINnscoresWHEREx0@4 AS all(x@3 IN n@0.scores WHERE gt(x@3, 0)), reduces0xINnscoressx@7 AS reduce(s@6 = 0, x@5 IN n@0.scores | add(s@6, x@5))] }
+RootPlan { names: [[x IN n.scores WHERE (x) > (60) | (x) * (2)], all(x IN n.scores WHERE (x) > (0)), reduce(s = 0, x IN n.scores | (s) + (x))] }
+└─ProduceResult { return_columns: xINnscoresWHEREx60x2@2,allxINnscoresWHEREx0@4,reduces0xINnscoressx@7 }
+  └─Project { exprs: [xINnscoresWHEREx60x2@2 AS [x@1 IN n@0.scores WHERE gt(x@1, 60) | multiply(x@1, 2)], allxINnscoresWHEREx0@4 AS all(x@3 IN n@0.scores WHERE gt(x@3, 0)), reduces0xINnscoressx@7 AS reduce(s@6 = 0, x@5 IN n@0.scores | add(s@6, x@5))] }
+    └─Filter { condition: n@0:Person }
+      └─AllNodeScan { variable: n@0 }
+*/
+
+-- pattern comprehension is collected by an apply for each input row
+MATCH (n:Person) RETURN [(n)-[:KNOWS]->(m) WHERE m.age > 20 | m.name]
+
+/*
+RootIR { names: [[(n)-[:KNOWS]->(m) WHERE (m.age) > (20) | m.name]] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person }
+  │ └─pattern_comprehensions
+  │   └─PatternComprehension { variable: anon@3, projection: m@1.name }
+  │     └─QueryGraph { imported: [n@0], nodes: [n@0, m@1], rels: [(n@0)-[anon@2:]->(m@1)], filter: gt(m@1.age, 20) }
+  └─Project { items: [nKNOWSmWHEREmage20mname@4 AS anon@3] }
+RootPlan { names: [[(n)-[:KNOWS]->(m) WHERE (m.age) > (20) | m.name]] }
+└─ProduceResult { return_columns: nKNOWSmWHEREmage20mname@4 }
+  └─Project { exprs: [nKNOWSmWHEREmage20mname@4 AS anon@3] }
+    └─Apply
+      ├─Filter { condition: n@0:Person }
+      │ └─AllNodeScan { variable: n@0 }
+      └─Aggregate { aggregates: [anon@3 AS collect(m@1.name)] }
+        └─Filter { condition: gt(m@1.age, 20) }
+          └─ExpandAll { from: n@0, to: m@1, rel: anon@2, direction: ->, types: [KNOWS] }
+            └─Argument { variables: [n@0] }
+*/
+
+-- pattern comprehension is only allowed in projections
+MATCH (n:Person) WHERE [(n)-[:KNOWS]->(m) | m] <> [] RETURN n
+
+/*
+Error
+Pattern comprehension is not allowed in MATCH WHERE
+*/
+
@@ -28,3 +28,34 @@ RootPlan { names: [p] }
       └─AllNodeScan { variable: anon@0 }
 */
 
+-- path with variable length relationship
+MATCH p = (a)-[:KNOWS*1..3]->(b) RETURN p
+
+/*
+RootIR { names: [p] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [a@0, b@1], rels: [(a@0)-[anon@2:*1..3]->(b@1)] }
+  └─Project { items: [p@4 AS (a@0)-[anon@2*]->(b@1)] }
+RootPlan { names: [p] }
+└─ProduceResult { return_columns: p@4 }
+  └─Project { exprs: [p@4 AS (a@0)-[anon@2*]->(b@1)] }
+    └─VarExpandAll { from: a@0, to: b@1, rel_pattern: (a@0)-[anon@2:*1..3]->(b@1), path_mode: Trail }
+      └─AllNodeScan { variable: a@0 }
+*/
+
+-- path functions
+MATCH p = (a)-[r:KNOWS]->(b)-[:KNOWS*]->(c) RETURN length(p), nodes(p), relationships(p), startNode(r), endNode(r)
+
+/*
+RootIR { names: [length(p), nodes(p), relationships(p), startNode(r), endNode(r)] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [a@0, b@1, c@2], rels: [(a@0)-[r@3:]->(b@1), (b@1)-[anon@4:*1..18446744073709551615]->(c@2)], filter: rels_disjoint(r@3, anon@4) }
+  └─Project { items: [lengthp@6 AS length((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), nodesp@7 AS nodes((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), relationshipsp@8 AS relationships((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), startNoder@9 AS startnode(r@3), endNoder@10 AS endnode(r@3)] }
+RootPlan { names: [length(p), nodes(p), relationships(p), startNode(r), endNode(r)] }
+└─ProduceResult { return_columns: lengthp@6,nodesp@7,relationshipsp@8,startNoder@9,endNoder@10 }
+  └─Project { exprs: [lengthp@6 AS length((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), nodesp@7 AS nodes((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), relationshipsp@8 AS relationships((a@0)-[r@3]->(b@1)-[anon@4*]->(c@2)), startNoder@9 AS startnode(r@3), endNoder@10 AS endnode(r@3)] }
+    └─Filter { condition: rels_disjoint(r@3, anon@4) }
+      └─VarExpandAll { from: b@1, to: c@2, rel_pattern: (b@1)-[anon@4:*1..18446744073709551615]->(c@2), path_mode: Trail }
+        └─RelScan { left: a@0, rel: r@3, right: b@1, direction: ->, types: [KNOWS] }
+*/
+
@@ -0,0 +1,10 @@
+-- string predicates and IN are bound as functions
+MATCH (n:Person) WHERE n.name STARTS WITH 'A' AND n.name =~ 'A.*e' AND n.age IN [30, 40] RETURN n
+
+/*
+RootIR { names: [n] }
+└─IrSingleQueryPart
+  ├─QueryGraph { nodes: [n@0], filter: n@0:Person AND and(and(starts_with(n@0.name, 'A'), regex_match(n@0.name, 'A.*e')), in(n@0.age, [30, 40])) }
+  └─Project { items: [n@1 AS n@0] }
+*/
+
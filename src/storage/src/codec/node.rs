@@ -5,6 +5,11 @@
 //! NumLabels ::= u16
 //!
 //! LabelBlock ::= <LabelId>{NumLabels}
+//!
+//! Nodes are also indexed by label, so that a label can be scanned without touching the other nodes.
+//!
+//! LabelKey ::= <cf_property::LABEL_KEY_PREFIX> <label_id> <node_id>
+//! LabelValue ::= <empty>
 
 use bytes::{BufMut, Bytes, BytesMut};
 use elio_common::mapb::{PropertyMapMut, PropertyMapRef};
@@ -27,6 +32,29 @@ impl NodeFormat {
         NodeId::from_be_bytes(buf[1..9].try_into().unwrap())
     }
 
+    pub fn encode_label_key(label_id: LabelId, node_id: NodeId) -> Bytes {
+        let mut key = BytesMut::new();
+        key.put_slice(crate::cf_property::LABEL_KEY_PREFIX);
+        key.put_u16(label_id);
+        key.put_u64(*node_id);
+        key.freeze()
+    }
+
+    pub fn decode_label_key(buf: &[u8]) -> (LabelId, NodeId) {
+        assert_eq!(buf.len(), 11);
+        let label_id = LabelId::from_be_bytes(buf[1..3].try_into().unwrap());
+        let node_id = NodeId::from_be_bytes(buf[3..11].try_into().unwrap());
+        (label_id, node_id)
+    }
+
+    // label scan prefix: label id
+    pub fn label_iter_prefix(label_id: LabelId) -> Bytes {
+        let mut key = BytesMut::new();
+        key.put_slice(crate::cf_property::LABEL_KEY_PREFIX);
+        key.put_u16(label_id);
+        key.freeze()
+    }
+
     pub fn encode_node_value(
         labels: &[LabelId],
         key_ids: &[TokenId],
@@ -4,6 +4,11 @@
 //! DIRECTION ::= <cf_topology::DIR_OUT> | <cf_topology::DIR_IN>
 //!
 //! RelationshipValue ::= <PropertyBlock>
+//!
+//! Relationships are also indexed by type, so that a type can be scanned without touching the others.
+//!
+//! RelTypeKey ::= <cf_topology::RELTYPE_KEY_PREFIX> <reltype_id> <src_node_id> <dst_node_id> <rel_id>
+//! RelTypeValue ::= <PropertyBlock>
 
 use bytes::{BufMut, Bytes, BytesMut};
 use elio_common::mapb::{PropertyMapMut, PropertyMapRef};
@@ -43,6 +48,38 @@ impl RelFormat {
         (src_node_id, direction, reltype, dst_node_id, rel_id)
     }
 
+    pub fn encode_reltype_key(
+        reltype: TokenId,
+        src_node_id: NodeId,
+        dst_node_id: NodeId,
+        rel_id: RelationshipId,
+    ) -> Bytes {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(cf_topology::RELTYPE_KEY_PREFIX);
+        bytes.put_u16(reltype);
+        bytes.put_u64(*src_node_id);
+        bytes.put_u64(*dst_node_id);
+        bytes.put_u64(*rel_id);
+        bytes.freeze()
+    }
+
+    pub fn decode_reltype_key(buf: &[u8]) -> (TokenId, NodeId, NodeId, RelationshipId) {
+        assert!(buf.len() >= 27);
+        let reltype = TokenId::from_be_bytes(buf[1..3].try_into().unwrap());
+        let src_node_id = NodeId::from_be_bytes(buf[3..11].try_into().unwrap());
+        let dst_node_id = NodeId::from_be_bytes(buf[11..19].try_into().unwrap());
+        let rel_id = RelationshipId::from_be_bytes(buf[19..27].try_into().unwrap());
+        (reltype, src_node_id, dst_node_id, rel_id)
+    }
+
+    // reltype scan prefix: reltype id
+    pub fn reltype_iter_prefix(reltype: TokenId) -> Bytes {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(cf_topology::RELTYPE_KEY_PREFIX);
+        bytes.put_u16(reltype);
+        bytes.freeze()
+    }
+
     pub fn encode_value(key_ids: &[TokenId], property_map: StructValueRef<'_>) -> Result<Bytes, String> {
         let mut buf = BytesMut::new();
         let mut mapb_mut = PropertyMapMut::with_capacity(key_ids.len());
@@ -95,4 +132,15 @@ mod test {
         assert_eq!(dst_node_id, NodeId::from(3));
         assert_eq!(rel_id, RelationshipId::from(4));
     }
+
+    #[test]
+    fn test_encode_decode_reltype_key() {
+        let key = RelFormat::encode_reltype_key(2, NodeId::from(1), NodeId::from(3), RelationshipId::from(4));
+        assert!(key.starts_with(&RelFormat::reltype_iter_prefix(2)));
+        let (reltype, src_node_id, dst_node_id, rel_id) = RelFormat::decode_reltype_key(&key);
+        assert_eq!(reltype, 2);
+        assert_eq!(src_node_id, NodeId::from(1));
+        assert_eq!(dst_node_id, NodeId::from(3));
+        assert_eq!(rel_id, RelationshipId::from(4));
+    }
 }
@@ -84,7 +84,19 @@ impl<'a> TopoFormatWriter<'a> {
         self.header.num += 1;
     }
 
-    pub fn delete_relationship(&mut self, _rel_id: RelationshipId) {
-        // TODO: implement delete relationship
+    pub fn delete_relationship(&mut self, rel_id: RelationshipId) {
+        // entries are (rel_id, other_node_id), move the last entry into the deleted slot
+        const ENTRY_SIZE: usize = 16;
+        let header_size = size_of::<TopoHeader>();
+        let num = self.header.num as usize;
+        let found = (0..num).map(|i| header_size + i * ENTRY_SIZE).find(|offset| {
+            u64::from_le_bytes(self.buf[*offset..*offset + 8].try_into().unwrap()) == rel_id
+        });
+        if let Some(offset) = found {
+            let last = header_size + (num - 1) * ENTRY_SIZE;
+            self.buf.copy_within(last..last + ENTRY_SIZE, offset);
+            self.buf.truncate(last);
+            self.header.num -= 1;
+        }
     }
 }
@@ -7,7 +7,7 @@
 use std::sync::Arc;
 
 use bytes::{BufMut, Bytes, BytesMut};
-use elio_common::{LabelId, NodeId, PropertyKeyId};
+use elio_common::{LabelId, NodeId, PropertyKeyId, RelationshipId, RelationshipTypeId, TokenId};
 
 use crate::cf_constraint;
 use crate::error::GraphStoreError;
@@ -37,6 +37,7 @@ pub enum ConstraintKind {
     Unique = 0,
     NodeKey = 1,
     NotNull = 2,
+    RelationshipKey = 3,
 }
 
 impl ConstraintKind {
@@ -45,6 +46,7 @@ impl ConstraintKind {
             0 => Some(ConstraintKind::Unique),
             1 => Some(ConstraintKind::NodeKey),
             2 => Some(ConstraintKind::NotNull),
+            3 => Some(ConstraintKind::RelationshipKey),
             _ => None,
         }
     }
@@ -55,6 +57,7 @@ impl ConstraintKind {
 pub struct ConstraintMeta {
     pub name: String,
     pub entity_type: EntityType,
+    // the relationship type id for a relationship constraint
     pub label_id: LabelId,
     pub constraint_kind: ConstraintKind,
     pub property_key_ids: Vec<PropertyKeyId>,
@@ -148,6 +151,33 @@ impl ConstraintCodec {
         buf.put_u16_le(label_id);
         buf.freeze()
     }
+
+    /// Encode reltype-to-constraint mapping key
+    /// Format: | prefix (1B) | reltype_id (2B) | name_len (2B) | name |
+    pub fn encode_reltype_constraint_key(reltype_id: RelationshipTypeId, name: &str) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_constraint::RELTYPE_CONSTRAINT_PREFIX);
+        buf.put_u16_le(reltype_id);
+        buf.put_u16_le(name.len() as u16);
+        buf.put_slice(name.as_bytes());
+        buf.freeze()
+    }
+
+    /// Encode reltype-to-constraint prefix for iteration
+    pub fn encode_reltype_constraint_prefix(reltype_id: RelationshipTypeId) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_constraint::RELTYPE_CONSTRAINT_PREFIX);
+        buf.put_u16_le(reltype_id);
+        buf.freeze()
+    }
+
+    /// Encode the label or reltype to constraint mapping key, depending on the entity type of the constraint
+    pub fn encode_mapping_key(meta: &ConstraintMeta) -> Bytes {
+        match meta.entity_type {
+            EntityType::Node => Self::encode_label_constraint_key(meta.label_id, &meta.name),
+            EntityType::Relationship => Self::encode_reltype_constraint_key(meta.label_id, &meta.name),
+        }
+    }
 }
 
 /// Codec for unique index
@@ -159,11 +189,35 @@ impl UniqueIndexCodec {
     ///
     /// For composite keys, prop_key_ids and values are concatenated
     pub fn encode_key(label_id: LabelId, prop_key_ids: &[PropertyKeyId], prop_values: &[&[u8]]) -> Bytes {
+        Self::encode_key_with_prefix(cf_constraint::UNIQUE_INDEX_PREFIX, label_id, prop_key_ids, prop_values)
+    }
+
+    /// Encode relationship unique index key
+    /// Format: | prefix (1B) | reltype_id (2B) | prop_key_id (2B) | prop_value_len (4B) | prop_value |
+    pub fn encode_rel_key(
+        reltype_id: RelationshipTypeId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+    ) -> Bytes {
+        Self::encode_key_with_prefix(
+            cf_constraint::REL_UNIQUE_INDEX_PREFIX,
+            reltype_id,
+            prop_key_ids,
+            prop_values,
+        )
+    }
+
+    fn encode_key_with_prefix(
+        prefix: u8,
+        token_id: TokenId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+    ) -> Bytes {
         assert_eq!(prop_key_ids.len(), prop_values.len());
 
         let mut buf = BytesMut::new();
-        buf.put_u8(cf_constraint::UNIQUE_INDEX_PREFIX);
-        buf.put_u16_le(label_id);
+        buf.put_u8(prefix);
+        buf.put_u16_le(token_id);
 
         for (prop_key_id, prop_value) in prop_key_ids.iter().zip(prop_values.iter()) {
             buf.put_u16_le(*prop_key_id);
@@ -188,6 +242,21 @@ impl UniqueIndexCodec {
         }
         Some(NodeId::from_le_bytes(buf[0..8].try_into().ok()?))
     }
+
+    /// Encode relationship unique index value (just the rel_id)
+    pub fn encode_rel_value(rel_id: RelationshipId) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u64_le(*rel_id);
+        buf.freeze()
+    }
+
+    /// Decode relationship unique index value to rel_id
+    pub fn decode_rel_value(buf: &[u8]) -> Option<RelationshipId> {
+        if buf.len() < 8 {
+            return None;
+        }
+        Some(RelationshipId(u64::from_le_bytes(buf[0..8].try_into().ok()?)))
+    }
 }
 
 /// Constraint store operations
@@ -260,8 +329,8 @@ impl ConstraintStore {
         let meta_value = ConstraintCodec::encode_meta_value(meta);
         self.db.put_cf(&cf, &meta_key, &meta_value)?;
 
-        // Store label-to-constraint mapping
-        let label_key = ConstraintCodec::encode_label_constraint_key(meta.label_id, &meta.name);
+        // Store label/reltype-to-constraint mapping
+        let label_key = ConstraintCodec::encode_mapping_key(meta);
         self.db.put_cf(&cf, &label_key, [])?;
 
         Ok(())
@@ -273,8 +342,8 @@ impl ConstraintStore {
 
         // Get the constraint first to find the label_id
         if let Some(meta) = self.get_constraint(name)? {
-            // Delete label-to-constraint mapping
-            let label_key = ConstraintCodec::encode_label_constraint_key(meta.label_id, name);
+            // Delete label/reltype-to-constraint mapping
+            let label_key = ConstraintCodec::encode_mapping_key(&meta);
             self.db.delete_cf(&cf, &label_key)?;
         }
 
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use elio_common::NodeId;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,11 +17,30 @@ pub enum GraphStoreError {
     TypeMismatch(String),
     #[error("token not found: {0}")]
     Token(String),
+    #[error("write conflict with a concurrent transaction, the transaction can be retried: {0}")]
+    WriteConflict(String),
+    #[error("constraint violation: {constraint} - {reason}")]
+    ConstraintViolation { constraint: String, reason: String },
+    #[error("incompatible store format version {found}, expected {expected}")]
+    IncompatibleFormat { found: u32, expected: u32 },
+    #[error("cannot delete node {0}, because it still has relationships, use DETACH DELETE to delete them as well")]
+    DeleteConnectedNode(NodeId),
 }
 
 impl GraphStoreError {
+    /// Errors of committing a rocksdb transaction, the conflicts with other transactions are retryable
     pub fn tx_commit(e: rocksdb::Error) -> Self {
-        Self::TxCommit(e)
+        match e.kind() {
+            rocksdb::ErrorKind::Busy | rocksdb::ErrorKind::TryAgain | rocksdb::ErrorKind::TimedOut => {
+                Self::WriteConflict(e.into_string())
+            }
+            _ => Self::TxCommit(e),
+        }
+    }
+
+    /// The operation may succeed if the transaction is run again
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::WriteConflict(_))
     }
 
     pub fn internal<T: Display>(msg: T) -> Self {
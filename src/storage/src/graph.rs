@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use elio_common::LabelId;
+use elio_common::{LabelId, RelationshipTypeId, TokenId};
 use parking_lot::RwLock;
 use rocksdb;
 use rocksdb::{ColumnFamilyDescriptor, Options};
@@ -9,36 +10,82 @@ use rocksdb::{ColumnFamilyDescriptor, Options};
 use crate::constraint::ConstraintStore;
 use crate::dict::IdStore;
 use crate::error::GraphStoreError;
+use crate::index::IndexStore;
 use crate::token::TokenStore;
-use crate::transaction::TransactionImpl;
-use crate::{cf_constraint, cf_meta, cf_property, cf_topology};
+use crate::transaction::{IsolationLevel, TransactionImpl};
+use crate::{cf_constraint, cf_index, cf_meta, cf_property, cf_topology};
 
 pub struct GraphStore {
     db: Arc<rocksdb::TransactionDB>,
     dict: Arc<IdStore>,
     token: Arc<TokenStore>,
     constraint: Arc<ConstraintStore>,
+    index: Arc<IndexStore>,
     /// Label-level locks for constraint operations
     /// Read lock: normal writes (CREATE node)
-    /// Write lock: CREATE CONSTRAINT (exclusive)
-    label_locks: RwLock<HashMap<LabelId, Arc<RwLock<()>>>>,
+    /// Write lock: CREATE CONSTRAINT and MERGE (exclusive)
+    label_locks: RwLock<HashMap<LabelId, Arc<TokenLock>>>,
+    /// Relationship type locks, taken exclusively by MERGE of relationships
+    reltype_locks: RwLock<HashMap<RelationshipTypeId, Arc<TokenLock>>>,
+    /// Number of the commits so far, the sequence number of the latest commit
+    commit_seq: Arc<AtomicU64>,
+    /// Isolation level of the new transactions
+    isolation: IsolationLevel,
+}
+
+/// Lock of a label or relationship type, it guards the sequence numbers of the last commits of the transactions
+/// holding it exclusively
+type TokenLock = RwLock<TokenSeqs>;
+
+#[derive(Default)]
+struct TokenSeqs {
+    /// Last commit of any exclusive holder, MERGE or DDL
+    commit: u64,
+    /// Last commit of a DDL changing the constraints or indexes
+    schema: u64,
 }
 
 /// Guard for label read lock (allows concurrent writes)
 /// Uses Arc to ensure the lock lives long enough and is Send-safe
 pub struct LabelReadGuard {
-    _lock: Arc<RwLock<()>>,
-    _guard: parking_lot::RwLockReadGuard<'static, ()>,
+    _lock: Arc<TokenLock>,
+    guard: parking_lot::RwLockReadGuard<'static, TokenSeqs>,
+}
+
+impl LabelReadGuard {
+    /// Sequence number of the last commit changing the constraints or indexes, 0 if there is none
+    pub fn schema_seq(&self) -> u64 {
+        self.guard.schema
+    }
 }
 
 // Safety: The guard holds an Arc to the lock, ensuring it lives long enough
 unsafe impl Send for LabelReadGuard {}
 unsafe impl Sync for LabelReadGuard {}
 
-/// Guard for label write lock (exclusive for CREATE CONSTRAINT)
+/// Guard for label or relationship type write lock (exclusive for CREATE CONSTRAINT and MERGE)
 pub struct LabelWriteGuard {
-    _lock: Arc<RwLock<()>>,
-    _guard: parking_lot::RwLockWriteGuard<'static, ()>,
+    _lock: Arc<TokenLock>,
+    guard: parking_lot::RwLockWriteGuard<'static, TokenSeqs>,
+}
+
+impl LabelWriteGuard {
+    /// Sequence number of the last commit of a transaction holding this lock, 0 if there is none
+    pub fn last_commit_seq(&self) -> u64 {
+        self.guard.commit
+    }
+
+    /// Record the commit of the transaction holding this lock
+    pub fn set_last_commit_seq(&mut self, seq: u64) {
+        self.guard.commit = seq;
+    }
+
+    /// Record the commit of a DDL holding this lock, the transactions which started before it and wrote under a
+    /// read lock fail to commit
+    pub fn set_schema_seq(&mut self, seq: u64) {
+        self.guard.commit = seq;
+        self.guard.schema = seq;
+    }
 }
 
 // Safety: The guard holds an Arc to the lock, ensuring it lives long enough
@@ -61,6 +108,7 @@ impl GraphStore {
             ColumnFamilyDescriptor::new(cf_topology::CF_NAME, Options::default()),
             ColumnFamilyDescriptor::new(cf_property::CF_NAME, Options::default()),
             ColumnFamilyDescriptor::new(cf_constraint::CF_NAME, Options::default()),
+            ColumnFamilyDescriptor::new(cf_index::CF_NAME, Options::default()),
         ];
         let tx_db_opts = rocksdb::TransactionDBOptions::default();
 
@@ -77,25 +125,37 @@ impl GraphStore {
                 db.create_cf(cf_topology::CF_NAME, &cf_opts)?;
                 db.create_cf(cf_property::CF_NAME, &cf_opts)?;
                 db.create_cf(cf_constraint::CF_NAME, &cf_opts)?;
+                db.create_cf(cf_index::CF_NAME, &cf_opts)?;
 
                 db
             }
         };
 
+        check_format_version(&db)?;
         let db = Arc::new(db);
         let dict = Arc::new(IdStore::new(db.clone())?);
         let token = Arc::new(TokenStore::new(db.clone())?);
         let constraint = Arc::new(ConstraintStore::new(db.clone()));
+        let index = Arc::new(IndexStore::new(db.clone()));
 
         Ok(Self {
             db,
             dict,
             token,
             constraint,
+            index,
             label_locks: RwLock::new(HashMap::new()),
+            reltype_locks: RwLock::new(HashMap::new()),
+            commit_seq: Arc::new(AtomicU64::new(0)),
+            isolation: IsolationLevel::default(),
         })
     }
 
+    pub fn with_isolation_level(mut self, isolation: IsolationLevel) -> Self {
+        self.isolation = isolation;
+        self
+    }
+
     pub fn token_store(&self) -> &Arc<TokenStore> {
         &self.token
     }
@@ -104,6 +164,10 @@ impl GraphStore {
         &self.constraint
     }
 
+    pub fn index_store(&self) -> &Arc<IndexStore> {
+        &self.index
+    }
+
     pub fn db(&self) -> &Arc<rocksdb::TransactionDB> {
         &self.db
     }
@@ -113,24 +177,19 @@ impl GraphStore {
             self.db.clone(),
             self.dict.clone(),
             self.token.clone(),
+            self.isolation,
+            self.commit_seq.clone(),
         ))
     }
 
+    /// Sequence number of the latest commit, see [`TransactionImpl::start_seq`]
+    pub fn last_commit_seq(&self) -> u64 {
+        self.commit_seq.load(Ordering::SeqCst)
+    }
+
     /// Get or create a lock for the given label
-    fn get_label_lock(&self, label_id: LabelId) -> Arc<RwLock<()>> {
-        // Try read first
-        {
-            let locks = self.label_locks.read();
-            if let Some(lock) = locks.get(&label_id) {
-                return lock.clone();
-            }
-        }
-        // Create if not exists
-        let mut locks = self.label_locks.write();
-        locks
-            .entry(label_id)
-            .or_insert_with(|| Arc::new(RwLock::new(())))
-            .clone()
+    fn get_label_lock(&self, label_id: LabelId) -> Arc<TokenLock> {
+        get_or_create_lock(&self.label_locks, label_id)
     }
 
     /// Acquire read lock for a label (allows concurrent writes)
@@ -141,26 +200,14 @@ impl GraphStore {
         let lock_ptr = Arc::as_ptr(&lock);
         let guard = unsafe { (*lock_ptr).read() };
         // Transmute to 'static lifetime - safe because we hold the Arc
-        let guard: parking_lot::RwLockReadGuard<'static, ()> = unsafe { std::mem::transmute(guard) };
-        LabelReadGuard {
-            _lock: lock,
-            _guard: guard,
-        }
+        let guard: parking_lot::RwLockReadGuard<'static, TokenSeqs> = unsafe { std::mem::transmute(guard) };
+        LabelReadGuard { _lock: lock, guard }
     }
 
     /// Acquire write lock for a label (exclusive)
     /// Used by: CREATE CONSTRAINT FOR (n:Label) ...
     pub fn acquire_label_write(&self, label_id: LabelId) -> LabelWriteGuard {
-        let lock = self.get_label_lock(label_id);
-        // Safety: We keep the Arc in the guard, so the lock lives long enough
-        let lock_ptr = Arc::as_ptr(&lock);
-        let guard = unsafe { (*lock_ptr).write() };
-        // Transmute to 'static lifetime - safe because we hold the Arc
-        let guard: parking_lot::RwLockWriteGuard<'static, ()> = unsafe { std::mem::transmute(guard) };
-        LabelWriteGuard {
-            _lock: lock,
-            _guard: guard,
-        }
+        write_guard(self.get_label_lock(label_id))
     }
 
     /// Acquire read locks for multiple labels (sorted to avoid deadlock)
@@ -170,4 +217,153 @@ impl GraphStore {
         sorted_ids.dedup();
         sorted_ids.into_iter().map(|id| self.acquire_label_read(id)).collect()
     }
+
+    /// Acquire write locks for multiple labels (sorted to avoid deadlock)
+    /// Used by: MERGE, the locks are held until the transaction ends
+    pub fn acquire_labels_write(&self, label_ids: &[LabelId]) -> Vec<LabelWriteGuard> {
+        let mut sorted_ids = label_ids.to_vec();
+        sorted_ids.sort();
+        sorted_ids.dedup();
+        sorted_ids.into_iter().map(|id| self.acquire_label_write(id)).collect()
+    }
+
+    /// Acquire write locks for multiple relationship types (sorted to avoid deadlock)
+    /// Used by: MERGE of relationships, the locks are held until the transaction ends
+    pub fn acquire_reltypes_write(&self, reltype_ids: &[RelationshipTypeId]) -> Vec<LabelWriteGuard> {
+        let mut sorted_ids = reltype_ids.to_vec();
+        sorted_ids.sort();
+        sorted_ids.dedup();
+        sorted_ids
+            .into_iter()
+            .map(|id| write_guard(get_or_create_lock(&self.reltype_locks, id)))
+            .collect()
+    }
+}
+
+/// Version of the on-disk format, it changes with the encoding of the keys. Stores of another version are not opened.
+pub const FORMAT_VERSION: u32 = 1;
+
+// Check the format version of the store, a new store gets the current one. Stores created before the version was
+// recorded are only accepted without unique constraint or index entries, which are keyed by the encoding of values.
+fn check_format_version(db: &rocksdb::TransactionDB) -> Result<(), GraphStoreError> {
+    let meta = db.cf_handle(cf_meta::CF_NAME).unwrap();
+    let found = match db.get_cf(&meta, cf_meta::FORMAT_VERSION_KEY)? {
+        Some(value) => u32::from_be_bytes(value.as_slice().try_into().map_err(GraphStoreError::internal)?),
+        None if has_value_keys(db)? => 0,
+        None => {
+            db.put_cf(&meta, cf_meta::FORMAT_VERSION_KEY, FORMAT_VERSION.to_be_bytes())?;
+            FORMAT_VERSION
+        }
+    };
+    if found != FORMAT_VERSION {
+        return Err(GraphStoreError::IncompatibleFormat {
+            found,
+            expected: FORMAT_VERSION,
+        });
+    }
+    Ok(())
+}
+
+// whether the store has keys holding encoded property values
+fn has_value_keys(db: &rocksdb::TransactionDB) -> Result<bool, GraphStoreError> {
+    let prefixes = [
+        (cf_constraint::CF_NAME, cf_constraint::UNIQUE_INDEX_PREFIX),
+        (cf_constraint::CF_NAME, cf_constraint::REL_UNIQUE_INDEX_PREFIX),
+        (cf_index::CF_NAME, cf_index::INDEX_ENTRY_PREFIX),
+    ];
+    for (cf_name, prefix) in prefixes {
+        let cf = db.cf_handle(cf_name).unwrap();
+        if let Some(item) = db.prefix_iterator_cf(&cf, [prefix]).next() {
+            let (key, _) = item?;
+            if key.starts_with(&[prefix]) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn get_or_create_lock(locks: &RwLock<HashMap<TokenId, Arc<TokenLock>>>, id: TokenId) -> Arc<TokenLock> {
+    // Try read first
+    {
+        let locks = locks.read();
+        if let Some(lock) = locks.get(&id) {
+            return lock.clone();
+        }
+    }
+    // Create if not exists
+    let mut locks = locks.write();
+    locks.entry(id).or_insert_with(|| Arc::new(RwLock::default())).clone()
+}
+
+fn write_guard(lock: Arc<TokenLock>) -> LabelWriteGuard {
+    // Safety: We keep the Arc in the guard, so the lock lives long enough
+    let lock_ptr = Arc::as_ptr(&lock);
+    let guard = unsafe { (*lock_ptr).write() };
+    // Transmute to 'static lifetime - safe because we hold the Arc
+    let guard: parking_lot::RwLockWriteGuard<'static, TokenSeqs> = unsafe { std::mem::transmute(guard) };
+    LabelWriteGuard { _lock: lock, guard }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(dir: &tempfile::TempDir) -> Result<GraphStore, GraphStoreError> {
+        GraphStore::open(dir.path().to_str().unwrap())
+    }
+
+    #[test]
+    fn test_format_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open(&dir).unwrap();
+        let meta = store.db().cf_handle(cf_meta::CF_NAME).unwrap();
+        let version = store.db().get_cf(&meta, cf_meta::FORMAT_VERSION_KEY).unwrap();
+        assert_eq!(version, Some(FORMAT_VERSION.to_be_bytes().to_vec()));
+        drop(meta);
+        drop(store);
+        assert!(open(&dir).is_ok());
+
+        // a store of another version
+        let store = open(&dir).unwrap();
+        let meta = store.db().cf_handle(cf_meta::CF_NAME).unwrap();
+        store
+            .db()
+            .put_cf(&meta, cf_meta::FORMAT_VERSION_KEY, (FORMAT_VERSION + 1).to_be_bytes())
+            .unwrap();
+        drop(meta);
+        drop(store);
+        assert!(matches!(
+            open(&dir),
+            Err(GraphStoreError::IncompatibleFormat { found, .. }) if found == FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_unversioned_store() {
+        // without unique constraint or index entries, the version is added
+        let dir = tempfile::tempdir().unwrap();
+        let store = open(&dir).unwrap();
+        let meta = store.db().cf_handle(cf_meta::CF_NAME).unwrap();
+        store.db().delete_cf(&meta, cf_meta::FORMAT_VERSION_KEY).unwrap();
+        drop(meta);
+        drop(store);
+        assert!(open(&dir).is_ok());
+
+        // the entries of a unique constraint were encoded by an older format
+        let store = open(&dir).unwrap();
+        let meta = store.db().cf_handle(cf_meta::CF_NAME).unwrap();
+        let constraint = store.db().cf_handle(cf_constraint::CF_NAME).unwrap();
+        store.db().delete_cf(&meta, cf_meta::FORMAT_VERSION_KEY).unwrap();
+        store
+            .db()
+            .put_cf(&constraint, [cf_constraint::UNIQUE_INDEX_PREFIX, 0, 1], [])
+            .unwrap();
+        drop((meta, constraint));
+        drop(store);
+        assert!(matches!(
+            open(&dir),
+            Err(GraphStoreError::IncompatibleFormat { found: 0, .. })
+        ));
+    }
 }
@@ -0,0 +1,204 @@
+//! Secondary index metadata and operations
+//!
+//! This module provides:
+//! - Index metadata storage and retrieval
+//! - Index entry encoding, entries are ordered by label, properties and values
+
+use std::sync::Arc;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use elio_common::mapb::{IndexKeyCodec, PropertyMapMut, PropertyMapRef};
+use elio_common::scalar::ScalarRef;
+use elio_common::{LabelId, NodeId, PropertyKeyId};
+
+use crate::cf_index;
+use crate::error::GraphStoreError;
+
+/// Index metadata stored in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexMeta {
+    pub name: String,
+    pub label_id: LabelId,
+    pub property_key_ids: Vec<PropertyKeyId>,
+}
+
+/// Codec for index metadata and entries
+pub struct IndexCodec;
+
+impl IndexCodec {
+    /// Encode index metadata key
+    /// Format: | prefix (1B) | name_len (2B) | name |
+    pub fn encode_meta_key(name: &str) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_index::INDEX_META_PREFIX);
+        buf.put_u16_le(name.len() as u16);
+        buf.put_slice(name.as_bytes());
+        buf.freeze()
+    }
+
+    /// Encode index metadata value
+    /// Format: | label_id (2B) | prop_count (2B) | prop_ids... |
+    pub fn encode_meta_value(meta: &IndexMeta) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u16_le(meta.label_id);
+        buf.put_u16_le(meta.property_key_ids.len() as u16);
+        for prop_id in &meta.property_key_ids {
+            buf.put_u16_le(*prop_id);
+        }
+        buf.freeze()
+    }
+
+    /// Decode index metadata value
+    pub fn decode_meta_value(name: String, buf: &[u8]) -> Option<IndexMeta> {
+        if buf.len() < 4 {
+            return None;
+        }
+        let label_id = u16::from_le_bytes([buf[0], buf[1]]);
+        let prop_count = u16::from_le_bytes([buf[2], buf[3]]) as usize;
+        if buf.len() < 4 + prop_count * 2 {
+            return None;
+        }
+        let property_key_ids = (0..prop_count)
+            .map(|i| u16::from_le_bytes([buf[4 + i * 2], buf[5 + i * 2]]))
+            .collect();
+        Some(IndexMeta {
+            name,
+            label_id,
+            property_key_ids,
+        })
+    }
+
+    /// Encode label-to-index mapping key
+    /// Format: | prefix (1B) | label_id (2B) | name_len (2B) | name |
+    pub fn encode_label_index_key(label_id: LabelId, name: &str) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_index::LABEL_INDEX_PREFIX);
+        buf.put_u16_le(label_id);
+        buf.put_u16_le(name.len() as u16);
+        buf.put_slice(name.as_bytes());
+        buf.freeze()
+    }
+
+    /// Encode label-to-index prefix for iteration
+    pub fn encode_label_index_prefix(label_id: LabelId) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_index::LABEL_INDEX_PREFIX);
+        buf.put_u16_le(label_id);
+        buf.freeze()
+    }
+
+    /// Decode index name from label-to-index mapping key
+    pub fn decode_label_index_key(buf: &[u8]) -> Option<String> {
+        if buf.len() < 5 || buf[0] != cf_index::LABEL_INDEX_PREFIX {
+            return None;
+        }
+        let name_len = u16::from_le_bytes([buf[3], buf[4]]) as usize;
+        if buf.len() < 5 + name_len {
+            return None;
+        }
+        String::from_utf8(buf[5..5 + name_len].to_vec()).ok()
+    }
+
+    /// Encode the prefix of the entries of an index, followed by the given leading values
+    /// Format: | prefix (1B) | label_id (2B) | prop_count (2B) | prop_key_ids (2B each) | values... |
+    ///
+    /// The ids are big endian and the values are memcomparable, so the entries of an index are ordered by
+    /// their values, and the entries with the same leading values share a prefix.
+    pub fn encode_entry_prefix(label_id: LabelId, prop_key_ids: &[PropertyKeyId], prop_values: &[&[u8]]) -> Bytes {
+        assert!(prop_values.len() <= prop_key_ids.len());
+
+        let mut buf = BytesMut::new();
+        buf.put_u8(cf_index::INDEX_ENTRY_PREFIX);
+        buf.put_u16(label_id);
+        buf.put_u16(prop_key_ids.len() as u16);
+        for prop_key_id in prop_key_ids {
+            buf.put_u16(*prop_key_id);
+        }
+        for prop_value in prop_values {
+            buf.put_slice(prop_value);
+        }
+        buf.freeze()
+    }
+
+    /// Encode index entry key, the entry prefix with all the values followed by the node id
+    pub fn encode_entry_key(
+        label_id: LabelId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+        node_id: NodeId,
+    ) -> Bytes {
+        assert_eq!(prop_key_ids.len(), prop_values.len());
+
+        let mut buf = BytesMut::from(Self::encode_entry_prefix(label_id, prop_key_ids, prop_values).as_ref());
+        buf.put_u64(*node_id);
+        buf.freeze()
+    }
+
+    /// Decode node id from index entry key
+    pub fn decode_entry_node_id(buf: &[u8]) -> NodeId {
+        NodeId::from_be_bytes(buf[buf.len() - 8..].try_into().unwrap())
+    }
+
+    /// Encode index entry value, the indexed properties so that the values can be checked without reading the node
+    pub fn encode_entry_value(prop_key_ids: &[PropertyKeyId], values: &[ScalarRef<'_>]) -> Result<Bytes, String> {
+        let mut map = PropertyMapMut::with_capacity(prop_key_ids.len());
+        for (prop_key_id, value) in prop_key_ids.iter().zip(values) {
+            map.insert(*prop_key_id, Some(value))?;
+        }
+        let mut buf = BytesMut::new();
+        map.freeze().write(&mut buf);
+        Ok(buf.freeze())
+    }
+
+    /// Decode index entry value
+    pub fn decode_entry_value(buf: &[u8]) -> PropertyMapRef<'_> {
+        PropertyMapRef::new(buf)
+    }
+
+    /// Encode a value of an indexed property, None for null which is not indexed
+    pub fn encode_value(value: &ScalarRef<'_>) -> Option<Vec<u8>> {
+        (!matches!(value, ScalarRef::Null)).then(|| IndexKeyCodec::encode_single(value))
+    }
+}
+
+/// Index store operations, reading the committed indexes
+pub struct IndexStore {
+    db: Arc<rocksdb::TransactionDB>,
+}
+
+impl IndexStore {
+    pub fn new(db: Arc<rocksdb::TransactionDB>) -> Self {
+        Self { db }
+    }
+
+    /// Get index metadata by name
+    pub fn get_index(&self, name: &str) -> Result<Option<IndexMeta>, GraphStoreError> {
+        let cf = self.db.cf_handle(cf_index::CF_NAME).unwrap();
+        let key = IndexCodec::encode_meta_key(name);
+        match self.db.get_cf(&cf, &key)? {
+            Some(value) => Ok(IndexCodec::decode_meta_value(name.to_string(), &value)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get all indexes for a label
+    pub fn get_indexes_for_label(&self, label_id: LabelId) -> Result<Vec<IndexMeta>, GraphStoreError> {
+        let cf = self.db.cf_handle(cf_index::CF_NAME).unwrap();
+        let prefix = IndexCodec::encode_label_index_prefix(label_id);
+
+        let mut indexes = Vec::new();
+        for item in self.db.prefix_iterator_cf(&cf, &prefix) {
+            let (key, _) = item?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            if let Some(name) = IndexCodec::decode_label_index_key(&key)
+                && let Some(meta) = self.get_index(&name)?
+            {
+                indexes.push(meta);
+            }
+        }
+
+        Ok(indexes)
+    }
+}
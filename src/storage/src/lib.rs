@@ -3,6 +3,7 @@ pub mod constraint;
 pub mod dict;
 pub mod error;
 pub mod graph;
+pub mod index;
 pub mod token;
 pub mod transaction;
 
@@ -15,17 +16,23 @@ pub(crate) mod cf_meta {
     // id allocation
     pub(crate) const MAX_NODE_ID_KEY: &[u8; 1] = &[0x04];
     pub(crate) const MAX_REL_ID_KEY: &[u8; 1] = &[0x05];
+    // version of the on-disk format
+    pub(crate) const FORMAT_VERSION_KEY: &[u8; 1] = &[0x06];
 }
 
 pub(crate) mod cf_topology {
     pub const CF_NAME: &str = "cf_topology";
     pub const REL_KEY_PREFIX: u8 = 0x01;
+    // relationships by type: | prefix | reltype_id | src_node_id | dst_node_id | rel_id |
+    pub const RELTYPE_KEY_PREFIX: u8 = 0x02;
 }
 
 pub(crate) mod cf_property {
     // node property
     pub const CF_NAME: &str = "cf_property";
     pub const NODE_KEY_PREFIX: &[u8; 1] = &[0x01];
+    // nodes by label: | prefix | label_id | node_id |
+    pub const LABEL_KEY_PREFIX: &[u8; 1] = &[0x02];
 }
 
 pub(crate) mod cf_constraint {
@@ -36,4 +43,18 @@ pub(crate) mod cf_constraint {
     pub const UNIQUE_INDEX_PREFIX: u8 = 0x02;
     // Label to constraints mapping: | prefix | label_id | constraint_name |
     pub const LABEL_CONSTRAINT_PREFIX: u8 = 0x03;
+    // Relationship type to constraints mapping: | prefix | reltype_id | constraint_name |
+    pub const RELTYPE_CONSTRAINT_PREFIX: u8 = 0x04;
+    // Relationship unique index: | prefix | reltype_id | prop_key_ids... | prop_values... |
+    pub const REL_UNIQUE_INDEX_PREFIX: u8 = 0x05;
+}
+
+pub(crate) mod cf_index {
+    pub const CF_NAME: &str = "cf_index";
+    // Index metadata: | prefix | index_name |
+    pub const INDEX_META_PREFIX: u8 = 0x01;
+    // Label to indexes mapping: | prefix | label_id | index_name |
+    pub const LABEL_INDEX_PREFIX: u8 = 0x02;
+    // Index entry: | prefix | label_id | prop_key_ids... | prop_values... | node_id |
+    pub const INDEX_ENTRY_PREFIX: u8 = 0x03;
 }
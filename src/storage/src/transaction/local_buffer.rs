@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::Peekable;
+use std::ops::Bound;
+
+use bytes::Bytes;
+
+use crate::error::GraphStoreError;
+
+pub(crate) type KeyValue = (Box<[u8]>, Box<[u8]>);
+// iterator over the committed data seen by a transaction
+pub(crate) type StoreIter<'a> = Box<dyn Iterator<Item = Result<KeyValue, rocksdb::Error>> + Send + 'a>;
+
+/// Writes of a transaction not committed yet, by column family. A deleted key is kept as None, so that it hides
+/// the value in the snapshot.
+#[derive(Default)]
+pub(crate) struct LocalBuffer {
+    cfs: HashMap<&'static str, BTreeMap<Bytes, Option<Bytes>>>,
+}
+
+impl LocalBuffer {
+    pub fn put(&mut self, cf: &'static str, key: impl Into<Bytes>, value: impl Into<Bytes>) {
+        self.cfs.entry(cf).or_default().insert(key.into(), Some(value.into()));
+    }
+
+    pub fn delete(&mut self, cf: &'static str, key: impl Into<Bytes>) {
+        self.cfs.entry(cf).or_default().insert(key.into(), None);
+    }
+
+    /// None if the key is not written in this transaction, Some(None) if it is deleted
+    pub fn get(&self, cf: &'static str, key: &[u8]) -> Option<Option<&Bytes>> {
+        self.cfs.get(cf)?.get(key).map(Option::as_ref)
+    }
+
+    /// The entries whose key starts with the prefix from the key `start` on, in key order
+    pub fn range_entries(&self, cf: &'static str, prefix: &[u8], start: &[u8]) -> Vec<(Bytes, Option<Bytes>)> {
+        let Some(entries) = self.cfs.get(cf) else {
+            return vec![];
+        };
+        entries
+            .range::<[u8], _>((Bound::Included(start), Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.cfs.clear();
+    }
+
+    /// Apply all the writes to the rocksdb transaction, in the order of the keys
+    pub fn write_to(
+        &self,
+        db: &rocksdb::TransactionDB,
+        txn: &rocksdb::Transaction<'_, rocksdb::TransactionDB>,
+    ) -> Result<(), rocksdb::Error> {
+        for (cf_name, entries) in self.cfs.iter() {
+            let cf = db.cf_handle(cf_name).unwrap();
+            for (key, value) in entries {
+                match value {
+                    Some(value) => txn.put_cf(&cf, key, value)?,
+                    None => txn.delete_cf(&cf, key)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merges a snapshot iterator with the local writes under the same prefix, local writes take precedence. The local
+/// writes are copied when the iterator is created, writes made afterwards are not seen.
+pub(crate) struct MergedIter<'a> {
+    snapshot: Peekable<StoreIter<'a>>,
+    local: Peekable<std::vec::IntoIter<(Bytes, Option<Bytes>)>>,
+}
+
+impl<'a> MergedIter<'a> {
+    pub fn new(snapshot: StoreIter<'a>, local: Vec<(Bytes, Option<Bytes>)>) -> Self {
+        Self {
+            snapshot: snapshot.peekable(),
+            local: local.into_iter().peekable(),
+        }
+    }
+
+    // the next local entry, None if it is a deletion
+    fn next_local(&mut self) -> Option<KeyValue> {
+        let (key, value) = self.local.next()?;
+        value.map(|value| (key.to_vec().into(), value.to_vec().into()))
+    }
+}
+
+impl Iterator for MergedIter<'_> {
+    type Item = Result<KeyValue, GraphStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let order = match (self.snapshot.peek(), self.local.peek()) {
+                (None, None) => return None,
+                (Some(Err(_)), _) | (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(Ok((snapshot_key, _))), Some((local_key, _))) => (**snapshot_key).cmp(local_key.as_ref()),
+            };
+            match order {
+                Ordering::Less => return self.snapshot.next().map(|item| item.map_err(Into::into)),
+                Ordering::Equal => {
+                    // overwritten or deleted in this transaction
+                    self.snapshot.next();
+                }
+                Ordering::Greater => {}
+            }
+            if let Some(entry) = self.next_local() {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
@@ -1,56 +1,132 @@
-use std::ops::Deref;
+use std::collections::{BTreeSet, HashSet};
+use std::ops::{Bound, Deref};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use bitvec::vec::BitVec;
+use bytes::Bytes;
 use elio_common::array::chunk::DataChunk;
 use elio_common::array::{ArrayImpl, NodeArray, RelArray, StructArray, VirtualNodeArray};
-use elio_common::{LabelId, NodeId, PropertyKeyId, SemanticDirection, TokenId};
+use elio_common::scalar::{NodeValue, RelValue, ScalarValue, VirtualRelRef};
+use elio_common::{LabelId, NodeId, PropertyKeyId, RelationshipId, RelationshipTypeId, SemanticDirection, TokenId};
 
-use crate::cf_constraint;
 use crate::constraint::{ConstraintCodec, ConstraintMeta, UniqueIndexCodec};
 use crate::dict::IdStore;
 use crate::error::GraphStoreError;
+use crate::index::{IndexCodec, IndexMeta};
 use crate::token::TokenStore;
-use crate::transaction::node::{batch_materialize_node, batch_node_create, batch_node_scan};
-use crate::transaction::relationship::{NodeIdContainer, RelIterForNode, batch_rel_create, rel_iter_for_node};
-
+use crate::transaction::local_buffer::{LocalBuffer, MergedIter, StoreIter};
+use crate::transaction::node::{
+    batch_label_scan, batch_materialize_node, batch_node_create, batch_node_delete, batch_node_scan, build_index,
+    get_node, node_update,
+};
+use crate::transaction::relationship::{
+    NodeIdContainer, RelIterForNode, batch_rel_create, batch_rel_delete, batch_rel_scan, get_relationship,
+    rel_iter_for_node, relationship_update,
+};
+use crate::{cf_constraint, cf_index};
+
+mod local_buffer;
 mod node;
 mod relationship;
 
-pub struct RelScanOptions {}
+pub struct RelScanOptions {
+    pub batch_size: usize,
+    // relationship types to scan
+    pub reltypes: Vec<TokenId>,
+}
 pub struct NodeScanOptions {
     pub batch_size: usize,
 }
+pub struct LabelScanOptions {
+    pub batch_size: usize,
+    // label of the nodes to scan
+    pub label_id: LabelId,
+}
 
 #[async_trait]
 pub trait DataChunkIterator: Send {
     fn next_batch(&mut self) -> Result<Option<DataChunk>, GraphStoreError>;
 }
 
-// Simple transaction implementation with snapshot and write batch buffer
+/// Isolation level of the transactions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// Reads see the data committed before the transaction started. The commit fails with a write conflict if
+    /// another transaction committed a write to the same data after the start.
+    #[default]
+    Snapshot,
+    /// Reads see the latest committed data. Commits writing the same data are serialized, the last one wins.
+    ReadCommitted,
+}
+
+// Simple transaction implementation with snapshot and write batch buffer. The writes are applied to a rocksdb
+// transaction on commit, which detects the conflicts with other transactions.
 pub struct TransactionImpl {
+    // taken before the snapshot, None once the transaction ends. Dropped before the db.
+    db_txn: Mutex<Option<rocksdb::Transaction<'static, rocksdb::TransactionDB>>>,
     pub(crate) inner: OwnedSnapshot,
+    isolation: IsolationLevel,
     dict: Arc<IdStore>,
     token: Arc<TokenStore>,
+    // commit sequence of the store, and its value before the snapshot was taken
+    commit_seq: Arc<AtomicU64>,
+    start_seq: u64,
     // write buffer
     write_state: Mutex<WriteState>,
 }
 
 #[derive(Default)]
 pub struct WriteState {
-    // TODO(pgao): should we use transaction db?
-    pub(crate) batch: rocksdb::WriteBatchWithTransaction<true>,
-    // TODO(pgao): local buffer
-    // local_cache: HashMap<Vec<u8>, Option<Vec<u8>>>,
+    // all the writes of this transaction, reads merge them over the snapshot
+    pub(crate) local: LocalBuffer,
+    // relationships deleted in this transaction
+    pub(crate) deleted_rels: HashSet<RelationshipId>,
+    // nodes deleted without DETACH, they must have no relationship left on commit
+    pub(crate) deleted_nodes: HashSet<NodeId>,
+    // keys read by the writes, the commit fails if another transaction changed them, e.g. the end nodes of the
+    // created relationships
+    pub(crate) validated_reads: BTreeSet<(&'static str, Bytes)>,
+}
+
+impl WriteState {
+    fn clear(&mut self) {
+        self.local.clear();
+        self.deleted_rels.clear();
+        self.deleted_nodes.clear();
+        self.validated_reads.clear();
+    }
 }
 
 impl TransactionImpl {
-    pub fn new(db: Arc<rocksdb::TransactionDB>, dict: Arc<IdStore>, token: Arc<TokenStore>) -> Self {
+    pub fn new(
+        db: Arc<rocksdb::TransactionDB>,
+        dict: Arc<IdStore>,
+        token: Arc<TokenStore>,
+        isolation: IsolationLevel,
+        commit_seq: Arc<AtomicU64>,
+    ) -> Self {
+        // read before the snapshot, a commit after it has a larger sequence number
+        let start_seq = commit_seq.load(Ordering::SeqCst);
+        let mut txn_opts = rocksdb::TransactionOptions::default();
+        // the snapshot of the rocksdb transaction makes the commit validate the written keys
+        txn_opts.set_snapshot(isolation == IsolationLevel::Snapshot);
+        let db_txn = unsafe {
+            let txn = db.transaction_opt(&rocksdb::WriteOptions::default(), &txn_opts);
+            std::mem::transmute::<
+                rocksdb::Transaction<'_, rocksdb::TransactionDB>,
+                rocksdb::Transaction<'static, rocksdb::TransactionDB>,
+            >(txn)
+        };
         Self {
+            db_txn: Mutex::new(Some(db_txn)),
             inner: OwnedSnapshot::new(db),
+            isolation,
             dict,
             token,
+            commit_seq,
+            start_seq,
             write_state: WriteState::default().into(),
         }
     }
@@ -58,14 +134,20 @@ impl TransactionImpl {
 
 // impl Transaction for TransactionImpl {
 impl TransactionImpl {
-    pub fn rel_scan(&self, _opts: &RelScanOptions) -> Result<Box<dyn DataChunkIterator>, GraphStoreError> {
-        todo!()
+    /// Scan the relationships of the given types, the chunks are (rel, start node, end node).
+    pub fn rel_scan(&self, opts: RelScanOptions) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
+        batch_rel_scan(self, opts)
     }
 
     pub fn node_scan(&self, opts: NodeScanOptions) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
         batch_node_scan(self, opts)
     }
 
+    /// Scan the nodes with the label, the chunks are (node).
+    pub fn label_scan(&self, opts: LabelScanOptions) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
+        batch_label_scan(self, opts)
+    }
+
     pub fn materialize_node(&self, node_ids: &VirtualNodeArray, vis: &BitVec) -> Result<NodeArray, GraphStoreError> {
         batch_materialize_node(self, node_ids, vis)
     }
@@ -88,12 +170,34 @@ impl TransactionImpl {
         batch_rel_create(self, rtype, start, end, prop)
     }
 
-    pub fn node_delete(&self, _node: &DataChunk) -> Result<(), GraphStoreError> {
-        todo!()
+    /// Delete nodes with their unique index entries. With `detach`, their relationships are deleted as well,
+    /// otherwise the nodes must not have relationships when the transaction commits.
+    pub fn node_delete(&self, node_ids: &[NodeId], detach: bool) -> Result<(), GraphStoreError> {
+        batch_node_delete(self, node_ids, detach)
+    }
+
+    pub fn relationship_delete(&self, rels: &[VirtualRelRef<'_>]) -> Result<(), GraphStoreError> {
+        batch_rel_delete(self, rels)
     }
 
-    pub fn relationship_delete(&self, _rel: &DataChunk) -> Result<(), GraphStoreError> {
-        todo!()
+    /// Read a node with the updates of this transaction.
+    pub fn get_node(&self, node_id: NodeId) -> Result<Option<NodeValue>, GraphStoreError> {
+        get_node(self, node_id)
+    }
+
+    /// Overwrite labels and properties of an existing node, null properties are removed.
+    pub fn node_update(&self, node: &NodeValue) -> Result<(), GraphStoreError> {
+        node_update(self, node)
+    }
+
+    /// Read a relationship with the updates of this transaction.
+    pub fn get_relationship(&self, rel: &VirtualRelRef<'_>) -> Result<Option<RelValue>, GraphStoreError> {
+        get_relationship(self, rel)
+    }
+
+    /// Overwrite properties of an existing relationship, null properties are removed.
+    pub fn relationship_update(&self, rel: &RelValue) -> Result<(), GraphStoreError> {
+        relationship_update(self, rel)
     }
 
     pub fn rel_iter_for_node(
@@ -106,56 +210,143 @@ impl TransactionImpl {
     }
 
     pub fn commit(&self) -> Result<(), GraphStoreError> {
+        // deleted nodes must not leave dangling relationships, the deleted relationships are not seen any more
+        let deleted_nodes = self.write_state.lock().unwrap().deleted_nodes.clone();
+        for node_id in deleted_nodes {
+            if let Some(rel) = self.rel_iter_for_node(node_id, SemanticDirection::Both, &[])?.next() {
+                rel?;
+                return Err(GraphStoreError::DeleteConnectedNode(node_id));
+            }
+        }
         let mut state = self.write_state.lock().unwrap();
-        let batch = std::mem::take(&mut state.batch);
-        self.inner._db.write(batch)?;
+        let txn = self
+            .db_txn
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| GraphStoreError::internal("transaction already ended"))?;
+        let db = &self.inner._db;
+        for (cf_name, key) in state.validated_reads.iter() {
+            let cf = db.cf_handle(cf_name).unwrap();
+            txn.get_for_update_cf(&cf, key, true)
+                .map_err(GraphStoreError::tx_commit)?;
+        }
+        state.local.write_to(db, &txn).map_err(GraphStoreError::tx_commit)?;
+        txn.commit().map_err(GraphStoreError::tx_commit)?;
+        self.commit_seq.fetch_add(1, Ordering::SeqCst);
+        state.clear();
         Ok(())
     }
 
+    /// Sequence number of the latest commit before the snapshot was taken, the writes of the commits with a larger
+    /// sequence number are not seen by the snapshot
+    pub fn start_seq(&self) -> u64 {
+        self.start_seq
+    }
+
     pub fn abort(&self) -> Result<(), GraphStoreError> {
-        let mut state = self.write_state.lock().unwrap();
-        state.batch.clear();
+        // dropping the rocksdb transaction rolls it back
+        self.db_txn.lock().unwrap().take();
+        self.write_state.lock().unwrap().clear();
         Ok(())
     }
 
+    /// Read a key with the writes of this transaction
+    pub(crate) fn get_cf(&self, cf_name: &'static str, key: &[u8]) -> Result<Option<Vec<u8>>, GraphStoreError> {
+        if let Some(value) = self.write_state.lock().unwrap().local.get(cf_name, key) {
+            return Ok(value.map(|v| v.to_vec()));
+        }
+        let cf = self.inner._db.cf_handle(cf_name).unwrap();
+        match self.isolation {
+            IsolationLevel::Snapshot => Ok(self.inner.snapshot.get_cf(&cf, key)?),
+            IsolationLevel::ReadCommitted => Ok(self.inner._db.get_cf(&cf, key)?),
+        }
+    }
+
+    /// Read the committed values of the keys, without the writes of this transaction
+    pub(crate) fn multi_get_committed(
+        &self,
+        cf_name: &'static str,
+        keys: &[Bytes],
+    ) -> Vec<Result<Option<Vec<u8>>, rocksdb::Error>> {
+        let cf = self.inner._db.cf_handle(cf_name).unwrap();
+        let keys_cf = keys.iter().map(|k| (&cf, k));
+        match self.isolation {
+            IsolationLevel::Snapshot => self.inner.snapshot.multi_get_cf(keys_cf),
+            IsolationLevel::ReadCommitted => self.inner._db.multi_get_cf(keys_cf),
+        }
+    }
+
+    /// Iterate the keys with the prefix, writes of this transaction made before the call are seen
+    pub(crate) fn prefix_iter(&self, cf_name: &'static str, prefix: &[u8]) -> MergedIter<'_> {
+        self.range_iter(cf_name, prefix, prefix)
+    }
+
+    /// Iterate the keys with the prefix starting from the key `start`, which must have the prefix
+    pub(crate) fn range_iter(&self, cf_name: &'static str, prefix: &[u8], start: &[u8]) -> MergedIter<'_> {
+        debug_assert!(start.starts_with(prefix));
+        let cf = self.inner._db.cf_handle(cf_name).unwrap();
+        let mut readopts = rocksdb::ReadOptions::default();
+        readopts.set_prefix_same_as_start(true);
+        let mode = rocksdb::IteratorMode::From(start, rocksdb::Direction::Forward);
+        let iter: StoreIter<'_> = match self.isolation {
+            IsolationLevel::Snapshot => Box::new(self.inner.snapshot.iterator_cf_opt(&cf, readopts, mode)),
+            IsolationLevel::ReadCommitted => Box::new(self.inner._db.iterator_cf_opt(&cf, readopts, mode)),
+        };
+        let local = self
+            .write_state
+            .lock()
+            .unwrap()
+            .local
+            .range_entries(cf_name, prefix, start);
+        MergedIter::new(iter, local)
+    }
+
+    /// Fail the commit if another transaction changes the key after it is read by this transaction
+    pub(crate) fn validate_read(&self, cf_name: &'static str, key: Bytes) {
+        self.write_state.lock().unwrap().validated_reads.insert((cf_name, key));
+    }
+
     // ==================== Constraint Operations ====================
 
-    /// Check if a constraint exists (reads from snapshot)
+    /// Check if a constraint exists
     pub fn constraint_exists(&self, name: &str) -> Result<bool, GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let key = ConstraintCodec::encode_meta_key(name);
-        Ok(self.inner.snapshot.get_cf(&cf, &key)?.is_some())
+        Ok(self.get_cf(cf_constraint::CF_NAME, &key)?.is_some())
     }
 
-    /// Get constraint metadata by name (reads from snapshot)
+    /// Get constraint metadata by name
     pub fn get_constraint(&self, name: &str) -> Result<Option<ConstraintMeta>, GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let key = ConstraintCodec::encode_meta_key(name);
-        match self.inner.snapshot.get_cf(&cf, &key)? {
+        match self.get_cf(cf_constraint::CF_NAME, &key)? {
             Some(value) => Ok(ConstraintCodec::decode_meta_value(name.to_string(), &value)),
             None => Ok(None),
         }
     }
 
-    /// Get all constraints for a label (reads from snapshot)
+    /// Get all constraints for a label
     pub fn get_constraints_for_label(&self, label_id: LabelId) -> Result<Vec<ConstraintMeta>, GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
-        let prefix = ConstraintCodec::encode_label_constraint_prefix(label_id);
+        self.get_constraints_by_prefix(&ConstraintCodec::encode_label_constraint_prefix(label_id))
+    }
 
-        let mut constraints = Vec::new();
-        let mut readopts = rocksdb::ReadOptions::default();
-        readopts.set_prefix_same_as_start(true);
-        let mode = rocksdb::IteratorMode::From(&prefix, rocksdb::Direction::Forward);
-        let iter = self.inner.snapshot.iterator_cf_opt(&cf, readopts, mode);
+    /// Get all constraints for a relationship type
+    pub fn get_constraints_for_reltype(
+        &self,
+        reltype_id: RelationshipTypeId,
+    ) -> Result<Vec<ConstraintMeta>, GraphStoreError> {
+        self.get_constraints_by_prefix(&ConstraintCodec::encode_reltype_constraint_prefix(reltype_id))
+    }
 
-        for item in iter {
+    fn get_constraints_by_prefix(&self, prefix: &[u8]) -> Result<Vec<ConstraintMeta>, GraphStoreError> {
+        let mut constraints = Vec::new();
+        for item in self.prefix_iter(cf_constraint::CF_NAME, prefix) {
             let (key, _) = item?;
-            if !key.starts_with(&prefix) {
+            if !key.starts_with(prefix) {
                 break;
             }
 
             // Extract constraint name from the key
-            let name_len_offset = 3; // prefix (1) + label_id (2)
+            let name_len_offset = 3; // prefix (1) + label_id/reltype_id (2)
             if key.len() < name_len_offset + 2 {
                 continue;
             }
@@ -176,36 +367,33 @@ impl TransactionImpl {
 
     /// Store a constraint (buffered in write batch)
     pub fn put_constraint(&self, meta: &ConstraintMeta) -> Result<(), GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let mut guard = self.write_state.lock().unwrap();
 
         // Store metadata
         let meta_key = ConstraintCodec::encode_meta_key(&meta.name);
         let meta_value = ConstraintCodec::encode_meta_value(meta);
-        guard.batch.put_cf(&cf, &meta_key, &meta_value);
+        guard.local.put(cf_constraint::CF_NAME, meta_key, meta_value);
 
-        // Store label-to-constraint mapping
-        let label_key = ConstraintCodec::encode_label_constraint_key(meta.label_id, &meta.name);
-        guard.batch.put_cf(&cf, &label_key, []);
+        // Store label/reltype-to-constraint mapping
+        let label_key = ConstraintCodec::encode_mapping_key(meta);
+        guard.local.put(cf_constraint::CF_NAME, label_key, Bytes::new());
 
         Ok(())
     }
 
     /// Delete a constraint (buffered in write batch)
     pub fn delete_constraint(&self, name: &str) -> Result<(), GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
-
         // Get the constraint first to find the label_id
         if let Some(meta) = self.get_constraint(name)? {
             let mut guard = self.write_state.lock().unwrap();
 
-            // Delete label-to-constraint mapping
-            let label_key = ConstraintCodec::encode_label_constraint_key(meta.label_id, name);
-            guard.batch.delete_cf(&cf, &label_key);
+            // Delete label/reltype-to-constraint mapping
+            let label_key = ConstraintCodec::encode_mapping_key(&meta);
+            guard.local.delete(cf_constraint::CF_NAME, label_key);
 
             // Delete metadata
             let meta_key = ConstraintCodec::encode_meta_key(name);
-            guard.batch.delete_cf(&cf, &meta_key);
+            guard.local.delete(cf_constraint::CF_NAME, meta_key);
         }
 
         Ok(())
@@ -213,28 +401,25 @@ impl TransactionImpl {
 
     // ==================== Unique Index Operations ====================
 
-    /// Check if a unique index entry exists (reads from snapshot)
+    /// Check if a unique index entry exists
     pub fn unique_index_exists(
         &self,
         label_id: LabelId,
         prop_key_ids: &[PropertyKeyId],
         prop_values: &[&[u8]],
     ) -> Result<bool, GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
-        let key = UniqueIndexCodec::encode_key(label_id, prop_key_ids, prop_values);
-        Ok(self.inner.snapshot.get_cf(&cf, &key)?.is_some())
+        Ok(self.get_unique_index(label_id, prop_key_ids, prop_values)?.is_some())
     }
 
-    /// Get node_id from unique index (reads from snapshot)
+    /// Get node_id from unique index, entries written in this transaction take precedence over the snapshot
     pub fn get_unique_index(
         &self,
         label_id: LabelId,
         prop_key_ids: &[PropertyKeyId],
         prop_values: &[&[u8]],
     ) -> Result<Option<NodeId>, GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let key = UniqueIndexCodec::encode_key(label_id, prop_key_ids, prop_values);
-        match self.inner.snapshot.get_cf(&cf, &key)? {
+        match self.get_cf(cf_constraint::CF_NAME, &key)? {
             Some(value) => Ok(UniqueIndexCodec::decode_value(&value)),
             None => Ok(None),
         }
@@ -248,12 +433,11 @@ impl TransactionImpl {
         prop_values: &[&[u8]],
         node_id: NodeId,
     ) -> Result<(), GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let key = UniqueIndexCodec::encode_key(label_id, prop_key_ids, prop_values);
         let value = UniqueIndexCodec::encode_value(node_id);
 
         let mut guard = self.write_state.lock().unwrap();
-        guard.batch.put_cf(&cf, &key, &value);
+        guard.local.put(cf_constraint::CF_NAME, key, value);
         Ok(())
     }
 
@@ -264,13 +448,174 @@ impl TransactionImpl {
         prop_key_ids: &[PropertyKeyId],
         prop_values: &[&[u8]],
     ) -> Result<(), GraphStoreError> {
-        let cf = self.inner._db.cf_handle(cf_constraint::CF_NAME).unwrap();
         let key = UniqueIndexCodec::encode_key(label_id, prop_key_ids, prop_values);
 
         let mut guard = self.write_state.lock().unwrap();
-        guard.batch.delete_cf(&cf, &key);
+        guard.local.delete(cf_constraint::CF_NAME, key);
         Ok(())
     }
+
+    /// Get rel_id from relationship unique index
+    pub fn get_rel_unique_index(
+        &self,
+        reltype_id: RelationshipTypeId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+    ) -> Result<Option<RelationshipId>, GraphStoreError> {
+        let key = UniqueIndexCodec::encode_rel_key(reltype_id, prop_key_ids, prop_values);
+        match self.get_cf(cf_constraint::CF_NAME, &key)? {
+            Some(value) => Ok(UniqueIndexCodec::decode_rel_value(&value)),
+            None => Ok(None),
+        }
+    }
+
+    /// Put relationship unique index entry (buffered in write batch)
+    pub fn put_rel_unique_index(
+        &self,
+        reltype_id: RelationshipTypeId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+        rel_id: RelationshipId,
+    ) -> Result<(), GraphStoreError> {
+        let key = UniqueIndexCodec::encode_rel_key(reltype_id, prop_key_ids, prop_values);
+        let value = UniqueIndexCodec::encode_rel_value(rel_id);
+
+        let mut guard = self.write_state.lock().unwrap();
+        guard.local.put(cf_constraint::CF_NAME, key, value);
+        Ok(())
+    }
+
+    /// Delete relationship unique index entry (buffered in write batch)
+    pub fn delete_rel_unique_index(
+        &self,
+        reltype_id: RelationshipTypeId,
+        prop_key_ids: &[PropertyKeyId],
+        prop_values: &[&[u8]],
+    ) -> Result<(), GraphStoreError> {
+        let key = UniqueIndexCodec::encode_rel_key(reltype_id, prop_key_ids, prop_values);
+
+        let mut guard = self.write_state.lock().unwrap();
+        guard.local.delete(cf_constraint::CF_NAME, key);
+        Ok(())
+    }
+
+    // ==================== Index Operations ====================
+
+    /// Get index metadata by name
+    pub fn get_index(&self, name: &str) -> Result<Option<IndexMeta>, GraphStoreError> {
+        let key = IndexCodec::encode_meta_key(name);
+        match self.get_cf(cf_index::CF_NAME, &key)? {
+            Some(value) => Ok(IndexCodec::decode_meta_value(name.to_string(), &value)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get all indexes for a label
+    pub fn get_indexes_for_label(&self, label_id: LabelId) -> Result<Vec<IndexMeta>, GraphStoreError> {
+        let prefix = IndexCodec::encode_label_index_prefix(label_id);
+        let mut indexes = Vec::new();
+        for item in self.prefix_iter(cf_index::CF_NAME, &prefix) {
+            let (key, _) = item?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            if let Some(name) = IndexCodec::decode_label_index_key(&key)
+                && let Some(meta) = self.get_index(&name)?
+            {
+                indexes.push(meta);
+            }
+        }
+        Ok(indexes)
+    }
+
+    /// Store an index (buffered in write batch), the entries are added by the caller
+    pub fn put_index(&self, meta: &IndexMeta) -> Result<(), GraphStoreError> {
+        let mut guard = self.write_state.lock().unwrap();
+        let meta_key = IndexCodec::encode_meta_key(&meta.name);
+        guard
+            .local
+            .put(cf_index::CF_NAME, meta_key, IndexCodec::encode_meta_value(meta));
+        let label_key = IndexCodec::encode_label_index_key(meta.label_id, &meta.name);
+        guard.local.put(cf_index::CF_NAME, label_key, Bytes::new());
+        Ok(())
+    }
+
+    /// Delete an index and all of its entries (buffered in write batch)
+    pub fn delete_index(&self, name: &str) -> Result<(), GraphStoreError> {
+        let Some(meta) = self.get_index(name)? else {
+            return Ok(());
+        };
+        let prefix = IndexCodec::encode_entry_prefix(meta.label_id, &meta.property_key_ids, &[]);
+        let entry_keys = self
+            .prefix_iter(cf_index::CF_NAME, &prefix)
+            .map(|item| item.map(|(key, _)| key))
+            .take_while(|item| item.as_ref().map_or(true, |key| key.starts_with(&prefix)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut guard = self.write_state.lock().unwrap();
+        for key in entry_keys {
+            guard.local.delete(cf_index::CF_NAME, Bytes::from(key));
+        }
+        let label_key = IndexCodec::encode_label_index_key(meta.label_id, name);
+        guard.local.delete(cf_index::CF_NAME, label_key);
+        guard.local.delete(cf_index::CF_NAME, IndexCodec::encode_meta_key(name));
+        Ok(())
+    }
+
+    /// Add the entries of the existing nodes to a new index (buffered in write batch)
+    pub fn build_index(&self, meta: &IndexMeta) -> Result<(), GraphStoreError> {
+        build_index(self, meta)
+    }
+
+    /// Seek the entries of an index whose leading properties have the given encoded values, and whose next
+    /// values are within the bounds. The bounds are compared with the encoded remainder of the entry keys: an
+    /// excluded lower bound skips the keys starting with it, and an included upper bound keeps them. The nodes are
+    /// returned in index order, with the values of all the indexed properties.
+    pub fn index_seek<'a>(
+        &'a self,
+        meta: &IndexMeta,
+        prop_values: &[&[u8]],
+        lower: Bound<&[u8]>,
+        upper: Bound<&'a [u8]>,
+    ) -> impl Iterator<Item = Result<(NodeId, Vec<ScalarValue>), GraphStoreError>> + 'a {
+        let prefix = IndexCodec::encode_entry_prefix(meta.label_id, &meta.property_key_ids, prop_values);
+        let start = match lower {
+            Bound::Included(value) | Bound::Excluded(value) => [prefix.as_ref(), value].concat(),
+            Bound::Unbounded => prefix.to_vec(),
+        };
+        let skipped = match lower {
+            Bound::Excluded(_) => Some(start.clone()),
+            _ => None,
+        };
+        let prop_key_ids = meta.property_key_ids.clone();
+        let prefix_len = prefix.len();
+        self.range_iter(cf_index::CF_NAME, &prefix, &start)
+            .take_while(move |item| {
+                item.as_ref().map_or(true, |(key, _)| {
+                    let rest = &key[prefix_len.min(key.len())..];
+                    key.starts_with(&prefix)
+                        && match upper {
+                            Bound::Included(value) => rest <= value || rest.starts_with(value),
+                            Bound::Excluded(value) => rest < value,
+                            Bound::Unbounded => true,
+                        }
+                })
+            })
+            .filter(move |item| {
+                item.as_ref().map_or(true, |(key, _)| {
+                    skipped.as_ref().is_none_or(|skipped| !key.starts_with(skipped))
+                })
+            })
+            .map(move |item| {
+                let (key, value) = item?;
+                let props = IndexCodec::decode_entry_value(&value);
+                let values = prop_key_ids
+                    .iter()
+                    .map(|key_id| props.get(*key_id).map_or(ScalarValue::Unknown, |v| v.to_owned_scalar()))
+                    .collect();
+                Ok((IndexCodec::decode_entry_node_id(&key), values))
+            })
+    }
 }
 
 struct OwnedSnapshot {
@@ -1,15 +1,24 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
 use bitvec::vec::BitVec;
-use elio_common::TokenKind;
+use bytes::Bytes;
 use elio_common::array::chunk::DataChunk;
 use elio_common::array::{Array, ArrayImpl, NodeArray, NodeArrayBuilder, VirtualNodeArray, VirtualNodeArrayBuilder};
-use elio_common::scalar::{NodeValueRef, StructValue};
+use elio_common::mapb::{IndexKeyCodec, PropertyMapRef};
+use elio_common::scalar::{NodeValue, NodeValueRef, ScalarVTable, ScalarValue, StructValue};
+use elio_common::store_types::RelDirection;
+use elio_common::{LabelId, NodeId, SemanticDirection, TokenKind};
 
-use crate::cf_property;
 use crate::codec::NodeFormat;
+use crate::constraint::ConstraintKind;
 use crate::error::GraphStoreError;
-use crate::transaction::{DataChunkIterator, NodeScanOptions, TransactionImpl};
+use crate::index::{IndexCodec, IndexMeta};
+use crate::transaction::local_buffer::MergedIter;
+use crate::transaction::relationship::delete_rel_keys;
+use crate::transaction::{DataChunkIterator, LabelScanOptions, NodeScanOptions, TransactionImpl};
+use crate::{cf_index, cf_property};
 
 // props only accept the fowlling array types
 // - StructArray
@@ -54,11 +63,25 @@ pub(crate) fn batch_node_create(
         values.push(value);
     }
 
+    // index entries of the nodes
+    let mut indexes = HashMap::new();
+    for (node_id, value) in node_ids.iter().zip(&values) {
+        update_node_index_entries(tx, &mut indexes, *node_id, None, Some(value))?;
+    }
+
     // construct batch
-    let cf = tx.inner._db.cf_handle(cf_property::CF_NAME).unwrap();
     let mut guard = tx.write_state.lock().unwrap();
-    for (k, v) in keys.iter().zip(values.iter()) {
-        guard.batch.put_cf(&cf, k, v);
+    for (k, v) in keys.into_iter().zip(values) {
+        guard.local.put(cf_property::CF_NAME, k, v);
+    }
+    for node_id in node_ids.iter() {
+        for label_id in &label_ids {
+            guard.local.put(
+                cf_property::CF_NAME,
+                NodeFormat::encode_label_key(*label_id, *node_id),
+                Bytes::new(),
+            );
+        }
     }
     drop(guard);
 
@@ -77,19 +100,280 @@ pub(crate) fn batch_node_create(
     Ok(builder.finish())
 }
 
+/// 1. detach: delete all the relationships of the node, otherwise remember the node to be checked on commit
+/// 2. delete the unique index, index and label entries pointing to the node
+/// 3. delete the node record
+pub(crate) fn batch_node_delete(
+    tx: &TransactionImpl,
+    node_ids: &[NodeId],
+    detach: bool,
+) -> Result<(), GraphStoreError> {
+    let mut indexes = HashMap::new();
+    for node_id in node_ids {
+        if detach {
+            for rel in tx.rel_iter_for_node(*node_id, SemanticDirection::Both, &[])? {
+                let (from, dir, rtype_id, end, rel_id, _) = rel?;
+                let (start_id, end_id) = match dir {
+                    RelDirection::Out => (from, end),
+                    RelDirection::In => (end, from),
+                };
+                delete_rel_keys(tx, rel_id, rtype_id, start_id, end_id)?;
+            }
+        } else {
+            tx.write_state.lock().unwrap().deleted_nodes.insert(*node_id);
+        }
+
+        let key = NodeFormat::encode_node_key(*node_id);
+        // node was deleted before or never existed
+        let Some(val) = read_node_value(tx, *node_id)? else {
+            continue;
+        };
+        let (label_ids, prop_map) = NodeFormat::decode_node_value(&val).map_err(GraphStoreError::internal)?;
+        for label_id in label_ids.iter() {
+            for meta in tx.get_constraints_for_label(label_id)? {
+                if !matches!(meta.constraint_kind, ConstraintKind::Unique | ConstraintKind::NodeKey) {
+                    continue;
+                }
+                // nodes missing any of the properties are not indexed
+                let Some(prop_values) = meta
+                    .property_key_ids
+                    .iter()
+                    .map(|key_id| {
+                        let value = prop_map.get(*key_id)?.to_owned_scalar();
+                        (!matches!(value, ScalarValue::Unknown))
+                            .then(|| IndexKeyCodec::encode_single(&value.as_scalar_ref()))
+                    })
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+                let prop_values = prop_values.iter().map(|v| v.as_slice()).collect::<Vec<_>>();
+                if tx.get_unique_index(label_id, &meta.property_key_ids, &prop_values)? == Some(*node_id) {
+                    tx.delete_unique_index(label_id, &meta.property_key_ids, &prop_values)?;
+                }
+            }
+        }
+        update_node_index_entries(tx, &mut indexes, *node_id, Some(&val), None)?;
+        let mut guard = tx.write_state.lock().unwrap();
+        for label_id in label_ids.iter() {
+            guard
+                .local
+                .delete(cf_property::CF_NAME, NodeFormat::encode_label_key(label_id, *node_id));
+        }
+        guard.local.delete(cf_property::CF_NAME, key);
+    }
+    Ok(())
+}
+
+/// Keep the index entries of the node in sync with the change of its encoded value, `old` is None for a new node and
+/// `new` is None for a deleted node. The indexes of the labels are cached in `indexes` across the nodes of a batch.
+fn update_node_index_entries(
+    tx: &TransactionImpl,
+    indexes: &mut HashMap<LabelId, Vec<IndexMeta>>,
+    node_id: NodeId,
+    old: Option<&[u8]>,
+    new: Option<&[u8]>,
+) -> Result<(), GraphStoreError> {
+    let old_entries = old
+        .map(|val| node_index_entries(tx, indexes, node_id, val))
+        .transpose()?
+        .unwrap_or_default();
+    let new_entries = new
+        .map(|val| node_index_entries(tx, indexes, node_id, val))
+        .transpose()?
+        .unwrap_or_default();
+    if old_entries.is_empty() && new_entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut guard = tx.write_state.lock().unwrap();
+    for (key, _) in old_entries.iter().filter(|(key, _)| !new_entries.contains_key(*key)) {
+        guard.local.delete(cf_index::CF_NAME, key.clone());
+    }
+    for (key, value) in new_entries {
+        if old_entries.get(&key) != Some(&value) {
+            guard.local.put(cf_index::CF_NAME, key, value);
+        }
+    }
+    Ok(())
+}
+
+// index entries of an encoded node value, for the indexes of its labels
+fn node_index_entries(
+    tx: &TransactionImpl,
+    indexes: &mut HashMap<LabelId, Vec<IndexMeta>>,
+    node_id: NodeId,
+    val: &[u8],
+) -> Result<HashMap<Bytes, Bytes>, GraphStoreError> {
+    let (label_ids, prop_map) = NodeFormat::decode_node_value(val).map_err(GraphStoreError::internal)?;
+    let mut entries = HashMap::new();
+    for label_id in label_ids.iter() {
+        let metas = match indexes.entry(label_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(tx.get_indexes_for_label(label_id)?),
+        };
+        for meta in metas.iter() {
+            entries.extend(index_entry(meta, node_id, &prop_map)?);
+        }
+    }
+    Ok(entries)
+}
+
+// the entry of the node in the index, nodes missing any of the properties or with a value that can not be indexed
+// are not in the index
+fn index_entry(
+    meta: &IndexMeta,
+    node_id: NodeId,
+    prop_map: &PropertyMapRef<'_>,
+) -> Result<Option<(Bytes, Bytes)>, GraphStoreError> {
+    let Some(values) = meta
+        .property_key_ids
+        .iter()
+        .map(|key_id| Some(prop_map.get(*key_id)?.to_owned_scalar()))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+    let values = values.iter().map(|v| v.as_scalar_ref()).collect::<Vec<_>>();
+    let Some(encoded) = values.iter().map(IndexCodec::encode_value).collect::<Option<Vec<_>>>() else {
+        return Ok(None);
+    };
+    let encoded = encoded.iter().map(|v| v.as_slice()).collect::<Vec<_>>();
+    let key = IndexCodec::encode_entry_key(meta.label_id, &meta.property_key_ids, &encoded, node_id);
+    let value = IndexCodec::encode_entry_value(&meta.property_key_ids, &values).map_err(GraphStoreError::internal)?;
+    Ok(Some((key, value)))
+}
+
+/// Add the entries of the existing nodes with the label of the index
+pub(crate) fn build_index(tx: &TransactionImpl, meta: &IndexMeta) -> Result<(), GraphStoreError> {
+    let prefix = NodeFormat::label_iter_prefix(meta.label_id);
+    let node_ids = tx
+        .prefix_iter(cf_property::CF_NAME, &prefix)
+        .map(|item| item.map(|(key, _)| key))
+        .take_while(|item| item.as_ref().map_or(true, |key| key.starts_with(&prefix)))
+        .map(|item| item.map(|key| NodeFormat::decode_label_key(&key).1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut entries = vec![];
+    for node_id in node_ids {
+        let Some(val) = read_node_value(tx, node_id)? else {
+            continue;
+        };
+        let (_, prop_map) = NodeFormat::decode_node_value(&val).map_err(GraphStoreError::internal)?;
+        entries.extend(index_entry(meta, node_id, &prop_map)?);
+    }
+
+    let mut guard = tx.write_state.lock().unwrap();
+    for (key, value) in entries {
+        guard.local.put(cf_index::CF_NAME, key, value);
+    }
+    Ok(())
+}
+
+// value of the node, updates of this transaction are visible
+fn read_node_value(tx: &TransactionImpl, node_id: NodeId) -> Result<Option<Vec<u8>>, GraphStoreError> {
+    tx.get_cf(cf_property::CF_NAME, &NodeFormat::encode_node_key(node_id))
+}
+
+fn decode_node(tx: &TransactionImpl, node_id: NodeId, val: &[u8]) -> Result<NodeValue, GraphStoreError> {
+    // TODO(pgao): lazy deserialize
+    let (label_ids, prop_map) = NodeFormat::decode_node_value(val).map_err(GraphStoreError::internal)?;
+    let labels = label_ids
+        .iter()
+        .map(|id| tx.token.get_token_val(id, TokenKind::Label))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut fields = vec![];
+    for entry in prop_map.iter() {
+        let key = tx.token.get_token_val(entry.key(), TokenKind::PropertyKey)?;
+        fields.push((key, entry.value().to_owned_scalar()));
+    }
+    Ok(NodeValue {
+        id: node_id,
+        labels,
+        props: StructValue::new(fields),
+    })
+}
+
+pub(crate) fn get_node(tx: &TransactionImpl, node_id: NodeId) -> Result<Option<NodeValue>, GraphStoreError> {
+    read_node_value(tx, node_id)?
+        .map(|val| decode_node(tx, node_id, &val))
+        .transpose()
+}
+
+pub(crate) fn node_update(tx: &TransactionImpl, node: &NodeValue) -> Result<(), GraphStoreError> {
+    let label_ids = node
+        .labels
+        .iter()
+        .map(|l| tx.token.get_or_create_token(l, TokenKind::Label))
+        .collect::<Result<Vec<_>, _>>()?;
+    // null properties are not stored
+    let props = StructValue::new(
+        node.props
+            .iter()
+            .filter(|(_, v)| !matches!(v, ScalarValue::Unknown))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    let token_ids = props
+        .iter()
+        .map(|(k, _)| tx.token.get_or_create_token(k, TokenKind::PropertyKey))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value = NodeFormat::encode_node_value(&label_ids, &token_ids, props.as_scalar_ref())
+        .map_err(GraphStoreError::internal)?;
+
+    let old = read_node_value(tx, node.id)?;
+    update_node_index_entries(tx, &mut HashMap::new(), node.id, old.as_deref(), Some(&value))?;
+    let old_label_ids = match &old {
+        Some(old) => {
+            let (old_label_ids, _) = NodeFormat::decode_node_value(old).map_err(GraphStoreError::internal)?;
+            old_label_ids.iter().collect()
+        }
+        None => vec![],
+    };
+
+    let mut guard = tx.write_state.lock().unwrap();
+    // labels removed from and added to the node
+    for label_id in old_label_ids.iter().filter(|id| !label_ids.contains(id)) {
+        guard
+            .local
+            .delete(cf_property::CF_NAME, NodeFormat::encode_label_key(*label_id, node.id));
+    }
+    for label_id in label_ids.iter().filter(|id| !old_label_ids.contains(id)) {
+        guard.local.put(
+            cf_property::CF_NAME,
+            NodeFormat::encode_label_key(*label_id, node.id),
+            Bytes::new(),
+        );
+    }
+    guard
+        .local
+        .put(cf_property::CF_NAME, NodeFormat::encode_node_key(node.id), value);
+    Ok(())
+}
+
 pub(crate) fn batch_node_scan(
     tx: &TransactionImpl,
     opts: NodeScanOptions,
 ) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
-    let cf_handle = tx.inner._db.cf_handle(cf_property::CF_NAME).unwrap();
-    let mut readopts = rocksdb::ReadOptions::default();
     // TODO(pgao): check the behavior of prefix scan
-    readopts.set_prefix_same_as_start(true);
-    let mode = rocksdb::IteratorMode::From(cf_property::NODE_KEY_PREFIX, rocksdb::Direction::Forward);
-    let iter = tx.inner.snapshot.iterator_cf_opt(&cf_handle, readopts, mode);
+    let iter = tx.prefix_iter(cf_property::CF_NAME, cf_property::NODE_KEY_PREFIX);
     Ok(Box::new(NodeIterator { iter, opts }))
 }
 
+/// Scan the nodes with the label through the label index, in node id order
+pub(crate) fn batch_label_scan(
+    tx: &TransactionImpl,
+    opts: LabelScanOptions,
+) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
+    let prefix = NodeFormat::label_iter_prefix(opts.label_id);
+    let iter = tx.prefix_iter(cf_property::CF_NAME, &prefix);
+    Ok(Box::new(LabelIterator {
+        iter,
+        prefix,
+        batch_size: opts.batch_size,
+    }))
+}
+
 // null node id handling:
 // two pass:
 // 1. pass over the valid map of node id array, collect the valid node id
@@ -103,7 +387,6 @@ pub(crate) fn batch_materialize_node(
     node_ids: &VirtualNodeArray,
     vis: &BitVec,
 ) -> Result<NodeArray, GraphStoreError> {
-    let cf_handle = tx.inner._db.cf_handle(cf_property::CF_NAME).unwrap();
     let mut builder = NodeArrayBuilder::with_capacity(node_ids.len());
 
     let mut valid_node_keys = vec![];
@@ -114,9 +397,9 @@ pub(crate) fn batch_materialize_node(
         }
     }
 
-    // rocksdb batch read
-    let keys_cf = valid_node_keys.iter().map(|k| (&cf_handle, k));
-    let batch = tx.inner.snapshot.multi_get_cf(keys_cf);
+    // rocksdb batch read, nodes written in this transaction are read from the write state
+    let batch = tx.multi_get_committed(cf_property::CF_NAME, &valid_node_keys);
+    let guard = tx.write_state.lock().unwrap();
     let mut batch_iter = batch.into_iter();
 
     for (idx, node_id) in node_ids.iter().enumerate() {
@@ -128,31 +411,16 @@ pub(crate) fn batch_materialize_node(
         let node_id = node_id.unwrap();
         // SAFETY: rocksdb will guarantee the length of batch eq to length of valid node_ids
         let val = batch_iter.next().unwrap()?;
+        let val = match guard
+            .local
+            .get(cf_property::CF_NAME, &NodeFormat::encode_node_key(node_id))
+        {
+            Some(local) => local.map(|v| v.to_vec()),
+            None => val,
+        };
         if let Some(val) = val {
-            // deserialize
-            // TODO(pgao): lazy deserialize
-            let (label_ids, prop_map) = NodeFormat::decode_node_value(&val).map_err(GraphStoreError::internal)?;
-
-            let label_strs = label_ids
-                .iter()
-                .map(|id| tx.token.get_token_val(id, TokenKind::Label))
-                .collect::<Result<Vec<_>, _>>()?;
-
-            let struct_value = {
-                let mut fileds = vec![];
-                for entry in prop_map.iter() {
-                    let key = tx.token.get_token_val(entry.key(), TokenKind::PropertyKey)?;
-                    fileds.push((key, entry.value().to_owned_scalar()));
-                }
-                StructValue::new(fileds)
-            };
-
-            let node_ref = NodeValueRef {
-                id: node_id,
-                labels: &label_strs,
-                props: struct_value.as_scalar_ref(),
-            };
-            builder.push(Some(node_ref));
+            let node = decode_node(tx, node_id, &val)?;
+            builder.push(Some(node.as_scalar_ref()));
         } else {
             // if val does not exists, then push None
             tracing::warn!("node id {} not found", node_id);
@@ -163,17 +431,17 @@ pub(crate) fn batch_materialize_node(
     Ok(builder.finish())
 }
 
-pub struct NodeIterator<'a, D: rocksdb::DBAccess> {
-    iter: rocksdb::DBIteratorWithThreadMode<'a, D>,
+pub struct NodeIterator<'a> {
+    iter: MergedIter<'a>,
     opts: NodeScanOptions,
 }
 
-impl<'a, D: rocksdb::DBAccess> DataChunkIterator for NodeIterator<'a, D> {
+impl DataChunkIterator for NodeIterator<'_> {
     fn next_batch(&mut self) -> Result<Option<DataChunk>, GraphStoreError> {
         let mut builder = VirtualNodeArrayBuilder::with_capacity(self.opts.batch_size);
         for _ in 0..self.opts.batch_size {
             if let Some(item) = self.iter.next() {
-                let (key, _val) = item.map_err(GraphStoreError::Rocksdb)?;
+                let (key, _val) = item?;
                 if !key.starts_with(cf_property::NODE_KEY_PREFIX) {
                     break;
                 }
@@ -194,3 +462,35 @@ impl<'a, D: rocksdb::DBAccess> DataChunkIterator for NodeIterator<'a, D> {
         }
     }
 }
+
+pub struct LabelIterator<'a> {
+    iter: MergedIter<'a>,
+    prefix: Bytes,
+    batch_size: usize,
+}
+
+impl DataChunkIterator for LabelIterator<'_> {
+    fn next_batch(&mut self) -> Result<Option<DataChunk>, GraphStoreError> {
+        let mut builder = VirtualNodeArrayBuilder::with_capacity(self.batch_size);
+        for _ in 0..self.batch_size {
+            let Some(item) = self.iter.next() else {
+                break;
+            };
+            let (key, _) = item?;
+            if !key.starts_with(&self.prefix) {
+                break;
+            }
+            let (_, node_id) = NodeFormat::decode_label_key(&key);
+            builder.push(Some(node_id));
+        }
+
+        let array = builder.finish();
+        if array.is_empty() {
+            Ok(None)
+        } else {
+            let vis = BitVec::repeat(true, array.len());
+            let chunk = DataChunk::new(vec![Arc::new(array.into())], vis);
+            Ok(Some(chunk))
+        }
+    }
+}
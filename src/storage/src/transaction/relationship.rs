@@ -1,15 +1,23 @@
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use bitvec::vec::BitVec;
-use elio_common::array::{Array, NodeArray, RelArray, RelArrayBuilder, StructArray, VirtualNodeArray};
-use elio_common::scalar::{RelValueRef, StructValue};
+use bytes::Bytes;
+use elio_common::array::chunk::DataChunk;
+use elio_common::array::{
+    Array, NodeArray, RelArray, RelArrayBuilder, StructArray, VirtualNodeArray, VirtualNodeArrayBuilder,
+};
+use elio_common::mapb::IndexKeyCodec;
+use elio_common::scalar::{RelValue, RelValueRef, ScalarValue, StructValue, VirtualRelRef};
 use elio_common::store_types::RelDirection;
 use elio_common::{NodeId, RelationshipId, SemanticDirection, TokenId, TokenKind};
 
-use crate::cf_topology;
-use crate::codec::RelFormat;
+use crate::codec::{NodeFormat, RelFormat};
+use crate::constraint::{ConstraintKind, ConstraintMeta};
 use crate::error::GraphStoreError;
-use crate::transaction::TransactionImpl;
+use crate::transaction::local_buffer::{KeyValue, MergedIter};
+use crate::transaction::{DataChunkIterator, RelScanOptions, TransactionImpl};
+use crate::{cf_property, cf_topology};
 
 /// start/end are expected to be :
 ///   - VirtualNodeArray
@@ -22,7 +30,8 @@ use crate::transaction::TransactionImpl;
 /// 2. mapping property key ids to TokenId
 /// 3. create relationship id
 /// 3. serialize key-value
-/// 4. batch write
+/// 4. check the UNIQUE/RELATIONSHIP KEY constraints and put the unique index entries
+/// 5. batch write
 pub(crate) fn batch_rel_create<A, B>(
     tx: &TransactionImpl,
     rtype: &Arc<str>,
@@ -52,6 +61,7 @@ where
 
     let mut out_keys = Vec::with_capacity(len);
     let mut in_keys = Vec::with_capacity(len);
+    let mut reltype_keys = Vec::with_capacity(len);
     let mut values = Vec::with_capacity(len);
     let empty_prop = StructValue::default();
     let empty_prop_ref = empty_prop.as_scalar_ref();
@@ -66,18 +76,37 @@ where
         let in_key = RelFormat::encode_key(end_id, RelDirection::In, rtype_id, start_id, *rel_id);
         out_keys.push(out_key);
         in_keys.push(in_key);
+        reltype_keys.push(RelFormat::encode_reltype_key(rtype_id, start_id, end_id, *rel_id));
 
         let value = RelFormat::encode_value(&prop_key_ids, prop.unwrap_or(empty_prop_ref))
             .map_err(|e| GraphStoreError::internal(e.to_string()))?;
         values.push(value);
     }
 
+    let constraints = rel_unique_constraints(tx, rtype_id)?;
+    for (rel_id, value) in rel_ids.iter().zip(values.iter()) {
+        update_rel_unique_indexes(tx, &constraints, rtype_id, *rel_id, None, Some(value))?;
+    }
+
+    // the end nodes must not be deleted by concurrent transactions
+    for i in 0..len {
+        for node_id in [start.get_unchecked(i), end.get_unchecked(i)] {
+            tx.validate_read(cf_property::CF_NAME, NodeFormat::encode_node_key(node_id));
+        }
+    }
+
     // construct batch
-    let cf = tx.inner._db.cf_handle(cf_topology::CF_NAME).unwrap();
     let mut guard = tx.write_state.lock().unwrap();
     for i in 0..values.len() {
-        guard.batch.put_cf(&cf, &out_keys[i], &values[i]);
-        guard.batch.put_cf(&cf, &in_keys[i], &values[i]);
+        guard
+            .local
+            .put(cf_topology::CF_NAME, out_keys[i].clone(), values[i].clone());
+        guard
+            .local
+            .put(cf_topology::CF_NAME, in_keys[i].clone(), values[i].clone());
+        guard
+            .local
+            .put(cf_topology::CF_NAME, reltype_keys[i].clone(), values[i].clone());
     }
     drop(guard);
 
@@ -98,19 +127,240 @@ where
     Ok(builder.finish())
 }
 
+/// Delete the outgoing, incoming and reltype keys of the relationships.
+pub(crate) fn batch_rel_delete(tx: &TransactionImpl, rels: &[VirtualRelRef<'_>]) -> Result<(), GraphStoreError> {
+    for rel in rels {
+        let rtype_id = tx
+            .token
+            .get_reltype_id(rel.reltype)
+            .ok_or_else(|| GraphStoreError::Token(rel.reltype.to_string()))?;
+        delete_rel_keys(tx, rel.id, rtype_id, rel.start_id, rel.end_id)?;
+    }
+    Ok(())
+}
+
+/// Delete the keys of a relationship and its unique index entries
+pub(crate) fn delete_rel_keys(
+    tx: &TransactionImpl,
+    rel_id: RelationshipId,
+    rtype_id: TokenId,
+    start_id: NodeId,
+    end_id: NodeId,
+) -> Result<(), GraphStoreError> {
+    // a self loop is seen from both directions of the node, delete it once
+    if !tx.write_state.lock().unwrap().deleted_rels.insert(rel_id) {
+        return Ok(());
+    }
+    let out_key = RelFormat::encode_key(start_id, RelDirection::Out, rtype_id, end_id, rel_id);
+    let in_key = RelFormat::encode_key(end_id, RelDirection::In, rtype_id, start_id, rel_id);
+
+    let constraints = rel_unique_constraints(tx, rtype_id)?;
+    if !constraints.is_empty()
+        && let Some(old) = tx.get_cf(cf_topology::CF_NAME, &out_key)?
+    {
+        update_rel_unique_indexes(tx, &constraints, rtype_id, rel_id, Some(&old), None)?;
+    }
+
+    let mut guard = tx.write_state.lock().unwrap();
+    guard.local.delete(cf_topology::CF_NAME, out_key);
+    guard.local.delete(cf_topology::CF_NAME, in_key);
+    guard.local.delete(
+        cf_topology::CF_NAME,
+        RelFormat::encode_reltype_key(rtype_id, start_id, end_id, rel_id),
+    );
+    Ok(())
+}
+
+// the UNIQUE/RELATIONSHIP KEY constraints of the relationship type, which are backed by a unique index
+fn rel_unique_constraints(tx: &TransactionImpl, rtype_id: TokenId) -> Result<Vec<ConstraintMeta>, GraphStoreError> {
+    Ok(tx
+        .get_constraints_for_reltype(rtype_id)?
+        .into_iter()
+        .filter(|meta| {
+            matches!(
+                meta.constraint_kind,
+                ConstraintKind::Unique | ConstraintKind::RelationshipKey
+            )
+        })
+        .collect())
+}
+
+/// Move the unique index entries of a relationship from the old properties to the new ones, the properties are
+/// the encoded values of the relationship keys and None if the relationship does not exist before or after.
+///
+/// Returns an error if the new properties violate a UNIQUE or RELATIONSHIP KEY constraint.
+fn update_rel_unique_indexes(
+    tx: &TransactionImpl,
+    constraints: &[ConstraintMeta],
+    rtype_id: TokenId,
+    rel_id: RelationshipId,
+    old: Option<&[u8]>,
+    new: Option<&[u8]>,
+) -> Result<(), GraphStoreError> {
+    for meta in constraints {
+        let old_values = old.and_then(|value| rel_index_values(meta, value).ok());
+        let new_values = match new.map(|value| rel_index_values(meta, value)) {
+            Some(Err(key_id)) if meta.constraint_kind == ConstraintKind::RelationshipKey => {
+                let prop_name = tx.token.get_token_val(key_id, TokenKind::PropertyKey)?;
+                return Err(rel_constraint_violation(
+                    tx,
+                    meta,
+                    rtype_id,
+                    rel_id,
+                    format!("must have property '{}'", prop_name),
+                )?);
+            }
+            values => values.and_then(Result::ok),
+        };
+        if old_values == new_values {
+            continue;
+        }
+
+        if let Some(old_values) = &old_values {
+            let prop_value_refs: Vec<&[u8]> = old_values.iter().map(|v| v.as_slice()).collect();
+            if tx.get_rel_unique_index(rtype_id, &meta.property_key_ids, &prop_value_refs)? == Some(rel_id) {
+                tx.delete_rel_unique_index(rtype_id, &meta.property_key_ids, &prop_value_refs)?;
+            }
+        }
+        if let Some(new_values) = &new_values {
+            let prop_value_refs: Vec<&[u8]> = new_values.iter().map(|v| v.as_slice()).collect();
+            if let Some(other) = tx.get_rel_unique_index(rtype_id, &meta.property_key_ids, &prop_value_refs)?
+                && other != rel_id
+            {
+                let prop_names = meta
+                    .property_key_ids
+                    .iter()
+                    .map(|key_id| {
+                        Ok(format!(
+                            "'{}'",
+                            tx.token.get_token_val(*key_id, TokenKind::PropertyKey)?
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, GraphStoreError>>()?;
+                return Err(rel_constraint_violation(
+                    tx,
+                    meta,
+                    rtype_id,
+                    rel_id,
+                    format!(
+                        "has the same value of {} as Relationship({})",
+                        prop_names.join(", "),
+                        other
+                    ),
+                )?);
+            }
+            tx.put_rel_unique_index(rtype_id, &meta.property_key_ids, &prop_value_refs, rel_id)?;
+        }
+    }
+    Ok(())
+}
+
+// encoded values of the constrained properties, or the first property which is missing or NULL
+fn rel_index_values(meta: &ConstraintMeta, value: &[u8]) -> Result<Vec<Vec<u8>>, TokenId> {
+    let prop_map = RelFormat::decode_value(value);
+    meta.property_key_ids
+        .iter()
+        .map(|key_id| {
+            let value = prop_map.get(*key_id).map(|v| v.to_owned_scalar());
+            match value {
+                Some(value) if !matches!(value, ScalarValue::Unknown) => {
+                    Ok(IndexKeyCodec::encode_single(&value.as_scalar_ref()))
+                }
+                _ => Err(*key_id),
+            }
+        })
+        .collect()
+}
+
+fn rel_constraint_violation(
+    tx: &TransactionImpl,
+    meta: &ConstraintMeta,
+    rtype_id: TokenId,
+    rel_id: RelationshipId,
+    reason: String,
+) -> Result<GraphStoreError, GraphStoreError> {
+    let reltype = tx.token.get_token_val(rtype_id, TokenKind::RelationshipType)?;
+    Ok(GraphStoreError::ConstraintViolation {
+        constraint: meta.name.clone(),
+        reason: format!("Relationship({}) with type '{}' {}", rel_id, reltype, reason),
+    })
+}
+
+pub(crate) fn get_relationship(
+    tx: &TransactionImpl,
+    rel: &VirtualRelRef<'_>,
+) -> Result<Option<RelValue>, GraphStoreError> {
+    let Some(rtype_id) = tx.token.get_reltype_id(rel.reltype) else {
+        return Ok(None);
+    };
+    let key = RelFormat::encode_key(rel.start_id, RelDirection::Out, rtype_id, rel.end_id, rel.id);
+    let Some(value) = tx.get_cf(cf_topology::CF_NAME, &key)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(RelValue {
+        id: rel.id,
+        reltype: rel.reltype.into(),
+        start_id: rel.start_id,
+        end_id: rel.end_id,
+        props: decode_rel_props(tx, &value)?,
+    }))
+}
+
+fn decode_rel_props(tx: &TransactionImpl, value: &[u8]) -> Result<StructValue, GraphStoreError> {
+    let prop_map = RelFormat::decode_value(value);
+    let mut fields = vec![];
+    for entry in prop_map.iter() {
+        let key = tx.token.get_token_val(entry.key(), TokenKind::PropertyKey)?;
+        fields.push((key, entry.value().to_owned_scalar()));
+    }
+    Ok(StructValue::new(fields))
+}
+
+pub(crate) fn relationship_update(tx: &TransactionImpl, rel: &RelValue) -> Result<(), GraphStoreError> {
+    let rtype_id = tx
+        .token
+        .get_reltype_id(&rel.reltype)
+        .ok_or_else(|| GraphStoreError::Token(rel.reltype.to_string()))?;
+    // null properties are not stored
+    let props = StructValue::new(
+        rel.props
+            .iter()
+            .filter(|(_, v)| !matches!(v, ScalarValue::Unknown))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    let prop_key_ids = props
+        .iter()
+        .map(|(k, _)| tx.token.get_or_create_token(k, TokenKind::PropertyKey))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value = RelFormat::encode_value(&prop_key_ids, props.as_scalar_ref()).map_err(GraphStoreError::internal)?;
+
+    let out_key = RelFormat::encode_key(rel.start_id, RelDirection::Out, rtype_id, rel.end_id, rel.id);
+    let in_key = RelFormat::encode_key(rel.end_id, RelDirection::In, rtype_id, rel.start_id, rel.id);
+    let reltype_key = RelFormat::encode_reltype_key(rtype_id, rel.start_id, rel.end_id, rel.id);
+
+    let constraints = rel_unique_constraints(tx, rtype_id)?;
+    if !constraints.is_empty() {
+        let old = tx.get_cf(cf_topology::CF_NAME, &out_key)?;
+        update_rel_unique_indexes(tx, &constraints, rtype_id, rel.id, old.as_deref(), Some(&value))?;
+    }
+
+    let mut guard = tx.write_state.lock().unwrap();
+    guard.local.put(cf_topology::CF_NAME, out_key, value.clone());
+    guard.local.put(cf_topology::CF_NAME, in_key, value.clone());
+    guard.local.put(cf_topology::CF_NAME, reltype_key, value);
+    Ok(())
+}
+
 pub(crate) fn rel_iter_for_node<'a>(
     tx: &'a TransactionImpl,
     node_id: NodeId,
     dir: SemanticDirection,
     rtypes: &[TokenId],
 ) -> Result<RelIterForNode<'a>, GraphStoreError> {
-    let cf = tx.inner._db.cf_handle(cf_topology::CF_NAME).unwrap();
     let prefix = RelFormat::node_rel_iter_prefix(node_id, dir);
-
-    let mut readopts = rocksdb::ReadOptions::default();
-    readopts.set_prefix_same_as_start(true);
-    let mode = rocksdb::IteratorMode::From(&prefix, rocksdb::Direction::Forward);
-    let iter = tx.inner.snapshot.iterator_cf_opt(&cf, readopts, mode);
+    let iter = tx.prefix_iter(cf_topology::CF_NAME, &prefix);
     Ok(RelIterForNode {
         iter,
         from_id: node_id,
@@ -156,7 +406,7 @@ impl NodeIdContainer for VirtualNodeArray {
 }
 
 pub struct RelIterForNode<'a> {
-    iter: rocksdb::DBIteratorWithThreadMode<'a, rocksdb::DBWithThreadMode<rocksdb::MultiThreaded>>,
+    iter: MergedIter<'a>,
     from_id: NodeId,
     dir: SemanticDirection,
     // TODO(pgao): use binary search?
@@ -177,9 +427,13 @@ impl<'a> Iterator for RelIterForNode<'a> {
         for item in self.iter.by_ref() {
             match item {
                 Err(e) => {
-                    return Some(Err(e.into()));
+                    return Some(Err(e));
                 }
                 Ok((key, val)) => {
+                    // reltype keys follow the relationship keys in the same column family
+                    if key[0] != cf_topology::REL_KEY_PREFIX {
+                        return None;
+                    }
                     let (from, dir, reltype, end, rel_id) = RelFormat::decode_key(&key);
                     if from != self.from_id {
                         return None;
@@ -198,3 +452,87 @@ impl<'a> Iterator for RelIterForNode<'a> {
         None
     }
 }
+
+pub(crate) fn batch_rel_scan(
+    tx: &TransactionImpl,
+    opts: RelScanOptions,
+) -> Result<Box<dyn DataChunkIterator + '_>, GraphStoreError> {
+    Ok(Box::new(RelIterator {
+        tx,
+        reltypes: opts.reltypes.into_iter().collect(),
+        iter: None,
+        batch_size: opts.batch_size,
+    }))
+}
+
+/// Scan the relationships of the given types one after another, by the reltype keys.
+/// Produces chunks of (rel, start node, end node).
+pub struct RelIterator<'a> {
+    tx: &'a TransactionImpl,
+    // types not scanned yet
+    reltypes: VecDeque<TokenId>,
+    // the iterator of the current type and its prefix
+    iter: Option<(Bytes, MergedIter<'a>)>,
+    batch_size: usize,
+}
+
+impl RelIterator<'_> {
+    // the next key-value pair of the remaining types
+    fn next_entry(&mut self) -> Result<Option<KeyValue>, GraphStoreError> {
+        loop {
+            if let Some((prefix, iter)) = &mut self.iter {
+                if let Some(item) = iter.next() {
+                    let (key, val) = item?;
+                    if key.starts_with(prefix) {
+                        return Ok(Some((key, val)));
+                    }
+                }
+                // the current type is done
+                self.iter = None;
+            }
+            let Some(reltype) = self.reltypes.pop_front() else {
+                return Ok(None);
+            };
+            let prefix = RelFormat::reltype_iter_prefix(reltype);
+            let iter = self.tx.prefix_iter(cf_topology::CF_NAME, &prefix);
+            self.iter = Some((prefix, iter));
+        }
+    }
+}
+
+impl DataChunkIterator for RelIterator<'_> {
+    fn next_batch(&mut self) -> Result<Option<DataChunk>, GraphStoreError> {
+        let mut rels = RelArrayBuilder::with_capacity(self.batch_size);
+        let mut starts = VirtualNodeArrayBuilder::with_capacity(self.batch_size);
+        let mut ends = VirtualNodeArrayBuilder::with_capacity(self.batch_size);
+        for _ in 0..self.batch_size {
+            let Some((key, val)) = self.next_entry()? else {
+                break;
+            };
+            let (reltype_id, start_id, end_id, rel_id) = RelFormat::decode_reltype_key(&key);
+            let reltype = self.tx.token.get_token_val(reltype_id, TokenKind::RelationshipType)?;
+            let props = decode_rel_props(self.tx, &val)?;
+            rels.push(Some(RelValueRef {
+                id: rel_id,
+                reltype: &reltype,
+                start_id,
+                end_id,
+                props: props.as_scalar_ref(),
+            }));
+            starts.push(Some(start_id));
+            ends.push(Some(end_id));
+        }
+
+        let rels = rels.finish();
+        if rels.is_empty() {
+            return Ok(None);
+        }
+        let vis = BitVec::repeat(true, rels.len());
+        let columns = vec![
+            Arc::new(rels.into()),
+            Arc::new(starts.finish().into()),
+            Arc::new(ends.finish().into()),
+        ];
+        Ok(Some(DataChunk::new(columns, vis)))
+    }
+}